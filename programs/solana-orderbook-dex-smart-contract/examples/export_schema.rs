@@ -0,0 +1,13 @@
+//! Print the JSON layout of every DEX account type
+//!
+//! cargo run --example export_schema --features serde > layouts.json
+
+fn main() {
+    match solana_orderbook_dex::schema::export_json() {
+        Ok(json) => println!("{}", json),
+        Err(err) => {
+            eprintln!("failed to export schema: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -13,6 +13,18 @@ pub enum DexError {
     InvalidMarketParams,
     #[msg("Invalid base or quote mint")]
     InvalidMint,
+    #[msg("Market is in cancel-only mode")]
+    MarketCancelOnly,
+    #[msg("Market is halted by its circuit breaker")]
+    MarketHalted,
+    #[msg("Invalid market status transition")]
+    InvalidMarketStatus,
+    #[msg("Market is not delisted")]
+    MarketNotDelisted,
+    #[msg("Competition is invalid or a market is already in one")]
+    InvalidCompetition,
+    #[msg("Not allowed in the competition's current phase")]
+    WrongCompetitionPhase,
 
     // Order errors (0x1100-0x11FF)
     #[msg("Order not found")]
@@ -35,6 +47,22 @@ pub enum DexError {
     InvalidTimeInForce,
     #[msg("Post-only order would cross spread")]
     PostOnlyWouldCross,
+    #[msg("Price outside peg band")]
+    PriceOutsidePegBand,
+    #[msg("Order notional exceeds market maximum")]
+    OrderNotionalTooLarge,
+    #[msg("Order notional below market minimum")]
+    OrderNotionalTooSmall,
+    #[msg("UI price or size does not convert exactly to ticks/lots")]
+    UiAmountNotRepresentable,
+    #[msg("Resting notional would exceed the market's exposure cap")]
+    ExposureCapExceeded,
+    #[msg("Reduce-only order would increase exposure")]
+    ReduceOnlyWouldIncrease,
+    #[msg("Trader cancelled too many orders per fill in the current window")]
+    CancelRatioExceeded,
+    #[msg("Deposit balance does not cover the market's placement deposit")]
+    PlacementDepositRequired,
 
     // Orderbook errors (0x1200-0x12FF)
     #[msg("Orderbook is full")]
@@ -45,6 +73,10 @@ pub enum DexError {
     InvalidOrderbookState,
     #[msg("Orderbook depth exceeded")]
     OrderbookDepthExceeded,
+    #[msg("Event queue is full")]
+    EventQueueFull,
+    #[msg("Event no longer retained in the event queue")]
+    EventNotRetained,
 
     // Matching errors (0x1300-0x13FF)
     #[msg("No matching orders available")]
@@ -55,6 +87,8 @@ pub enum DexError {
     InvalidMatchPrice,
     #[msg("Insufficient liquidity")]
     InsufficientLiquidity,
+    #[msg("Swap output below minimum")]
+    SlippageExceeded,
 
     // Settlement errors (0x1400-0x14FF)
     #[msg("Settlement failed")]
@@ -65,6 +99,12 @@ pub enum DexError {
     InvalidFillId,
     #[msg("Fill already settled")]
     FillAlreadySettled,
+    #[msg("Settlement program account missing or invalid")]
+    InvalidSettlementProgram,
+    #[msg("Revenue share table is invalid or the market has already traded")]
+    InvalidRevenueShare,
+    #[msg("Fees on this market are split by its revenue share")]
+    FeesSplitByRevenueShare,
 
     // Account errors (0x1500-0x15FF)
     #[msg("Insufficient funds")]
@@ -75,14 +115,46 @@ pub enum DexError {
     AccountNotInitialized,
     #[msg("Invalid account state")]
     InvalidAccountState,
+    #[msg("Mints with transfer fees are not supported here")]
+    TransferFeeNotSupported,
+    #[msg("Vault balance is below the ledger balance")]
+    VaultBalanceMismatch,
+    #[msg("Account passed more than once")]
+    DuplicateAccount,
+    #[msg("Fee-discount pass does not qualify for this market")]
+    InvalidFeePass,
 
     // Authority errors (0x1600-0x16FF)
     #[msg("Unauthorized")]
     Unauthorized,
     #[msg("Invalid authority")]
     InvalidAuthority,
+    #[msg("No authority transfer is pending for this signer")]
+    AuthorityTransferNotPending,
     #[msg("Market creation not allowed")]
     MarketCreationNotAllowed,
+    #[msg("Withdrawal requires co-signer approval")]
+    CoSignatureRequired,
+    #[msg("Invalid co-signer")]
+    InvalidCoSigner,
+    #[msg("Withdrawal destination not in allowlist")]
+    WithdrawalDestinationNotAllowed,
+    #[msg("Invalid withdrawal allowlist")]
+    InvalidWithdrawalAllowlist,
+    #[msg("Withdrawal allowlist change not yet effective")]
+    AllowlistChangeNotReady,
+    #[msg("Account is locked")]
+    AccountLocked,
+    #[msg("Withdrawal cooldown after deposit has not ended")]
+    WithdrawalCooldownActive,
+    #[msg("Market requires a trading seat")]
+    SeatRequired,
+    #[msg("No staged parameter update is pending")]
+    NoPendingUpdate,
+    #[msg("Staged parameter update is still timelocked")]
+    UpdateNotReady,
+    #[msg("Tick or lot size can only change with no resting orders or unconsumed events")]
+    MarketNotDrained,
 
     // Math errors (0x1700-0x17FF)
     #[msg("Math overflow")]
@@ -109,4 +181,6 @@ pub enum DexError {
     OperationNotSupported,
     #[msg("Reentrancy detected")]
     ReentrancyDetected,
+    #[msg("Current epoch has not ended")]
+    EpochNotOver,
 }
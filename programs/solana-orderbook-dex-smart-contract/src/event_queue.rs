@@ -0,0 +1,539 @@
+use anchor_lang::prelude::*;
+use bytemuck::{Pod, Zeroable};
+use crate::errors::DexError;
+use crate::matching::calculate_fee;
+use crate::orderbook::Order;
+use crate::state::TraderState;
+
+/// Event queue entry types
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum QueueEventType {
+    /// A bid and an ask traded
+    Fill = 0,
+    /// An order left the book without its owner present (funds to release)
+    Out = 1,
+}
+
+/// Event queue entry
+/// Fixed-size `repr(C)` record without implicit padding, stored raw after the header
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[repr(C)]
+pub struct QueueEvent {
+    /// Bid order ID (Out on the ask side: 0)
+    pub bid_order_id: u128,
+    
+    /// Ask order ID (Out on the bid side: 0)
+    pub ask_order_id: u128,
+    
+    /// Bid trader (Out on the ask side: Pubkey::default())
+    pub bid_trader: Pubkey,
+    
+    /// Ask trader (Out on the bid side: Pubkey::default())
+    pub ask_trader: Pubkey,
+    
+    /// Sequence number assigned when the event was pushed
+    pub seq_num: u64,
+    
+    /// Fill price (Out: the order's limit price)
+    pub price: u64,
+    
+    /// Base size filled (Out: the remaining size released)
+    pub size: u64,
+    
+    /// Bid limit price, used to release the bid's locked quote
+    pub bid_price: u64,
+    
+    /// Timestamp of the event
+    pub timestamp: i64,
+    
+    /// When the bid was placed (Out on the ask side: 0)
+    pub bid_placed_at: i64,
+    
+    /// When the ask was placed (Out on the bid side: 0)
+    pub ask_placed_at: i64,
+    
+    /// Placement deposit held for the bid, released once it leaves the book
+    pub bid_deposit: u64,
+    
+    /// Placement deposit held for the ask, released once it leaves the book
+    pub ask_deposit: u64,
+    
+    /// Fee charged to the bid side (in bps of base received; negative = rebate in bps of quote)
+    pub bid_fee_bps: i16,
+    
+    /// Fee charged to the ask side (in bps of quote received; negative = rebate in bps of base)
+    pub ask_fee_bps: i16,
+    
+    /// Event type (see `QueueEventType`)
+    pub event_type: u8,
+    
+    /// Sides still to apply and orders that left the book (see flag consts)
+    pub flags: u8,
+    
+    /// Explicit padding to keep the layout free of implicit padding
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _padding: [u8; 2],
+}
+
+unsafe impl Pod for QueueEvent {}
+unsafe impl Zeroable for QueueEvent {}
+
+impl QueueEvent {
+    pub const SIZE: usize = 16 + // bid_order_id
+        16 + // ask_order_id
+        32 + // bid_trader
+        32 + // ask_trader
+        8 +  // seq_num
+        8 +  // price
+        8 +  // size
+        8 +  // bid_price
+        8 +  // timestamp
+        8 +  // bid_placed_at
+        8 +  // ask_placed_at
+        8 +  // bid_deposit
+        8 +  // ask_deposit
+        2 +  // bid_fee_bps
+        2 +  // ask_fee_bps
+        1 +  // event_type
+        1 +  // flags
+        2;   // padding
+    
+    /// Bid side still has to be applied to its trader state
+    pub const BID_PENDING: u8 = 1;
+    /// Ask side still has to be applied to its trader state
+    pub const ASK_PENDING: u8 = 2;
+    /// Bid order left the book (fully filled or out)
+    pub const BID_DONE: u8 = 4;
+    /// Ask order left the book (fully filled or out)
+    pub const ASK_DONE: u8 = 8;
+    /// Out events: the order expired without a fill, so its deposit is forfeited
+    pub const DEPOSIT_FORFEIT: u8 = 16;
+    
+    /// Build a fill between a resting/incoming bid and ask
+    /// Both sides start pending; `*_DONE` flags are set for orders that left the book
+    pub fn fill(
+        bid: &Order,
+        ask: &Order,
+        price: u64,
+        size: u64,
+        bid_fee_bps: i16,
+        ask_fee_bps: i16,
+        timestamp: i64,
+    ) -> Self {
+        let mut flags = Self::BID_PENDING | Self::ASK_PENDING;
+        if bid.is_filled() {
+            flags |= Self::BID_DONE;
+        }
+        if ask.is_filled() {
+            flags |= Self::ASK_DONE;
+        }
+        
+        Self {
+            bid_order_id: bid.order_id,
+            ask_order_id: ask.order_id,
+            bid_trader: bid.trader,
+            ask_trader: ask.trader,
+            seq_num: 0,
+            price,
+            size,
+            bid_price: bid.price,
+            timestamp,
+            bid_placed_at: bid.timestamp,
+            ask_placed_at: ask.timestamp,
+            bid_deposit: bid.deposit_lamports as u64,
+            ask_deposit: ask.deposit_lamports as u64,
+            bid_fee_bps,
+            ask_fee_bps,
+            event_type: QueueEventType::Fill as u8,
+            flags,
+            _padding: [0; 2],
+        }
+    }
+    
+    /// Build an out event releasing a removed order's remaining funds and its
+    /// placement deposit (forfeited if the order expired without a fill)
+    pub fn out(order: &Order, timestamp: i64) -> Self {
+        let mut event = Self::zeroed();
+        event.price = order.price;
+        event.size = order.remaining_size;
+        event.timestamp = timestamp;
+        event.event_type = QueueEventType::Out as u8;
+        if order.is_bid() {
+            event.bid_order_id = order.order_id;
+            event.bid_trader = order.trader;
+            event.bid_price = order.price;
+            event.bid_placed_at = order.timestamp;
+            event.bid_deposit = order.deposit_lamports as u64;
+            event.flags = Self::BID_PENDING | Self::BID_DONE;
+        } else {
+            event.ask_order_id = order.order_id;
+            event.ask_trader = order.trader;
+            event.ask_placed_at = order.timestamp;
+            event.ask_deposit = order.deposit_lamports as u64;
+            event.flags = Self::ASK_PENDING | Self::ASK_DONE;
+        }
+        if order.cumulative_filled == 0 && order.is_expired(timestamp) {
+            event.flags |= Self::DEPOSIT_FORFEIT;
+        }
+        event
+    }
+    
+    /// Placement deposit this event forfeits (0 unless it is an untouched expiry)
+    pub fn forfeited_deposit(&self) -> u64 {
+        if self.flags & Self::DEPOSIT_FORFEIT == 0 {
+            return 0;
+        }
+        self.bid_deposit + self.ask_deposit // Out events carry one side only
+    }
+    
+    /// Apply the bid side to the buyer's trader state and clear its pending flag
+    /// Returns the fee charged (in base units) and the maker rebate paid (in quote units)
+    ///
+    /// A rebate is paid in the asset the seller's taker fee was charged in, so
+    /// each fill funds its own rebate.
+    pub fn apply_bid(&mut self, trader_state: &mut TraderState, lot_size: u64) -> Result<(u64, u64)> {
+        let quote_released = self.bid_quote_released(lot_size)?;
+        let (fee, rebate) = if self.event_type == QueueEventType::Fill as u8 {
+            let quote_amount = self.quote_amount(lot_size)?;
+            let (fee, rebate) = self.bid_fee(lot_size)?;
+            trader_state.settle_bid_fill(quote_released, quote_amount, self.size - fee)?;
+            trader_state.quote_available = trader_state.quote_available
+                .checked_add(rebate)
+                .ok_or(DexError::MathOverflow)?;
+            trader_state.record_order_activity(0, 1, self.timestamp);
+            (fee, rebate)
+        } else {
+            trader_state.unlock_quote(quote_released)?;
+            (0, 0)
+        };
+        
+        if self.flags & Self::BID_DONE != 0 {
+            trader_state.open_order_count = trader_state.open_order_count.saturating_sub(1);
+            trader_state.release_deposit(self.bid_deposit, self.flags & Self::DEPOSIT_FORFEIT != 0)?;
+        }
+        self.flags &= !Self::BID_PENDING;
+        Ok((fee, rebate))
+    }
+    
+    /// Apply the ask side to the seller's trader state and clear its pending flag
+    /// Returns the fee charged (in quote units) and the maker rebate paid (in base units)
+    ///
+    /// An ask's rebate is paid in base, not quote: the buying taker's fee is
+    /// charged in base, and a quote rebate would have to come out of quote
+    /// fees other fills paid, which a market with mostly buying takers may
+    /// not have accrued.
+    pub fn apply_ask(&mut self, trader_state: &mut TraderState, lot_size: u64) -> Result<(u64, u64)> {
+        let (fee, rebate) = if self.event_type == QueueEventType::Fill as u8 {
+            let quote_amount = self.quote_amount(lot_size)?;
+            let (fee, rebate) = self.ask_fee(lot_size)?;
+            trader_state.settle_ask_fill(self.size, quote_amount - fee)?;
+            trader_state.base_available = trader_state.base_available
+                .checked_add(rebate)
+                .ok_or(DexError::MathOverflow)?;
+            trader_state.record_order_activity(0, 1, self.timestamp);
+            (fee, rebate)
+        } else {
+            trader_state.unlock_base(self.size)?;
+            (0, 0)
+        };
+        
+        if self.flags & Self::ASK_DONE != 0 {
+            trader_state.open_order_count = trader_state.open_order_count.saturating_sub(1);
+            trader_state.release_deposit(self.ask_deposit, self.flags & Self::DEPOSIT_FORFEIT != 0)?;
+        }
+        self.flags &= !Self::ASK_PENDING;
+        Ok((fee, rebate))
+    }
+    
+    /// Fee charged to the buyer of a fill (in base units) and maker rebate paid to it (in quote units)
+    pub fn bid_fee(&self, lot_size: u64) -> Result<(u64, u64)> {
+        if self.bid_fee_bps >= 0 {
+            Ok((calculate_fee(self.size, self.bid_fee_bps as u16), 0))
+        } else {
+            Ok((0, calculate_fee(self.quote_amount(lot_size)?, self.bid_fee_bps.unsigned_abs())))
+        }
+    }
+    
+    /// Fee charged to the seller of a fill (in quote units) and maker rebate paid to it (in base units)
+    pub fn ask_fee(&self, lot_size: u64) -> Result<(u64, u64)> {
+        if self.ask_fee_bps >= 0 {
+            Ok((calculate_fee(self.quote_amount(lot_size)?, self.ask_fee_bps as u16), 0))
+        } else {
+            Ok((0, calculate_fee(self.size, self.ask_fee_bps.unsigned_abs())))
+        }
+    }
+    
+    /// Whether both sides have been applied
+    pub fn is_applied(&self) -> bool {
+        self.flags & (Self::BID_PENDING | Self::ASK_PENDING) == 0
+    }
+    
+    /// Quote amount exchanged by a fill
+    pub fn quote_amount(&self, lot_size: u64) -> Result<u64> {
+        self.price
+            .checked_mul(self.size)
+            .and_then(|v| v.checked_div(lot_size))
+            .ok_or(DexError::MathOverflow.into())
+    }
+    
+    /// How long the bid (or the ask) rested on the book before this event
+    pub fn rested_seconds(&self, is_bid: bool) -> i64 {
+        let placed_at = if is_bid { self.bid_placed_at } else { self.ask_placed_at };
+        self.timestamp.saturating_sub(placed_at).max(0)
+    }
+    
+    /// Quote locked by the bid for the filled (or released) size
+    pub fn bid_quote_released(&self, lot_size: u64) -> Result<u64> {
+        self.bid_price
+            .checked_mul(self.size)
+            .and_then(|v| v.checked_div(lot_size))
+            .ok_or(DexError::MathOverflow.into())
+    }
+}
+
+const _: () = assert!(std::mem::size_of::<QueueEvent>() == QueueEvent::SIZE);
+
+/// Per-market ring buffer of fill/out events
+///
+/// Matching appends events; `consume_events` applies the makers' balance
+/// changes and advances `head`. Events are stored as raw bytes after the
+/// header, so capacity is set by the account size. Consumed events stay in
+/// their slot until overwritten, and the event with sequence number `s`
+/// always lives at index `s % capacity`, so the last `capacity` events can
+/// be replayed.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EventQueue {
+    /// Market this queue belongs to
+    pub market: Pubkey,
+    
+    /// Index of the oldest unconsumed event
+    pub head: u64,
+    
+    /// Number of unconsumed events
+    pub count: u64,
+    
+    /// Sequence number of the next event pushed
+    pub seq_num: u64,
+    
+    /// Reserved space for future extensions
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _reserved: [u8; 32],
+    
+    // Event ring buffer follows (stored as raw bytes)
+}
+
+impl EventQueue {
+    pub const HEADER_SIZE: usize = 8 + // discriminator
+        32 + // market
+        8 +  // head
+        8 +  // count
+        8 +  // seq_num
+        32;  // reserved
+    
+    /// Number of events the account can hold
+    pub fn capacity(data: &[u8]) -> u64 {
+        (data.len().saturating_sub(Self::HEADER_SIZE) / QueueEvent::SIZE) as u64
+    }
+    
+    /// Append an event, assigning its sequence number
+    pub fn push(&mut self, data: &mut [u8], mut event: QueueEvent) -> Result<()> {
+        let capacity = Self::capacity(data);
+        require!(self.count < capacity, DexError::EventQueueFull);
+        
+        event.seq_num = self.seq_num;
+        let index = (self.head + self.count) % capacity;
+        Self::write_event(data, index, &event);
+        
+        self.count += 1;
+        self.seq_num = self.seq_num
+            .checked_add(1)
+            .ok_or(DexError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Oldest unconsumed event
+    pub fn peek_front(&self, data: &[u8]) -> Option<QueueEvent> {
+        if self.count == 0 {
+            return None;
+        }
+        let offset = Self::HEADER_SIZE + self.head as usize * QueueEvent::SIZE;
+        Some(bytemuck::pod_read_unaligned(&data[offset..offset + QueueEvent::SIZE]))
+    }
+    
+    /// Overwrite the oldest event (after partially applying it)
+    pub fn update_front(&mut self, data: &mut [u8], event: &QueueEvent) {
+        Self::write_event(data, self.head, event);
+    }
+    
+    /// Drop the oldest event (its bytes are kept for replay until overwritten)
+    pub fn pop_front(&mut self, data: &mut [u8]) -> Result<()> {
+        require!(self.count > 0, DexError::InvalidAccountState);
+        self.head = (self.head + 1) % Self::capacity(data);
+        self.count -= 1;
+        Ok(())
+    }
+    
+    /// Event with sequence number `seq_num`, consumed or not, if still retained
+    pub fn get_by_seq(&self, data: &[u8], seq_num: u64) -> Option<QueueEvent> {
+        let capacity = Self::capacity(data);
+        if capacity == 0 || seq_num >= self.seq_num || seq_num < self.seq_num.saturating_sub(capacity) {
+            return None;
+        }
+        let offset = Self::HEADER_SIZE + (seq_num % capacity) as usize * QueueEvent::SIZE;
+        Some(bytemuck::pod_read_unaligned(&data[offset..offset + QueueEvent::SIZE]))
+    }
+    
+    /// Whether the event with sequence number `seq_num` has been consumed
+    pub fn is_consumed(&self, seq_num: u64) -> bool {
+        seq_num < self.seq_num.saturating_sub(self.count)
+    }
+    
+    fn write_event(data: &mut [u8], index: u64, event: &QueueEvent) {
+        let offset = Self::HEADER_SIZE + index as usize * QueueEvent::SIZE;
+        data[offset..offset + QueueEvent::SIZE].copy_from_slice(bytemuck::bytes_of(event));
+    }
+}
+
+/// Load the trader states a settling crank was passed as remaining accounts
+///
+/// Each must belong to the market and be passed only once: every copy is
+/// written back on exit, so a stale duplicate would overwrite the balances
+/// credited to the first one.
+pub fn load_trader_states<'info>(
+    market_key: &Pubkey,
+    account_infos: &'info [AccountInfo<'info>],
+) -> Result<Vec<Account<'info, TraderState>>> {
+    let mut trader_states: Vec<Account<'info, TraderState>> = Vec::with_capacity(account_infos.len());
+    for account_info in account_infos.iter() {
+        require!(
+            !trader_states.iter().any(|t| t.key() == account_info.key()),
+            DexError::DuplicateAccount
+        );
+        let trader_state = Account::<TraderState>::try_from(account_info)?;
+        require!(trader_state.market == *market_key, DexError::InvalidAccountState);
+        trader_states.push(trader_state);
+    }
+    Ok(trader_states)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::{Side, TimeInForce};
+    use crate::test_utils::{account_data, zeroed};
+    
+    const LOT_SIZE: u64 = 1_000;
+    
+    fn trader_state() -> TraderState {
+        zeroed(TraderState::SIZE)
+    }
+    
+    /// A fill of 10 lots at 2_000 between a bid limited at 2_100 and an ask
+    fn fill_event(bid_fee_bps: i16, ask_fee_bps: i16) -> QueueEvent {
+        let clock = Clock::default();
+        let mut bid = Order::new(1, Pubkey::new_unique(), Side::Bid, 2_100, 10_000, TimeInForce::GTC, &clock);
+        let mut ask = Order::new(2, Pubkey::new_unique(), Side::Ask, 2_000, 10_000, TimeInForce::GTC, &clock);
+        bid.fill(10_000).unwrap();
+        ask.fill(10_000).unwrap();
+        QueueEvent::fill(&bid, &ask, 2_000, 10_000, bid_fee_bps, ask_fee_bps, 0)
+    }
+    
+    #[test]
+    fn taker_bid_pays_its_fee_in_base_and_maker_ask_earns_a_base_rebate() {
+        let mut event = fill_event(30, -10);
+        
+        let mut buyer = trader_state();
+        buyer.quote_locked = 21_000;
+        buyer.open_order_count = 1;
+        assert_eq!(event.apply_bid(&mut buyer, LOT_SIZE).unwrap(), (30, 0));
+        assert_eq!(buyer.base_available, 9_970);
+        assert_eq!(buyer.quote_locked, 0);
+        assert_eq!(buyer.quote_available, 1_000); // Locked at 2_100, paid 2_000
+        assert_eq!(buyer.open_order_count, 0);
+        
+        let mut seller = trader_state();
+        seller.base_locked = 10_000;
+        assert_eq!(event.apply_ask(&mut seller, LOT_SIZE).unwrap(), (0, 10));
+        assert_eq!(seller.base_locked, 0);
+        assert_eq!(seller.base_available, 10);
+        assert_eq!(seller.quote_available, 20_000);
+        assert!(event.is_applied());
+    }
+    
+    #[test]
+    fn taker_ask_pays_its_fee_in_quote_and_maker_bid_earns_a_quote_rebate() {
+        let mut event = fill_event(-10, 30);
+        
+        let mut buyer = trader_state();
+        buyer.quote_locked = 21_000;
+        assert_eq!(event.apply_bid(&mut buyer, LOT_SIZE).unwrap(), (0, 20));
+        assert_eq!(buyer.base_available, 10_000);
+        assert_eq!(buyer.quote_available, 1_020);
+        
+        let mut seller = trader_state();
+        seller.base_locked = 10_000;
+        assert_eq!(event.apply_ask(&mut seller, LOT_SIZE).unwrap(), (60, 0));
+        assert_eq!(seller.quote_available, 19_940);
+        assert_eq!(seller.base_available, 0);
+    }
+    
+    #[test]
+    fn out_event_releases_the_remaining_lock() {
+        let clock = Clock::default();
+        let mut bid = Order::new(1, Pubkey::new_unique(), Side::Bid, 2_000, 10_000, TimeInForce::GTC, &clock);
+        bid.fill(4_000).unwrap();
+        let mut event = QueueEvent::out(&bid, 0);
+        
+        let mut buyer = trader_state();
+        buyer.quote_locked = 12_000;
+        buyer.open_order_count = 1;
+        assert_eq!(event.apply_bid(&mut buyer, LOT_SIZE).unwrap(), (0, 0));
+        assert_eq!(buyer.quote_locked, 0);
+        assert_eq!(buyer.quote_available, 12_000);
+        assert_eq!(buyer.open_order_count, 0);
+    }
+    
+    fn trader_state_data(market: Pubkey) -> Vec<u8> {
+        let mut trader_state = trader_state();
+        trader_state.trader = Pubkey::new_unique();
+        trader_state.market = market;
+        account_data(&trader_state)
+    }
+    
+    #[test]
+    fn load_trader_states_rejects_duplicates() {
+        let market = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = trader_state_data(market);
+        let account_info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        
+        let single = [account_info.clone()];
+        let loaded = load_trader_states(&market, &single).unwrap();
+        assert_eq!(loaded.len(), 1);
+        
+        let twice = [account_info.clone(), account_info];
+        assert_eq!(
+            load_trader_states(&market, &twice).err().unwrap(),
+            DexError::DuplicateAccount.into()
+        );
+    }
+    
+    #[test]
+    fn load_trader_states_rejects_other_markets() {
+        let market = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = trader_state_data(Pubkey::new_unique());
+        let account_info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        
+        let accounts = [account_info];
+        assert_eq!(
+            load_trader_states(&market, &accounts).err().unwrap(),
+            DexError::InvalidAccountState.into()
+        );
+    }
+}
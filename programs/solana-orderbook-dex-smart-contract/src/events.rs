@@ -1,4 +1,85 @@
 use anchor_lang::prelude::*;
+#[cfg(not(feature = "event-cpi"))]
+use std::marker::PhantomData;
+
+/// Destination for the events an instruction emits
+///
+/// By default events are written to the program log, exactly like `emit!`.
+/// Built with the `event-cpi` feature, each event is instead sent as a
+/// self-CPI (Anchor's `emit_cpi!`), so it lands in the transaction's inner
+/// instructions and cannot be lost to log truncation in busy transactions.
+/// Handlers build one with `event_sink!(ctx)` and pass it to helpers.
+pub struct EventSink<'info> {
+    #[cfg(feature = "event-cpi")]
+    authority: AccountInfo<'info>,
+    #[cfg(feature = "event-cpi")]
+    bump: u8,
+    #[cfg(not(feature = "event-cpi"))]
+    _info: PhantomData<AccountInfo<'info>>,
+}
+
+impl<'info> EventSink<'info> {
+    #[cfg(not(feature = "event-cpi"))]
+    pub fn new() -> Self {
+        Self { _info: PhantomData }
+    }
+    
+    #[cfg(feature = "event-cpi")]
+    pub fn new(authority: AccountInfo<'info>, bump: u8) -> Self {
+        Self { authority, bump }
+    }
+    
+    #[cfg(not(feature = "event-cpi"))]
+    pub fn emit<E: anchor_lang::Event>(&self, event: E) -> Result<()> {
+        anchor_lang::solana_program::log::sol_log_data(&[&event.data()]);
+        Ok(())
+    }
+    
+    #[cfg(feature = "event-cpi")]
+    pub fn emit<E: anchor_lang::Event>(&self, event: E) -> Result<()> {
+        use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+        
+        let data: Vec<u8> = anchor_lang::event::EVENT_IX_TAG_LE
+            .into_iter()
+            .chain(event.data())
+            .collect();
+        let ix = Instruction::new_with_bytes(
+            crate::ID,
+            &data,
+            vec![AccountMeta::new_readonly(*self.authority.key, true)],
+        );
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            std::slice::from_ref(&self.authority),
+            &[&[b"__event_authority", &[self.bump]]],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "event-cpi"))]
+impl Default for EventSink<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Build the instruction's `EventSink` from its context
+///
+/// With `event-cpi` the accounts struct must carry `#[event_cpi]`.
+macro_rules! event_sink {
+    ($ctx:ident) => {{
+        #[cfg(feature = "event-cpi")]
+        let sink = $crate::events::EventSink::new(
+            $ctx.accounts.event_authority.to_account_info(),
+            $ctx.bumps.event_authority,
+        );
+        #[cfg(not(feature = "event-cpi"))]
+        let sink = $crate::events::EventSink::new();
+        sink
+    }};
+}
+pub(crate) use event_sink;
 
 /// Event emitted when a new market is created
 #[event]
@@ -11,16 +92,42 @@ pub struct MarketCreated {
     pub timestamp: i64,
 }
 
+/// Event emitted when a trader posts a batch of orders with place_multiple_orders
+#[event]
+pub struct MultipleOrdersPlaced {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub count: u8,
+    pub base_locked: u64,
+    pub quote_locked: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market creator seeds the book with a ladder of orders
+#[event]
+pub struct LiquiditySeeded {
+    pub market: Pubkey,
+    pub start_price: u64,
+    pub levels: u8, // Per side
+    pub spacing: u64, // Price distance between levels
+    pub size_per_level: u64,
+    pub base_deposited: u64,
+    pub quote_deposited: u64,
+    pub timestamp: i64,
+}
+
 /// Event emitted when an order is placed
 #[event]
 pub struct OrderPlaced {
     pub market: Pubkey,
     pub trader: Pubkey,
     pub order_id: u128,
+    pub client_order_id: u64,
     pub side: u8, // 0 = bid, 1 = ask
     pub price: u64,
     pub size: u64,
     pub time_in_force: u8,
+    pub origin: u32, // 0 = untagged, 1 = UI, 2 = API, >= 256 = CPI program tag
     pub timestamp: i64,
 }
 
@@ -30,24 +137,283 @@ pub struct OrderCancelled {
     pub market: Pubkey,
     pub trader: Pubkey,
     pub order_id: u128,
+    pub client_order_id: u64,
     pub remaining_size: u64,
     pub timestamp: i64,
 }
 
+/// Event emitted when a trader pulls one side of their quotes
+#[event]
+pub struct OrdersCancelledBySide {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub side: u8, // 0 = bid, 1 = ask
+    pub orders_cancelled: u16,
+    pub released: u64, // Quote for bids, base for asks
+    pub timestamp: i64,
+}
+
+/// Event emitted when a resting order's price or size is changed
+#[event]
+pub struct OrderModified {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub order_id: u128,
+    pub client_order_id: u64,
+    pub old_price: u64,
+    pub new_price: u64,
+    pub old_size: u64, // Remaining size before the change
+    pub new_size: u64,
+    pub priority_kept: bool,
+    pub timestamp: i64,
+}
+
+/// Event emitted for IOC orders placed with a book impact guard
+#[event]
+pub struct BookImpactGuarded {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub order_id: u128,
+    pub mid_price: u64, // Pre-trade mid the guard is measured from
+    pub max_book_impact_bps: u16,
+    pub worst_fill_price: u64, // 0 when nothing filled
+    pub realized_impact_bps: u64,
+    pub filled_size: u64,
+    pub cancelled_size: u64, // Remainder cancelled by the guard or the IOC limit
+    pub timestamp: i64,
+}
+
 /// Event emitted when orders are matched
 #[event]
 pub struct OrderMatched {
     pub market: Pubkey,
     pub bid_order_id: u128,
     pub ask_order_id: u128,
-    pub price: u64,
+    pub bid_client_order_id: u64,
+    pub ask_client_order_id: u64,
+    pub price: u64, // Execution price under the market's match_price_model (batch clearing price in batch auctions)
     pub size: u64,
     pub bid_trader: Pubkey,
     pub ask_trader: Pubkey,
+    pub bid_origin: u32,
+    pub ask_origin: u32,
     pub fill_id: u128,
     pub timestamp: i64,
 }
 
+/// Event emitted to each side of a match with that order's fill progress
+#[event]
+pub struct OrderFilled {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub order_id: u128,
+    pub client_order_id: u64,
+    pub side: u8, // 0 = bid, 1 = ask
+    pub maker: bool, // Resting side; both sides of a batch auction fill are makers
+    pub fill_id: u128, // Shared with the fill's OrderMatched event
+    pub price: u64, // Execution price, as in OrderMatched
+    pub size: u64,
+    pub cumulative_filled: u64,
+    pub remaining_size: u64,
+    pub fee: u64, // Bid: base units, ask: quote units
+    pub rebate: u64, // Maker rebate; bid: quote units, ask: base units
+    pub timestamp: i64,
+}
+
+/// Event emitted when a wallet-to-wallet swap executes against the book
+#[event]
+pub struct SwapExecuted {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub side: u8, // 0 = buy base, 1 = sell base
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64, // Buys: base units, sells: quote units
+    pub fill_count: u8,
+    pub origin: u32,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a platform opens its integrator fee account
+#[event]
+pub struct IntegratorRegistered {
+    pub market: Pubkey,
+    pub platform: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market authority grants a trading seat
+#[event]
+pub struct SeatGranted {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market authority revokes a trading seat
+#[event]
+pub struct SeatRevoked {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market starts or stops requiring trading seats
+#[event]
+pub struct SeatRequirementUpdated {
+    pub market: Pubkey,
+    pub requires_seat: bool,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a platform claims its share of taker fees
+#[event]
+pub struct IntegratorFeesClaimed {
+    pub market: Pubkey,
+    pub platform: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's accrued protocol fees are swept to the fee recipient
+#[event]
+pub struct ProtocolFeesClaimed {
+    pub market: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's fees are put under a revenue-share split
+#[event]
+pub struct RevenueShareCreated {
+    pub market: Pubkey,
+    pub revenue_share: Pubkey,
+    pub beneficiaries: Vec<Pubkey>,
+    pub shares_bps: Vec<u16>, // In the same order as `beneficiaries`
+    pub timestamp: i64,
+}
+
+/// Event emitted when a revenue-share beneficiary claims its cut
+#[event]
+pub struct RevenueShareClaimed {
+    pub market: Pubkey,
+    pub beneficiary: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a trading competition is set up and its prize funded
+#[event]
+pub struct CompetitionCreated {
+    pub competition: Pubkey,
+    pub markets: Vec<Pubkey>,
+    pub start: i64,
+    pub end: i64,
+    pub prize_mint: Pubkey,
+    pub prize_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a trader enters a competition with its trader states
+#[event]
+pub struct CompetitionEntered {
+    pub competition: Pubkey,
+    pub trader: Pubkey,
+    pub markets: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an entrant's competition volume is recorded as its score
+#[event]
+pub struct CompetitionScoreRecorded {
+    pub competition: Pubkey,
+    pub trader: Pubkey,
+    pub volume: u64,      // Recorded by this call
+    pub score: u64,       // Entry total after it
+    pub total_score: u64, // Competition total after it
+    pub timestamp: i64,
+}
+
+/// Event emitted when an entrant claims its share of a competition prize
+#[event]
+pub struct CompetitionPrizeClaimed {
+    pub competition: Pubkey,
+    pub trader: Pubkey,
+    pub score: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when what is left of a competition prize is swept to the fee recipient
+#[event]
+pub struct CompetitionPrizeSwept {
+    pub competition: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a trader prepays placement-deposit lamports
+#[event]
+pub struct PlacementDepositsFunded {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub lamports: u64,
+    pub deposit_balance: u64, // Unheld balance after the deposit
+    pub timestamp: i64,
+}
+
+/// Event emitted when a trader takes back unheld placement-deposit lamports
+#[event]
+pub struct PlacementDepositsWithdrawn {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub lamports: u64,
+    pub deposit_balance: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when forfeited placement deposits are swept to the fee recipient
+#[event]
+pub struct ForfeitedDepositsClaimed {
+    pub market: Pubkey,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the protocol authority proposes (or withdraws) a successor
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey, // Pubkey::default() = proposal withdrawn
+    pub timestamp: i64,
+}
+
+/// Event emitted when the proposed authority accepts and takes over the protocol
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a different order becomes first in line at the best bid or ask
+#[event]
+pub struct TopOfBookChanged {
+    pub market: Pubkey,
+    pub side: u8, // 0 = bid, 1 = ask
+    pub order_id: u128, // 0 when the side is empty
+    pub trader: Pubkey, // Maker now on the touch
+    pub price: u64,
+    pub size: u64,
+    pub timestamp: i64,
+}
+
 /// Event emitted when a fill is settled
 #[event]
 pub struct FillSettled {
@@ -62,6 +428,77 @@ pub struct FillSettled {
     pub timestamp: i64,
 }
 
+/// Event emitted when a market's event queue is initialized
+#[event]
+pub struct EventQueueInitialized {
+    pub market: Pubkey,
+    pub event_queue: Pubkey,
+    pub capacity: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's orderbook is initialized
+#[event]
+pub struct OrderbookInitialized {
+    pub market: Pubkey,
+    pub orderbook: Pubkey,
+    pub capacity: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's trade tape is initialized
+#[event]
+pub struct TradeTapeInitialized {
+    pub market: Pubkey,
+    pub trade_tape: Pubkey,
+    pub capacity: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when queued events are applied by the crank
+#[event]
+pub struct EventsConsumed {
+    pub market: Pubkey,
+    pub consumed: u64,
+    pub remaining: u64,
+    pub base_fees: u64,
+    pub quote_fees: u64,
+    pub base_rebates: u64, // Maker rebates paid in base
+    pub quote_rebates: u64, // Maker rebates paid in quote
+    pub timestamp: i64,
+}
+
+/// Event emitted when match_and_settle matches and settles fills in one instruction
+#[event]
+pub struct MatchedAndSettled {
+    pub market: Pubkey,
+    pub fills: u64,
+    pub quote_volume: u64,
+    pub base_fees: u64,
+    pub quote_fees: u64,
+    pub base_rebates: u64, // Maker rebates paid in base
+    pub quote_rebates: u64, // Maker rebates paid in quote
+    pub timestamp: i64,
+}
+
+/// Event re-emitted from the event queue by replay_events
+#[event]
+pub struct QueueEventReplayed {
+    pub market: Pubkey,
+    pub seq_num: u64,
+    pub event_type: u8, // 0 = fill, 1 = out
+    pub bid_order_id: u128,
+    pub ask_order_id: u128,
+    pub bid_trader: Pubkey,
+    pub ask_trader: Pubkey,
+    pub price: u64,
+    pub size: u64,
+    pub bid_fee_bps: i16,
+    pub ask_fee_bps: i16,
+    pub consumed: bool,
+    pub timestamp: i64,
+}
+
 /// Event emitted when a trader deposits funds
 #[event]
 pub struct DepositEvent {
@@ -79,17 +516,136 @@ pub struct WithdrawEvent {
     pub trader: Pubkey,
     pub market: Pubkey,
     pub mint: Pubkey,
-    pub amount: u64,
+    pub amount: u64, // Debited from the balance, including the fee
+    pub fee: u64, // Early-withdrawal fee kept by the protocol
     pub new_balance: u64,
     pub timestamp: i64,
 }
 
+/// Event emitted when a trader configures withdrawal co-signing
+#[event]
+pub struct WithdrawalCoSignerUpdated {
+    pub trader: Pubkey,
+    pub market: Pubkey,
+    pub co_signer: Pubkey, // Pubkey::default() when removed
+    pub threshold: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a large withdrawal is queued for co-signer approval
+#[event]
+pub struct WithdrawalRequested {
+    pub trader: Pubkey,
+    pub market: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a pending withdrawal is cancelled and funds released
+#[event]
+pub struct WithdrawalCancelled {
+    pub trader: Pubkey,
+    pub market: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a trader's withdrawal allowlist takes effect
+#[event]
+pub struct WithdrawalAllowlistUpdated {
+    pub trader: Pubkey,
+    pub market: Pubkey,
+    pub destinations: Vec<Pubkey>, // Empty when the allowlist is removed
+    pub timestamp: i64,
+}
+
+/// Event emitted when a withdrawal allowlist change is proposed
+#[event]
+pub struct WithdrawalAllowlistChangeProposed {
+    pub trader: Pubkey,
+    pub market: Pubkey,
+    pub destinations: Vec<Pubkey>,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a trader sets or removes their lockdown guardian
+#[event]
+pub struct GuardianUpdated {
+    pub trader: Pubkey,
+    pub market: Pubkey,
+    pub guardian: Pubkey, // Pubkey::default() when removed
+    pub timestamp: i64,
+}
+
+/// Event emitted when a trader account is locked down
+#[event]
+pub struct AccountLockedEvent {
+    pub trader: Pubkey,
+    pub market: Pubkey,
+    pub locked_by: Pubkey,
+    pub orders_cancelled: u16,
+    pub locked_until: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's statistics epoch is finalized
+#[event]
+pub struct EpochRolled {
+    pub market: Pubkey,
+    pub epoch: u64, // The finalized epoch
+    pub started_at: i64,
+    pub volume: u128, // In quote units
+    pub fill_count: u64,
+    pub next_epoch_started_at: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market parameter change is staged behind the timelock
+#[event]
+pub struct MarketParamsUpdateStaged {
+    pub market: Pubkey,
+    pub tick_size: Option<u64>,
+    pub lot_size: Option<u64>,
+    pub min_resting_slots: Option<u64>,
+    pub batch_matching: Option<bool>,
+    pub event_verbosity: Option<u8>,
+    pub match_price_model: Option<u8>, // 0 = maker price, 1 = taker price, 2 = midpoint
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a fee schedule change is staged behind the timelock
+#[event]
+pub struct ProtocolFeesUpdateStaged {
+    pub maker_fee_bps: i16,
+    pub taker_fee_bps: u16,
+    pub max_integrator_fee_bps: u16,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a staged fee schedule takes effect
+#[event]
+pub struct ProtocolFeesUpdated {
+    pub maker_fee_bps: i16,
+    pub taker_fee_bps: u16,
+    pub max_integrator_fee_bps: u16,
+    pub timestamp: i64,
+}
+
 /// Event emitted when market parameters are updated
 #[event]
 pub struct MarketParamsUpdated {
     pub market: Pubkey,
     pub tick_size: Option<u64>,
     pub lot_size: Option<u64>,
+    pub min_resting_slots: Option<u64>,
+    pub batch_matching: Option<bool>,
+    pub event_verbosity: Option<u8>,
+    pub match_price_model: Option<u8>, // 0 = maker price, 1 = taker price, 2 = midpoint
     pub timestamp: i64,
 }
 
@@ -100,3 +656,270 @@ pub struct MarketPauseUpdated {
     pub paused: bool,
     pub timestamp: i64,
 }
+
+/// Event emitted when a market moves to a new lifecycle stage
+#[event]
+pub struct MarketStatusUpdated {
+    pub market: Pubkey,
+    pub previous_status: u8,
+    pub status: u8, // 0 = active, 1 = cancel-only, 2 = delisted
+    pub timestamp: i64,
+}
+
+/// Event emitted when a match_orders or consume_events caller is paid for the fills it cranked
+#[event]
+pub struct CrankRewardPaid {
+    pub market: Pubkey,
+    pub recipient: Pubkey,
+    pub fills: u64,
+    pub amount: u64, // Quote units, taken from accrued protocol fees
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's crank reward changes
+#[event]
+pub struct CrankRewardUpdated {
+    pub market: Pubkey,
+    pub reward_per_fill: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's fee-discount pass changes
+#[event]
+pub struct FeePassUpdated {
+    pub market: Pubkey,
+    pub mint: Pubkey, // Pubkey::default() when the pass was removed
+    pub is_collection: bool,
+    pub min_amount: u64,
+    pub discount_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's loyalty points schedule changes
+#[event]
+pub struct PointsScheduleUpdated {
+    pub market: Pubkey,
+    pub taker_weight: u16,
+    pub maker_weight: u16,
+    pub start: i64,
+    pub end: i64, // 0 when open-ended
+    pub halving_seconds: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's oracle configuration changes
+#[event]
+pub struct MarketOracleUpdated {
+    pub market: Pubkey,
+    pub oracle: Pubkey, // Pubkey::default() when detached
+    pub oracle_type: u8, // 0 = Pyth, 1 = Switchboard
+    pub max_staleness: u64,
+    pub max_deviation_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market oracle change is staged behind the timelock
+#[event]
+pub struct MarketOracleUpdateStaged {
+    pub market: Pubkey,
+    pub oracle: Pubkey, // Pubkey::default() when detaching
+    pub oracle_type: u8, // 0 = Pyth, 1 = Switchboard
+    pub max_staleness: u64,
+    pub max_deviation_bps: u16,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when settled fills are archived and their accounts closed
+#[event]
+pub struct FillsArchived {
+    pub market: Pubkey,
+    pub batch_root: [u8; 32], // Merkle root of this batch's fill leaves
+    pub archive_root: [u8; 32], // Market's cumulative root after this batch
+    pub fill_count: u64,
+    pub archived_fill_count: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's external settlement program changes
+#[event]
+pub struct SettlementProgramUpdated {
+    pub market: Pubkey,
+    pub settlement_program: Pubkey, // Pubkey::default() when detached
+    pub timestamp: i64,
+}
+
+/// Aggregated resting size within one price bucket
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DepthBucket {
+    pub price: u64, // Bucket edge closest to the touch
+    pub size: u64,
+    pub order_count: u16,
+}
+
+/// Event emitted when a trader opens an open-orders slot index
+#[event]
+pub struct OpenOrdersInitialized {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub open_orders: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an orderbook's slab capacity is raised
+#[event]
+pub struct OrderbookExpanded {
+    pub market: Pubkey,
+    pub orderbook: Pubkey,
+    pub payer: Pubkey,
+    pub old_capacity: u64,
+    pub new_capacity: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's premium data feed is opened
+#[event]
+pub struct DataFeedCreated {
+    pub market: Pubkey,
+    pub data_feed: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a subscriber deposits lamports into a data feed
+#[event]
+pub struct DataFeedFunded {
+    pub market: Pubkey,
+    pub subscriber: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the data feed crank rewrites the snapshot
+#[event]
+pub struct DataFeedRefreshed {
+    pub market: Pubkey,
+    pub cranker: Pubkey,
+    pub reward: u64, // Lamports paid out of subscriber deposits
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted by the depth snapshot crank (for heat-map visualizations)
+#[event]
+pub struct DepthSnapshot {
+    pub market: Pubkey,
+    pub bucket_width: u64, // In quote price units
+    pub bids: Vec<DepthBucket>, // Best bid bucket first
+    pub asks: Vec<DepthBucket>, // Best ask bucket first
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's peg protection is configured or re-armed
+#[event]
+pub struct PegProtectionUpdated {
+    pub market: Pubkey,
+    pub peg_price: u64,
+    pub peg_band_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's circuit breaker is configured or trading is resumed
+#[event]
+pub struct CircuitBreakerUpdated {
+    pub market: Pubkey,
+    pub move_bps: u16,
+    pub window_seconds: i64,
+    pub cooldown_seconds: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a fill would move the price past the circuit breaker (market halts)
+#[event]
+pub struct CircuitBreakerTripped {
+    pub market: Pubkey,
+    pub reference_price: u64, // First fill price of the window
+    pub price: u64, // Fill price that tripped the breaker (not executed)
+    pub move_bps: u16,
+    pub halted_until: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a trade would break the peg band (market flips to cancel-only)
+#[event]
+pub struct PegBroken {
+    pub market: Pubkey,
+    pub price: u64,
+    pub peg_price: u64,
+    pub peg_band_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's operator or its powers change
+#[event]
+pub struct MarketOperatorUpdated {
+    pub market: Pubkey,
+    pub operator: Pubkey, // Pubkey::default() when revoked
+    pub permissions: u8,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market's withdrawal cooldown changes
+#[event]
+pub struct WithdrawalCooldownUpdated {
+    pub market: Pubkey,
+    pub cooldown_seconds: u32,
+    pub early_withdrawal_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a risk parameter change is staged behind the timelock
+#[event]
+pub struct RiskParamsUpdateStaged {
+    pub market: Pubkey,
+    pub oracle_max_deviation_bps: Option<u16>, // Staged with the market's oracle configuration
+    pub oracle_max_staleness: Option<u64>,
+    pub peg_band_bps: Option<u16>,
+    pub max_order_notional: Option<u64>,
+    pub min_notional: Option<u64>,
+    pub dust_threshold: Option<u64>,
+    pub max_side_notional: Option<u64>,
+    pub max_open_notional: Option<u64>,
+    pub breaker_move_bps: Option<u16>,
+    pub breaker_window_seconds: Option<i64>,
+    pub breaker_cooldown_seconds: Option<i64>,
+    pub max_cancel_to_fill_ratio: Option<u16>,
+    pub placement_deposit_lamports: Option<u32>,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when staged market risk parameters are applied (changed fields only)
+/// Oracle band changes are reported by MarketOracleUpdated
+#[event]
+pub struct RiskParamsUpdated {
+    pub market: Pubkey,
+    pub peg_band_bps: Option<u16>,
+    pub max_order_notional: Option<u64>,
+    pub min_notional: Option<u64>,
+    pub dust_threshold: Option<u64>,
+    pub max_side_notional: Option<u64>,
+    pub max_open_notional: Option<u64>,
+    pub breaker_move_bps: Option<u16>,
+    pub breaker_window_seconds: Option<i64>,
+    pub breaker_cooldown_seconds: Option<i64>,
+    pub max_cancel_to_fill_ratio: Option<u16>,
+    pub placement_deposit_lamports: Option<u32>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when balance moves between two traders without a token transfer
+#[event]
+pub struct InternalTransferEvent {
+    pub market: Pubkey,
+    pub from_trader: Pubkey,
+    pub to_trader: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
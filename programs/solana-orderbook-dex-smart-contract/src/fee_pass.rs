@@ -0,0 +1,259 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::errors::DexError;
+use crate::state::Market;
+
+/// Metaplex Token Metadata program, which owns NFT metadata accounts
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Metaplex account key of a metadata account (`Key::MetadataV1`)
+const METADATA_V1_KEY: u8 = 4;
+
+// Byte offsets inside a Metaplex metadata account, up to the variable-length fields
+const METADATA_MINT_OFFSET: usize = 33;
+const METADATA_NAME_OFFSET: usize = 65;
+
+/// Taker fee discount `trader` earns with `pass`, in bps (0 without a pass)
+///
+/// A pass is a token account owned by the trader holding at least
+/// `fee_pass_min_amount` of the market's `fee_pass_mint`. On collection
+/// markets it is instead an NFT whose metadata (passed as `metadata`)
+/// carries a verified collection equal to `fee_pass_mint`. A pass that does
+/// not qualify fails the instruction rather than silently charging the
+/// full fee.
+pub fn load_fee_pass_discount(
+    market: &Market,
+    trader: &Pubkey,
+    pass: Option<&InterfaceAccount<TokenAccount>>,
+    metadata: Option<&AccountInfo>,
+) -> Result<u16> {
+    let Some(pass) = pass else {
+        return Ok(0);
+    };
+    require!(market.has_fee_pass(), DexError::InvalidFeePass);
+    require!(pass.owner == *trader, DexError::InvalidFeePass);
+    require!(pass.amount >= market.fee_pass_min_amount.max(1), DexError::InvalidFeePass);
+    
+    if market.fee_pass_is_collection {
+        let metadata = metadata.ok_or(DexError::InvalidFeePass)?;
+        let collection = read_verified_collection(&pass.mint, metadata)?;
+        require!(collection == Some(market.fee_pass_mint), DexError::InvalidFeePass);
+    } else {
+        require!(pass.mint == market.fee_pass_mint, DexError::InvalidFeePass);
+    }
+    Ok(market.fee_pass_discount_bps)
+}
+
+/// Verified collection of the NFT `mint`, read from its metadata account
+fn read_verified_collection(mint: &Pubkey, metadata: &AccountInfo) -> Result<Option<Pubkey>> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    );
+    require!(metadata.key() == expected, DexError::InvalidFeePass);
+    require!(*metadata.owner == TOKEN_METADATA_PROGRAM_ID, DexError::InvalidFeePass);
+    
+    let data = metadata.try_borrow_data()?;
+    let mut reader = MetadataReader { data: &data, offset: 0 };
+    require!(reader.u8()? == METADATA_V1_KEY, DexError::InvalidFeePass);
+    require!(
+        data.get(METADATA_MINT_OFFSET..METADATA_NAME_OFFSET) == Some(mint.as_ref()),
+        DexError::InvalidFeePass
+    );
+    
+    // name, symbol and uri, then seller_fee_basis_points
+    reader.offset = METADATA_NAME_OFFSET;
+    for _ in 0..3 {
+        let len = reader.u32()? as usize;
+        reader.skip(len)?;
+    }
+    reader.skip(2)?;
+    
+    // creators: Option<Vec<Creator>>, 34 bytes per creator
+    if reader.u8()? == 1 {
+        let creators = reader.u32()? as usize;
+        reader.skip(creators.checked_mul(34).ok_or(DexError::InvalidFeePass)?)?;
+    }
+    reader.skip(2)?; // primary_sale_happened, is_mutable
+    for _ in 0..2 {
+        // edition_nonce, token_standard: Option<u8>
+        if reader.u8()? == 1 {
+            reader.skip(1)?;
+        }
+    }
+    
+    // collection: Option<Collection { verified, key }>
+    if reader.u8()? != 1 {
+        return Ok(None);
+    }
+    let verified = reader.u8()? == 1;
+    let key = Pubkey::try_from(reader.bytes(32)?).map_err(|_| DexError::InvalidFeePass)?;
+    Ok(verified.then_some(key))
+}
+
+/// Cursor over Borsh-encoded metadata, failing on truncated data
+struct MetadataReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> MetadataReader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.offset.checked_add(len).ok_or(DexError::InvalidFeePass)?;
+        let bytes = self.data.get(self.offset..end).ok_or(DexError::InvalidFeePass)?;
+        self.offset = end;
+        Ok(bytes)
+    }
+    
+    fn skip(&mut self, len: usize) -> Result<()> {
+        self.bytes(len).map(|_| ())
+    }
+    
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+    
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::{Side, TimeInForce};
+    use crate::state::{GlobalConfig, TraderState};
+    use crate::test_utils::{account_infos, zeroed, TestAccount, Venue};
+    
+    /// Metadata for the NFT `mint` whose collection is `collection`
+    fn metadata(mint: Pubkey, collection: Option<(bool, Pubkey)>) -> TestAccount {
+        let mut data = vec![METADATA_V1_KEY];
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // update_authority
+        data.extend_from_slice(mint.as_ref());
+        for field in ["Pass #1", "PASS", "https://example.com/1.json"] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+        data.extend_from_slice(&500u16.to_le_bytes());
+        data.push(1); // one creator
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&[1, 100]); // verified, share
+        data.extend_from_slice(&[1, 1]); // primary_sale_happened, is_mutable
+        data.extend_from_slice(&[1, 255]); // edition_nonce
+        data.push(0); // no token_standard
+        match collection {
+            Some((verified, key)) => {
+                data.extend_from_slice(&[1, verified as u8]);
+                data.extend_from_slice(key.as_ref());
+            }
+            None => data.push(0),
+        }
+        let (key, _) = Pubkey::find_program_address(
+            &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+            &TOKEN_METADATA_PROGRAM_ID,
+        );
+        TestAccount::new(TOKEN_METADATA_PROGRAM_ID, data).at(key)
+    }
+    
+    #[test]
+    fn a_discount_never_drops_the_taker_fee_below_the_rebate_it_funds() {
+        let mut config: GlobalConfig = zeroed(GlobalConfig::SIZE);
+        config.taker_fee_bps = 30;
+        config.maker_fee_bps = -10;
+        
+        assert_eq!(config.discounted_taker_fee_bps(0, 0), 30);
+        assert_eq!(config.discounted_taker_fee_bps(15, 0), 15);
+        assert_eq!(config.discounted_taker_fee_bps(25, 0), 10);
+        // Half of the fee goes to the integrator, so the market keeps 10 bps of 20
+        assert_eq!(config.discounted_taker_fee_bps(25, 5_000), 20);
+        
+        config.maker_fee_bps = 0;
+        assert_eq!(config.discounted_taker_fee_bps(10_000, 0), 0);
+    }
+    
+    #[test]
+    fn a_token_pass_must_be_the_traders_own_and_hold_enough() {
+        let trader = Pubkey::new_unique();
+        let mut market: Market = zeroed(Market::SIZE);
+        market.fee_pass_mint = Pubkey::new_unique();
+        market.fee_pass_min_amount = 100;
+        market.fee_pass_discount_bps = 15;
+        let mut passes = [
+            TestAccount::token_account(market.fee_pass_mint, trader, 100),
+            TestAccount::token_account(market.fee_pass_mint, trader, 99),
+            TestAccount::token_account(market.fee_pass_mint, Pubkey::new_unique(), 100),
+            TestAccount::token_account(Pubkey::new_unique(), trader, 100),
+        ];
+        let infos = account_infos(&mut passes);
+        let passes: Vec<_> = infos.iter().map(|info| InterfaceAccount::try_from(info).unwrap()).collect();
+        
+        assert_eq!(load_fee_pass_discount(&market, &trader, None, None).unwrap(), 0);
+        assert_eq!(load_fee_pass_discount(&market, &trader, Some(&passes[0]), None).unwrap(), 15);
+        for pass in &passes[1..] {
+            assert_eq!(
+                load_fee_pass_discount(&market, &trader, Some(pass), None).err().unwrap(),
+                DexError::InvalidFeePass.into()
+            );
+        }
+        
+        market.fee_pass_mint = Pubkey::default();
+        assert_eq!(
+            load_fee_pass_discount(&market, &trader, Some(&passes[0]), None).err().unwrap(),
+            DexError::InvalidFeePass.into()
+        );
+    }
+    
+    #[test]
+    fn a_collection_pass_needs_verified_metadata_for_its_own_mint() {
+        let trader = Pubkey::new_unique();
+        let collection = Pubkey::new_unique();
+        let mut market: Market = zeroed(Market::SIZE);
+        market.fee_pass_mint = collection;
+        market.fee_pass_is_collection = true;
+        market.fee_pass_discount_bps = 15;
+        let nft = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let mut accounts = [
+            TestAccount::token_account(nft, trader, 1),
+            metadata(nft, Some((true, collection))),
+            metadata(nft, Some((false, collection))),
+            metadata(nft, Some((true, Pubkey::new_unique()))),
+            metadata(nft, None),
+            metadata(other, Some((true, collection))),
+        ];
+        let infos = account_infos(&mut accounts);
+        let pass = InterfaceAccount::try_from(&infos[0]).unwrap();
+        
+        assert_eq!(load_fee_pass_discount(&market, &trader, Some(&pass), Some(&infos[1])).unwrap(), 15);
+        for metadata in infos[2..].iter().map(Some).chain([None]) {
+            assert_eq!(
+                load_fee_pass_discount(&market, &trader, Some(&pass), metadata).err().unwrap(),
+                DexError::InvalidFeePass.into()
+            );
+        }
+    }
+    
+    #[test]
+    fn a_taker_holding_the_pass_pays_the_discounted_fee() {
+        let pass_mint = Pubkey::new_unique();
+        let mut venue = Venue::new(|market| {
+            market.fee_pass_mint = pass_mint;
+            market.fee_pass_discount_bps = 15;
+        });
+        let maker = venue.add_trader(20_000, 0);
+        let taker = venue.add_trader(0, 40_000);
+        let taker_key = venue.accounts[taker].key;
+        venue.accounts.push(TestAccount::token_account(pass_mint, taker_key, 1));
+        let pass = venue.accounts.len() - 1;
+        let infos = account_infos(&mut venue.accounts);
+        Venue::place(&infos, maker, Venue::order(Side::Ask, 2_000, 20_000, TimeInForce::GTC)).unwrap();
+        let bid = || Venue::order(Side::Bid, 2_000, 10_000, TimeInForce::IOC);
+        
+        // 30 bps of 10_000 base without the pass, 15 bps with it
+        Venue::place(&infos, taker, bid()).unwrap();
+        assert_eq!(Account::<TraderState>::try_from(&infos[taker + 1]).unwrap().base_available, 9_970);
+        Venue::place_with(&infos, taker, bid(), None, Some(pass)).unwrap();
+        assert_eq!(Account::<TraderState>::try_from(&infos[taker + 1]).unwrap().base_available, 19_955);
+    }
+}
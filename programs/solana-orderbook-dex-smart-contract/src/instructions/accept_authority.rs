@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::GlobalConfig;
+use crate::errors::DexError;
+use crate::events::{event_sink, AuthorityTransferred};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = global_config.pending_authority != Pubkey::default() @ DexError::AuthorityTransferNotPending,
+        constraint = new_authority.key() == global_config.pending_authority @ DexError::AuthorityTransferNotPending
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub new_authority: Signer<'info>,
+}
+
+/// Complete a transfer started by propose_authority
+/// The proposed key proves it can sign before it takes over
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let global_config = &mut ctx.accounts.global_config;
+    let previous_authority = global_config.authority;
+    
+    global_config.authority = ctx.accounts.new_authority.key();
+    global_config.pending_authority = Pubkey::default();
+    
+    sink.emit(AuthorityTransferred {
+        previous_authority,
+        new_authority: global_config.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Authority transferred: {} -> {}", previous_authority, global_config.authority);
+    
+    Ok(())
+}
@@ -0,0 +1,301 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::event_queue::EventQueue;
+use crate::orderbook::Orderbook;
+use crate::events::{
+    event_sink, CircuitBreakerUpdated, MarketOracleUpdated, MarketParamsUpdated, ProtocolFeesUpdated,
+    RiskParamsUpdated,
+};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ApplyPendingUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    /// Market whose staged parameters should be applied, if any
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Option<Account<'info, Market>>,
+    
+    /// CHECK: The market's orderbook, required when the tick or lot size changes
+    pub orderbook: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: The market's event queue, required when the tick or lot size changes
+    pub event_queue: Option<UncheckedAccount<'info>>,
+}
+
+/// The new value if it differs from the old one, for update events that
+/// report only what changed
+fn changed<T: PartialEq>(old: T, new: T) -> Option<T> {
+    (old != new).then_some(new)
+}
+
+/// Require a market with nothing priced in its current tick and lot size
+///
+/// Resting orders locked funds at `price * size / lot_size`, and queued
+/// events release them the same way, so both must be gone before the lot
+/// size (or the tick grid orders sit on) can change.
+fn require_drained(market: &Account<Market>, orderbook: Option<&UncheckedAccount>, event_queue: Option<&UncheckedAccount>) -> Result<()> {
+    let orderbook = orderbook.ok_or(DexError::InvalidOrderbookState)?;
+    let orderbook = Orderbook::try_deserialize(&mut &orderbook.try_borrow_data()?[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    require!(orderbook.order_count == 0, DexError::MarketNotDrained);
+    
+    if market.event_queue != Pubkey::default() {
+        let event_queue = event_queue.ok_or(DexError::InvalidAccountState)?;
+        require_keys_eq!(event_queue.key(), market.event_queue, DexError::InvalidAccountState);
+        let event_queue = EventQueue::try_deserialize(&mut &event_queue.try_borrow_data()?[..EventQueue::HEADER_SIZE])?;
+        require!(event_queue.count == 0, DexError::MarketNotDrained);
+    }
+    Ok(())
+}
+
+/// Apply the staged fee schedule and/or the market's staged parameters, oracle and risk limits
+///
+/// Anyone may call this; the change was authorized when it was staged and
+/// the delay gave users time to react. Whatever is due is applied; the call
+/// fails only if nothing is. A new tick or lot size additionally waits for
+/// the book and event queue to be empty.
+pub fn handler(ctx: Context<ApplyPendingUpdate>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
+    let mut pending = false;
+    let mut applied = false;
+    
+    let global_config = &mut ctx.accounts.global_config;
+    if global_config.fees_effective_at != 0 {
+        pending = true;
+        if clock.unix_timestamp >= global_config.fees_effective_at {
+            global_config.maker_fee_bps = global_config.pending_maker_fee_bps;
+            global_config.taker_fee_bps = global_config.pending_taker_fee_bps;
+            global_config.max_integrator_fee_bps = global_config.pending_max_integrator_fee_bps;
+            global_config.fees_effective_at = 0;
+            global_config.validate_fees()?;
+            applied = true;
+            
+            sink.emit(ProtocolFeesUpdated {
+                maker_fee_bps: global_config.maker_fee_bps,
+                taker_fee_bps: global_config.taker_fee_bps,
+                max_integrator_fee_bps: global_config.max_integrator_fee_bps,
+                timestamp: clock.unix_timestamp,
+            })?;
+            
+            msg!("Protocol fees updated: maker={}bps, taker={}bps, max_integrator={}bps", 
+                 global_config.maker_fee_bps, global_config.taker_fee_bps, global_config.max_integrator_fee_bps);
+        }
+    }
+    
+    if let Some(market) = ctx.accounts.market.as_mut() {
+        if market.params_effective_at != 0 {
+            pending = true;
+            if clock.unix_timestamp >= market.params_effective_at {
+                let event = MarketParamsUpdated {
+                    market: market.key(),
+                    tick_size: changed(market.tick_size, market.pending_tick_size),
+                    lot_size: changed(market.lot_size, market.pending_lot_size),
+                    min_resting_slots: changed(market.min_resting_slots, market.pending_min_resting_slots),
+                    batch_matching: changed(market.batch_matching, market.pending_batch_matching),
+                    event_verbosity: changed(market.event_verbosity, market.pending_event_verbosity),
+                    match_price_model: changed(market.match_price_model, market.pending_match_price_model),
+                    timestamp: clock.unix_timestamp,
+                };
+                if event.tick_size.is_some() || event.lot_size.is_some() {
+                    require_drained(market, ctx.accounts.orderbook.as_ref(), ctx.accounts.event_queue.as_ref())?;
+                }
+                
+                market.tick_size = market.pending_tick_size;
+                market.lot_size = market.pending_lot_size;
+                market.min_resting_slots = market.pending_min_resting_slots;
+                market.batch_matching = market.pending_batch_matching;
+                market.event_verbosity = market.pending_event_verbosity;
+                market.match_price_model = market.pending_match_price_model;
+                market.params_effective_at = 0;
+                applied = true;
+                
+                sink.emit(event)?;
+                
+                msg!("Market params updated: market={}", market.key());
+            }
+        }
+        
+        if market.oracle_effective_at != 0 {
+            pending = true;
+            if clock.unix_timestamp >= market.oracle_effective_at {
+                market.oracle = market.pending_oracle;
+                market.oracle_type = market.pending_oracle_type;
+                market.oracle_max_staleness = market.pending_oracle_max_staleness;
+                market.oracle_max_deviation_bps = market.pending_oracle_max_deviation_bps;
+                market.oracle_effective_at = 0;
+                applied = true;
+                
+                sink.emit(MarketOracleUpdated {
+                    market: market.key(),
+                    oracle: market.oracle,
+                    oracle_type: market.oracle_type,
+                    max_staleness: market.oracle_max_staleness,
+                    max_deviation_bps: market.oracle_max_deviation_bps,
+                    timestamp: clock.unix_timestamp,
+                })?;
+                
+                msg!("Market oracle updated: market={}, oracle={}, type={}", 
+                     market.key(), market.oracle, market.oracle_type);
+            }
+        }
+        
+        if market.risk_effective_at != 0 {
+            pending = true;
+            if clock.unix_timestamp >= market.risk_effective_at {
+                let event = RiskParamsUpdated {
+                    market: market.key(),
+                    peg_band_bps: changed(market.peg_band_bps, market.pending_peg_band_bps),
+                    max_order_notional: changed(market.max_order_notional, market.pending_max_order_notional),
+                    min_notional: changed(market.min_notional, market.pending_min_notional),
+                    dust_threshold: changed(market.dust_threshold, market.pending_dust_threshold),
+                    max_side_notional: changed(market.max_side_notional, market.pending_max_side_notional),
+                    max_open_notional: changed(market.max_open_notional, market.pending_max_open_notional),
+                    breaker_move_bps: changed(market.breaker_move_bps, market.pending_breaker_move_bps),
+                    breaker_window_seconds: changed(market.breaker_window_seconds, market.pending_breaker_window_seconds),
+                    breaker_cooldown_seconds: changed(market.breaker_cooldown_seconds, market.pending_breaker_cooldown_seconds),
+                    max_cancel_to_fill_ratio: changed(market.max_cancel_to_fill_ratio, market.pending_max_cancel_to_fill_ratio),
+                    placement_deposit_lamports: changed(market.placement_deposit_lamports, market.pending_placement_deposit_lamports),
+                    timestamp: clock.unix_timestamp,
+                };
+                let breaker_changed = event.breaker_move_bps.is_some() ||
+                    event.breaker_window_seconds.is_some() ||
+                    event.breaker_cooldown_seconds.is_some();
+                
+                market.peg_band_bps = market.pending_peg_band_bps;
+                market.max_order_notional = market.pending_max_order_notional;
+                market.min_notional = market.pending_min_notional;
+                market.dust_threshold = market.pending_dust_threshold;
+                market.max_side_notional = market.pending_max_side_notional;
+                market.max_open_notional = market.pending_max_open_notional;
+                market.breaker_move_bps = market.pending_breaker_move_bps;
+                market.breaker_window_seconds = market.pending_breaker_window_seconds;
+                market.breaker_cooldown_seconds = market.pending_breaker_cooldown_seconds;
+                market.max_cancel_to_fill_ratio = market.pending_max_cancel_to_fill_ratio;
+                market.placement_deposit_lamports = market.pending_placement_deposit_lamports;
+                market.risk_effective_at = 0;
+                applied = true;
+                
+                sink.emit(event)?;
+                
+                // New breaker limits start a fresh window; a halt in progress runs its course
+                if breaker_changed {
+                    market.breaker_reference_price = 0;
+                    market.breaker_window_start = 0;
+                    
+                    sink.emit(CircuitBreakerUpdated {
+                        market: market.key(),
+                        move_bps: market.breaker_move_bps,
+                        window_seconds: market.breaker_window_seconds,
+                        cooldown_seconds: market.breaker_cooldown_seconds,
+                        timestamp: clock.unix_timestamp,
+                    })?;
+                }
+                
+                msg!("Risk params updated: market={}", market.key());
+            }
+        }
+    }
+    
+    require!(pending, DexError::NoPendingUpdate);
+    require!(applied, DexError::UpdateNotReady);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::{Side, TimeInForce};
+    use crate::test_utils::{account_infos, install_clock, load, Venue, NOW};
+    
+    fn run<'info>(infos: &'info [AccountInfo<'info>]) -> Result<()> {
+        install_clock();
+        let mut accounts = ApplyPendingUpdate {
+            global_config: Account::try_from(&infos[Venue::GLOBAL_CONFIG])?,
+            market: Some(Account::try_from(&infos[Venue::MARKET])?),
+            orderbook: Some(UncheckedAccount::try_from(&infos[Venue::ORDERBOOK])),
+            event_queue: Some(UncheckedAccount::try_from(&infos[Venue::EVENT_QUEUE])),
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()))?;
+        accounts.exit(&crate::ID)
+    }
+    
+    /// Stage a lot size change that is due now, keeping everything else
+    fn stage_lot_size(market: &mut Market, lot_size: u64) {
+        market.pending_tick_size = market.tick_size;
+        market.pending_lot_size = lot_size;
+        market.params_effective_at = NOW;
+    }
+    
+    #[test]
+    fn a_new_lot_size_waits_for_resting_orders_to_leave() {
+        let mut venue = Venue::new(|market| stage_lot_size(market, 2 * Venue::LOT_SIZE));
+        let maker = venue.add_trader(10_000, 0);
+        let infos = account_infos(&mut venue.accounts);
+        Venue::place(&infos, maker, Venue::order(Side::Ask, 2_000, 10_000, TimeInForce::GTC)).unwrap();
+        
+        assert_eq!(run(&infos).err().unwrap(), DexError::MarketNotDrained.into());
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!(market.lot_size, Venue::LOT_SIZE);
+    }
+    
+    #[test]
+    fn a_new_lot_size_waits_for_queued_fills() {
+        let mut venue = Venue::new(|market| stage_lot_size(market, 2 * Venue::LOT_SIZE));
+        let maker = venue.add_trader(10_000, 0);
+        let taker = venue.add_trader(0, 20_000);
+        let infos = account_infos(&mut venue.accounts);
+        Venue::place(&infos, maker, Venue::order(Side::Ask, 2_000, 10_000, TimeInForce::GTC)).unwrap();
+        Venue::place(&infos, taker, Venue::order(Side::Bid, 2_000, 10_000, TimeInForce::IOC)).unwrap();
+        
+        // The book is empty again, but the maker's fill is still queued
+        let orderbook: Orderbook = load(&infos[Venue::ORDERBOOK]);
+        assert_eq!(orderbook.order_count, 0);
+        assert_eq!(run(&infos).err().unwrap(), DexError::MarketNotDrained.into());
+    }
+    
+    #[test]
+    fn a_due_update_applies_on_a_drained_market() {
+        let mut venue = Venue::new(|market| {
+            stage_lot_size(market, 2 * Venue::LOT_SIZE);
+            market.pending_min_resting_slots = 2;
+        });
+        let infos = account_infos(&mut venue.accounts);
+        
+        run(&infos).unwrap();
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!(market.lot_size, 2 * Venue::LOT_SIZE);
+        assert_eq!(market.min_resting_slots, 2);
+        assert_eq!(market.params_effective_at, 0);
+        assert_eq!(run(&infos).err().unwrap(), DexError::NoPendingUpdate.into());
+    }
+    
+    #[test]
+    fn an_update_staged_for_later_is_not_applied() {
+        let mut venue = Venue::new(|market| {
+            stage_lot_size(market, 2 * Venue::LOT_SIZE);
+            market.params_effective_at = NOW + 1;
+        });
+        let infos = account_infos(&mut venue.accounts);
+        
+        assert_eq!(run(&infos).err().unwrap(), DexError::UpdateNotReady.into());
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!(market.lot_size, Venue::LOT_SIZE);
+    }
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState, WithdrawalAllowlist};
+use crate::errors::DexError;
+use crate::events::{event_sink, WithdrawalAllowlistUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ApplyWithdrawalAllowlist<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal_allowlist", trader_state.key().as_ref()],
+        bump = withdrawal_allowlist.bump,
+        has_one = trader_state
+    )]
+    pub withdrawal_allowlist: Account<'info, WithdrawalAllowlist>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+}
+
+/// Apply a proposed allowlist change once its delay has passed
+/// An empty list disables the allowlist and closes its account
+pub fn handler(ctx: Context<ApplyWithdrawalAllowlist>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
+    let allowlist = &mut ctx.accounts.withdrawal_allowlist;
+    
+    require!(allowlist.pending_effective_at != 0, DexError::InvalidWithdrawalAllowlist);
+    require!(
+        clock.unix_timestamp >= allowlist.pending_effective_at,
+        DexError::AllowlistChangeNotReady
+    );
+    
+    let destinations: Vec<Pubkey> = allowlist.pending_destinations
+        .iter()
+        .copied()
+        .filter(|destination| *destination != Pubkey::default())
+        .collect();
+    
+    if destinations.is_empty() {
+        ctx.accounts.trader_state.withdrawal_allowlist_enabled = false;
+        allowlist.close(ctx.accounts.trader.to_account_info())?;
+    } else {
+        allowlist.destinations = allowlist.pending_destinations;
+        allowlist.pending_destinations = [Pubkey::default(); 4];
+        allowlist.pending_effective_at = 0;
+    }
+    
+    sink.emit(WithdrawalAllowlistUpdated {
+        trader: ctx.accounts.trader.key(),
+        market: ctx.accounts.market.key(),
+        destinations,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Withdrawal allowlist applied: trader={}", ctx.accounts.trader.key());
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::propose_withdrawal_allowlist::{self, ProposeWithdrawalAllowlist};
+    use crate::test_utils::{account_infos, install_clock, load, zeroed, TestAccount, Venue, NOW};
+    
+    /// Propose `destinations` for the trader whose signer is at `trader`;
+    /// its allowlist sits at `allowlist`
+    fn propose<'info>(infos: &'info [AccountInfo<'info>], trader: usize, allowlist: usize, destinations: Vec<Pubkey>) -> Result<()> {
+        install_clock();
+        let mut accounts = ProposeWithdrawalAllowlist {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            trader_state: Account::try_from(&infos[trader + 1])?,
+            withdrawal_allowlist: Account::try_from(&infos[allowlist])?,
+            trader: Signer::try_from(&infos[trader])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        propose_withdrawal_allowlist::handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), destinations)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    fn apply<'info>(infos: &'info [AccountInfo<'info>], trader: usize, allowlist: usize) -> Result<()> {
+        install_clock();
+        let mut accounts = ApplyWithdrawalAllowlist {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            trader_state: Account::try_from(&infos[trader + 1])?,
+            withdrawal_allowlist: Account::try_from(&infos[allowlist])?,
+            trader: Signer::try_from(&infos[trader])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()))?;
+        accounts.exit(&crate::ID)
+    }
+    
+    /// Pretend the proposal's delay has passed
+    fn make_due<'info>(info: &'info AccountInfo<'info>) {
+        let mut allowlist = Account::<WithdrawalAllowlist>::try_from(info).unwrap();
+        allowlist.pending_effective_at = NOW;
+        allowlist.exit(&crate::ID).unwrap();
+    }
+    
+    #[test]
+    fn allowlist_changes_wait_out_the_delay_from_the_latest_proposal() {
+        let mut venue = Venue::new(|_| {});
+        let trader = venue.add_trader(0, 0);
+        let mut allowlist: WithdrawalAllowlist = zeroed(WithdrawalAllowlist::SIZE);
+        allowlist.trader_state = venue.accounts[trader + 1].key;
+        allowlist.destinations[0] = Pubkey::new_unique();
+        venue.accounts.push(TestAccount::program_owned(&allowlist));
+        let allowlist = venue.accounts.len() - 1;
+        let infos = account_infos(&mut venue.accounts);
+        let mut trader_state = Account::<TraderState>::try_from(&infos[trader + 1]).unwrap();
+        trader_state.withdrawal_allowlist_enabled = true;
+        trader_state.exit(&crate::ID).unwrap();
+        
+        let replacement = Pubkey::new_unique();
+        propose(&infos, trader, allowlist, vec![replacement]).unwrap();
+        let proposed: WithdrawalAllowlist = load(&infos[allowlist]);
+        assert_eq!(proposed.pending_effective_at, NOW + WithdrawalAllowlist::CHANGE_DELAY_SECONDS);
+        assert_eq!(apply(&infos, trader, allowlist).unwrap_err(), DexError::AllowlistChangeNotReady.into());
+        
+        make_due(&infos[allowlist]);
+        apply(&infos, trader, allowlist).unwrap();
+        let applied: WithdrawalAllowlist = load(&infos[allowlist]);
+        assert!(applied.allows(&replacement));
+        assert_eq!(applied.pending_effective_at, 0);
+        
+        // A newer proposal restarts the delay
+        propose(&infos, trader, allowlist, vec![Pubkey::new_unique()]).unwrap();
+        make_due(&infos[allowlist]);
+        propose(&infos, trader, allowlist, Vec::new()).unwrap();
+        assert_eq!(apply(&infos, trader, allowlist).unwrap_err(), DexError::AllowlistChangeNotReady.into());
+        assert!(load::<WithdrawalAllowlist>(&infos[allowlist]).allows(&replacement));
+    }
+}
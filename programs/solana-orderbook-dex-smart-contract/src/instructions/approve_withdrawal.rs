@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Market, PendingWithdrawal, TraderState, WithdrawalAllowlist};
+use crate::errors::DexError;
+use crate::events::{event_sink, WithdrawEvent};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ApproveWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.withdrawal_co_signer == co_signer.key() @ DexError::InvalidCoSigner
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(
+        mut,
+        close = trader,
+        seeds = [b"pending_withdrawal", trader_state.key().as_ref()],
+        bump = pending_withdrawal.bump,
+        has_one = trader_state,
+        has_one = market
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+    
+    /// Required when the trader has enabled a withdrawal allowlist
+    #[account(
+        seeds = [b"withdrawal_allowlist", trader_state.key().as_ref()],
+        bump = withdrawal_allowlist.bump
+    )]
+    pub withdrawal_allowlist: Option<Account<'info, WithdrawalAllowlist>>,
+    
+    /// CHECK: Trader wallet; receives the tokens and the pending account's rent
+    #[account(mut)]
+    pub trader: UncheckedAccount<'info>,
+    
+    pub co_signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = trader_token_account.owner == trader.key() @ DexError::InvalidAccountOwner,
+        constraint = trader_token_account.mint == pending_withdrawal.mint @ DexError::InvalidMint
+    )]
+    pub trader_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = pending_withdrawal.mint @ DexError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    /// CHECK: Market authority for vault signer
+    pub market_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Co-signer releases a pending withdrawal to the trader's wallet
+pub fn handler(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &ctx.accounts.market;
+    let pending = &ctx.accounts.pending_withdrawal;
+    
+    // Validate vault matches the reserved mint
+    let expected_vault = if pending.mint == market.base_mint {
+        market.base_vault
+    } else {
+        market.quote_vault
+    };
+    require!(
+        ctx.accounts.vault.key() == expected_vault,
+        DexError::InvalidMint
+    );
+    require!(
+        !ctx.accounts.trader_state.is_locked(Clock::get()?.unix_timestamp),
+        DexError::AccountLocked
+    );
+    
+    // Locked accounts may only withdraw to allowlisted destinations
+    if ctx.accounts.trader_state.withdrawal_allowlist_enabled {
+        let allowlist = ctx.accounts.withdrawal_allowlist
+            .as_ref()
+            .ok_or(DexError::WithdrawalDestinationNotAllowed)?;
+        require!(
+            allowlist.allows(&ctx.accounts.trader_token_account.key()),
+            DexError::WithdrawalDestinationNotAllowed
+        );
+    }
+    
+    // Transfer tokens from vault to trader
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"market",
+        &market_id_bytes,
+        &[market.bump],
+    ];
+    let signer = &[seeds];
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.trader_token_account.to_account_info(),
+        authority: ctx.accounts.market_authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, pending.amount, ctx.accounts.mint.decimals)?;
+    
+    let trader_state = &ctx.accounts.trader_state;
+    sink.emit(WithdrawEvent {
+        trader: trader_state.trader,
+        market: market.key(),
+        mint: pending.mint,
+        amount: pending.amount,
+        fee: 0,
+        new_balance: if pending.mint == market.base_mint {
+            trader_state.base_available
+        } else {
+            trader_state.quote_available
+        },
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Withdrawal approved: trader={}, mint={}, amount={}", 
+         trader_state.trader, pending.mint, pending.amount);
+    
+    // Vault amount is still the pre-transfer balance
+    let is_base = pending.mint == ctx.accounts.market.base_mint;
+    ctx.accounts.market.debit_vault(is_base, pending.amount, ctx.accounts.vault.amount)?;
+    
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::state::{Market, PendingFill};
+use crate::errors::DexError;
+use crate::events::{event_sink, FillsArchived};
+
+/// Maximum number of fill accounts archived in a single instruction
+pub const MAX_ARCHIVE_BATCH: usize = 32;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ArchiveFills<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority || 
+                     authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, crate::state::GlobalConfig>,
+    
+    /// Receives the rent of the closed fill accounts
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Merkle root over leaf hashes (odd nodes are promoted unchanged)
+fn merkle_root(mut level: Vec<[u8; 32]>) -> [u8; 32] {
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hashv(&[&[1u8], left, right]).to_bytes(),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level.first().copied().unwrap_or([0u8; 32])
+}
+
+/// Archive settled fills passed as remaining accounts
+///
+/// Each fill is hashed into a leaf, the batch's merkle root is folded into
+/// the market's cumulative archive root, and the fill accounts are closed.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ArchiveFills<'info>>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let fill_infos = ctx.remaining_accounts;
+    require!(
+        !fill_infos.is_empty() && fill_infos.len() <= MAX_ARCHIVE_BATCH,
+        DexError::InvalidInstruction
+    );
+    
+    let market_key = ctx.accounts.market.key();
+    let authority_info = ctx.accounts.authority.to_account_info();
+    let mut leaves = Vec::with_capacity(fill_infos.len());
+    
+    for info in fill_infos.iter() {
+        let fill = Account::<PendingFill>::try_from(info)?;
+        require!(fill.market == market_key, DexError::InvalidFillId);
+        require!(fill.settled, DexError::SettlementFailed);
+        
+        let fill_bytes = fill.try_to_vec()?;
+        leaves.push(hashv(&[&[0u8], &fill_bytes]).to_bytes());
+        
+        fill.close(authority_info.clone())?;
+    }
+    
+    let fill_count = leaves.len() as u64;
+    let batch_root = merkle_root(leaves);
+    
+    let market = &mut ctx.accounts.market;
+    market.fill_archive_root = hashv(&[&market.fill_archive_root, &batch_root]).to_bytes();
+    market.archived_fill_count = market.archived_fill_count
+        .checked_add(fill_count)
+        .ok_or(DexError::MathOverflow)?;
+    
+    sink.emit(FillsArchived {
+        market: market_key,
+        batch_root,
+        archive_root: market.fill_archive_root,
+        fill_count,
+        archived_fill_count: market.archived_fill_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Fills archived: market={}, count={}", market_key, fill_count);
+    
+    Ok(())
+}
@@ -1,14 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Token;
-use crate::state::{Market, TraderState, Orderbook};
-use crate::orderbook::Order;
+use crate::state::{Market, OpenOrders, TraderState};
+use crate::orderbook::{Order, Orderbook};
 use crate::errors::DexError;
-use crate::events::OrderCancelled;
+use crate::event_queue::{EventQueue, QueueEvent};
+use crate::events::{event_sink, EventSink, OrderCancelled};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(order_id: u128)]
 pub struct CancelOrder<'info> {
     #[account(
+        mut,
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
         bump = market.bump
     )]
@@ -19,12 +21,21 @@ pub struct CancelOrder<'info> {
     pub orderbook: UncheckedAccount<'info>,
     
     #[account(
+        mut,
         seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
         bump = trader_state.bump,
         constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
     )]
     pub trader_state: Account<'info, TraderState>,
     
+    /// Slot index of the trader's orders; skips the book scan when it knows the order
+    #[account(
+        mut,
+        seeds = [b"open_orders", trader_state.key().as_ref()],
+        bump = open_orders.bump
+    )]
+    pub open_orders: Option<Account<'info, OpenOrders>>,
+    
     #[account(mut)]
     pub trader: Signer<'info>,
     
@@ -32,36 +43,51 @@ pub struct CancelOrder<'info> {
 }
 
 pub fn handler(ctx: Context<CancelOrder>, order_id: u128) -> Result<()> {
-    let market = &ctx.accounts.market;
+    let sink = event_sink!(ctx);
+    let trader = ctx.accounts.trader.key();
+    let open_orders = ctx.accounts.open_orders.as_deref();
+    
+    cancel_resting_order(
+        &mut ctx.accounts.market,
+        &ctx.accounts.orderbook,
+        &mut ctx.accounts.trader_state,
+        &sink,
+        |orderbook, data| {
+            open_orders
+                .and_then(|open_orders| open_orders.lookup(orderbook, data, order_id))
+                .or_else(|| orderbook.find_order(data, order_id))
+                .filter(|(_, order)| order.trader == trader)
+        },
+    )?;
+    
+    if let Some(open_orders) = ctx.accounts.open_orders.as_mut() {
+        open_orders.remove(order_id);
+    }
     
+    Ok(())
+}
+
+/// Remove one of the trader's resting orders and release its locked funds
+/// `find` locates the order (slot and contents) in the loaded book
+pub fn cancel_resting_order<'info>(
+    market: &mut Account<'info, Market>,
+    orderbook_account_info: &AccountInfo<'info>,
+    trader_state: &mut TraderState,
+    sink: &EventSink,
+    find: impl FnOnce(&Orderbook, &[u8]) -> Option<(u64, Order)>,
+) -> Result<()> {
     // Load orderbook
-    let orderbook_account_info = &ctx.accounts.orderbook;
     require!(
         orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
         DexError::InvalidOrderbookState
     );
     
     let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
-    let mut orderbook = Account::<Orderbook>::try_deserialize(
-        &mut &orderbook_account_info.data.borrow()[..Orderbook::HEADER_SIZE]
-    )?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
     
     // Find order in orderbook
-    let mut found_slot = None;
-    let mut found_order = None;
-    
-    for i in 0..Orderbook::MAX_ORDERS {
-        if let Some(order) = orderbook.get_order(&orderbook_data, i as u64) {
-            if order.order_id == order_id && order.trader == ctx.accounts.trader.key() {
-                found_slot = Some(i as u64);
-                found_order = Some(order);
-                break;
-            }
-        }
-    }
-    
-    let (slot, order) = found_slot
-        .zip(found_order)
+    let (slot, order) = find(&orderbook, &orderbook_data)
         .ok_or(DexError::OrderNotFound)?;
     
     require!(
@@ -70,8 +96,6 @@ pub fn handler(ctx: Context<CancelOrder>, order_id: u128) -> Result<()> {
     );
     
     // Unlock tokens
-    let mut trader_state = ctx.accounts.trader_state.clone();
-    
     if order.is_bid() {
         // Unlock quote tokens
         let quote_locked = order.price
@@ -84,41 +108,142 @@ pub fn handler(ctx: Context<CancelOrder>, order_id: u128) -> Result<()> {
         // Unlock base tokens
         trader_state.unlock_base(order.remaining_size)?;
     }
+    trader_state.release_deposit(order.deposit_lamports as u64, false)?;
     
     // Remove order from orderbook
-    orderbook.free_slot(&mut orderbook_data, slot)?;
-    orderbook.order_count = orderbook.order_count
-        .checked_sub(1)
-        .ok_or(DexError::MathUnderflow)?;
-    orderbook.update_best_prices(&orderbook_data);
+    orderbook.remove_order(&mut orderbook_data, slot)?;
     
     // Save orderbook
     orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
     
     // Update trader state
-    ctx.accounts.trader_state.base_available = trader_state.base_available;
-    ctx.accounts.trader_state.quote_available = trader_state.quote_available;
-    ctx.accounts.trader_state.base_locked = trader_state.base_locked;
-    ctx.accounts.trader_state.quote_locked = trader_state.quote_locked;
-    ctx.accounts.trader_state.open_order_count = ctx.accounts.trader_state.open_order_count
+    trader_state.open_order_count = trader_state.open_order_count
         .checked_sub(1)
-        .ok_or(DexError::MathUnderflow)? as u16;
+        .ok_or(DexError::MathUnderflow)?;
+    trader_state.record_order_activity(1, 0, Clock::get()?.unix_timestamp);
     
     // Update market
-    let mut market_mut = ctx.accounts.market.as_mut();
-    market_mut.best_bid = orderbook.best_bid;
-    market_mut.best_ask = orderbook.best_ask;
-    market_mut.order_count = orderbook.order_count;
+    market.sync_book(&orderbook, &orderbook_data, sink)?;
+    
+    if market.logs_l3() {
+        sink.emit(OrderCancelled {
+            market: market.key(),
+            trader: order.trader,
+            order_id: order.order_id,
+            client_order_id: order.client_order_id,
+            remaining_size: order.remaining_size,
+            timestamp: Clock::get()?.unix_timestamp,
+        })?;
+    }
     
-    emit!(OrderCancelled {
-        market: market.key(),
-        trader: ctx.accounts.trader.key(),
-        order_id,
-        remaining_size: order.remaining_size,
-        timestamp: Clock::get()?.unix_timestamp,
-    });
+    msg!("Order cancelled: id={}, client_id={}, remaining_size={}", 
+         order.order_id, order.client_order_id, order.remaining_size);
     
-    msg!("Order cancelled: id={}, remaining_size={}", order_id, order.remaining_size);
+    Ok(())
+}
+
+/// Remove orders whose owners are not present (crank cancellations)
+/// Each removal is queued as an Out event so consume_events releases the funds
+pub fn evict_resting_orders(
+    market: &Account<Market>,
+    orderbook: &mut Orderbook,
+    orderbook_data: &mut [u8],
+    event_queue: &mut EventQueue,
+    event_queue_data: &mut [u8],
+    orders: &[(u64, Order)],
+    sink: &EventSink,
+) -> Result<()> {
+    for (slot, _) in orders.iter() {
+        orderbook.remove_order(orderbook_data, *slot)?;
+    }
+    
+    let removed: Vec<Order> = orders.iter().map(|(_, order)| *order).collect();
+    let timestamp = Clock::get()?.unix_timestamp;
+    queue_removed_orders(market, event_queue, event_queue_data, &removed, timestamp, sink)
+}
+
+/// Queue Out events for orders already taken off the book and log their cancellation
+pub fn queue_removed_orders(
+    market: &Account<Market>,
+    event_queue: &mut EventQueue,
+    event_queue_data: &mut [u8],
+    orders: &[Order],
+    timestamp: i64,
+    sink: &EventSink,
+) -> Result<()> {
+    for order in orders.iter() {
+        event_queue.push(event_queue_data, QueueEvent::out(order, timestamp))?;
+        
+        if market.logs_l3() {
+            sink.emit(OrderCancelled {
+                market: market.key(),
+                trader: order.trader,
+                order_id: order.order_id,
+                client_order_id: order.client_order_id,
+                remaining_size: order.remaining_size,
+                timestamp,
+            })?;
+        }
+    }
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::{Side, TimeInForce};
+    use crate::test_utils::{account_infos, install_clock, load, TestAccount, Venue, NOW};
+    
+    fn cancel<'info>(infos: &'info [AccountInfo<'info>], trader: usize, system_program: usize, order_id: u128) -> Result<()> {
+        install_clock();
+        let mut accounts = CancelOrder {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            orderbook: UncheckedAccount::try_from(&infos[Venue::ORDERBOOK]),
+            trader_state: Account::try_from(&infos[trader + 1])?,
+            open_orders: None,
+            trader: Signer::try_from(&infos[trader])?,
+            system_program: Program::try_from(&infos[system_program])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), order_id)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    /// Rest a one-lot bid for `trader` and return its order ID
+    fn quote<'info>(infos: &'info [AccountInfo<'info>], trader: usize) -> Result<u128> {
+        let order_id = load::<Market>(&infos[Venue::MARKET]).order_id_at(infos[Venue::MARKET].key, 0)?;
+        Venue::place(infos, trader, Venue::order(Side::Bid, 1_000, Venue::LOT_SIZE, TimeInForce::GTC))?;
+        Ok(order_id)
+    }
+    
+    #[test]
+    fn a_trader_cancelling_past_the_ratio_cannot_quote_until_it_fills() {
+        let mut venue = Venue::new(|market| market.max_cancel_to_fill_ratio = 2);
+        let maker = venue.add_trader(10_000, 1_000_000);
+        let buyer = venue.add_trader(0, 1_000_000);
+        venue.accounts.push(TestAccount::program(anchor_lang::system_program::ID));
+        let system_program = venue.accounts.len() - 1;
+        let infos = account_infos(&mut venue.accounts);
+        
+        for _ in 0..3 {
+            let order_id = quote(&infos, maker).unwrap();
+            cancel(&infos, maker, system_program, order_id).unwrap();
+        }
+        assert_eq!(quote(&infos, maker).err().unwrap(), DexError::CancelRatioExceeded.into());
+        let state: TraderState = load(&infos[maker + 1]);
+        assert!(!state.exceeds_cancel_ratio(2, NOW + TraderState::CANCEL_RATIO_WINDOW_SECONDS));
+        
+        // Taking liquidity stays open, and every fill raises the allowance
+        Venue::place(&infos, buyer, Venue::order(Side::Bid, 1_000, 2 * Venue::LOT_SIZE, TimeInForce::GTC)).unwrap();
+        for _ in 0..2 {
+            Venue::place(&infos, maker, Venue::order(Side::Ask, 1_000, Venue::LOT_SIZE, TimeInForce::IOC)).unwrap();
+        }
+        let state: TraderState = load(&infos[maker + 1]);
+        assert_eq!((state.window_cancels, state.window_fills), (3, 2));
+        quote(&infos, maker).unwrap();
+    }
+}
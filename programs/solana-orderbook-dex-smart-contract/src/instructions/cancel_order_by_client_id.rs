@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState};
+use crate::errors::DexError;
+use crate::instructions::cancel_order::cancel_resting_order;
+use crate::events::event_sink;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(client_order_id: u64)]
+pub struct CancelOrderByClientId<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Cancel the trader's resting order with the given client order ID
+/// If the ID was reused, the oldest matching order is cancelled
+pub fn handler(ctx: Context<CancelOrderByClientId>, client_order_id: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(client_order_id != 0, DexError::InvalidOrderParams);
+    let trader = ctx.accounts.trader.key();
+    
+    cancel_resting_order(
+        &mut ctx.accounts.market,
+        &ctx.accounts.orderbook,
+        &mut ctx.accounts.trader_state,
+        &sink,
+        |orderbook, data| orderbook.find_order_by_client_id(data, &trader, client_order_id),
+    )
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState};
+use crate::orderbook::{Order, Orderbook, Side};
+use crate::errors::DexError;
+use crate::events::{event_sink, OrderCancelled, OrdersCancelledBySide};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CancelOrdersBySide<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    pub trader: Signer<'info>,
+}
+
+/// Cancel up to `limit` of the trader's orders on one side, nearest the touch first
+/// Locked funds are released in one update per side
+pub fn handler(ctx: Context<CancelOrdersBySide>, side: u8, limit: u8) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let side = Side::from_u8(side).ok_or(DexError::InvalidOrderParams)?;
+    require!(limit > 0, DexError::InvalidInstruction);
+    
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    let trader_state = &mut ctx.accounts.trader_state;
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    
+    // Collect first; removing while walking the book would break the iterator
+    let orders: Vec<(u64, Order)> = orderbook.iter_side(&orderbook_data, side)
+        .filter(|(_, order)| order.trader == trader_state.trader)
+        .take(limit as usize)
+        .collect();
+    
+    let mut released = 0u64;
+    for (slot, order) in orders.iter() {
+        released = if side == Side::Bid {
+            order.price
+                .checked_mul(order.remaining_size)
+                .and_then(|v| v.checked_div(market.lot_size))
+                .and_then(|v| v.checked_add(released))
+        } else {
+            released.checked_add(order.remaining_size)
+        }
+        .ok_or(DexError::MathOverflow)?;
+        trader_state.release_deposit(order.deposit_lamports as u64, false)?;
+        
+        orderbook.remove_order(&mut orderbook_data, *slot)?;
+        
+        if market.logs_l3() {
+            sink.emit(OrderCancelled {
+                market: market.key(),
+                trader: order.trader,
+                order_id: order.order_id,
+                client_order_id: order.client_order_id,
+                remaining_size: order.remaining_size,
+                timestamp: clock.unix_timestamp,
+            })?;
+        }
+    }
+    
+    if side == Side::Bid {
+        trader_state.unlock_quote(released)?;
+    } else {
+        trader_state.unlock_base(released)?;
+    }
+    trader_state.open_order_count = trader_state.open_order_count
+        .saturating_sub(orders.len() as u16);
+    trader_state.record_order_activity(orders.len() as u32, 0, clock.unix_timestamp);
+    
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    
+    let market_key = market.key();
+    let market_mut = &mut ctx.accounts.market;
+    market_mut.sync_book(&orderbook, &orderbook_data, &sink)?;
+    
+    sink.emit(OrdersCancelledBySide {
+        market: market_key,
+        trader: trader_state.trader,
+        side: side as u8,
+        orders_cancelled: orders.len() as u16,
+        released,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Orders cancelled by side: trader={}, side={:?}, cancelled={}, released={}", 
+         trader_state.trader, side, orders.len(), released);
+    
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, PendingWithdrawal, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, WithdrawalCancelled};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(
+        mut,
+        close = trader,
+        seeds = [b"pending_withdrawal", trader_state.key().as_ref()],
+        bump = pending_withdrawal.bump,
+        has_one = trader_state,
+        has_one = market
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+}
+
+/// Drop a pending withdrawal and return its funds to the available balance
+pub fn handler(ctx: Context<CancelWithdrawal>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &ctx.accounts.market;
+    let pending = &ctx.accounts.pending_withdrawal;
+    let trader_state = &mut ctx.accounts.trader_state;
+    
+    if pending.mint == market.base_mint {
+        trader_state.base_available = trader_state.base_available
+            .checked_add(pending.amount)
+            .ok_or(DexError::MathOverflow)?;
+    } else {
+        trader_state.quote_available = trader_state.quote_available
+            .checked_add(pending.amount)
+            .ok_or(DexError::MathOverflow)?;
+    }
+    
+    sink.emit(WithdrawalCancelled {
+        trader: trader_state.trader,
+        market: market.key(),
+        mint: pending.mint,
+        amount: pending.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Withdrawal cancelled: trader={}, amount={}", trader_state.trader, pending.amount);
+    
+    Ok(())
+}
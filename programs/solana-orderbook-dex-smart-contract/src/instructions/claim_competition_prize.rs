@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Competition, CompetitionEntry};
+use crate::errors::DexError;
+use crate::events::{event_sink, CompetitionPrizeClaimed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimCompetitionPrize<'info> {
+    #[account(
+        seeds = [b"competition", competition.competition_id.to_le_bytes().as_ref()],
+        bump = competition.bump
+    )]
+    pub competition: Account<'info, Competition>,
+    
+    #[account(
+        mut,
+        close = trader,
+        seeds = [b"competition_entry", competition.key().as_ref(), trader.key().as_ref()],
+        bump = entry.bump,
+        has_one = competition,
+        has_one = trader
+    )]
+    pub entry: Account<'info, CompetitionEntry>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    #[account(mut, constraint = trader_token_account.mint == competition.prize_mint @ DexError::InvalidMint)]
+    pub trader_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = competition.prize_vault @ DexError::InvalidMint)]
+    pub prize_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = competition.prize_mint @ DexError::InvalidMint)]
+    pub prize_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pay an entrant its share of the prize, pro rata to its recorded score
+///
+/// Opens once scoring has closed, so every share is final, and lasts for
+/// `Competition::CLAIM_PERIOD_SECONDS`. The entry is closed to the trader.
+pub fn handler(ctx: Context<ClaimCompetitionPrize>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let now = Clock::get()?.unix_timestamp;
+    let competition = &ctx.accounts.competition;
+    let claims_open_at = competition.scoring_ends_at();
+    require!(
+        now >= claims_open_at && now < claims_open_at.saturating_add(Competition::CLAIM_PERIOD_SECONDS),
+        DexError::WrongCompetitionPhase
+    );
+    
+    let score = ctx.accounts.entry.score;
+    let amount = competition.prize_for(score);
+    require!(amount > 0, DexError::InsufficientFunds);
+    
+    let competition_id_bytes = competition.competition_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"competition",
+        &competition_id_bytes,
+        &[competition.bump],
+    ];
+    let signer = &[seeds];
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.prize_vault.to_account_info(),
+        mint: ctx.accounts.prize_mint.to_account_info(),
+        to: ctx.accounts.trader_token_account.to_account_info(),
+        authority: competition.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.prize_mint.decimals)?;
+    
+    sink.emit(CompetitionPrizeClaimed {
+        competition: competition.key(),
+        trader: ctx.accounts.trader.key(),
+        score,
+        amount,
+        timestamp: now,
+    })?;
+    
+    msg!("Competition prize claimed: trader={}, score={}, amount={}",
+         ctx.accounts.trader.key(), score, amount);
+    
+    Ok(())
+}
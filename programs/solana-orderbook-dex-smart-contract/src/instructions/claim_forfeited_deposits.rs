@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, ForfeitedDepositsClaimed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimForfeitedDeposits<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Receives the lamports; must be the protocol fee recipient
+    #[account(mut, address = global_config.fee_recipient @ DexError::Unauthorized)]
+    pub fee_recipient: UncheckedAccount<'info>,
+}
+
+/// Sweep the placement deposits a market's expired orders forfeited to the fee recipient
+pub fn handler(ctx: Context<ClaimForfeitedDeposits>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &mut ctx.accounts.market;
+    let lamports = market.forfeited_deposits;
+    require!(lamports > 0, DexError::InsufficientFunds);
+    market.forfeited_deposits = 0;
+    
+    // The market is program-owned, so its lamports can be moved directly
+    **market.to_account_info().try_borrow_mut_lamports()? -= lamports;
+    **ctx.accounts.fee_recipient.to_account_info().try_borrow_mut_lamports()? += lamports;
+    
+    sink.emit(ForfeitedDepositsClaimed {
+        market: market.key(),
+        recipient: ctx.accounts.fee_recipient.key(),
+        lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Forfeited deposits claimed: market={}, lamports={}", market.key(), lamports);
+    
+    Ok(())
+}
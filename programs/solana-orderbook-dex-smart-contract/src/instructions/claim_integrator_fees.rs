@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{IntegratorFees, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, IntegratorFeesClaimed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimIntegratorFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"integrator", market.key().as_ref(), platform.key().as_ref()],
+        bump = integrator_fees.bump,
+        has_one = market,
+        has_one = platform
+    )]
+    pub integrator_fees: Account<'info, IntegratorFees>,
+    
+    pub platform: Signer<'info>,
+    
+    #[account(mut, constraint = platform_base_account.mint == market.base_mint @ DexError::InvalidMint)]
+    pub platform_base_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, constraint = platform_quote_account.mint == market.quote_mint @ DexError::InvalidMint)]
+    pub platform_quote_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = market.base_vault @ DexError::InvalidMint)]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = market.quote_vault @ DexError::InvalidMint)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = market.base_mint @ DexError::InvalidMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(address = market.quote_mint @ DexError::InvalidMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Market authority for vault signer
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market_authority: UncheckedAccount<'info>,
+    
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pay out a platform's accrued integrator fees in both tokens
+pub fn handler(ctx: Context<ClaimIntegratorFees>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let accounts = &ctx.accounts;
+    let base_amount = accounts.integrator_fees.base_accrued;
+    let quote_amount = accounts.integrator_fees.quote_accrued;
+    require!(base_amount > 0 || quote_amount > 0, DexError::InsufficientFunds);
+    
+    let market_id_bytes = accounts.market.market_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"market",
+        &market_id_bytes,
+        &[accounts.market.bump],
+    ];
+    let signer = &[seeds];
+    
+    let payouts = [
+        (base_amount, &accounts.base_vault, &accounts.platform_base_account, &accounts.base_mint, &accounts.base_token_program),
+        (quote_amount, &accounts.quote_vault, &accounts.platform_quote_account, &accounts.quote_mint, &accounts.quote_token_program),
+    ];
+    for (amount, vault, destination, mint, token_program) in payouts {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = TransferChecked {
+            from: vault.to_account_info(),
+            mint: mint.to_account_info(),
+            to: destination.to_account_info(),
+            authority: accounts.market_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
+    }
+    
+    let integrator_fees = &mut ctx.accounts.integrator_fees;
+    integrator_fees.base_accrued = 0;
+    integrator_fees.quote_accrued = 0;
+    
+    sink.emit(IntegratorFeesClaimed {
+        market: integrator_fees.market,
+        platform: integrator_fees.platform,
+        base_amount,
+        quote_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Integrator fees claimed: platform={}, base={}, quote={}", 
+         integrator_fees.platform, base_amount, quote_amount);
+    
+    // Vault amounts are still the pre-transfer balances
+    let market = &mut ctx.accounts.market;
+    market.debit_vault(true, base_amount, ctx.accounts.base_vault.amount)?;
+    market.debit_vault(false, quote_amount, ctx.accounts.quote_vault.amount)?;
+    
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, ProtocolFeesClaimed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimProtocolFees<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = recipient_base_account.mint == market.base_mint @ DexError::InvalidMint,
+        constraint = recipient_base_account.owner == global_config.fee_recipient @ DexError::Unauthorized
+    )]
+    pub recipient_base_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = recipient_quote_account.mint == market.quote_mint @ DexError::InvalidMint,
+        constraint = recipient_quote_account.owner == global_config.fee_recipient @ DexError::Unauthorized
+    )]
+    pub recipient_quote_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = market.base_vault @ DexError::InvalidMint)]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = market.quote_vault @ DexError::InvalidMint)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = market.base_mint @ DexError::InvalidMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(address = market.quote_mint @ DexError::InvalidMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Market authority for vault signer
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market_authority: UncheckedAccount<'info>,
+    
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Sweep a market's accrued protocol fees to the fee recipient's token accounts
+///
+/// Fees reserved for maker rebates on unconsumed fills stay in the vault.
+/// Revenue-share markets are paid out through `claim_revenue_share` instead.
+pub fn handler(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let accounts = &ctx.accounts;
+    require!(!accounts.market.has_revenue_share(), DexError::FeesSplitByRevenueShare);
+    let base_amount = accounts.market.claimable_fees(true);
+    let quote_amount = accounts.market.claimable_fees(false);
+    require!(base_amount > 0 || quote_amount > 0, DexError::InsufficientFunds);
+    
+    let market_id_bytes = accounts.market.market_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"market",
+        &market_id_bytes,
+        &[accounts.market.bump],
+    ];
+    let signer = &[seeds];
+    
+    let payouts = [
+        (base_amount, &accounts.base_vault, &accounts.recipient_base_account, &accounts.base_mint, &accounts.base_token_program),
+        (quote_amount, &accounts.quote_vault, &accounts.recipient_quote_account, &accounts.quote_mint, &accounts.quote_token_program),
+    ];
+    for (amount, vault, destination, mint, token_program) in payouts {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = TransferChecked {
+            from: vault.to_account_info(),
+            mint: mint.to_account_info(),
+            to: destination.to_account_info(),
+            authority: accounts.market_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
+    }
+    
+    let fee_recipient = ctx.accounts.global_config.fee_recipient;
+    let market = &mut ctx.accounts.market;
+    market.base_fees_accrued -= base_amount;
+    market.quote_fees_accrued -= quote_amount;
+    
+    // Vault amounts are still the pre-transfer balances
+    market.debit_vault(true, base_amount, ctx.accounts.base_vault.amount)?;
+    market.debit_vault(false, quote_amount, ctx.accounts.quote_vault.amount)?;
+    
+    sink.emit(ProtocolFeesClaimed {
+        market: market.key(),
+        fee_recipient,
+        base_amount,
+        quote_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Protocol fees claimed: market={}, base={}, quote={}", 
+         market.key(), base_amount, quote_amount);
+    
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Market, RevenueShare};
+use crate::errors::DexError;
+use crate::events::{event_sink, RevenueShareClaimed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimRevenueShare<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"revenue_share", market.key().as_ref()],
+        bump = revenue_share.bump,
+        has_one = market
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+    
+    pub beneficiary: Signer<'info>,
+    
+    #[account(mut, constraint = beneficiary_base_account.mint == market.base_mint @ DexError::InvalidMint)]
+    pub beneficiary_base_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, constraint = beneficiary_quote_account.mint == market.quote_mint @ DexError::InvalidMint)]
+    pub beneficiary_quote_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = market.base_vault @ DexError::InvalidMint)]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = market.quote_vault @ DexError::InvalidMint)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = market.base_mint @ DexError::InvalidMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(address = market.quote_mint @ DexError::InvalidMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Market authority for vault signer
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market_authority: UncheckedAccount<'info>,
+    
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pay a revenue-share beneficiary its cut of the market's fees in both tokens
+///
+/// Collects the market's claimable fees into the split first, so every
+/// claim settles against all fees accrued so far. Beneficiaries claim
+/// independently; what one leaves unclaimed stays owed to it.
+pub fn handler(ctx: Context<ClaimRevenueShare>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let accounts = &mut *ctx.accounts;
+    let index = accounts.revenue_share.entries[..accounts.revenue_share.entry_count as usize]
+        .iter()
+        .position(|entry| entry.beneficiary == accounts.beneficiary.key())
+        .ok_or(DexError::Unauthorized)?;
+    accounts.revenue_share.collect(&mut accounts.market)?;
+    let (base_amount, quote_amount) = accounts.revenue_share.owed(index);
+    require!(base_amount > 0 || quote_amount > 0, DexError::InsufficientFunds);
+    
+    let market_id_bytes = accounts.market.market_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"market",
+        &market_id_bytes,
+        &[accounts.market.bump],
+    ];
+    let signer = &[seeds];
+    
+    let payouts = [
+        (base_amount, &accounts.base_vault, &accounts.beneficiary_base_account, &accounts.base_mint, &accounts.base_token_program),
+        (quote_amount, &accounts.quote_vault, &accounts.beneficiary_quote_account, &accounts.quote_mint, &accounts.quote_token_program),
+    ];
+    for (amount, vault, destination, mint, token_program) in payouts {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = TransferChecked {
+            from: vault.to_account_info(),
+            mint: mint.to_account_info(),
+            to: destination.to_account_info(),
+            authority: accounts.market_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
+    }
+    
+    let entry = &mut accounts.revenue_share.entries[index];
+    entry.base_claimed += base_amount;
+    entry.quote_claimed += quote_amount;
+    
+    // Vault amounts are still the pre-transfer balances
+    let market = &mut accounts.market;
+    market.debit_vault(true, base_amount, accounts.base_vault.amount)?;
+    market.debit_vault(false, quote_amount, accounts.quote_vault.amount)?;
+    
+    sink.emit(RevenueShareClaimed {
+        market: market.key(),
+        beneficiary: accounts.beneficiary.key(),
+        base_amount,
+        quote_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Revenue share claimed: market={}, beneficiary={}, base={}, quote={}",
+         market.key(), accounts.beneficiary.key(), base_amount, quote_amount);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{account_infos, install_clock, load, token_balance, zeroed, TestAccount, Venue};
+    use crate::state::RevenueShareEntry;
+    
+    /// Claim for the beneficiary whose signer is at `beneficiary`, followed by
+    /// its base and quote accounts; the accounts from `share` on are the
+    /// revenue share, then base and quote mint and vault, then the token program
+    fn claim<'info>(infos: &'info [AccountInfo<'info>], share: usize, beneficiary: usize) -> Result<()> {
+        install_clock();
+        let [revenue_share, base_mint, quote_mint, base_vault, quote_vault, token_program] =
+            std::array::from_fn(|i| &infos[share + i]);
+        let mut accounts = ClaimRevenueShare {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            revenue_share: Account::try_from(revenue_share)?,
+            beneficiary: Signer::try_from(&infos[beneficiary])?,
+            beneficiary_base_account: InterfaceAccount::try_from(&infos[beneficiary + 1])?,
+            beneficiary_quote_account: InterfaceAccount::try_from(&infos[beneficiary + 2])?,
+            base_vault: InterfaceAccount::try_from(base_vault)?,
+            quote_vault: InterfaceAccount::try_from(quote_vault)?,
+            base_mint: InterfaceAccount::try_from(base_mint)?,
+            quote_mint: InterfaceAccount::try_from(quote_mint)?,
+            market_authority: UncheckedAccount::try_from(&infos[Venue::MARKET]),
+            base_token_program: Interface::try_from(token_program)?,
+            quote_token_program: Interface::try_from(token_program)?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()))?;
+        accounts.exit(&crate::ID)
+    }
+    
+    fn accrue<'info>(infos: &'info [AccountInfo<'info>], base: u64, quote: u64) {
+        let mut market = Account::<Market>::try_from(&infos[Venue::MARKET]).unwrap();
+        market.base_fees_accrued += base;
+        market.quote_fees_accrued += quote;
+        market.exit(&crate::ID).unwrap();
+    }
+    
+    fn claimed(infos: &[AccountInfo], share: usize, index: usize) -> (u64, u64) {
+        let entry = load::<RevenueShare>(&infos[share]).entries[index];
+        (entry.base_claimed, entry.quote_claimed)
+    }
+    
+    #[test]
+    fn each_beneficiary_claims_its_cut_of_every_fee_so_far() {
+        let (partner, protocol, stranger) = (TestAccount::signer(), TestAccount::signer(), TestAccount::signer());
+        
+        let mut revenue_share: RevenueShare = zeroed(RevenueShare::SIZE);
+        revenue_share.entry_count = 2;
+        revenue_share.entries[0] = RevenueShareEntry { beneficiary: partner.key, share_bps: 7_000, ..Default::default() };
+        revenue_share.entries[1] = RevenueShareEntry { beneficiary: protocol.key, share_bps: 3_000, ..Default::default() };
+        let revenue_share = TestAccount::program_owned(&revenue_share);
+        
+        let revenue_share_key = revenue_share.key;
+        let mut venue = Venue::new(|market| market.revenue_share = revenue_share_key);
+        let share = venue.accounts.len();
+        venue.accounts.push(revenue_share);
+        venue.add_vaults(10_000, 10_000);
+        let mut beneficiaries = Vec::new();
+        for signer in [partner, protocol, stranger] {
+            let signer_index = venue.accounts.len();
+            beneficiaries.push(signer_index);
+            venue.accounts.push(signer);
+            venue.add_wallet(signer_index, true, 0);
+            venue.add_wallet(signer_index, false, 0);
+        }
+        let [partner, protocol, stranger] = beneficiaries[..] else { unreachable!() };
+        let infos = account_infos(&mut venue.accounts);
+        
+        accrue(&infos, 1_000, 2_000);
+        claim(&infos, share, partner).unwrap();
+        assert_eq!(claimed(&infos, share, 0), (700, 1_400));
+        
+        // The protocol's cut covers fees accrued after the partner claimed too
+        accrue(&infos, 1_000, 0);
+        claim(&infos, share, protocol).unwrap();
+        assert_eq!(claimed(&infos, share, 1), (600, 600));
+        claim(&infos, share, partner).unwrap();
+        assert_eq!(claimed(&infos, share, 0), (1_400, 1_400));
+        
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!((market.base_fees_accrued, market.quote_fees_accrued), (0, 0));
+        assert_eq!((market.base_vault_ledger, market.quote_vault_ledger), (8_000, 8_000));
+        assert_eq!((token_balance(&infos[partner + 1]), token_balance(&infos[partner + 2])), (1_400, 1_400));
+        
+        assert_eq!(claim(&infos, share, partner).err().unwrap(), DexError::InsufficientFunds.into());
+        assert_eq!(claim(&infos, share, stranger).err().unwrap(), DexError::Unauthorized.into());
+    }
+}
@@ -0,0 +1,436 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState};
+use crate::errors::DexError;
+use crate::event_queue::{load_trader_states, EventQueue, QueueEvent, QueueEventType};
+use crate::events::{event_sink, CrankRewardPaid, EventsConsumed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ConsumeEvents<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Event queue account (must be the market's)
+    #[account(mut, address = market.event_queue @ DexError::InvalidAccountState)]
+    pub event_queue: UncheckedAccount<'info>,
+    
+    /// Caller claiming the crank reward; must own `cranker_state`
+    pub cranker: Option<Signer<'info>>,
+    
+    /// Trader state credited with the crank reward, if the market pays one
+    #[account(
+        mut,
+        constraint = cranker_state.market == market.key() @ DexError::InvalidAccountState
+    )]
+    pub cranker_state: Option<Account<'info, TraderState>>,
+    
+    // Remaining accounts: TraderState accounts of the traders in the queued events
+}
+
+/// Apply queued events to the traders' balances, oldest first
+///
+/// Permissionless crank. Stops at `max_events` or at the first event whose
+/// trader state was not passed in; sides already applied are recorded in the
+/// event so the next call picks up where this one stopped. A caller passing
+/// its own `cranker_state` is paid the market's per-fill crank reward for
+/// the fills it settled, out of the accrued quote fees.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConsumeEvents<'info>>,
+    max_events: u8,
+) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(max_events > 0, DexError::InvalidInstruction);
+    
+    let market_key = ctx.accounts.market.key();
+    
+    // Load trader states passed by the cranker
+    let mut trader_states = load_trader_states(&market_key, ctx.remaining_accounts)?;
+    
+    // The reward goes to the signer's own trader state, which is written back
+    // on exit and so must not also be one of the remaining accounts
+    if let Some(cranker_state) = ctx.accounts.cranker_state.as_ref() {
+        let cranker = ctx.accounts.cranker.as_ref().ok_or(DexError::Unauthorized)?;
+        require_keys_eq!(cranker_state.trader, cranker.key(), DexError::Unauthorized);
+        require!(
+            !trader_states.iter().any(|t| t.key() == cranker_state.key()),
+            DexError::DuplicateAccount
+        );
+    }
+    
+    let mut event_queue_data = ctx.accounts.event_queue.try_borrow_mut_data()?;
+    let mut event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE])?;
+    require!(event_queue.market == market_key, DexError::InvalidAccountState);
+    
+    let mut consumed = 0u64;
+    let mut fills = 0u64;
+    let mut base_fees = 0u64;
+    let mut quote_fees = 0u64;
+    let mut base_rebates = 0u64;
+    let mut quote_rebates = 0u64;
+    let mut base_reserved = 0u64;
+    let mut quote_reserved = 0u64;
+    let mut forfeited = 0u64;
+    
+    while consumed < max_events as u64 {
+        let mut event = match event_queue.peek_front(&event_queue_data) {
+            Some(event) => event,
+            None => break, // Queue drained
+        };
+        
+        // The side paying a fee goes first, so the rebate it funds is
+        // reserved before the maker is paid
+        let sides = if event.bid_fee_bps < 0 { [false, true] } else { [true, false] };
+        for is_bid in sides {
+            let Some((fee, rebate, reserved)) = apply_side(&mut event, is_bid, &mut trader_states, &ctx.accounts.market)? else {
+                continue;
+            };
+            if is_bid {
+                base_fees = base_fees.checked_add(fee).ok_or(DexError::MathOverflow)?;
+                quote_rebates = quote_rebates.checked_add(rebate).ok_or(DexError::MathOverflow)?;
+                base_reserved = base_reserved.checked_add(reserved).ok_or(DexError::MathOverflow)?;
+            } else {
+                quote_fees = quote_fees.checked_add(fee).ok_or(DexError::MathOverflow)?;
+                base_rebates = base_rebates.checked_add(rebate).ok_or(DexError::MathOverflow)?;
+                quote_reserved = quote_reserved.checked_add(reserved).ok_or(DexError::MathOverflow)?;
+            }
+        }
+        
+        if !event.is_applied() {
+            // Missing trader state; keep the partial progress and stop here
+            event_queue.update_front(&mut event_queue_data, &event);
+            break;
+        }
+        
+        event_queue.pop_front(&mut event_queue_data)?;
+        consumed += 1;
+        if event.event_type == QueueEventType::Fill as u8 {
+            fills += 1;
+        }
+        forfeited = forfeited.checked_add(event.forfeited_deposit()).ok_or(DexError::MathOverflow)?;
+    }
+    
+    event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE])?;
+    
+    // Persist trader states (remaining accounts are not written back automatically)
+    for trader_state in trader_states.iter() {
+        trader_state.exit(&crate::ID)?;
+    }
+    
+    // Rebates come out of the taker fees reserved for them, which claims
+    // and crank rewards leave untouched
+    let market = &mut ctx.accounts.market;
+    market.accrue_fee(true, base_fees)?;
+    market.accrue_fee(false, quote_fees)?;
+    market.reserve_rebates(base_reserved, quote_reserved)?;
+    market.pay_rebates(base_rebates, quote_rebates)?;
+    market.forfeited_deposits = market.forfeited_deposits
+        .checked_add(forfeited)
+        .ok_or(DexError::MathOverflow)?;
+    market.last_consume_slot = Clock::get()?.slot;
+    
+    // Pay the caller for the fills it settled, out of the protocol's quote fees
+    if let Some(cranker_state) = ctx.accounts.cranker_state.as_mut() {
+        let reward = market.take_crank_reward(fills);
+        if reward > 0 {
+            cranker_state.quote_available = cranker_state.quote_available
+                .checked_add(reward)
+                .ok_or(DexError::MathOverflow)?;
+            
+            sink.emit(CrankRewardPaid {
+                market: market_key,
+                recipient: cranker_state.trader,
+                fills,
+                amount: reward,
+                timestamp: Clock::get()?.unix_timestamp,
+            })?;
+        }
+    }
+    
+    sink.emit(EventsConsumed {
+        market: market_key,
+        consumed,
+        remaining: event_queue.count,
+        base_fees,
+        quote_fees,
+        base_rebates,
+        quote_rebates,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Events consumed: market={}, consumed={}, remaining={}", 
+         market_key, consumed, event_queue.count);
+    
+    Ok(())
+}
+
+/// Apply one side of a queued event if its trader state was passed in
+/// Returns the fee charged, the rebate paid and the counterparty rebate
+/// reserved, or None if the side was not applied
+///
+/// Sides settled here rested on the book, so a fill earns them maker points.
+/// An order that expired untouched forfeits its placement deposit, which
+/// moves from the trader state's lamports to the market's.
+///
+/// A maker rebate is funded by the taker fee on the same fill, so a side
+/// earning one waits until the other side has been applied; applying the
+/// fee side while the maker is still pending reserves the maker's rebate.
+fn apply_side<'info>(
+    event: &mut QueueEvent,
+    is_bid: bool,
+    trader_states: &mut [Account<'info, TraderState>],
+    market: &Account<'info, Market>,
+) -> Result<Option<(u64, u64, u64)>> {
+    let lot_size = market.lot_size;
+    let (pending, other_pending, fee_bps, trader) = if is_bid {
+        (QueueEvent::BID_PENDING, QueueEvent::ASK_PENDING, event.bid_fee_bps, event.bid_trader)
+    } else {
+        (QueueEvent::ASK_PENDING, QueueEvent::BID_PENDING, event.ask_fee_bps, event.ask_trader)
+    };
+    if event.flags & pending == 0 || (fee_bps < 0 && event.flags & other_pending != 0) {
+        return Ok(None);
+    }
+    let Some(trader_state) = trader_states.iter_mut().find(|t| t.trader == trader) else {
+        return Ok(None);
+    };
+    
+    let (fee, rebate) = if is_bid {
+        event.apply_bid(trader_state, lot_size)?
+    } else {
+        event.apply_ask(trader_state, lot_size)?
+    };
+    if event.event_type == QueueEventType::Fill as u8 {
+        let quote_amount = event.quote_amount(lot_size)?;
+        let points = market.points_for(quote_amount, Some(event.rested_seconds(is_bid)), event.timestamp);
+        trader_state.points = trader_state.points.saturating_add(points);
+        trader_state.record_competition_volume(market, quote_amount, event.timestamp);
+    }
+    let forfeited = event.forfeited_deposit();
+    if forfeited > 0 {
+        // Both accounts are program-owned, so the lamports move directly
+        **trader_state.to_account_info().try_borrow_mut_lamports()? -= forfeited;
+        **market.to_account_info().try_borrow_mut_lamports()? += forfeited;
+    }
+    let reserved = match (event.flags & other_pending != 0, is_bid) {
+        (false, _) => 0,
+        (true, true) => event.ask_fee(lot_size)?.1,
+        (true, false) => event.bid_fee(lot_size)?.1,
+    };
+    Ok(Some((fee, rebate, reserved)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::claim_protocol_fees::{self, ClaimProtocolFees};
+    use crate::orderbook::{Side, TimeInForce};
+    use crate::test_utils::{account_infos, install_clock, load, token_balance, TestAccount, Venue, VenueVaults, NOW};
+    
+    /// Settle queued events for the trader states in `traders`
+    fn consume<'info>(infos: &'info [AccountInfo<'info>], traders: &'info [AccountInfo<'info>]) -> Result<()> {
+        install_clock();
+        let mut accounts = ConsumeEvents {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            event_queue: UncheckedAccount::try_from(&infos[Venue::EVENT_QUEUE]),
+            cranker: None,
+            cranker_state: None,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, traders, Default::default()), 8)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    /// Sweep the protocol fees, signed by the account at `authority`, into
+    /// the base and quote token accounts that follow it
+    fn claim<'info>(infos: &'info [AccountInfo<'info>], vaults: &VenueVaults, authority: usize) -> Result<()> {
+        install_clock();
+        let mut accounts = ClaimProtocolFees {
+            global_config: Account::try_from(&infos[Venue::GLOBAL_CONFIG])?,
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            authority: Signer::try_from(&infos[authority])?,
+            recipient_base_account: InterfaceAccount::try_from(&infos[authority + 1])?,
+            recipient_quote_account: InterfaceAccount::try_from(&infos[authority + 2])?,
+            base_vault: InterfaceAccount::try_from(&infos[vaults.base_vault])?,
+            quote_vault: InterfaceAccount::try_from(&infos[vaults.quote_vault])?,
+            base_mint: InterfaceAccount::try_from(&infos[vaults.base_mint])?,
+            quote_mint: InterfaceAccount::try_from(&infos[vaults.quote_mint])?,
+            market_authority: UncheckedAccount::try_from(&infos[Venue::MARKET]),
+            base_token_program: Interface::try_from(&infos[vaults.token_program])?,
+            quote_token_program: Interface::try_from(&infos[vaults.token_program])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        claim_protocol_fees::handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()))?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn fees_claimed_before_consuming_leave_the_maker_rebate_payable() {
+        let mut venue = Venue::new(|_| {});
+        let vaults = venue.add_vaults(10_000, 20_000);
+        let maker = venue.add_trader(10_000, 0);
+        let taker = venue.add_trader(0, 20_000);
+        venue.accounts.push(TestAccount::signer());
+        let authority = venue.accounts.len() - 1;
+        venue.add_wallet(authority, true, 0);
+        venue.add_wallet(authority, false, 0);
+        let infos = account_infos(&mut venue.accounts);
+        
+        // Ten lots at 2_000: the taker pays 30 base, 10 of it the maker's rebate
+        Venue::place(&infos, maker, Venue::order(Side::Ask, 2_000, 10_000, TimeInForce::GTC)).unwrap();
+        Venue::place(&infos, taker, Venue::order(Side::Bid, 2_000, 10_000, TimeInForce::IOC)).unwrap();
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!(market.base_fees_accrued, 30);
+        assert_eq!(market.base_rebates_reserved, 10);
+        assert_eq!(market.claimable_fees(true), 20);
+        
+        claim(&infos, &vaults, authority).unwrap();
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!(market.base_fees_accrued, 10);
+        assert_eq!(market.base_vault_ledger, 9_980);
+        assert_eq!(token_balance(&infos[authority + 1]), 20);
+        
+        consume(&infos, &infos[maker + 1..maker + 2]).unwrap();
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!(market.base_fees_accrued, 0);
+        assert_eq!(market.base_rebates_reserved, 0);
+        let maker_state: TraderState = load(&infos[maker + 1]);
+        assert_eq!(maker_state.base_available, 10);
+        assert_eq!(maker_state.base_locked, 0);
+        assert_eq!(maker_state.quote_available, 20_000);
+        let taker_state: TraderState = load(&infos[taker + 1]);
+        assert_eq!(taker_state.base_available, 9_970);
+    }
+    
+    #[test]
+    fn a_maker_rebate_waits_for_the_taker_fee_that_funds_it() {
+        let mut venue = Venue::new(|_| {});
+        let maker = venue.add_trader(10_000, 0);
+        let taker = venue.add_trader(0, 20_000);
+        let infos = account_infos(&mut venue.accounts);
+        Venue::place(&infos, maker, Venue::order(Side::Ask, 2_000, 10_000, TimeInForce::GTC)).unwrap();
+        let mut taker_state = Account::<TraderState>::try_from(&infos[taker + 1]).unwrap();
+        taker_state.lock_quote(20_000).unwrap();
+        taker_state.exit(&crate::ID).unwrap();
+        
+        // A crank-matched fill leaves both sides queued; settle the maker alone first
+        let mut event_queue_data = infos[Venue::EVENT_QUEUE].data.borrow_mut();
+        let mut event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE]).unwrap();
+        let clock = Clock::default();
+        let mut bid = crate::orderbook::Order::new(2, *infos[taker].key, Side::Bid, 2_000, 10_000, TimeInForce::GTC, &clock);
+        let mut ask = crate::orderbook::Order::new(1, *infos[maker].key, Side::Ask, 2_000, 10_000, TimeInForce::GTC, &clock);
+        bid.fill(10_000).unwrap();
+        ask.fill(10_000).unwrap();
+        event_queue.push(&mut event_queue_data, QueueEvent::fill(&bid, &ask, 2_000, 10_000, 30, -10, 0)).unwrap();
+        event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE]).unwrap();
+        drop(event_queue_data);
+        
+        consume(&infos, &infos[maker + 1..maker + 2]).unwrap();
+        let event_queue: EventQueue = load(&infos[Venue::EVENT_QUEUE]);
+        assert_eq!(event_queue.count, 1);
+        let maker_state: TraderState = load(&infos[maker + 1]);
+        assert_eq!(maker_state.base_available, 0); // Rebate not paid yet
+        
+        consume(&infos, &infos[taker + 1..taker + 2]).unwrap();
+        consume(&infos, &infos[maker + 1..maker + 2]).unwrap();
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!(market.base_fees_accrued, 20);
+        assert_eq!(market.base_rebates_reserved, 0);
+        let maker_state: TraderState = load(&infos[maker + 1]);
+        assert_eq!(maker_state.base_available, 10);
+        assert_eq!(maker_state.quote_available, 20_000);
+    }
+    
+    #[test]
+    fn makers_earn_points_for_the_time_their_filled_orders_rested() {
+        let mut venue = Venue::new(|market| {
+            market.points_taker_weight = 10_000;
+            market.points_maker_weight = 10_000;
+        });
+        let maker = venue.add_trader(20_000, 0);
+        let taker = venue.add_trader(0, 20_000);
+        let infos = account_infos(&mut venue.accounts);
+        
+        // A quote hit in the second it was placed earns its maker nothing
+        Venue::place(&infos, maker, Venue::order(Side::Ask, 2_000, 10_000, TimeInForce::GTC)).unwrap();
+        Venue::place(&infos, taker, Venue::order(Side::Bid, 2_000, 10_000, TimeInForce::IOC)).unwrap();
+        consume(&infos, &infos[maker + 1..maker + 2]).unwrap();
+        assert_eq!(load::<TraderState>(&infos[taker + 1]).points, 20_000);
+        assert_eq!(load::<TraderState>(&infos[maker + 1]).points, 0);
+        
+        // Half an hour on the book earns half the maker weight
+        let mut maker_state = Account::<TraderState>::try_from(&infos[maker + 1]).unwrap();
+        maker_state.lock_base(10_000).unwrap();
+        maker_state.exit(&crate::ID).unwrap();
+        let mut event_queue_data = infos[Venue::EVENT_QUEUE].data.borrow_mut();
+        let mut event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE]).unwrap();
+        let now = Clock { unix_timestamp: NOW, ..Clock::default() };
+        let placed = Clock { unix_timestamp: NOW - 1_800, ..Clock::default() };
+        let mut bid = crate::orderbook::Order::new(3, *infos[taker].key, Side::Bid, 2_000, 10_000, TimeInForce::IOC, &now);
+        let mut ask = crate::orderbook::Order::new(4, *infos[maker].key, Side::Ask, 2_000, 10_000, TimeInForce::GTC, &placed);
+        bid.fill(10_000).unwrap();
+        ask.fill(10_000).unwrap();
+        let fill = QueueEvent::fill(&bid, &ask, 2_000, 10_000, 0, 0, now.unix_timestamp);
+        event_queue.push(&mut event_queue_data, fill).unwrap();
+        event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE]).unwrap();
+        drop(event_queue_data);
+        
+        consume(&infos, &infos[maker + 1..maker + 2]).unwrap();
+        assert_eq!(load::<TraderState>(&infos[maker + 1]).points, 10_000);
+    }
+    
+    #[test]
+    fn an_order_that_expires_unfilled_forfeits_its_deposit_and_a_filled_one_gets_it_back() {
+        let mut venue = Venue::new(|market| market.placement_deposit_lamports = 5_000);
+        let maker = venue.add_trader(15_000, 0);
+        let taker = venue.add_trader(0, 20_000);
+        let infos = account_infos(&mut venue.accounts);
+        let ask = |price, size| Venue::order(Side::Ask, price, size, TimeInForce::GTC);
+        assert_eq!(
+            Venue::place(&infos, maker, ask(2_000, 10_000)).unwrap_err(),
+            DexError::PlacementDepositRequired.into()
+        );
+        
+        let mut maker_state = Account::<TraderState>::try_from(&infos[maker + 1]).unwrap();
+        maker_state.deposit_balance = 10_000;
+        maker_state.exit(&crate::ID).unwrap();
+        Venue::place(&infos, maker, ask(2_000, 10_000)).unwrap();
+        Venue::place(&infos, maker, Venue::order(Side::Ask, 2_100, 5_000, TimeInForce::GTC)).unwrap();
+        let maker_state: TraderState = load(&infos[maker + 1]);
+        assert_eq!((maker_state.deposit_balance, maker_state.deposits_held), (0, 10_000));
+        
+        // The filled order's deposit returns to the balance
+        Venue::place(&infos, taker, Venue::order(Side::Bid, 2_000, 10_000, TimeInForce::IOC)).unwrap();
+        consume(&infos, &infos[maker + 1..maker + 2]).unwrap();
+        let maker_state: TraderState = load(&infos[maker + 1]);
+        assert_eq!((maker_state.deposit_balance, maker_state.deposits_held), (5_000, 5_000));
+        
+        // The crank removes the other one at its expiry without a fill
+        let mut event_queue_data = infos[Venue::EVENT_QUEUE].data.borrow_mut();
+        let mut event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE]).unwrap();
+        let clock = Clock { unix_timestamp: NOW, ..Clock::default() };
+        let mut expired = crate::orderbook::Order::new(2, *infos[maker].key, Side::Ask, 2_100, 5_000, TimeInForce::GTC, &clock);
+        expired.expiry_timestamp = NOW + 60;
+        expired.deposit_lamports = 5_000;
+        event_queue.push(&mut event_queue_data, QueueEvent::out(&expired, NOW + 60)).unwrap();
+        event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE]).unwrap();
+        drop(event_queue_data);
+        
+        let (state_lamports, market_lamports) = (infos[maker + 1].lamports(), infos[Venue::MARKET].lamports());
+        consume(&infos, &infos[maker + 1..maker + 2]).unwrap();
+        let maker_state: TraderState = load(&infos[maker + 1]);
+        assert_eq!((maker_state.deposit_balance, maker_state.deposits_held), (5_000, 0));
+        assert_eq!(maker_state.base_locked, 0);
+        assert_eq!(infos[maker + 1].lamports(), state_lamports - 5_000);
+        assert_eq!(infos[Venue::MARKET].lamports(), market_lamports + 5_000);
+        assert_eq!(load::<Market>(&infos[Venue::MARKET]).forfeited_deposits, 5_000);
+    }
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Competition, GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, CompetitionCreated};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateCompetitionParams {
+    pub competition_id: u64,
+    pub start: i64,        // Unix timestamp, not in the past
+    pub end: i64,          // Unix timestamp, exclusive
+    pub prize_amount: u64, // Moved from `funding_account` into the prize vault
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(params: CreateCompetitionParams)]
+pub struct CreateCompetition<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Competition::SIZE,
+        seeds = [b"competition", params.competition_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub competition: Account<'info, Competition>,
+    
+    #[account(mint::token_program = token_program)]
+    pub prize_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = prize_mint,
+        token::authority = competition,
+        token::token_program = token_program,
+        seeds = [b"competition_vault", competition.key().as_ref()],
+        bump
+    )]
+    pub prize_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Authority's token account the prize is paid in from
+    #[account(mut, constraint = funding_account.mint == prize_mint.key() @ DexError::InvalidMint)]
+    pub funding_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    
+    // Remaining accounts: the competition's markets (writable), 1 to 4
+}
+
+/// Set up a trading competition over up to four markets and fund its prize
+///
+/// The markets must share a quote mint, so volumes add up across them, and
+/// none may be in a competition that has not ended yet. Each market records
+/// the competition and its window; fills inside the window count for
+/// traders who entered with `enter_competition`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateCompetition<'info>>,
+    params: CreateCompetitionParams,
+) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let now = Clock::get()?.unix_timestamp;
+    require!(params.start >= now && params.end > params.start, DexError::InvalidCompetition);
+    require!(params.prize_amount > 0, DexError::InvalidCompetition);
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= Competition::MAX_MARKETS,
+        DexError::InvalidCompetition
+    );
+    
+    let competition_key = ctx.accounts.competition.key();
+    let mut markets = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut quote_mint = None;
+    for market_info in ctx.remaining_accounts.iter() {
+        require!(!markets.contains(market_info.key), DexError::DuplicateAccount);
+        let mut market = Account::<Market>::try_from(market_info)?;
+        require!(*quote_mint.get_or_insert(market.quote_mint) == market.quote_mint, DexError::InvalidCompetition);
+        require!(market.competition_end <= now, DexError::InvalidCompetition);
+        
+        market.competition = competition_key;
+        market.competition_start = params.start;
+        market.competition_end = params.end;
+        market.exit(&crate::ID)?;
+        markets.push(market.key());
+    }
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.funding_account.to_account_info(),
+        mint: ctx.accounts.prize_mint.to_account_info(),
+        to: ctx.accounts.prize_vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, params.prize_amount, ctx.accounts.prize_mint.decimals)?;
+    
+    // The prize is whatever arrived, net of any transfer fee
+    ctx.accounts.prize_vault.reload()?;
+    let prize_amount = ctx.accounts.prize_vault.amount;
+    
+    let competition = &mut ctx.accounts.competition;
+    competition.competition_id = params.competition_id;
+    competition.market_count = markets.len() as u8;
+    competition.markets[..markets.len()].copy_from_slice(&markets);
+    competition.quote_mint = quote_mint.unwrap_or_default();
+    competition.start = params.start;
+    competition.end = params.end;
+    competition.prize_mint = ctx.accounts.prize_mint.key();
+    competition.prize_vault = ctx.accounts.prize_vault.key();
+    competition.prize_amount = prize_amount;
+    competition.bump = ctx.bumps.competition;
+    
+    sink.emit(CompetitionCreated {
+        competition: competition_key,
+        markets,
+        start: params.start,
+        end: params.end,
+        prize_mint: competition.prize_mint,
+        prize_amount,
+        timestamp: now,
+    })?;
+    
+    msg!("Competition created: id={}, markets={}, start={}, end={}, prize={}",
+         params.competition_id, competition.market_count, params.start, params.end, prize_amount);
+    
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::{DataFeed, Market};
+use crate::events::{event_sink, DataFeedCreated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CreateDataFeed<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = DataFeed::SIZE,
+        seeds = [b"data_feed", market.key().as_ref()],
+        bump
+    )]
+    pub data_feed: Account<'info, DataFeed>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Open a market's premium data feed; the payer covers its rent
+pub fn handler(ctx: Context<CreateDataFeed>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let data_feed = &mut ctx.accounts.data_feed;
+    data_feed.market = ctx.accounts.market.key();
+    data_feed.bump = ctx.bumps.data_feed;
+    
+    sink.emit(DataFeedCreated {
+        market: data_feed.market,
+        data_feed: data_feed.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Data feed created: market={}", data_feed.market);
+    
+    Ok(())
+}
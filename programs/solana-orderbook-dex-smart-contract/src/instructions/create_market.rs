@@ -1,16 +1,73 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::{GlobalConfig, Market};
 use crate::errors::DexError;
-use crate::events::MarketCreated;
+use crate::events::{event_sink, MarketCreated};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct CreateMarketParams {
     pub market_id: u64,
-    pub tick_size: u64,
-    pub lot_size: u64,
+    pub tick_size: u64, // Ignored unless template is Custom
+    pub lot_size: u64,  // Ignored unless template is Custom
+    pub template: u8,   // 0 = Custom, 1 = Majors, 2 = LongTail, 3 = StablePair
 }
 
+/// Named parameter presets for market creation
+/// Sizes are derived from the mint decimals so presets work for any pair
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MarketTemplate {
+    /// Caller-provided tick and lot sizes
+    Custom = 0,
+    /// Liquid pairs: 0.01 quote ticks, 0.001 base lots
+    Majors = 1,
+    /// Illiquid pairs: 0.0001 quote ticks, whole-token lots
+    LongTail = 2,
+    /// Stable-stable pairs: 0.0001 quote ticks, whole-token lots, 50bps peg band around 1.0
+    StablePair = 3,
+}
+
+impl MarketTemplate {
+    pub const STABLE_PEG_BAND_BPS: u16 = 50;
+    
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(MarketTemplate::Custom),
+            1 => Some(MarketTemplate::Majors),
+            2 => Some(MarketTemplate::LongTail),
+            3 => Some(MarketTemplate::StablePair),
+            _ => None,
+        }
+    }
+    
+    /// Expand the template into (tick_size, lot_size, peg_price, peg_band_bps)
+    pub fn expand(
+        &self,
+        params: &CreateMarketParams,
+        base_decimals: u8,
+        quote_decimals: u8,
+    ) -> Result<(u64, u64, u64, u16)> {
+        let base_unit = 10u64
+            .checked_pow(base_decimals as u32)
+            .ok_or(DexError::MathOverflow)?;
+        let quote_unit = 10u64
+            .checked_pow(quote_decimals as u32)
+            .ok_or(DexError::MathOverflow)?;
+        
+        let expanded = match self {
+            MarketTemplate::Custom => (params.tick_size, params.lot_size, 0, 0),
+            MarketTemplate::Majors => ((quote_unit / 100).max(1), (base_unit / 1_000).max(1), 0, 0),
+            MarketTemplate::LongTail => ((quote_unit / 10_000).max(1), base_unit, 0, 0),
+            // Price is quoted per lot, so with whole-token lots 1.0 == one quote unit
+            MarketTemplate::StablePair => {
+                ((quote_unit / 10_000).max(1), base_unit, quote_unit, Self::STABLE_PEG_BAND_BPS)
+            }
+        };
+        Ok(expanded)
+    }
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(params: CreateMarketParams)]
 pub struct CreateMarket<'info> {
@@ -29,38 +86,46 @@ pub struct CreateMarket<'info> {
     )]
     pub market: Account<'info, Market>,
     
-    pub base_mint: Account<'info, Mint>,
-    pub quote_mint: Account<'info, Mint>,
+    #[account(mint::token_program = base_token_program)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = quote_token_program)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         init,
         payer = authority,
         token::mint = base_mint,
         token::authority = market,
+        token::token_program = base_token_program,
         seeds = [b"base_vault", market.key().as_ref()],
         bump
     )]
-    pub base_vault: Account<'info, TokenAccount>,
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init,
         payer = authority,
         token::mint = quote_mint,
         token::authority = market,
+        token::token_program = quote_token_program,
         seeds = [b"quote_vault", market.key().as_ref()],
         bump
     )]
-    pub quote_vault: Account<'info, TokenAccount>,
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    /// Token program of the base mint (SPL Token or Token-2022)
+    pub base_token_program: Interface<'info, TokenInterface>,
+    /// Token program of the quote mint (SPL Token or Token-2022)
+    pub quote_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn handler(ctx: Context<CreateMarket>, params: CreateMarketParams) -> Result<()> {
+    let sink = event_sink!(ctx);
     let global_config = &ctx.accounts.global_config;
     
     // Check if market creation is allowed
@@ -71,15 +136,24 @@ pub fn handler(ctx: Context<CreateMarket>, params: CreateMarketParams) -> Result
         );
     }
     
+    // Expand the template into concrete parameters
+    let template = MarketTemplate::from_u8(params.template)
+        .ok_or(DexError::InvalidMarketParams)?;
+    let (tick_size, lot_size, peg_price, peg_band_bps) = template.expand(
+        &params,
+        ctx.accounts.base_mint.decimals,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+    
     // Validate parameters
-    require!(params.tick_size > 0, DexError::InvalidMarketParams);
-    require!(params.lot_size > 0, DexError::InvalidMarketParams);
+    require!(tick_size > 0, DexError::InvalidMarketParams);
+    require!(lot_size > 0, DexError::InvalidMarketParams);
     require!(
-        params.tick_size <= 1_000_000_000, // Reasonable upper bound
+        tick_size <= 1_000_000_000, // Reasonable upper bound
         DexError::InvalidMarketParams
     );
     require!(
-        params.lot_size <= 1_000_000_000_000, // Reasonable upper bound
+        lot_size <= 1_000_000_000_000, // Reasonable upper bound
         DexError::InvalidMarketParams
     );
     
@@ -89,27 +163,34 @@ pub fn handler(ctx: Context<CreateMarket>, params: CreateMarketParams) -> Result
     market.quote_mint = ctx.accounts.quote_mint.key();
     market.base_vault = ctx.accounts.base_vault.key();
     market.quote_vault = ctx.accounts.quote_vault.key();
-    market.tick_size = params.tick_size;
-    market.lot_size = params.lot_size;
+    market.tick_size = tick_size;
+    market.lot_size = lot_size;
     market.authority = ctx.accounts.authority.key();
     market.paused = false;
     market.best_bid = 0;
     market.best_ask = 0;
     market.order_count = 0;
     market.total_volume = 0;
-    market.bump = ctx.bumps.get("market").unwrap().clone();
+    market.template = params.template;
+    market.peg_price = peg_price;
+    market.peg_band_bps = peg_band_bps;
+    market.base_decimals = ctx.accounts.base_mint.decimals;
+    market.quote_decimals = ctx.accounts.quote_mint.decimals;
+    let now = Clock::get()?.unix_timestamp;
+    market.epoch_started_at = now - now.rem_euclid(Market::EPOCH_SECONDS);
+    market.bump = ctx.bumps.market;
     
-    emit!(MarketCreated {
+    sink.emit(MarketCreated {
         market: market.key(),
         base_mint: market.base_mint,
         quote_mint: market.quote_mint,
         tick_size: market.tick_size,
         lot_size: market.lot_size,
         timestamp: Clock::get()?.unix_timestamp,
-    });
+    })?;
     
-    msg!("Market created: id={}, base={}, quote={}", 
-         params.market_id, market.base_mint, market.quote_mint);
+    msg!("Market created: id={}, base={}, quote={}, template={:?}", 
+         params.market_id, market.base_mint, market.quote_mint, template);
     
     Ok(())
 }
@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, Mint};
-use crate::state::{Market, TraderState};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Market, Seat, TraderState};
 use crate::errors::DexError;
-use crate::events::DepositEvent;
+use crate::events::{event_sink, DepositEvent};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(amount: u64)]
 pub struct Deposit<'info> {
     #[account(
+        mut,
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
         bump = market.bump
     )]
@@ -25,22 +27,31 @@ pub struct Deposit<'info> {
     #[account(mut)]
     pub trader: Signer<'info>,
     
+    /// Trading seat, required when the market is permissioned
+    #[account(
+        seeds = [b"seat", market.key().as_ref(), trader.key().as_ref()],
+        bump = seat.bump
+    )]
+    pub seat: Option<Account<'info, Seat>>,
+    
     #[account(mut)]
-    pub trader_token_account: Account<'info, TokenAccount>,
+    pub trader_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
     require!(amount > 0, DexError::InvalidOrderParams);
     
     let market = &ctx.accounts.market;
+    require!(!market.requires_seat || ctx.accounts.seat.is_some(), DexError::SeatRequired);
     
     // Validate mint matches market
     let is_base = ctx.accounts.mint.key() == market.base_mint;
@@ -59,24 +70,33 @@ pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     );
     
     // Transfer tokens from trader to vault
-    let cpi_accounts = Transfer {
+    let vault_before = ctx.accounts.vault.amount;
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.trader_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
         to: ctx.accounts.vault.to_account_info(),
         authority: ctx.accounts.trader.to_account_info(),
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    anchor_spl::token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+    
+    // Credit what the vault received (Token-2022 transfer fees are withheld in transit)
+    ctx.accounts.vault.reload()?;
+    let amount = ctx.accounts.vault.amount
+        .checked_sub(vault_before)
+        .ok_or(DexError::MathUnderflow)?;
     
     // Update trader state
-    let mut trader_state = ctx.accounts.trader_state.as_mut();
+    let trader_state = &mut ctx.accounts.trader_state;
     
     if trader_state.trader == Pubkey::default() {
         // Initialize trader state
         trader_state.trader = ctx.accounts.trader.key();
         trader_state.market = market.key();
-        trader_state.bump = ctx.bumps.get("trader_state").unwrap().clone();
+        trader_state.bump = ctx.bumps.trader_state;
     }
+    trader_state.last_deposit_at = Clock::get()?.unix_timestamp;
     
     if is_base {
         trader_state.base_available = trader_state.base_available
@@ -88,7 +108,7 @@ pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
             .ok_or(DexError::MathOverflow)?;
     }
     
-    emit!(DepositEvent {
+    sink.emit(DepositEvent {
         trader: ctx.accounts.trader.key(),
         market: market.key(),
         mint: ctx.accounts.mint.key(),
@@ -99,10 +119,13 @@ pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
             trader_state.quote_available
         },
         timestamp: Clock::get()?.unix_timestamp,
-    });
+    })?;
     
     msg!("Deposit: trader={}, mint={}, amount={}", 
          ctx.accounts.trader.key(), ctx.accounts.mint.key(), amount);
     
+    // Keep the vault ledger in step with what the vault received
+    ctx.accounts.market.credit_vault(is_base, amount)?;
+    
     Ok(())
 }
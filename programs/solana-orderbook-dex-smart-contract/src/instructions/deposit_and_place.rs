@@ -0,0 +1,222 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{GlobalConfig, IntegratorFees, Market, OpenOrders, Seat, TraderState};
+use crate::orderbook::Side;
+use crate::errors::DexError;
+use crate::events::{event_sink, DepositEvent};
+use crate::instructions::place_order::{place, PlaceOrderAccounts, PlaceOrderParams};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(params: PlaceOrderParams)]
+pub struct DepositAndPlace<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account (we'll validate it's initialized)
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    /// CHECK: Event queue account (must be the market's)
+    #[account(mut, address = market.event_queue @ DexError::InvalidAccountState)]
+    pub event_queue: UncheckedAccount<'info>,
+    
+    /// CHECK: Trade tape, required when the market has one
+    #[account(mut)]
+    pub trade_tape: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = TraderState::SIZE,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    /// CHECK: Market oracle, required when the market enforces an oracle band
+    pub oracle: Option<UncheckedAccount<'info>>,
+    
+    /// Slot index of the trader's orders; records the order if it rests
+    #[account(
+        mut,
+        seeds = [b"open_orders", trader_state.key().as_ref()],
+        bump = open_orders.bump
+    )]
+    pub open_orders: Option<Account<'info, OpenOrders>>,
+    
+    /// Integrator (wallet, frontend) routing the order; earns a share of its taker fees
+    #[account(mut, has_one = market @ DexError::InvalidAccountState)]
+    pub integrator_fees: Option<Account<'info, IntegratorFees>>,
+    
+    /// Trading seat, required when the market is permissioned
+    #[account(
+        seeds = [b"seat", market.key().as_ref(), trader.key().as_ref()],
+        bump = seat.bump
+    )]
+    pub seat: Option<Account<'info, Seat>>,
+    
+    /// Fee-discount pass: the trader's token account holding the market's pass token or NFT
+    pub fee_pass: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Metaplex metadata of the pass NFT, required when the pass is a collection
+    pub fee_pass_metadata: Option<UncheckedAccount<'info>>,
+    
+    /// Token account the order is funded from (quote for bids, base for asks)
+    #[account(mut)]
+    pub trader_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit what an order needs and place it in one instruction
+///
+/// The order locks quote for bids (price * size, or the quote budget) and
+/// base for asks (size). Only the part the trader state does not already
+/// hold is transferred in, and the trader state is created on first use.
+/// Dry runs, sell-to-quote and reduce-only orders are rejected, as their
+/// funding is not known from the price and size alone.
+pub fn handler(ctx: Context<DepositAndPlace>, params: PlaceOrderParams) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(
+        !params.dry_run && params.quote_proceeds.is_none() && !params.reduce_only,
+        DexError::InvalidOrderParams
+    );
+    
+    let market = &ctx.accounts.market;
+    require!(!market.requires_seat || ctx.accounts.seat.is_some(), DexError::SeatRequired);
+    
+    // Funds the order will lock, from its price and size
+    let side = Side::from_u8(params.side)
+        .ok_or(DexError::InvalidOrderParams)?;
+    let price = match params.price_ui {
+        Some(price_ui) => market.price_from_ui(price_ui)?,
+        None => params.price,
+    };
+    let size = match params.size_ui {
+        Some(size_ui) => market.size_from_ui(size_ui)?,
+        None => params.size,
+    };
+    let is_base = side == Side::Ask;
+    let required = match (side, params.quote_budget) {
+        (Side::Bid, Some(quote_budget)) => quote_budget,
+        (Side::Bid, None) => price
+            .checked_mul(size)
+            .and_then(|v| v.checked_div(market.lot_size))
+            .ok_or(DexError::MathOverflow)?,
+        (Side::Ask, _) => size,
+    };
+    
+    // Validate mint and vault against the side's token
+    let (expected_mint, expected_vault) = if is_base {
+        (market.base_mint, market.base_vault)
+    } else {
+        (market.quote_mint, market.quote_vault)
+    };
+    require!(ctx.accounts.mint.key() == expected_mint, DexError::InvalidMint);
+    require!(ctx.accounts.vault.key() == expected_vault, DexError::InvalidMint);
+    
+    let trader_state = &mut ctx.accounts.trader_state;
+    if trader_state.trader == Pubkey::default() {
+        // Initialize trader state
+        trader_state.trader = ctx.accounts.trader.key();
+        trader_state.market = market.key();
+        trader_state.bump = ctx.bumps.trader_state;
+    }
+    
+    let available = if is_base {
+        trader_state.base_available
+    } else {
+        trader_state.quote_available
+    };
+    let amount = required.saturating_sub(available);
+    
+    if amount > 0 {
+        // Transfer the shortfall from trader to vault
+        let vault_before = ctx.accounts.vault.amount;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.trader_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.trader.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+        
+        // Credit what the vault received (Token-2022 transfer fees are withheld in transit)
+        ctx.accounts.vault.reload()?;
+        let received = ctx.accounts.vault.amount
+            .checked_sub(vault_before)
+            .ok_or(DexError::MathUnderflow)?;
+        
+        let trader_state = &mut ctx.accounts.trader_state;
+        trader_state.last_deposit_at = Clock::get()?.unix_timestamp;
+        let new_balance = if is_base {
+            trader_state.base_available = trader_state.base_available
+                .checked_add(received)
+                .ok_or(DexError::MathOverflow)?;
+            trader_state.base_available
+        } else {
+            trader_state.quote_available = trader_state.quote_available
+                .checked_add(received)
+                .ok_or(DexError::MathOverflow)?;
+            trader_state.quote_available
+        };
+        
+        sink.emit(DepositEvent {
+            trader: ctx.accounts.trader.key(),
+            market: ctx.accounts.market.key(),
+            mint: ctx.accounts.mint.key(),
+            amount: received,
+            new_balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        })?;
+        
+        // Keep the vault ledger in step with what the vault received
+        ctx.accounts.market.credit_vault(is_base, received)?;
+        
+        msg!("Deposit for order: trader={}, mint={}, amount={}",
+             ctx.accounts.trader.key(), ctx.accounts.mint.key(), received);
+    }
+    
+    let accounts = &mut *ctx.accounts;
+    place(
+        PlaceOrderAccounts {
+            market: &mut accounts.market,
+            orderbook: &accounts.orderbook,
+            event_queue: &accounts.event_queue,
+            trade_tape: accounts.trade_tape.as_deref(),
+            global_config: &accounts.global_config,
+            trader_state: &mut accounts.trader_state,
+            trader: &accounts.trader,
+            oracle: accounts.oracle.as_deref(),
+            open_orders: accounts.open_orders.as_mut(),
+            integrator_fees: accounts.integrator_fees.as_mut(),
+            seat: accounts.seat.as_ref(),
+            fee_pass: accounts.fee_pass.as_ref(),
+            fee_pass_metadata: accounts.fee_pass_metadata.as_deref(),
+        },
+        params,
+        &sink,
+    )
+}
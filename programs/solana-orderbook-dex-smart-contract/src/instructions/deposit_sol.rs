@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{self, SyncNative, TokenAccount, TokenInterface};
+use crate::state::{Market, Seat, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, DepositEvent};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = TraderState::SIZE,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    /// Trading seat, required when the market is permissioned
+    #[account(
+        seeds = [b"seat", market.key().as_ref(), trader.key().as_ref()],
+        bump = seat.bump
+    )]
+    pub seat: Option<Account<'info, Seat>>,
+    
+    /// The market's wSOL vault
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit native SOL into a market with a wSOL side
+///
+/// Lamports go straight into the wSOL vault and are wrapped in place with
+/// `sync_native`, so the trader needs no wSOL token account.
+pub fn handler(ctx: Context<DepositSol>, amount: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(amount > 0, DexError::InvalidOrderParams);
+    
+    let market = &ctx.accounts.market;
+    require!(!market.requires_seat || ctx.accounts.seat.is_some(), DexError::SeatRequired);
+    
+    // One side of the market must be wrapped SOL
+    let is_base = market.base_mint == native_mint::ID;
+    require!(is_base || market.quote_mint == native_mint::ID, DexError::InvalidMint);
+    
+    let expected_vault = if is_base {
+        market.base_vault
+    } else {
+        market.quote_vault
+    };
+    require!(
+        ctx.accounts.vault.key() == expected_vault,
+        DexError::InvalidMint
+    );
+    
+    // Move lamports into the vault and wrap them
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.trader.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, amount)?;
+    
+    let cpi_accounts = SyncNative {
+        account: ctx.accounts.vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::sync_native(cpi_ctx)?;
+    
+    // Update trader state
+    let trader_state = &mut ctx.accounts.trader_state;
+    
+    if trader_state.trader == Pubkey::default() {
+        // Initialize trader state
+        trader_state.trader = ctx.accounts.trader.key();
+        trader_state.market = market.key();
+        trader_state.bump = ctx.bumps.trader_state;
+    }
+    trader_state.last_deposit_at = Clock::get()?.unix_timestamp;
+    
+    if is_base {
+        trader_state.base_available = trader_state.base_available
+            .checked_add(amount)
+            .ok_or(DexError::MathOverflow)?;
+    } else {
+        trader_state.quote_available = trader_state.quote_available
+            .checked_add(amount)
+            .ok_or(DexError::MathOverflow)?;
+    }
+    
+    sink.emit(DepositEvent {
+        trader: ctx.accounts.trader.key(),
+        market: market.key(),
+        mint: native_mint::ID,
+        amount,
+        new_balance: if is_base {
+            trader_state.base_available
+        } else {
+            trader_state.quote_available
+        },
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Deposit SOL: trader={}, lamports={}", ctx.accounts.trader.key(), amount);
+    
+    // Keep the vault ledger in step with what the vault received
+    ctx.accounts.market.credit_vault(is_base, amount)?;
+    
+    Ok(())
+}
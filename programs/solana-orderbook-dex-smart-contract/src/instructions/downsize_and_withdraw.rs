@@ -0,0 +1,366 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Market, TraderState, WithdrawalAllowlist};
+use crate::orderbook::{Order, Orderbook, Side};
+use crate::errors::DexError;
+use crate::events::{event_sink, OrderCancelled, OrderModified, WithdrawEvent};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct DownsizeAndWithdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    /// Required when the trader has enabled a withdrawal allowlist
+    #[account(
+        seeds = [b"withdrawal_allowlist", trader_state.key().as_ref()],
+        bump = withdrawal_allowlist.bump
+    )]
+    pub withdrawal_allowlist: Option<Account<'info, WithdrawalAllowlist>>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    #[account(mut)]
+    pub trader_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Market authority for vault signer
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraw `amount`, shrinking resting orders first if the available balance is short
+///
+/// Orders that lock the withdrawn token (asks for base, bids for quote) are
+/// reduced starting with the least aggressive one, i.e. the furthest from
+/// the touch. An order that would drop below one lot is cancelled instead.
+/// Reductions keep time priority, as in modify_order.
+pub fn handler(ctx: Context<DownsizeAndWithdraw>, amount: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(amount > 0, DexError::InvalidOrderParams);
+    
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+    
+    // Validate mint and vault
+    let is_base = ctx.accounts.mint.key() == market.base_mint;
+    let is_quote = ctx.accounts.mint.key() == market.quote_mint;
+    require!(is_base || is_quote, DexError::InvalidMint);
+    
+    let expected_vault = if is_base {
+        market.base_vault
+    } else {
+        market.quote_vault
+    };
+    require!(
+        ctx.accounts.vault.key() == expected_vault,
+        DexError::InvalidMint
+    );
+    
+    let trader_state = &mut ctx.accounts.trader_state;
+    
+    // Same restrictions as withdraw
+    require!(
+        !trader_state.is_locked(clock.unix_timestamp),
+        DexError::AccountLocked
+    );
+    require!(
+        !trader_state.requires_co_signature(amount),
+        DexError::CoSignatureRequired
+    );
+    if trader_state.withdrawal_allowlist_enabled {
+        let allowlist = ctx.accounts.withdrawal_allowlist
+            .as_ref()
+            .ok_or(DexError::WithdrawalDestinationNotAllowed)?;
+        require!(
+            allowlist.allows(&ctx.accounts.trader_token_account.key()),
+            DexError::WithdrawalDestinationNotAllowed
+        );
+    }
+    
+    let available = if is_base {
+        trader_state.base_available
+    } else {
+        trader_state.quote_available
+    };
+    
+    if available < amount {
+        let orderbook_account_info = &ctx.accounts.orderbook;
+        require!(
+            orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+            DexError::InvalidOrderbookState
+        );
+        
+        let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+        let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+        require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+        
+        // Base is locked by asks and quote by bids; least aggressive orders first
+        let side = if is_base { Side::Ask } else { Side::Bid };
+        let trader = ctx.accounts.trader.key();
+        let mut orders: Vec<(u64, Order)> = orderbook.iter_side(&orderbook_data, side)
+            .filter(|(_, order)| order.trader == trader)
+            .collect();
+        orders.reverse();
+        
+        let mut shortfall = amount - available;
+        for (slot, mut order) in orders {
+            if shortfall == 0 {
+                break;
+            }
+            
+            let old_size = order.remaining_size;
+            let mut new_size = old_size.saturating_sub(size_to_release(&order, shortfall, market.lot_size)?);
+            if new_size < market.lot_size {
+                new_size = 0;
+            }
+            
+            let released = if is_base {
+                old_size - new_size
+            } else {
+                locked_quote(order.price, old_size, market.lot_size)?
+                    .checked_sub(locked_quote(order.price, new_size, market.lot_size)?)
+                    .ok_or(DexError::MathUnderflow)?
+            };
+            if is_base {
+                trader_state.unlock_base(released)?;
+            } else {
+                trader_state.unlock_quote(released)?;
+            }
+            shortfall = shortfall.saturating_sub(released);
+            
+            if new_size == 0 {
+                orderbook.remove_order(&mut orderbook_data, slot)?;
+                trader_state.open_order_count = trader_state.open_order_count
+                    .checked_sub(1)
+                    .ok_or(DexError::MathUnderflow)?;
+                trader_state.release_deposit(order.deposit_lamports as u64, false)?;
+                
+                if market.logs_l3() {
+                    sink.emit(OrderCancelled {
+                        market: market.key(),
+                        trader,
+                        order_id: order.order_id,
+                        client_order_id: order.client_order_id,
+                        remaining_size: old_size,
+                        timestamp: clock.unix_timestamp,
+                    })?;
+                }
+            } else {
+                order.size = order.size
+                    .checked_sub(old_size - new_size)
+                    .ok_or(DexError::MathUnderflow)?;
+                order.remaining_size = new_size;
+                orderbook.set_order(&mut orderbook_data, slot, &order)?;
+                
+                if market.logs_l3() {
+                    sink.emit(OrderModified {
+                        market: market.key(),
+                        trader,
+                        order_id: order.order_id,
+                        client_order_id: order.client_order_id,
+                        old_price: order.price,
+                        new_price: order.price,
+                        old_size,
+                        new_size,
+                        priority_kept: true,
+                        timestamp: clock.unix_timestamp,
+                    })?;
+                }
+            }
+        }
+        
+        require!(shortfall == 0, DexError::InsufficientFunds);
+        
+        orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+        market.sync_book(&orderbook, &orderbook_data, &sink)?;
+    }
+    
+    // Update trader state
+    if is_base {
+        trader_state.base_available = trader_state.base_available
+            .checked_sub(amount)
+            .ok_or(DexError::MathUnderflow)?;
+    } else {
+        trader_state.quote_available = trader_state.quote_available
+            .checked_sub(amount)
+            .ok_or(DexError::MathUnderflow)?;
+    }
+    
+    // Withdrawals soon after a deposit pay the early-withdrawal fee (or wait)
+    let fee = market.early_withdrawal_fee(trader_state.last_deposit_at, amount, clock.unix_timestamp)?;
+    let payout = amount - fee;
+    
+    // Transfer tokens from vault to trader
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"market",
+        &market_id_bytes,
+        &[market.bump],
+    ];
+    let signer = &[seeds];
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.trader_token_account.to_account_info(),
+        authority: ctx.accounts.market_authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, payout, ctx.accounts.mint.decimals)?;
+    
+    sink.emit(WithdrawEvent {
+        trader: ctx.accounts.trader.key(),
+        market: market.key(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+        fee,
+        new_balance: if is_base {
+            trader_state.base_available
+        } else {
+            trader_state.quote_available
+        },
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Downsize and withdraw: trader={}, mint={}, amount={}, freed={}",
+         ctx.accounts.trader.key(), ctx.accounts.mint.key(), amount, amount.saturating_sub(available));
+    
+    // Vault amount is still the pre-transfer balance
+    market.debit_vault(is_base, payout, ctx.accounts.vault.amount)?;
+    market.accrue_fee(is_base, fee)?;
+    
+    Ok(())
+}
+
+/// Quote locked by a bid of `size` at `price`
+fn locked_quote(price: u64, size: u64, lot_size: u64) -> Result<u64> {
+    price
+        .checked_mul(size)
+        .and_then(|v| v.checked_div(lot_size))
+        .ok_or(DexError::MathOverflow.into())
+}
+
+/// Smallest size reduction that frees at least `shortfall` of the order's locked token
+fn size_to_release(order: &Order, shortfall: u64, lot_size: u64) -> Result<u64> {
+    if !order.is_bid() {
+        return Ok(shortfall);
+    }
+    
+    // Rounding up keeps floor(price * size / lot) from freeing less than needed
+    let size = (shortfall as u128)
+        .checked_mul(lot_size as u128)
+        .map(|v| v.div_ceil(order.price as u128))
+        .ok_or(DexError::MathOverflow)?;
+    Ok(u64::try_from(size).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+    use crate::orderbook::TimeInForce;
+    use crate::test_utils::{account_infos, install_clock, load, Venue, VenueVaults};
+    
+    /// Downsize and withdraw `amount` of base for the trader whose signer is
+    /// at `trader` into the token account at `wallet`
+    fn downsize_and_withdraw<'info>(
+        infos: &'info [AccountInfo<'info>],
+        vaults: &VenueVaults,
+        trader: usize,
+        wallet: usize,
+        amount: u64,
+    ) -> Result<()> {
+        install_clock();
+        let mut accounts = DownsizeAndWithdraw {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            orderbook: UncheckedAccount::try_from(&infos[Venue::ORDERBOOK]),
+            trader_state: Account::try_from(&infos[trader + 1])?,
+            withdrawal_allowlist: None,
+            trader: Signer::try_from(&infos[trader])?,
+            trader_token_account: InterfaceAccount::try_from(&infos[wallet])?,
+            vault: InterfaceAccount::try_from(&infos[vaults.base_vault])?,
+            mint: InterfaceAccount::try_from(&infos[vaults.base_mint])?,
+            market_authority: UncheckedAccount::try_from(&infos[Venue::MARKET]),
+            token_program: Interface::try_from(&infos[vaults.token_program])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), amount)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    /// (price, remaining size) of each resting ask, best first
+    fn asks(info: &AccountInfo) -> Vec<(u64, u64)> {
+        let orderbook: Orderbook = load(info);
+        let data = info.try_borrow_data().unwrap();
+        orderbook.iter_side(&data, Side::Ask).map(|(_, order)| (order.price, order.remaining_size)).collect()
+    }
+    
+    #[test]
+    fn the_least_aggressive_asks_shrink_first_to_cover_the_withdrawal() {
+        let mut venue = Venue::new(|_| {});
+        let vaults = venue.add_vaults(30_000, 0);
+        let trader = venue.add_trader(30_000, 0);
+        let wallet = venue.add_wallet(trader, true, 0);
+        let infos = account_infos(&mut venue.accounts);
+        for price in [2_100, 2_200, 2_300] {
+            Venue::place(&infos, trader, Venue::order(Side::Ask, price, 10_000, TimeInForce::GTC)).unwrap();
+        }
+        
+        // 2_300 is cancelled outright and 2_200 gives up the remaining 5_000
+        downsize_and_withdraw(&infos, &vaults, trader, wallet, 15_000).unwrap();
+        assert_eq!(asks(&infos[Venue::ORDERBOOK]), vec![(2_100, 10_000), (2_200, 5_000)]);
+        let trader_state: TraderState = load(&infos[trader + 1]);
+        assert_eq!((trader_state.base_available, trader_state.base_locked), (0, 15_000));
+        assert_eq!(trader_state.open_order_count, 2);
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!((market.best_ask, market.base_vault_ledger), (2_100, 15_000));
+        
+        // The rest of the book cannot cover more than it locks
+        assert_eq!(
+            downsize_and_withdraw(&infos, &vaults, trader, wallet, 15_001).unwrap_err(),
+            DexError::InsufficientFunds.into()
+        );
+    }
+    
+    #[test]
+    fn a_bid_releases_enough_quote_despite_rounding() {
+        let mut order = Order::zeroed();
+        order.price = 1_500;
+        order.side = Side::Bid as u8;
+        let released = size_to_release(&order, 1_000, Venue::LOT_SIZE).unwrap();
+        let freed = locked_quote(order.price, 10_000, Venue::LOT_SIZE).unwrap()
+            - locked_quote(order.price, 10_000 - released, Venue::LOT_SIZE).unwrap();
+        assert_eq!((released, freed), (667, 1_001));
+    }
+}
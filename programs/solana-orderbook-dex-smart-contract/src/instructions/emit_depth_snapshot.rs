@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::orderbook::{Orderbook, Side};
+use crate::errors::DexError;
+use crate::events::{event_sink, DepthBucket, DepthSnapshot};
+
+/// Maximum number of buckets per side in a single snapshot
+pub const MAX_DEPTH_BUCKETS: u8 = 32;
+
+/// Minimum number of slots between two snapshots of the same market (~1 minute)
+pub const DEPTH_SNAPSHOT_INTERVAL_SLOTS: u64 = 150;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct EmitDepthSnapshot<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account (validated against market in instruction)
+    pub orderbook: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<EmitDepthSnapshot>, bucket_ticks: u64, max_buckets: u8) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(bucket_ticks > 0, DexError::InvalidInstruction);
+    require!(
+        max_buckets > 0 && max_buckets <= MAX_DEPTH_BUCKETS,
+        DexError::InvalidInstruction
+    );
+    
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+    
+    // Rate limit the crank so snapshots stay periodic
+    require!(
+        market.last_depth_snapshot_slot == 0 ||
+            clock.slot >= market.last_depth_snapshot_slot.saturating_add(DEPTH_SNAPSHOT_INTERVAL_SLOTS),
+        DexError::OperationNotSupported
+    );
+    
+    let bucket_width = market.tick_size
+        .checked_mul(bucket_ticks)
+        .ok_or(DexError::MathOverflow)?;
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let orderbook_data = orderbook_account_info.try_borrow_data()?;
+    let orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    
+    let mut bids: Vec<DepthBucket> = Vec::new();
+    let mut asks: Vec<DepthBucket> = Vec::new();
+    
+    let sides = orderbook.iter_side(&orderbook_data, Side::Bid)
+        .chain(orderbook.iter_side(&orderbook_data, Side::Ask));
+    for (_, order) in sides {
+        // Bucket index counted away from the touch
+        let (buckets, touch, distance) = if order.is_bid() {
+            (&mut bids, orderbook.best_bid, orderbook.best_bid.saturating_sub(order.price))
+        } else {
+            (&mut asks, orderbook.best_ask, order.price.saturating_sub(orderbook.best_ask))
+        };
+        
+        let index = (distance / bucket_width) as usize;
+        if index >= max_buckets as usize {
+            continue;
+        }
+        
+        if buckets.len() <= index {
+            for j in buckets.len()..=index {
+                let offset = bucket_width.saturating_mul(j as u64);
+                let price = if order.is_bid() {
+                    touch.saturating_sub(offset)
+                } else {
+                    touch.saturating_add(offset)
+                };
+                buckets.push(DepthBucket { price, size: 0, order_count: 0 });
+            }
+        }
+        
+        let bucket = &mut buckets[index];
+        bucket.size = bucket.size
+            .checked_add(order.remaining_size)
+            .ok_or(DexError::MathOverflow)?;
+        bucket.order_count = bucket.order_count.saturating_add(1);
+    }
+    
+    market.last_depth_snapshot_slot = clock.slot;
+    
+    msg!("Depth snapshot: market={}, bid_buckets={}, ask_buckets={}",
+         market.key(), bids.len(), asks.len());
+    
+    sink.emit(DepthSnapshot {
+        market: market.key(),
+        bucket_width,
+        bids,
+        asks,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    Ok(())
+}
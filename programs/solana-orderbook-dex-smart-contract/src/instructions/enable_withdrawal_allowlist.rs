@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState, WithdrawalAllowlist};
+use crate::errors::DexError;
+use crate::events::{event_sink, WithdrawalAllowlistUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct EnableWithdrawalAllowlist<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(
+        init,
+        payer = trader,
+        space = WithdrawalAllowlist::SIZE,
+        seeds = [b"withdrawal_allowlist", trader_state.key().as_ref()],
+        bump
+    )]
+    pub withdrawal_allowlist: Account<'info, WithdrawalAllowlist>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Lock withdrawals to a set of destinations
+/// Tightening takes effect immediately; later changes go through the delay
+pub fn handler(ctx: Context<EnableWithdrawalAllowlist>, destinations: Vec<Pubkey>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(!destinations.is_empty(), DexError::InvalidWithdrawalAllowlist);
+    
+    let allowlist = &mut ctx.accounts.withdrawal_allowlist;
+    allowlist.trader_state = ctx.accounts.trader_state.key();
+    allowlist.destinations = WithdrawalAllowlist::pack(&destinations)?;
+    allowlist.pending_destinations = [Pubkey::default(); 4];
+    allowlist.pending_effective_at = 0;
+    allowlist.bump = ctx.bumps.withdrawal_allowlist;
+    
+    let trader_state = &mut ctx.accounts.trader_state;
+    trader_state.withdrawal_allowlist_enabled = true;
+    
+    sink.emit(WithdrawalAllowlistUpdated {
+        trader: trader_state.trader,
+        market: ctx.accounts.market.key(),
+        destinations,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Withdrawal allowlist enabled: trader={}", trader_state.trader);
+    
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use crate::state::{Competition, CompetitionEntry, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, CompetitionEntered};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct EnterCompetition<'info> {
+    #[account(
+        mut,
+        seeds = [b"competition", competition.competition_id.to_le_bytes().as_ref()],
+        bump = competition.bump
+    )]
+    pub competition: Account<'info, Competition>,
+    
+    #[account(
+        init,
+        payer = trader,
+        space = CompetitionEntry::SIZE,
+        seeds = [b"competition_entry", competition.key().as_ref(), trader.key().as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, CompetitionEntry>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // Remaining accounts: the trader's TraderState on each competition market it trades (writable)
+}
+
+/// Enter a trading competition with the trader's states on its markets
+///
+/// Only volume filled after entering counts, and only on the trader states
+/// passed here: each is pointed at the competition and its running volume
+/// reset. A trader state still carrying an earlier competition's unrecorded
+/// volume gives it up. Entries close when the window does.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, EnterCompetition<'info>>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let now = Clock::get()?.unix_timestamp;
+    let competition = &mut ctx.accounts.competition;
+    require!(now < competition.end, DexError::WrongCompetitionPhase);
+    require!(!ctx.remaining_accounts.is_empty(), DexError::InvalidCompetition);
+    
+    let competition_key = competition.key();
+    let trader = ctx.accounts.trader.key();
+    let mut markets = Vec::with_capacity(ctx.remaining_accounts.len());
+    for trader_state_info in ctx.remaining_accounts.iter() {
+        let mut trader_state = Account::<TraderState>::try_from(trader_state_info)?;
+        require_keys_eq!(trader_state.trader, trader, DexError::Unauthorized);
+        require!(competition.includes(&trader_state.market), DexError::InvalidCompetition);
+        require!(!markets.contains(&trader_state.market), DexError::DuplicateAccount);
+        
+        trader_state.competition = competition_key;
+        trader_state.competition_volume = 0;
+        trader_state.exit(&crate::ID)?;
+        markets.push(trader_state.market);
+    }
+    
+    let entry = &mut ctx.accounts.entry;
+    entry.competition = competition_key;
+    entry.trader = trader;
+    entry.bump = ctx.bumps.entry;
+    competition.entrant_count = competition.entrant_count
+        .checked_add(1)
+        .ok_or(DexError::MathOverflow)?;
+    
+    sink.emit(CompetitionEntered {
+        competition: competition_key,
+        trader,
+        markets,
+        timestamp: now,
+    })?;
+    
+    msg!("Competition entered: competition={}, trader={}", competition_key, trader);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::{Side, TimeInForce};
+    use crate::state::Market;
+    use crate::test_utils::{account_infos, install_clock, load, zeroed, TestAccount, Venue, NOW};
+    
+    /// Enter the competition at `competition` (its entry follows it) for the
+    /// trader whose signer is at `trader`, with the trader states in `trader_states`
+    fn enter<'info>(
+        infos: &'info [AccountInfo<'info>],
+        competition: usize,
+        trader: usize,
+        trader_states: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        install_clock();
+        let mut accounts = EnterCompetition {
+            competition: Account::try_from(&infos[competition])?,
+            entry: Account::try_from(&infos[competition + 1])?,
+            trader: Signer::try_from(&infos[trader])?,
+            system_program: Program::try_from(&infos[competition + 2])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, trader_states, Default::default()))?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn entered_traders_accrue_the_volume_they_fill_inside_the_window() {
+        let competition_key = Pubkey::new_unique();
+        let mut venue = Venue::new(|market| {
+            market.competition = competition_key;
+            market.competition_start = NOW - 100;
+            market.competition_end = NOW + 100;
+        });
+        let market_key = venue.accounts[Venue::MARKET].key;
+        let maker = venue.add_trader(20_000, 0);
+        let taker = venue.add_trader(0, 40_000);
+        let mut competition: Competition = zeroed(Competition::SIZE);
+        competition.market_count = 1;
+        competition.markets[0] = market_key;
+        competition.end = NOW + 100;
+        let competition_index = venue.accounts.len();
+        let mut elsewhere: TraderState = zeroed(TraderState::SIZE);
+        elsewhere.trader = venue.accounts[taker].key;
+        elsewhere.market = Pubkey::new_unique();
+        venue.accounts.extend([
+            TestAccount::program_owned(&competition).at(competition_key),
+            TestAccount::program_owned(&zeroed::<CompetitionEntry>(CompetitionEntry::SIZE)),
+            TestAccount::program(anchor_lang::system_program::ID),
+            TestAccount::program_owned(&elsewhere),
+        ]);
+        let infos = account_infos(&mut venue.accounts);
+        
+        // Only the competition's own markets can be entered, and only by their trader
+        assert_eq!(
+            enter(&infos, competition_index, taker, &infos[competition_index + 3..]).unwrap_err(),
+            DexError::InvalidCompetition.into()
+        );
+        assert_eq!(
+            enter(&infos, competition_index, taker, &infos[maker + 1..maker + 2]).unwrap_err(),
+            DexError::Unauthorized.into()
+        );
+        enter(&infos, competition_index, taker, &infos[taker + 1..taker + 2]).unwrap();
+        let entry: CompetitionEntry = load(&infos[competition_index + 1]);
+        assert_eq!(entry.trader, *infos[taker].key);
+        assert_eq!(load::<Competition>(&infos[competition_index]).entrant_count, 1);
+        
+        // Ten lots at 2_000 count 20_000 quote for the entrant and nothing for the maker
+        Venue::place(&infos, maker, Venue::order(Side::Ask, 2_000, 20_000, TimeInForce::GTC)).unwrap();
+        Venue::place(&infos, taker, Venue::order(Side::Bid, 2_000, 10_000, TimeInForce::IOC)).unwrap();
+        assert_eq!(load::<TraderState>(&infos[taker + 1]).competition_volume, 20_000);
+        assert_eq!(load::<TraderState>(&infos[maker + 1]).competition_volume, 0);
+        
+        // Fills stamped after the window do not count
+        let market: Market = load(&infos[Venue::MARKET]);
+        let mut trader_state: TraderState = load(&infos[taker + 1]);
+        trader_state.record_competition_volume(&market, 20_000, NOW + 100);
+        assert_eq!(trader_state.competition_volume, 20_000);
+    }
+}
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::Market;
+use crate::orderbook::Orderbook;
+use crate::errors::DexError;
+use crate::events::{event_sink, OrderbookExpanded};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ExpandOrderbook<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account (validated against market in instruction)
+    #[account(mut, owner = crate::ID @ DexError::InvalidAccountOwner)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    /// Pays the rent for the added slots
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Raise the orderbook's slab capacity by `additional_orders`
+///
+/// Permissionless; the payer funds the extra rent. A single call can grow
+/// the account by at most `MAX_PERMITTED_DATA_INCREASE` bytes, so large
+/// expansions take several calls.
+pub fn handler(ctx: Context<ExpandOrderbook>, additional_orders: u32) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(additional_orders > 0, DexError::InvalidInstruction);
+    
+    let market_key = ctx.accounts.market.key();
+    let orderbook_account_info = ctx.accounts.orderbook.to_account_info();
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let mut orderbook = {
+        let orderbook_data = orderbook_account_info.try_borrow_data()?;
+        Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?
+    };
+    require!(orderbook.market == market_key, DexError::InvalidOrderbookState);
+    
+    let old_capacity = orderbook.capacity();
+    let new_capacity = old_capacity
+        .checked_add(additional_orders as usize)
+        .ok_or(DexError::MathOverflow)?;
+    let old_len = orderbook_account_info.data_len();
+    let new_len = Orderbook::size_for(new_capacity).max(old_len);
+    require!(
+        new_len - old_len <= MAX_PERMITTED_DATA_INCREASE,
+        DexError::OrderbookDepthExceeded
+    );
+    
+    // Top up rent before growing the account
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(orderbook_account_info.lamports());
+    if rent_due > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: orderbook_account_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, rent_due)?;
+    }
+    orderbook_account_info.realloc(new_len, true)?;
+    
+    orderbook.capacity = new_capacity as u64;
+    let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    
+    sink.emit(OrderbookExpanded {
+        market: market_key,
+        orderbook: orderbook_account_info.key(),
+        payer: ctx.accounts.payer.key(),
+        old_capacity: old_capacity as u64,
+        new_capacity: new_capacity as u64,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Orderbook expanded: market={}, capacity={}->{}", market_key, old_capacity, new_capacity);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::{Side, TimeInForce};
+    use crate::test_utils::{account_infos, install_clock, load, load_data, TestAccount, Venue};
+    
+    fn expand<'info>(infos: &'info [AccountInfo<'info>], payer: usize, additional_orders: u32) -> Result<()> {
+        install_clock();
+        let mut accounts = ExpandOrderbook {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            orderbook: UncheckedAccount::try_from(&infos[Venue::ORDERBOOK]),
+            payer: Signer::try_from(&infos[payer])?,
+            system_program: Program::try_from(&infos[payer + 1])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), additional_orders)
+    }
+    
+    #[test]
+    fn one_call_grows_the_slab_by_at_most_the_permitted_increase() {
+        let mut venue = Venue::new(|_| {});
+        venue.accounts.push(TestAccount::signer());
+        venue.accounts.push(TestAccount::program(anchor_lang::system_program::ID));
+        let payer = venue.accounts.len() - 2;
+        let infos = account_infos(&mut venue.accounts);
+        
+        let too_many = (MAX_PERMITTED_DATA_INCREASE / Orderbook::ORDER_SIZE + 1) as u32;
+        assert_eq!(expand(&infos, payer, 0).unwrap_err(), DexError::InvalidInstruction.into());
+        assert_eq!(expand(&infos, payer, too_many).unwrap_err(), DexError::OrderbookDepthExceeded.into());
+    }
+    
+    // The realloc itself needs the runtime's account layout, so the test
+    // grows the account the way the handler leaves it
+    #[test]
+    fn a_full_book_takes_orders_again_once_expanded() {
+        let mut venue = Venue::new(|_| {});
+        let trader = venue.add_trader(0, 100_000_000);
+        let bid = |price| Venue::order(Side::Bid, price, 1_000, TimeInForce::GTC);
+        {
+            let infos = account_infos(&mut venue.accounts);
+            for price in 0..Venue::CAPACITY as u64 {
+                Venue::place(&infos, trader, bid(1_000 + price)).unwrap();
+            }
+            assert_eq!(Venue::place(&infos, trader, bid(999)).unwrap_err(), DexError::OrderbookFull.into());
+        }
+        
+        let orderbook = &mut venue.accounts[Venue::ORDERBOOK];
+        let mut header: Orderbook = load_data(&orderbook.data);
+        header.capacity = Venue::CAPACITY as u64 + 1;
+        orderbook.data.resize(Orderbook::size_for(Venue::CAPACITY + 1), 0);
+        header.try_serialize(&mut &mut orderbook.data[..Orderbook::HEADER_SIZE]).unwrap();
+        
+        let infos = account_infos(&mut venue.accounts);
+        Venue::place(&infos, trader, bid(999)).unwrap();
+        assert_eq!(Venue::place(&infos, trader, bid(998)).unwrap_err(), DexError::OrderbookFull.into());
+        let orderbook: Orderbook = load(&infos[Venue::ORDERBOOK]);
+        let data = infos[Venue::ORDERBOOK].try_borrow_data().unwrap();
+        let bids: Vec<u64> = orderbook.iter_side(&data, Side::Bid).map(|(_, order)| order.price).collect();
+        assert_eq!(bids.len(), Venue::CAPACITY + 1);
+        assert_eq!((bids[0], bids[Venue::CAPACITY]), (1_015, 999));
+    }
+}
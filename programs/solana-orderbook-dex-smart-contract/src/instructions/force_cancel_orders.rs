@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketStatus};
+use crate::orderbook::{Order, Orderbook, Side};
+use crate::errors::DexError;
+use crate::event_queue::EventQueue;
+use crate::instructions::cancel_order::evict_resting_orders;
+use crate::events::event_sink;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ForceCancelOrders<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Delisted as u8 @ DexError::MarketNotDelisted
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    /// CHECK: Event queue account (must be the market's)
+    #[account(mut, address = market.event_queue @ DexError::InvalidAccountState)]
+    pub event_queue: UncheckedAccount<'info>,
+}
+
+/// Cancel up to `limit` resting orders on a delisted market
+///
+/// Permissionless crank, so traders can always get their funds out of a
+/// delisted market. Funds are released through Out events on the event
+/// queue, since the owners' trader states are not passed in.
+pub fn handler(ctx: Context<ForceCancelOrders>, limit: u8) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(limit > 0, DexError::InvalidInstruction);
+    
+    let market = &ctx.accounts.market;
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    
+    let orders: Vec<(u64, Order)> = orderbook.iter_side(&orderbook_data, Side::Bid)
+        .chain(orderbook.iter_side(&orderbook_data, Side::Ask))
+        .take(limit as usize)
+        .collect();
+    
+    let mut event_queue_data = ctx.accounts.event_queue.try_borrow_mut_data()?;
+    let mut event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE])?;
+    
+    evict_resting_orders(
+        market,
+        &mut orderbook,
+        &mut orderbook_data,
+        &mut event_queue,
+        &mut event_queue_data,
+        &orders,
+        &sink,
+    )?;
+    
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE])?;
+    
+    let market_key = market.key();
+    ctx.accounts.market.sync_book(&orderbook, &orderbook_data, &sink)?;
+    
+    msg!("Delisted market orders cancelled: market={}, cancelled={}, remaining={}", 
+         market_key, orders.len(), orderbook.order_count);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::consume_events::{self, ConsumeEvents};
+    use crate::instructions::set_market_status::{self, SetMarketStatus};
+    use crate::orderbook::TimeInForce;
+    use crate::state::TraderState;
+    use crate::test_utils::{account_infos, install_clock, load, TestAccount, Venue};
+    
+    fn set_status<'info>(infos: &'info [AccountInfo<'info>], authority: usize, status: MarketStatus) -> Result<()> {
+        install_clock();
+        let mut accounts = SetMarketStatus {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            global_config: Account::try_from(&infos[Venue::GLOBAL_CONFIG])?,
+            authority: Signer::try_from(&infos[authority])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        set_market_status::handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), status as u8)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    fn force_cancel<'info>(infos: &'info [AccountInfo<'info>], limit: u8) -> Result<()> {
+        install_clock();
+        let mut accounts = ForceCancelOrders {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            orderbook: UncheckedAccount::try_from(&infos[Venue::ORDERBOOK]),
+            event_queue: UncheckedAccount::try_from(&infos[Venue::EVENT_QUEUE]),
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), limit)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    fn consume<'info>(infos: &'info [AccountInfo<'info>], traders: &'info [AccountInfo<'info>]) -> Result<()> {
+        install_clock();
+        let mut accounts = ConsumeEvents {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            event_queue: UncheckedAccount::try_from(&infos[Venue::EVENT_QUEUE]),
+            cranker: None,
+            cranker_state: None,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        consume_events::handler(Context::new(&crate::ID, &mut accounts, traders, Default::default()), 8)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn a_delisted_market_can_be_emptied_by_anyone() {
+        let mut venue = Venue::new(|_| {});
+        let trader = venue.add_trader(10_000, 40_000);
+        venue.accounts.push(TestAccount::signer());
+        let authority = venue.accounts.len() - 1;
+        let infos = account_infos(&mut venue.accounts);
+        let bid = |price| Venue::order(Side::Bid, price, 10_000, TimeInForce::GTC);
+        Venue::place(&infos, trader, bid(1_900)).unwrap();
+        Venue::place(&infos, trader, bid(2_000)).unwrap();
+        Venue::place(&infos, trader, Venue::order(Side::Ask, 2_100, 10_000, TimeInForce::GTC)).unwrap();
+        
+        // Winding down stops new orders but can still be undone
+        set_status(&infos, authority, MarketStatus::CancelOnly).unwrap();
+        assert_eq!(Venue::place(&infos, trader, bid(1_800)).unwrap_err(), DexError::MarketCancelOnly.into());
+        set_status(&infos, authority, MarketStatus::Delisted).unwrap();
+        assert_eq!(
+            set_status(&infos, authority, MarketStatus::Active).unwrap_err(),
+            DexError::InvalidMarketStatus.into()
+        );
+        
+        // The crank works through the book in batches; settling the Out events frees the funds
+        force_cancel(&infos, 2).unwrap();
+        assert_eq!(load::<Market>(&infos[Venue::MARKET]).order_count, 1);
+        force_cancel(&infos, 2).unwrap();
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!((market.order_count, market.best_bid, market.best_ask), (0, 0, 0));
+        
+        consume(&infos, &infos[trader + 1..trader + 2]).unwrap();
+        let trader_state: TraderState = load(&infos[trader + 1]);
+        assert_eq!((trader_state.base_locked, trader_state.quote_locked), (0, 0));
+        assert_eq!((trader_state.base_available, trader_state.quote_available), (10_000, 40_000));
+        assert_eq!(trader_state.open_order_count, 0);
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::DataFeed;
+use crate::errors::DexError;
+use crate::events::{event_sink, DataFeedFunded};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FundDataFeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"data_feed", data_feed.market.as_ref()],
+        bump = data_feed.bump
+    )]
+    pub data_feed: Account<'info, DataFeed>,
+    
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit lamports that pay for future refreshes of a data feed
+pub fn handler(ctx: Context<FundDataFeed>, lamports: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(lamports > 0, DexError::InvalidInstruction);
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.subscriber.to_account_info(),
+        to: ctx.accounts.data_feed.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, lamports)?;
+    
+    let data_feed = &mut ctx.accounts.data_feed;
+    data_feed.total_funded = data_feed.total_funded
+        .checked_add(lamports)
+        .ok_or(DexError::MathOverflow)?;
+    
+    sink.emit(DataFeedFunded {
+        market: data_feed.market,
+        subscriber: ctx.accounts.subscriber.key(),
+        lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Data feed funded: market={}, subscriber={}, lamports={}",
+         data_feed.market, ctx.accounts.subscriber.key(), lamports);
+    
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::{Market, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, PlacementDepositsFunded};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FundPlacementDeposits<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Prepay lamports that placement deposits are held from
+///
+/// The lamports sit in the trader state; each order rested on a market that
+/// charges a deposit holds part of the balance until the order leaves the
+/// book, so placing orders needs no transfer of its own.
+pub fn handler(ctx: Context<FundPlacementDeposits>, lamports: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(lamports > 0, DexError::InvalidInstruction);
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.trader.to_account_info(),
+        to: ctx.accounts.trader_state.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, lamports)?;
+    
+    let trader_state = &mut ctx.accounts.trader_state;
+    trader_state.deposit_balance = trader_state.deposit_balance
+        .checked_add(lamports)
+        .ok_or(DexError::MathOverflow)?;
+    
+    sink.emit(PlacementDepositsFunded {
+        market: ctx.accounts.market.key(),
+        trader: ctx.accounts.trader.key(),
+        lamports,
+        deposit_balance: trader_state.deposit_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Placement deposits funded: trader={}, lamports={}, balance={}",
+         ctx.accounts.trader.key(), lamports, trader_state.deposit_balance);
+    
+    Ok(())
+}
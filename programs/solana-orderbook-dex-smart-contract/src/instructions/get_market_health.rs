@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::Market;
+use crate::orderbook::{Orderbook, Side};
+use crate::errors::DexError;
+use crate::event_queue::EventQueue;
+
+/// Operational snapshot of a market, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MarketHealth {
+    pub slot: u64,
+    pub paused: bool,
+    pub cancel_only: bool, // Winding down or peg band broken
+    pub status: u8,        // See MarketStatus
+    pub halted_until: i64, // Circuit breaker halt end (0 if never halted)
+    pub best_bid: u64,
+    pub best_ask: u64,
+    pub bid_orders: u64,
+    pub ask_orders: u64,
+    pub bid_depth: u64, // Resting base size
+    pub ask_depth: u64,
+    pub unconsumed_events: u64, // Fills and outs waiting for consume_events
+    pub event_queue_capacity: u64,
+    pub last_match_slot: u64,
+    pub last_consume_slot: u64,
+    pub last_depth_snapshot_slot: u64,
+    pub base_vault_delta: i128, // Vault balance minus ledger; negative means missing funds
+    pub quote_vault_delta: i128,
+    pub volume_24h: u128, // Quote units, from the hourly buckets
+    pub fill_count_24h: u64,
+    pub price_change_24h: i128, // Last fill price minus the first one in the window
+    pub last_trade_price: u64,
+    pub last_trade_timestamp: i64,
+    pub mark_price: u64, // Median of best bid, best ask and last trade
+}
+
+#[derive(Accounts)]
+pub struct GetMarketHealth<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account (validated against market in instruction)
+    pub orderbook: UncheckedAccount<'info>,
+    
+    /// CHECK: Event queue account (must be the market's)
+    #[account(address = market.event_queue @ DexError::InvalidAccountState)]
+    pub event_queue: UncheckedAccount<'info>,
+    
+    #[account(address = market.base_vault @ DexError::InvalidMint)]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = market.quote_vault @ DexError::InvalidMint)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Report book depth, crank backlog and vault/ledger drift in one call
+///
+/// Read-only; meant to be simulated by dashboards and alerting. The
+/// Borsh-encoded `MarketHealth` is set as the transaction's return data.
+pub fn handler(ctx: Context<GetMarketHealth>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let orderbook_data = orderbook_account_info.try_borrow_data()?;
+    let orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    
+    let event_queue_data = ctx.accounts.event_queue.try_borrow_data()?;
+    let event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE])?;
+    
+    let mut depth = [(0u64, 0u64); 2];
+    for (entry, side) in depth.iter_mut().zip([Side::Bid, Side::Ask]) {
+        for (_, order) in orderbook.iter_side(&orderbook_data, side) {
+            entry.0 += 1;
+            entry.1 = entry.1.saturating_add(order.remaining_size);
+        }
+    }
+    
+    let clock = Clock::get()?;
+    let stats = market.rolling_stats(clock.unix_timestamp);
+    let health = MarketHealth {
+        slot: clock.slot,
+        paused: market.paused,
+        cancel_only: !market.accepts_new_orders(),
+        status: market.status,
+        halted_until: market.halted_until,
+        best_bid: orderbook.best_bid,
+        best_ask: orderbook.best_ask,
+        bid_orders: depth[0].0,
+        ask_orders: depth[1].0,
+        bid_depth: depth[0].1,
+        ask_depth: depth[1].1,
+        unconsumed_events: event_queue.count,
+        event_queue_capacity: EventQueue::capacity(&event_queue_data),
+        last_match_slot: market.last_match_slot,
+        last_consume_slot: market.last_consume_slot,
+        last_depth_snapshot_slot: market.last_depth_snapshot_slot,
+        base_vault_delta: ctx.accounts.base_vault.amount as i128 - market.base_vault_ledger as i128,
+        quote_vault_delta: ctx.accounts.quote_vault.amount as i128 - market.quote_vault_ledger as i128,
+        volume_24h: stats.volume,
+        fill_count_24h: stats.fill_count,
+        price_change_24h: stats.price_change(),
+        last_trade_price: market.last_trade_price,
+        last_trade_timestamp: market.last_trade_timestamp,
+        mark_price: market.mark_price(),
+    };
+    anchor_lang::solana_program::program::set_return_data(&health.try_to_vec()?);
+    
+    Ok(())
+}
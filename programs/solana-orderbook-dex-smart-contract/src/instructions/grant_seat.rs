@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, Seat};
+use crate::errors::DexError;
+use crate::events::{event_sink, SeatGranted};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct GrantSeat<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Seat::SIZE,
+        seeds = [b"seat", market.key().as_ref(), trader.key().as_ref()],
+        bump
+    )]
+    pub seat: Account<'info, Seat>,
+    
+    /// CHECK: Trader receiving the seat; any key
+    pub trader: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Grant a trader a seat on a permissioned market
+pub fn handler(ctx: Context<GrantSeat>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
+    let seat = &mut ctx.accounts.seat;
+    seat.market = ctx.accounts.market.key();
+    seat.trader = ctx.accounts.trader.key();
+    seat.granted_at = clock.unix_timestamp;
+    seat.bump = ctx.bumps.seat;
+    
+    sink.emit(SeatGranted {
+        market: seat.market,
+        trader: seat.trader,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Seat granted: market={}, trader={}", seat.market, seat.trader);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::deposit::{self, Deposit};
+    use crate::orderbook::{Side, TimeInForce};
+    use crate::state::TraderState;
+    use crate::test_utils::{account_infos, install_clock, load, token_balance, zeroed, TestAccount, Venue, VenueVaults};
+    
+    /// Grant the signer at `trader` the seat at `seat`, paid by `authority`
+    /// (whose system program follows it)
+    fn grant<'info>(infos: &'info [AccountInfo<'info>], seat: usize, trader: usize, authority: usize) -> Result<()> {
+        install_clock();
+        let mut accounts = GrantSeat {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            seat: Account::try_from(&infos[seat])?,
+            trader: UncheckedAccount::try_from(&infos[trader]),
+            authority: Signer::try_from(&infos[authority])?,
+            system_program: Program::try_from(&infos[authority + 1])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()))?;
+        accounts.exit(&crate::ID)
+    }
+    
+    /// Deposit quote from the wallet at `wallet`, presenting the seat at `seat` if any
+    fn deposit<'info>(
+        infos: &'info [AccountInfo<'info>],
+        vaults: &VenueVaults,
+        trader: usize,
+        wallet: usize,
+        seat: Option<usize>,
+        system_program: usize,
+        amount: u64,
+    ) -> Result<()> {
+        install_clock();
+        let mut accounts = Deposit {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            trader_state: Account::try_from(&infos[trader + 1])?,
+            trader: Signer::try_from(&infos[trader])?,
+            seat: seat.map(|index| Account::try_from(&infos[index])).transpose()?,
+            trader_token_account: InterfaceAccount::try_from(&infos[wallet])?,
+            vault: InterfaceAccount::try_from(&infos[vaults.quote_vault])?,
+            mint: InterfaceAccount::try_from(&infos[vaults.quote_mint])?,
+            token_program: Interface::try_from(&infos[vaults.token_program])?,
+            system_program: Program::try_from(&infos[system_program])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        deposit::handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), amount)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn a_permissioned_market_only_takes_funds_and_orders_from_seat_holders() {
+        let mut venue = Venue::new(|market| market.requires_seat = true);
+        let vaults = venue.add_vaults(0, 0);
+        let trader = venue.add_trader(0, 0);
+        let wallet = venue.add_wallet(trader, false, 20_000);
+        venue.accounts.extend([
+            TestAccount::program_owned(&zeroed::<Seat>(Seat::SIZE)),
+            TestAccount::signer(),
+            TestAccount::program(anchor_lang::system_program::ID),
+        ]);
+        let seat = venue.accounts.len() - 3;
+        let (authority, system_program) = (seat + 1, seat + 2);
+        let infos = account_infos(&mut venue.accounts);
+        
+        assert_eq!(
+            deposit(&infos, &vaults, trader, wallet, None, system_program, 20_000).unwrap_err(),
+            DexError::SeatRequired.into()
+        );
+        
+        grant(&infos, seat, trader, authority).unwrap();
+        let granted: Seat = load(&infos[seat]);
+        assert_eq!((granted.market, granted.trader), (*infos[Venue::MARKET].key, *infos[trader].key));
+        deposit(&infos, &vaults, trader, wallet, Some(seat), system_program, 20_000).unwrap();
+        assert_eq!(token_balance(&infos[vaults.quote_vault]), 20_000);
+        assert_eq!(load::<TraderState>(&infos[trader + 1]).quote_available, 20_000);
+        
+        // Orders need the seat too
+        assert_eq!(
+            Venue::place(&infos, trader, Venue::order(Side::Bid, 2_000, 10_000, TimeInForce::GTC)).unwrap_err(),
+            DexError::SeatRequired.into()
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::DexError;
+use crate::event_queue::{EventQueue, QueueEvent};
+use crate::events::{event_sink, EventQueueInitialized};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitEventQueue<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority || 
+                     authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, crate::state::GlobalConfig>,
+    
+    /// CHECK: Pre-allocated, zeroed account owned by this program (sized for the ring buffer)
+    #[account(mut, owner = crate::ID @ DexError::InvalidAccountState)]
+    pub event_queue: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<InitEventQueue>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &mut ctx.accounts.market;
+    require!(market.event_queue == Pubkey::default(), DexError::InvalidAccountState);
+    
+    let mut event_queue_data = ctx.accounts.event_queue.try_borrow_mut_data()?;
+    require!(
+        event_queue_data.len() >= EventQueue::HEADER_SIZE + QueueEvent::SIZE,
+        DexError::InvalidAccountState
+    );
+    require!(
+        event_queue_data[..8].iter().all(|b| *b == 0),
+        DexError::InvalidAccountState
+    );
+    
+    let event_queue = EventQueue {
+        market: market.key(),
+        head: 0,
+        count: 0,
+        seq_num: 0,
+        _reserved: [0; 32],
+    };
+    event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE])?;
+    let capacity = EventQueue::capacity(&event_queue_data);
+    
+    market.event_queue = ctx.accounts.event_queue.key();
+    
+    sink.emit(EventQueueInitialized {
+        market: market.key(),
+        event_queue: market.event_queue,
+        capacity,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Event queue initialized: market={}, capacity={}", market.key(), capacity);
+    
+    Ok(())
+}
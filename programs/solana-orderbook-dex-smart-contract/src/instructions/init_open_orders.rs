@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, OpenOrders, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, OpenOrdersInitialized};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitOpenOrders<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(
+        init,
+        payer = trader,
+        space = OpenOrders::SIZE,
+        seeds = [b"open_orders", trader_state.key().as_ref()],
+        bump
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Open the account that records where the trader's orders rest
+/// Orders placed with it passed can later be cancelled without a book scan
+pub fn handler(ctx: Context<InitOpenOrders>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let open_orders = &mut ctx.accounts.open_orders;
+    open_orders.trader_state = ctx.accounts.trader_state.key();
+    open_orders.bump = ctx.bumps.open_orders;
+    
+    sink.emit(OpenOrdersInitialized {
+        market: ctx.accounts.market.key(),
+        trader: ctx.accounts.trader.key(),
+        open_orders: open_orders.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Open orders initialized: trader={}", ctx.accounts.trader.key());
+    
+    Ok(())
+}
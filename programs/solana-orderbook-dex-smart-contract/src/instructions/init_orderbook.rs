@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::orderbook::Orderbook;
+use crate::errors::DexError;
+use crate::events::{event_sink, OrderbookInitialized};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitOrderbook<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority || 
+                     authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, crate::state::GlobalConfig>,
+    
+    /// CHECK: Pre-allocated, zeroed account owned by this program (sized for `MAX_ORDERS`)
+    #[account(mut, owner = crate::ID @ DexError::InvalidAccountOwner)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Write an empty orderbook header bound to the market
+///
+/// Every handler that loads a book checks it belongs to the market, so this
+/// is the only place `Orderbook::market` is ever set.
+pub fn handler(ctx: Context<InitOrderbook>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market_key = ctx.accounts.market.key();
+    
+    let mut orderbook_data = ctx.accounts.orderbook.try_borrow_mut_data()?;
+    require!(
+        orderbook_data.len() >= Orderbook::MAX_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    require!(
+        orderbook_data[..8].iter().all(|b| *b == 0),
+        DexError::InvalidOrderbookState
+    );
+    
+    let orderbook = Orderbook {
+        market: market_key,
+        best_bid: 0,
+        best_ask: 0,
+        order_count: 0,
+        free_list_head: 0,
+        bid_head: 0,
+        ask_head: 0,
+        slab_len: 0,
+        bid_value: 0,
+        ask_value: 0,
+        capacity: 0,
+    };
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    
+    sink.emit(OrderbookInitialized {
+        market: market_key,
+        orderbook: ctx.accounts.orderbook.key(),
+        capacity: orderbook.capacity() as u64,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Orderbook initialized: market={}, capacity={}", market_key, orderbook.capacity());
+    
+    Ok(())
+}
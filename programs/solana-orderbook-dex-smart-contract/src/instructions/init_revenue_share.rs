@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market, RevenueShare, RevenueShareEntry};
+use crate::errors::DexError;
+use crate::events::{event_sink, RevenueShareCreated};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RevenueSplit {
+    pub beneficiary: Pubkey,
+    pub share_bps: u16, // Shares of all splits sum to 10_000
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitRevenueShare<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RevenueShare::SIZE,
+        seeds = [b"revenue_share", market.key().as_ref()],
+        bump
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Make a new market a revenue-share market, splitting its fees between partners
+///
+/// The fees are the protocol's to give away, so only the protocol authority
+/// can do this, and only before the market's first fill. There is no
+/// instruction to change the table afterwards: each beneficiary claims its
+/// share with `claim_revenue_share`, and `claim_protocol_fees` no longer
+/// applies to the market (the protocol takes part as an entry of its own).
+pub fn handler(ctx: Context<InitRevenueShare>, splits: Vec<RevenueSplit>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(
+        !splits.is_empty() && splits.len() <= RevenueShare::MAX_ENTRIES,
+        DexError::InvalidRevenueShare
+    );
+    let mut total_bps = 0u32;
+    for (index, split) in splits.iter().enumerate() {
+        require!(
+            split.beneficiary != Pubkey::default() && split.share_bps > 0,
+            DexError::InvalidRevenueShare
+        );
+        require!(
+            !splits[..index].iter().any(|other| other.beneficiary == split.beneficiary),
+            DexError::InvalidRevenueShare
+        );
+        total_bps += split.share_bps as u32;
+    }
+    require!(total_bps == 10_000, DexError::InvalidRevenueShare);
+    
+    let market = &mut ctx.accounts.market;
+    require!(market.fill_sequence == 0 && !market.has_revenue_share(), DexError::InvalidRevenueShare);
+    
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    revenue_share.market = market.key();
+    revenue_share.entry_count = splits.len() as u8;
+    for (entry, split) in revenue_share.entries.iter_mut().zip(&splits) {
+        *entry = RevenueShareEntry {
+            beneficiary: split.beneficiary,
+            share_bps: split.share_bps,
+            ..Default::default()
+        };
+    }
+    revenue_share.bump = ctx.bumps.revenue_share;
+    market.revenue_share = revenue_share.key();
+    
+    sink.emit(RevenueShareCreated {
+        market: market.key(),
+        revenue_share: revenue_share.key(),
+        beneficiaries: splits.iter().map(|split| split.beneficiary).collect(),
+        shares_bps: splits.iter().map(|split| split.share_bps).collect(),
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Revenue share created: market={}, beneficiaries={}", market.key(), splits.len());
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{account_infos, install_clock, load, zeroed, TestAccount, Venue};
+    
+    /// Create the revenue share at `share`, signed by the authority after it
+    fn run<'info>(infos: &'info [AccountInfo<'info>], share: usize, splits: Vec<RevenueSplit>) -> Result<()> {
+        install_clock();
+        let mut accounts = InitRevenueShare {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            revenue_share: Account::try_from(&infos[share])?,
+            global_config: Account::try_from(&infos[Venue::GLOBAL_CONFIG])?,
+            authority: Signer::try_from(&infos[share + 1])?,
+            system_program: Program::try_from(&infos[share + 2])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), splits)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn the_split_must_cover_all_fees_between_distinct_parties_before_trading() {
+        let mut venue = Venue::new(|_| {});
+        let share = venue.accounts.len();
+        venue.accounts.extend([
+            TestAccount::program_owned(&zeroed::<RevenueShare>(RevenueShare::SIZE)),
+            TestAccount::signer(),
+            TestAccount::program(anchor_lang::system_program::ID),
+        ]);
+        let infos = account_infos(&mut venue.accounts);
+        let (partner, protocol) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let split = |beneficiary, share_bps| RevenueSplit { beneficiary, share_bps };
+        
+        for splits in [
+            vec![split(partner, 7_000), split(protocol, 2_000)],
+            vec![split(partner, 5_000), split(partner, 5_000)],
+            vec![split(partner, 10_000), split(protocol, 0)],
+            vec![split(Pubkey::default(), 10_000)],
+            Vec::new(),
+        ] {
+            assert_eq!(run(&infos, share, splits).err().unwrap(), DexError::InvalidRevenueShare.into());
+        }
+        
+        let mut market = Account::<Market>::try_from(&infos[Venue::MARKET]).unwrap();
+        market.fill_sequence = 1;
+        market.exit(&crate::ID).unwrap();
+        let splits = || vec![split(partner, 7_000), split(protocol, 3_000)];
+        assert_eq!(run(&infos, share, splits()).err().unwrap(), DexError::InvalidRevenueShare.into());
+        
+        let mut market = Account::<Market>::try_from(&infos[Venue::MARKET]).unwrap();
+        market.fill_sequence = 0;
+        market.exit(&crate::ID).unwrap();
+        run(&infos, share, splits()).unwrap();
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!(market.revenue_share, *infos[share].key);
+        let revenue_share: RevenueShare = load(&infos[share]);
+        assert_eq!(revenue_share.entry_count, 2);
+        assert_eq!(revenue_share.entries[1].beneficiary, protocol);
+        assert_eq!(revenue_share.entries[1].share_bps, 3_000);
+        
+        // The table is set once
+        assert_eq!(run(&infos, share, splits()).err().unwrap(), DexError::InvalidRevenueShare.into());
+    }
+}
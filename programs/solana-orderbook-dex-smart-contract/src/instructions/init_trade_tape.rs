@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::trade_tape::{TradeRecord, TradeTape};
+use crate::events::{event_sink, TradeTapeInitialized};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitTradeTape<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority || 
+                     authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    /// CHECK: Pre-allocated, zeroed account owned by this program (sized for the ring buffer)
+    #[account(mut, owner = crate::ID @ DexError::InvalidAccountState)]
+    pub trade_tape: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Attach a trade tape to a market
+/// From then on every instruction that fills orders must pass it
+pub fn handler(ctx: Context<InitTradeTape>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &mut ctx.accounts.market;
+    require!(market.trade_tape == Pubkey::default(), DexError::InvalidAccountState);
+    
+    let mut trade_tape_data = ctx.accounts.trade_tape.try_borrow_mut_data()?;
+    require!(
+        trade_tape_data.len() >= TradeTape::HEADER_SIZE + TradeRecord::SIZE,
+        DexError::InvalidAccountState
+    );
+    require!(
+        trade_tape_data[..8].iter().all(|b| *b == 0),
+        DexError::InvalidAccountState
+    );
+    
+    let trade_tape = TradeTape {
+        market: market.key(),
+        seq_num: 0,
+        _reserved: [0; 32],
+    };
+    trade_tape.try_serialize(&mut &mut trade_tape_data[..TradeTape::HEADER_SIZE])?;
+    let capacity = TradeTape::capacity(&trade_tape_data);
+    
+    market.trade_tape = ctx.accounts.trade_tape.key();
+    
+    sink.emit(TradeTapeInitialized {
+        market: market.key(),
+        trade_tape: market.trade_tape,
+        capacity,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Trade tape initialized: market={}, capacity={}", market.key(), capacity);
+    
+    Ok(())
+}
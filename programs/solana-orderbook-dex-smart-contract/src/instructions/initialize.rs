@@ -1,10 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::state::GlobalConfig;
-use crate::errors::DexError;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct InitializeParams {
-    pub maker_fee_bps: u16,
+    pub maker_fee_bps: i16, // Negative = maker rebate
     pub taker_fee_bps: u16,
     pub permissionless_markets: bool,
     pub market_creation_fee: u64,
@@ -31,15 +30,6 @@ pub struct Initialize<'info> {
 }
 
 pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
-    require!(
-        params.maker_fee_bps <= 1000, // Max 10%
-        DexError::InvalidFeeCalculation
-    );
-    require!(
-        params.taker_fee_bps <= 1000, // Max 10%
-        DexError::InvalidFeeCalculation
-    );
-    
     let global_config = &mut ctx.accounts.global_config;
     global_config.authority = ctx.accounts.authority.key();
     global_config.fee_recipient = ctx.accounts.fee_recipient.key();
@@ -47,7 +37,9 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     global_config.taker_fee_bps = params.taker_fee_bps;
     global_config.permissionless_markets = params.permissionless_markets;
     global_config.market_creation_fee = params.market_creation_fee;
-    global_config.bump = ctx.bumps.get("global_config").unwrap().clone();
+    global_config.risk_authority = ctx.accounts.authority.key();
+    global_config.validate_fees()?; // Max 10% each; a rebate must fit in the taker fee
+    global_config.bump = ctx.bumps.global_config;
     
     msg!("Global config initialized: maker_fee={}bps, taker_fee={}bps", 
          params.maker_fee_bps, params.taker_fee_bps);
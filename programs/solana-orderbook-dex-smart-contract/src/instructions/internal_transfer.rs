@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, InternalTransferEvent};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(amount: u64, is_base: bool)]
+pub struct InternalTransfer<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = from_trader_state.bump,
+        constraint = from_trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub from_trader_state: Account<'info, TraderState>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", to_trader_state.trader.as_ref(), market.key().as_ref()],
+        bump = to_trader_state.bump,
+        constraint = to_trader_state.key() != from_trader_state.key() @ DexError::InvalidAccountState
+    )]
+    pub to_trader_state: Account<'info, TraderState>,
+    
+    pub trader: Signer<'info>,
+}
+
+/// Move available balance between two trader states of the same market
+/// The vaults already hold the tokens, so no token transfer is needed
+pub fn handler(ctx: Context<InternalTransfer>, amount: u64, is_base: bool) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(amount > 0, DexError::InvalidOrderParams);
+    
+    let market = &ctx.accounts.market;
+    let from = &mut ctx.accounts.from_trader_state;
+    let to = &mut ctx.accounts.to_trader_state;
+    
+    // Moving funds out is a withdrawal as far as the lockdown is concerned
+    require!(!from.is_locked(Clock::get()?.unix_timestamp), DexError::AccountLocked);
+    
+    // Moving funds out is a withdrawal as far as co-signing is concerned
+    require!(!from.requires_co_signature(amount), DexError::CoSignatureRequired);
+    
+    // An allowlist would be pointless if funds could leave via another trader state
+    require!(
+        !from.withdrawal_allowlist_enabled,
+        DexError::WithdrawalDestinationNotAllowed
+    );
+    
+    if is_base {
+        require!(from.base_available >= amount, DexError::InsufficientFunds);
+        from.base_available = from.base_available
+            .checked_sub(amount)
+            .ok_or(DexError::MathUnderflow)?;
+        to.base_available = to.base_available
+            .checked_add(amount)
+            .ok_or(DexError::MathOverflow)?;
+    } else {
+        require!(from.quote_available >= amount, DexError::InsufficientFunds);
+        from.quote_available = from.quote_available
+            .checked_sub(amount)
+            .ok_or(DexError::MathUnderflow)?;
+        to.quote_available = to.quote_available
+            .checked_add(amount)
+            .ok_or(DexError::MathOverflow)?;
+    }
+    
+    sink.emit(InternalTransferEvent {
+        market: market.key(),
+        from_trader: from.trader,
+        to_trader: to.trader,
+        mint: if is_base { market.base_mint } else { market.quote_mint },
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Internal transfer: from={}, to={}, amount={}", from.trader, to.trader, amount);
+    
+    Ok(())
+}
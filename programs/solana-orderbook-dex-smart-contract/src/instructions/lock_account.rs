@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState};
+use crate::orderbook::{Order, Orderbook, Side};
+use crate::errors::DexError;
+use crate::events::{event_sink, AccountLockedEvent, OrderCancelled};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct LockAccount<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader_state.trader.as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = authority.key() == trader_state.trader ||
+                     (trader_state.guardian != Pubkey::default() &&
+                      authority.key() == trader_state.guardian) @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    /// Trader or their guardian
+    pub authority: Signer<'info>,
+}
+
+/// Panic button: cancel all of the trader's orders and block orders and
+/// withdrawals for `TraderState::ACCOUNT_LOCK_SECONDS`
+///
+/// Calling it again while locked restarts the cooldown.
+pub fn handler(ctx: Context<LockAccount>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    let trader_state = &mut ctx.accounts.trader_state;
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    
+    // Collect first; removing while walking the book would break the iterator
+    let orders: Vec<(u64, Order)> = orderbook.iter_side(&orderbook_data, Side::Bid)
+        .chain(orderbook.iter_side(&orderbook_data, Side::Ask))
+        .filter(|(_, order)| order.trader == trader_state.trader)
+        .collect();
+    
+    for (slot, order) in orders.iter() {
+        if order.is_bid() {
+            let quote_locked = order.price
+                .checked_mul(order.remaining_size)
+                .and_then(|v| v.checked_div(market.lot_size))
+                .ok_or(DexError::MathOverflow)?;
+            trader_state.unlock_quote(quote_locked)?;
+        } else {
+            trader_state.unlock_base(order.remaining_size)?;
+        }
+        trader_state.release_deposit(order.deposit_lamports as u64, false)?;
+        
+        orderbook.remove_order(&mut orderbook_data, *slot)?;
+        
+        if market.logs_l3() {
+            sink.emit(OrderCancelled {
+                market: market.key(),
+                trader: order.trader,
+                order_id: order.order_id,
+                client_order_id: order.client_order_id,
+                remaining_size: order.remaining_size,
+                timestamp: clock.unix_timestamp,
+            })?;
+        }
+    }
+    
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    
+    trader_state.open_order_count = trader_state.open_order_count
+        .saturating_sub(orders.len() as u16);
+    trader_state.locked_until = clock.unix_timestamp
+        .checked_add(TraderState::ACCOUNT_LOCK_SECONDS)
+        .ok_or(DexError::MathOverflow)?;
+    
+    let market_key = market.key();
+    let market_mut = &mut ctx.accounts.market;
+    market_mut.sync_book(&orderbook, &orderbook_data, &sink)?;
+    
+    sink.emit(AccountLockedEvent {
+        trader: trader_state.trader,
+        market: market_key,
+        locked_by: ctx.accounts.authority.key(),
+        orders_cancelled: orders.len() as u16,
+        locked_until: trader_state.locked_until,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Account locked: trader={}, orders_cancelled={}, locked_until={}", 
+         trader_state.trader, orders.len(), trader_state.locked_until);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::TimeInForce;
+    use crate::test_utils::{account_infos, install_clock, load, TestAccount, Venue, NOW};
+    
+    /// Lock the trader whose signer is at `trader`, signed by the account at `authority`
+    fn lock<'info>(infos: &'info [AccountInfo<'info>], trader: usize, authority: usize) -> Result<()> {
+        install_clock();
+        let mut accounts = LockAccount {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            orderbook: UncheckedAccount::try_from(&infos[Venue::ORDERBOOK]),
+            trader_state: Account::try_from(&infos[trader + 1])?,
+            authority: Signer::try_from(&infos[authority])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()))?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn the_guardian_locks_the_account_and_pulls_only_its_orders() {
+        let mut venue = Venue::new(|_| {});
+        let trader = venue.add_trader(10_000, 20_000);
+        let other = venue.add_trader(10_000, 0);
+        venue.accounts.push(TestAccount::signer());
+        let guardian = venue.accounts.len() - 1;
+        let guardian_key = venue.accounts[guardian].key;
+        let infos = account_infos(&mut venue.accounts);
+        let mut trader_state = Account::<TraderState>::try_from(&infos[trader + 1]).unwrap();
+        trader_state.guardian = guardian_key;
+        trader_state.exit(&crate::ID).unwrap();
+        
+        Venue::place(&infos, trader, Venue::order(Side::Bid, 1_900, 10_000, TimeInForce::GTC)).unwrap();
+        Venue::place(&infos, trader, Venue::order(Side::Ask, 2_100, 10_000, TimeInForce::GTC)).unwrap();
+        Venue::place(&infos, other, Venue::order(Side::Ask, 2_200, 10_000, TimeInForce::GTC)).unwrap();
+        
+        lock(&infos, trader, guardian).unwrap();
+        let trader_state: TraderState = load(&infos[trader + 1]);
+        assert_eq!((trader_state.base_locked, trader_state.quote_locked), (0, 0));
+        assert_eq!((trader_state.base_available, trader_state.quote_available), (10_000, 20_000));
+        assert_eq!(trader_state.open_order_count, 0);
+        assert_eq!(trader_state.locked_until, NOW + TraderState::ACCOUNT_LOCK_SECONDS);
+        let orderbook: Orderbook = load(&infos[Venue::ORDERBOOK]);
+        assert_eq!(orderbook.order_count, 1);
+        assert_eq!(load::<Market>(&infos[Venue::MARKET]).best_ask, 2_200);
+        
+        assert_eq!(
+            Venue::place(&infos, trader, Venue::order(Side::Bid, 1_900, 10_000, TimeInForce::GTC)).unwrap_err(),
+            DexError::AccountLocked.into()
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::orderbook::{Orderbook, Side};
+use crate::errors::DexError;
+use crate::event_queue::{load_trader_states, QueueEvent};
+use crate::events::{event_sink, MatchedAndSettled, OrderCancelled, OrderMatched};
+use crate::matching::{order_fills, store_filled_order};
+use crate::oracle::{is_within_oracle_band, load_band_price};
+use crate::trade_tape::{record_trades, TradeRecord};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct MatchAndSettle<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    /// CHECK: Trade tape, required when the market has one
+    #[account(mut)]
+    pub trade_tape: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    /// CHECK: Market oracle, required when the market enforces an oracle band
+    pub oracle: Option<UncheckedAccount<'info>>,
+    
+    // Remaining accounts: TraderState accounts of both sides of the crossing orders
+}
+
+/// Match crossing orders and settle both sides in the same instruction
+///
+/// Permissionless crank, like match_orders, except that fills are applied to
+/// the trader states passed as remaining accounts instead of going through
+/// the event queue. Matching stops at the first crossing pair whose trader
+/// states were not passed in, and at anything that needs the event queue or
+/// a side effect of match_orders (expired orders, a broken peg band); those
+/// are left for match_orders. Batch-per-slot markets must use match_orders.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MatchAndSettle<'info>>,
+    max_iterations: u8,
+) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &ctx.accounts.market;
+    
+    require!(!market.paused, DexError::MarketPaused);
+    require!(market.accepts_new_orders(), DexError::MarketCancelOnly);
+    require!(!market.is_halted(Clock::get()?.unix_timestamp), DexError::MarketHalted);
+    require!(!market.batch_matching, DexError::OperationNotSupported);
+    
+    let market_key = market.key();
+    let lot_size = market.lot_size;
+    
+    // Load trader states passed by the caller
+    let mut trader_states = load_trader_states(&market_key, ctx.remaining_accounts)?;
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market_key, DexError::InvalidOrderbookState);
+    
+    let global_config = &ctx.accounts.global_config;
+    let clock = Clock::get()?;
+    let band_price = load_band_price(market, ctx.accounts.oracle.as_deref(), clock.unix_timestamp)?;
+    
+    let mut iterations = 0u8;
+    let mut quote_volume = 0u64;
+    let mut fill_count = 0u64;
+    let mut base_fees = 0u64;
+    let mut quote_fees = 0u64;
+    let mut base_rebates = 0u64;
+    let mut quote_rebates = 0u64;
+    let mut trades = Vec::new();
+    let (mut first_price, mut last_price, mut last_size) = (0u64, 0u64, 0u64);
+    let mut breaker_reference = market.breaker_reference(clock.unix_timestamp);
+    let mut breaker_price = None;
+    
+    while iterations < max_iterations {
+        let (bid_slot, mut bid_order) = match orderbook.find_best_bid(&orderbook_data) {
+            Some(best) => best,
+            None => break, // No bids
+        };
+        let (ask_slot, mut ask_order) = match orderbook.find_best_ask(&orderbook_data) {
+            Some(best) => best,
+            None => break, // No asks
+        };
+        
+        if bid_order.is_expired(clock.unix_timestamp) || ask_order.is_expired(clock.unix_timestamp) {
+            break; // Evicted through the event queue by match_orders or prune_expired_orders
+        }
+        if !bid_order.can_match(&ask_order) {
+            break; // No more matches possible (a self-trade pair is cancelled by match_orders)
+        }
+        if !bid_order.has_rested(clock.slot, market.min_resting_slots) ||
+            !ask_order.has_rested(clock.slot, market.min_resting_slots) {
+            break;
+        }
+        
+        // Older order is maker
+        let is_bid_maker = bid_order.timestamp <= ask_order.timestamp;
+        let match_price = if is_bid_maker {
+            market.match_price(bid_order.price, ask_order.price)
+        } else {
+            market.match_price(ask_order.price, bid_order.price)
+        };
+        if !is_within_oracle_band(match_price, band_price) || !market.is_within_peg_band(match_price) {
+            break;
+        }
+        if breaker_reference == 0 {
+            breaker_reference = match_price;
+        }
+        if market.breaker_exceeded(breaker_reference, match_price) {
+            breaker_price = Some(match_price);
+            break;
+        }
+        
+        let bid_index = trader_states.iter().position(|t| t.trader == bid_order.trader);
+        let ask_index = trader_states.iter().position(|t| t.trader == ask_order.trader);
+        let (bid_index, ask_index) = match (bid_index, ask_index) {
+            (Some(bid_index), Some(ask_index)) => (bid_index, ask_index),
+            _ => break, // Counterparty not passed in
+        };
+        
+        let fill_size = bid_order.remaining_size.min(ask_order.remaining_size);
+        bid_order.fill(fill_size)?;
+        ask_order.fill(fill_size)?;
+        
+        let (bid_fee_bps, ask_fee_bps) = if is_bid_maker {
+            (global_config.maker_fee_bps, global_config.taker_fee_bps as i16)
+        } else {
+            (global_config.taker_fee_bps as i16, global_config.maker_fee_bps)
+        };
+        
+        let fill_id = market.fill_id_at(fill_count)?;
+        
+        let bid_dust = store_filled_order(&mut orderbook, &mut orderbook_data, bid_slot, &bid_order, market)?;
+        let ask_dust = store_filled_order(&mut orderbook, &mut orderbook_data, ask_slot, &ask_order, market)?;
+        
+        // Settle both sides exactly as consume_events would
+        let mut event = QueueEvent::fill(
+            &bid_order,
+            &ask_order,
+            match_price,
+            fill_size,
+            bid_fee_bps,
+            ask_fee_bps,
+            clock.unix_timestamp,
+        );
+        let (fee, rebate) = event.apply_bid(&mut trader_states[bid_index], lot_size)?;
+        base_fees = base_fees.checked_add(fee).ok_or(DexError::MathOverflow)?;
+        quote_rebates = quote_rebates.checked_add(rebate).ok_or(DexError::MathOverflow)?;
+        let (fee, rebate) = event.apply_ask(&mut trader_states[ask_index], lot_size)?;
+        quote_fees = quote_fees.checked_add(fee).ok_or(DexError::MathOverflow)?;
+        base_rebates = base_rebates.checked_add(rebate).ok_or(DexError::MathOverflow)?;
+        
+        let quote_amount = event.quote_amount(lot_size)?;
+        for (index, is_bid) in [(bid_index, true), (ask_index, false)] {
+            let rested_seconds = (is_bid == is_bid_maker).then(|| event.rested_seconds(is_bid));
+            let points = market.points_for(quote_amount, rested_seconds, event.timestamp);
+            trader_states[index].points = trader_states[index].points.saturating_add(points);
+            trader_states[index].record_competition_volume(market, quote_amount, event.timestamp);
+        }
+        
+        let taker_side = if is_bid_maker { Side::Ask } else { Side::Bid };
+        trades.push(TradeRecord::from_fill(&event, Some(taker_side)));
+        quote_volume = quote_volume
+            .checked_add(quote_amount)
+            .ok_or(DexError::MathOverflow)?;
+        fill_count += 1;
+        if first_price == 0 {
+            first_price = match_price;
+        }
+        last_price = match_price;
+        last_size = fill_size;
+        
+        sink.emit(OrderMatched {
+            market: market_key,
+            bid_order_id: bid_order.order_id,
+            ask_order_id: ask_order.order_id,
+            bid_client_order_id: bid_order.client_order_id,
+            ask_client_order_id: ask_order.client_order_id,
+            price: match_price,
+            size: fill_size,
+            bid_trader: bid_order.trader,
+            ask_trader: ask_order.trader,
+            bid_origin: bid_order.origin,
+            ask_origin: ask_order.origin,
+            fill_id,
+            timestamp: clock.unix_timestamp,
+        })?;
+        for filled in order_fills(market_key, &event, &bid_order, &ask_order, Some(taker_side), fill_id, lot_size)? {
+            sink.emit(filled)?;
+        }
+        
+        // Release dust remainders straight away, as consume_events would for their Out events
+        let dust = [(bid_dust, bid_index), (ask_dust, ask_index)];
+        for (order, index) in dust.iter().filter_map(|(order, index)| order.map(|order| (order, *index))) {
+            let mut out = QueueEvent::out(&order, clock.unix_timestamp);
+            if order.is_bid() {
+                out.apply_bid(&mut trader_states[index], lot_size)?;
+            } else {
+                out.apply_ask(&mut trader_states[index], lot_size)?;
+            }
+            
+            if market.logs_l3() {
+                sink.emit(OrderCancelled {
+                    market: market_key,
+                    trader: order.trader,
+                    order_id: order.order_id,
+                    client_order_id: order.client_order_id,
+                    remaining_size: order.remaining_size,
+                    timestamp: clock.unix_timestamp,
+                })?;
+            }
+        }
+        
+        iterations = iterations.checked_add(1).ok_or(DexError::MathOverflow)?;
+    }
+    
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    record_trades(market, ctx.accounts.trade_tape.as_deref(), &trades)?;
+    
+    // Persist trader states (remaining accounts are not written back automatically)
+    for trader_state in trader_states.iter() {
+        trader_state.exit(&crate::ID)?;
+    }
+    
+    // Both sides settle here, so each rebate is netted against the taker
+    // fee charged on the same fill and never touches earlier accruals
+    let market = &mut ctx.accounts.market;
+    market.accrue_fee(true, base_fees.checked_sub(base_rebates).ok_or(DexError::MathUnderflow)?)?;
+    market.accrue_fee(false, quote_fees.checked_sub(quote_rebates).ok_or(DexError::MathUnderflow)?)?;
+    market.sync_book(&orderbook, &orderbook_data, &sink)?;
+    market.record_fills(quote_volume, fill_count, first_price, last_price, last_size, clock.unix_timestamp)?;
+    market.last_match_slot = clock.slot;
+    if let Some(price) = breaker_price {
+        market.trip_breaker(market_key, price, clock.unix_timestamp, &sink)?;
+    }
+    
+    sink.emit(MatchedAndSettled {
+        market: market_key,
+        fills: fill_count,
+        quote_volume,
+        base_fees,
+        quote_fees,
+        base_rebates,
+        quote_rebates,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Matched and settled: market={}, fills={}, quote_volume={}", 
+         market_key, fill_count, quote_volume);
+    
+    Ok(())
+}
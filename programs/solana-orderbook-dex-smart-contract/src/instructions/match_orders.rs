@@ -1,14 +1,20 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Token;
-use crate::state::{Market, Orderbook, PendingFill};
-use crate::orderbook::Order;
+use crate::state::{Market, TraderState};
+use crate::orderbook::{Order, Orderbook, Side};
 use crate::errors::DexError;
-use crate::events::OrderMatched;
+use crate::event_queue::{EventQueue, QueueEvent};
+use crate::events::{event_sink, CrankRewardPaid, OrderMatched, PegBroken};
 use crate::state::GlobalConfig;
+use crate::instructions::cancel_order::{evict_resting_orders, queue_removed_orders};
+use crate::matching::{collect_batch_level, match_batch, order_fills, store_filled_order};
+use crate::oracle::{is_within_oracle_band, load_band_price};
+use crate::trade_tape::{record_trades, TradeRecord};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct MatchOrders<'info> {
     #[account(
+        mut,
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
         bump = market.bump
     )]
@@ -18,12 +24,33 @@ pub struct MatchOrders<'info> {
     #[account(mut)]
     pub orderbook: UncheckedAccount<'info>,
     
+    /// CHECK: Event queue account (must be the market's)
+    #[account(mut, address = market.event_queue @ DexError::InvalidAccountState)]
+    pub event_queue: UncheckedAccount<'info>,
+    
+    /// CHECK: Trade tape, required when the market has one
+    #[account(mut)]
+    pub trade_tape: Option<UncheckedAccount<'info>>,
+    
     #[account(
         seeds = [b"global_config"],
         bump = global_config.bump
     )]
     pub global_config: Account<'info, GlobalConfig>,
     
+    /// CHECK: Market oracle, required when the market enforces an oracle band
+    pub oracle: Option<UncheckedAccount<'info>>,
+    
+    /// Caller claiming the crank reward; must own `cranker_state`
+    pub cranker: Option<Signer<'info>>,
+    
+    /// Trader state credited with the crank reward, if the market pays one
+    #[account(
+        mut,
+        constraint = cranker_state.market == market.key() @ DexError::InvalidAccountState
+    )]
+    pub cranker_state: Option<Account<'info, TraderState>>,
+    
     /// CHECK: Pending fills account (can be any account, we'll create fills)
     #[account(mut)]
     pub pending_fills: UncheckedAccount<'info>,
@@ -32,9 +59,18 @@ pub struct MatchOrders<'info> {
 }
 
 pub fn handler(ctx: Context<MatchOrders>, max_iterations: u8) -> Result<()> {
+    let sink = event_sink!(ctx);
     let market = &ctx.accounts.market;
     
     require!(!market.paused, DexError::MarketPaused);
+    require!(market.accepts_new_orders(), DexError::MarketCancelOnly);
+    require!(!market.is_halted(Clock::get()?.unix_timestamp), DexError::MarketHalted);
+    
+    // The reward goes to the signer's own trader state
+    if let Some(cranker_state) = ctx.accounts.cranker_state.as_ref() {
+        let cranker = ctx.accounts.cranker.as_ref().ok_or(DexError::Unauthorized)?;
+        require_keys_eq!(cranker_state.trader, cranker.key(), DexError::Unauthorized);
+    }
     
     // Load orderbook
     let orderbook_account_info = &ctx.accounts.orderbook;
@@ -44,15 +80,114 @@ pub fn handler(ctx: Context<MatchOrders>, max_iterations: u8) -> Result<()> {
     );
     
     let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
-    let mut orderbook = Account::<Orderbook>::try_deserialize(
-        &mut &orderbook_account_info.data.borrow()[..Orderbook::HEADER_SIZE]
-    )?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    
+    // Load event queue; fills are settled by consume_events
+    let mut event_queue_data = ctx.accounts.event_queue.try_borrow_mut_data()?;
+    let mut event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE])?;
     
     let global_config = &ctx.accounts.global_config;
     let mut iterations = 0u8;
+    let mut peg_broken = false;
+    let current_slot = Clock::get()?.slot;
+    let mut quote_volume = 0u64;
+    let mut fill_count = 0u64;
+    let mut trades = Vec::new();
+    let (mut first_price, mut last_price, mut last_size) = (0u64, 0u64, 0u64);
+    let band_price = load_band_price(market, ctx.accounts.oracle.as_deref(), Clock::get()?.unix_timestamp)?;
+    let mut breaker_reference = market.breaker_reference(Clock::get()?.unix_timestamp);
+    let mut breaker_price = None;
     
     // Matching loop
     while iterations < max_iterations {
+        // Batch-per-slot markets clear one price level per iteration, pro-rata
+        if market.batch_matching {
+            let min_age = market.min_resting_slots.max(1);
+            let mut bids = collect_batch_level(&orderbook, &orderbook_data, Side::Bid, current_slot, min_age);
+            let mut asks = collect_batch_level(&orderbook, &orderbook_data, Side::Ask, current_slot, min_age);
+            
+            let (bid_price, ask_price) = match (bids.first(), asks.first()) {
+                (Some((_, bid)), Some((_, ask))) => (bid.price, ask.price),
+                _ => break, // One side has no closed batch
+            };
+            if bid_price < ask_price {
+                break; // No more matches possible
+            }
+            
+            let match_price = bid_price.min(ask_price);
+            if !is_within_oracle_band(match_price, band_price) {
+                msg!("Oracle band exceeded: price={}, oracle={:?}", match_price, band_price);
+                break;
+            }
+            if !market.is_within_peg_band(match_price) {
+                sink.emit(PegBroken {
+                    market: market.key(),
+                    price: match_price,
+                    peg_price: market.peg_price,
+                    peg_band_bps: market.peg_band_bps,
+                    timestamp: Clock::get()?.unix_timestamp,
+                })?;
+                msg!("Peg band broken: price={}, peg={}", match_price, market.peg_price);
+                peg_broken = true;
+                break;
+            }
+            if breaker_reference == 0 {
+                breaker_reference = match_price;
+            }
+            if market.breaker_exceeded(breaker_reference, match_price) {
+                breaker_price = Some(match_price);
+                break;
+            }
+            
+            let events = match_batch(
+                &mut bids,
+                &mut asks,
+                market,
+                global_config,
+                fill_count,
+                &sink,
+            )?;
+            if events.is_empty() {
+                break; // Only self-trades left at the top of book
+            }
+            
+            // Write back or remove every order in both batches
+            let mut dust = Vec::new();
+            for (slot, order) in bids.iter().chain(asks.iter()) {
+                if let Some(order) = store_filled_order(&mut orderbook, &mut orderbook_data, *slot, order, market)? {
+                    dust.push(order);
+                }
+            }
+            
+            let fills = events.len();
+            last_size = events.last().map_or(0, |event| event.size);
+            for event in events {
+                trades.push(TradeRecord::from_fill(&event, None));
+                quote_volume = quote_volume
+                    .checked_add(event.quote_amount(market.lot_size)?)
+                    .ok_or(DexError::MathOverflow)?;
+                event_queue.push(&mut event_queue_data, event)?;
+            }
+            queue_removed_orders(
+                market,
+                &mut event_queue,
+                &mut event_queue_data,
+                &dust,
+                Clock::get()?.unix_timestamp,
+                &sink,
+            )?;
+            fill_count += fills as u64;
+            if first_price == 0 {
+                first_price = match_price;
+            }
+            last_price = match_price;
+            
+            msg!("Batch matched: price={}, fills={}", match_price, fills);
+            iterations = iterations.checked_add(1).ok_or(DexError::MathOverflow)?;
+            continue;
+        }
+        
         // Find best bid and best ask
         let best_bid_opt = orderbook.find_best_bid(&orderbook_data);
         let best_ask_opt = orderbook.find_best_ask(&orderbook_data);
@@ -67,13 +202,95 @@ pub fn handler(ctx: Context<MatchOrders>, max_iterations: u8) -> Result<()> {
             None => break, // No asks
         };
         
+        // Drop expired good-till-date orders from the top of book instead of filling them
+        let now = Clock::get()?.unix_timestamp;
+        let expired: Vec<(u64, Order)> = [(bid_slot, bid_order), (ask_slot, ask_order)]
+            .into_iter()
+            .filter(|(_, order)| order.is_expired(now))
+            .collect();
+        if !expired.is_empty() {
+            evict_resting_orders(
+                market,
+                &mut orderbook,
+                &mut orderbook_data,
+                &mut event_queue,
+                &mut event_queue_data,
+                &expired,
+                &sink,
+            )?;
+            iterations = iterations.checked_add(1).ok_or(DexError::MathOverflow)?;
+            continue;
+        }
+        
+        // A trader's own crossing pair cannot fill, so the newer order is
+        // cancelled (released by consume_events) rather than stalling the crank
+        if bid_order.trader == ask_order.trader && bid_order.crosses(&ask_order) {
+            let newer = if bid_order.timestamp <= ask_order.timestamp {
+                (ask_slot, ask_order)
+            } else {
+                (bid_slot, bid_order)
+            };
+            evict_resting_orders(
+                market,
+                &mut orderbook,
+                &mut orderbook_data,
+                &mut event_queue,
+                &mut event_queue_data,
+                &[newer],
+                &sink,
+            )?;
+            msg!("Self-trade prevented: order {} cancelled", newer.1.order_id);
+            iterations = iterations.checked_add(1).ok_or(DexError::MathOverflow)?;
+            continue;
+        }
+        
         // Check if orders can match
         if !bid_order.can_match(&ask_order) {
             break; // No more matches possible
         }
         
-        // Calculate match price (use bid price for simplicity, could use mid-price)
-        let match_price = bid_order.price.min(ask_order.price);
+        // Speed bump: both sides must have rested long enough
+        if !bid_order.has_rested(current_slot, market.min_resting_slots) ||
+            !ask_order.has_rested(current_slot, market.min_resting_slots) {
+            break;
+        }
+        
+        // Determine maker/taker (older order is maker) and the price per the market's model
+        let is_bid_maker = bid_order.timestamp <= ask_order.timestamp;
+        let match_price = if is_bid_maker {
+            market.match_price(bid_order.price, ask_order.price)
+        } else {
+            market.match_price(ask_order.price, bid_order.price)
+        };
+        
+        // Fills too far from the oracle wait until the book or the oracle moves
+        if !is_within_oracle_band(match_price, band_price) {
+            msg!("Oracle band exceeded: price={}, oracle={:?}", match_price, band_price);
+            break;
+        }
+        
+        // A trade outside the peg band flips the market to cancel-only instead of executing
+        if !market.is_within_peg_band(match_price) {
+            sink.emit(PegBroken {
+                market: market.key(),
+                price: match_price,
+                peg_price: market.peg_price,
+                peg_band_bps: market.peg_band_bps,
+                timestamp: Clock::get()?.unix_timestamp,
+            })?;
+            msg!("Peg band broken: price={}, peg={}", match_price, market.peg_price);
+            peg_broken = true;
+            break;
+        }
+        
+        // A fill too far from the breaker window's first fill halts the market instead
+        if breaker_reference == 0 {
+            breaker_reference = match_price;
+        }
+        if market.breaker_exceeded(breaker_reference, match_price) {
+            breaker_price = Some(match_price);
+            break;
+        }
         
         // Calculate fill size (minimum of remaining sizes)
         let fill_size = bid_order.remaining_size.min(ask_order.remaining_size);
@@ -82,80 +299,80 @@ pub fn handler(ctx: Context<MatchOrders>, max_iterations: u8) -> Result<()> {
         bid_order.fill(fill_size)?;
         ask_order.fill(fill_size)?;
         
-        // Calculate fees
-        let quote_amount = match_price
-            .checked_mul(fill_size)
-            .and_then(|v| v.checked_div(market.lot_size))
-            .ok_or(DexError::MathOverflow)?;
-        
-        // Determine maker/taker (older order is maker)
-        let is_bid_maker = bid_order.timestamp <= ask_order.timestamp;
-        let maker_fee = if is_bid_maker {
-            quote_amount
-                .checked_mul(global_config.maker_fee_bps as u64)
-                .and_then(|v| v.checked_div(10000))
-                .unwrap_or(0)
-        } else {
-            quote_amount
-                .checked_mul(global_config.taker_fee_bps as u64)
-                .and_then(|v| v.checked_div(10000))
-                .unwrap_or(0)
-        };
-        
-        let taker_fee = if is_bid_maker {
-            quote_amount
-                .checked_mul(global_config.taker_fee_bps as u64)
-                .and_then(|v| v.checked_div(10000))
-                .unwrap_or(0)
+        let (bid_fee_bps, ask_fee_bps) = if is_bid_maker {
+            (global_config.maker_fee_bps, global_config.taker_fee_bps as i16)
         } else {
-            quote_amount
-                .checked_mul(global_config.maker_fee_bps as u64)
-                .and_then(|v| v.checked_div(10000))
-                .unwrap_or(0)
+            (global_config.taker_fee_bps as i16, global_config.maker_fee_bps)
         };
         
         // Generate fill ID
         let clock = Clock::get()?;
-        let fill_id = u128::from(clock.unix_timestamp)
-            .checked_mul(1_000_000)
-            .and_then(|v| v.checked_add(u128::from(clock.slot)))
-            .and_then(|v| v.checked_add(u128::from(iterations)))
-            .ok_or(DexError::MathOverflow)?;
-        
-        // Update orders in orderbook
-        orderbook.set_order(&mut orderbook_data, bid_slot, &bid_order)?;
-        orderbook.set_order(&mut orderbook_data, ask_slot, &ask_order)?;
+        let fill_id = market.fill_id_at(fill_count)?;
         
-        // Remove filled orders
-        if bid_order.is_filled() {
-            orderbook.free_slot(&mut orderbook_data, bid_slot)?;
-            orderbook.order_count = orderbook.order_count
-                .checked_sub(1)
-                .ok_or(DexError::MathUnderflow)?;
+        // Update orders in orderbook, removing filled ones and dust remainders
+        let mut dust = Vec::new();
+        for (slot, order) in [(bid_slot, &bid_order), (ask_slot, &ask_order)] {
+            if let Some(order) = store_filled_order(&mut orderbook, &mut orderbook_data, slot, order, market)? {
+                dust.push(order);
+            }
         }
         
-        if ask_order.is_filled() {
-            orderbook.free_slot(&mut orderbook_data, ask_slot)?;
-            orderbook.order_count = orderbook.order_count
-                .checked_sub(1)
-                .ok_or(DexError::MathUnderflow)?;
+        quote_volume = match_price
+            .checked_mul(fill_size)
+            .and_then(|v| v.checked_div(market.lot_size))
+            .and_then(|v| v.checked_add(quote_volume))
+            .ok_or(DexError::MathOverflow)?;
+        fill_count += 1;
+        if first_price == 0 {
+            first_price = match_price;
         }
+        last_price = match_price;
+        last_size = fill_size;
         
-        // Update best prices
-        orderbook.update_best_prices(&orderbook_data);
+        // Queue the fill for consume_events
+        let event = QueueEvent::fill(
+            &bid_order,
+            &ask_order,
+            match_price,
+            fill_size,
+            bid_fee_bps,
+            ask_fee_bps,
+            clock.unix_timestamp,
+        );
+        let taker_side = if is_bid_maker { Side::Ask } else { Side::Bid };
+        trades.push(TradeRecord::from_fill(&event, Some(taker_side)));
+        let fills = order_fills(market.key(), &event, &bid_order, &ask_order, Some(taker_side), fill_id, market.lot_size)?;
+        event_queue.push(&mut event_queue_data, event)?;
         
         // Emit match event
-        emit!(OrderMatched {
+        sink.emit(OrderMatched {
             market: market.key(),
             bid_order_id: bid_order.order_id,
             ask_order_id: ask_order.order_id,
+            bid_client_order_id: bid_order.client_order_id,
+            ask_client_order_id: ask_order.client_order_id,
             price: match_price,
             size: fill_size,
             bid_trader: bid_order.trader,
             ask_trader: ask_order.trader,
+            bid_origin: bid_order.origin,
+            ask_origin: ask_order.origin,
             fill_id,
             timestamp: clock.unix_timestamp,
-        });
+        })?;
+        for filled in fills {
+            sink.emit(filled)?;
+        }
+        
+        // Release dust remainders after their last fill
+        queue_removed_orders(
+            market,
+            &mut event_queue,
+            &mut event_queue_data,
+            &dust,
+            clock.unix_timestamp,
+            &sink,
+        )?;
         
         msg!("Orders matched: bid={}, ask={}, price={}, size={}", 
              bid_order.order_id, ask_order.order_id, match_price, fill_size);
@@ -163,14 +380,41 @@ pub fn handler(ctx: Context<MatchOrders>, max_iterations: u8) -> Result<()> {
         iterations = iterations.checked_add(1).ok_or(DexError::MathOverflow)?;
     }
     
-    // Save orderbook
+    // Save orderbook and event queue
     orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE])?;
+    record_trades(market, ctx.accounts.trade_tape.as_deref(), &trades)?;
     
     // Update market
-    let mut market_mut = ctx.accounts.market.as_mut();
-    market_mut.best_bid = orderbook.best_bid;
-    market_mut.best_ask = orderbook.best_ask;
-    market_mut.order_count = orderbook.order_count;
+    let market_mut = &mut ctx.accounts.market;
+    if peg_broken {
+        market_mut.peg_cancel_only = true;
+    }
+    market_mut.sync_book(&orderbook, &orderbook_data, &sink)?;
+    market_mut.record_fills(quote_volume, fill_count, first_price, last_price, last_size, Clock::get()?.unix_timestamp)?;
+    market_mut.last_match_slot = current_slot;
+    if let Some(price) = breaker_price {
+        let market_key = market_mut.key();
+        market_mut.trip_breaker(market_key, price, Clock::get()?.unix_timestamp, &sink)?;
+    }
+    
+    // Pay the caller for the fills it produced, out of the protocol's quote fees
+    if let Some(cranker_state) = ctx.accounts.cranker_state.as_mut() {
+        let reward = market_mut.take_crank_reward(fill_count);
+        if reward > 0 {
+            cranker_state.quote_available = cranker_state.quote_available
+                .checked_add(reward)
+                .ok_or(DexError::MathOverflow)?;
+            
+            sink.emit(CrankRewardPaid {
+                market: market_mut.key(),
+                recipient: cranker_state.trader,
+                fills: fill_count,
+                amount: reward,
+                timestamp: Clock::get()?.unix_timestamp,
+            })?;
+        }
+    }
     
     Ok(())
 }
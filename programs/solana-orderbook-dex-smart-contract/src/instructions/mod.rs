@@ -1,23 +1,167 @@
+// Every module exports its own `handler`, which lib.rs calls by path; the
+// globs are only there to re-export the Accounts structs Anchor expects
+#![allow(ambiguous_glob_reexports)]
+
+pub mod accept_authority;
+pub mod apply_pending_update;
+pub mod apply_withdrawal_allowlist;
+pub mod approve_withdrawal;
+pub mod archive_fills;
+pub mod cancel_diverged_orders;
 pub mod cancel_order;
+pub mod cancel_order_by_client_id;
+pub mod cancel_orders_by_side;
+pub mod cancel_withdrawal;
+pub mod claim_competition_prize;
+pub mod claim_forfeited_deposits;
+pub mod claim_integrator_fees;
+pub mod claim_protocol_fees;
+pub mod claim_revenue_share;
+pub mod consume_events;
+pub mod create_competition;
+pub mod create_data_feed;
 pub mod create_market;
 pub mod deposit;
+pub mod deposit_and_place;
+pub mod deposit_sol;
+pub mod downsize_and_withdraw;
+pub mod emit_depth_snapshot;
+pub mod enable_withdrawal_allowlist;
+pub mod enter_competition;
+pub mod expand_orderbook;
+pub mod force_cancel_orders;
+pub mod fund_data_feed;
+pub mod fund_placement_deposits;
+pub mod get_market_health;
+pub mod grant_seat;
+pub mod init_event_queue;
+pub mod init_open_orders;
+pub mod init_orderbook;
+pub mod init_revenue_share;
+pub mod init_trade_tape;
 pub mod initialize;
+pub mod internal_transfer;
+pub mod lock_account;
+pub mod match_and_settle;
 pub mod match_orders;
+pub mod modify_order;
 pub mod pause_market;
+pub mod place_multiple_orders;
 pub mod place_order;
+pub mod propose_authority;
+pub mod propose_withdrawal_allowlist;
+pub mod prune_expired_orders;
+pub mod record_competition_score;
+pub mod refresh_data_feed;
+pub mod register_integrator;
+pub mod replay_events;
+pub mod request_withdrawal;
+pub mod reset_circuit_breaker;
+pub mod revoke_seat;
+pub mod roll_epoch;
+pub mod seed_liquidity;
+pub mod set_crank_reward;
+pub mod set_fee_pass;
+pub mod set_guardian;
+pub mod set_market_operator;
+pub mod set_market_oracle;
+pub mod set_market_status;
+pub mod set_peg_protection;
+pub mod set_points_schedule;
+pub mod set_risk_authority;
+pub mod set_seat_requirement;
+pub mod set_settlement_program;
+pub mod set_withdrawal_co_signer;
+pub mod set_withdrawal_cooldown;
 pub mod settle;
+pub mod swap;
+pub mod sweep_competition_prize;
 pub mod update_market_params;
 pub mod update_protocol_fees;
+pub mod update_risk_params;
 pub mod withdraw;
+pub mod withdraw_multi;
+pub mod withdraw_placement_deposits;
+pub mod withdraw_sol;
 
+pub use accept_authority::*;
+pub use apply_pending_update::*;
+pub use apply_withdrawal_allowlist::*;
+pub use approve_withdrawal::*;
+pub use archive_fills::*;
+pub use cancel_diverged_orders::*;
 pub use cancel_order::*;
+pub use cancel_order_by_client_id::*;
+pub use cancel_orders_by_side::*;
+pub use cancel_withdrawal::*;
+pub use claim_competition_prize::*;
+pub use claim_forfeited_deposits::*;
+pub use claim_integrator_fees::*;
+pub use claim_protocol_fees::*;
+pub use claim_revenue_share::*;
+pub use consume_events::*;
+pub use create_competition::*;
+pub use create_data_feed::*;
 pub use create_market::*;
 pub use deposit::*;
+pub use deposit_and_place::*;
+pub use deposit_sol::*;
+pub use downsize_and_withdraw::*;
+pub use emit_depth_snapshot::*;
+pub use enable_withdrawal_allowlist::*;
+pub use enter_competition::*;
+pub use expand_orderbook::*;
+pub use force_cancel_orders::*;
+pub use fund_data_feed::*;
+pub use fund_placement_deposits::*;
+pub use get_market_health::*;
+pub use grant_seat::*;
+pub use init_event_queue::*;
+pub use init_open_orders::*;
+pub use init_orderbook::*;
+pub use init_revenue_share::*;
+pub use init_trade_tape::*;
 pub use initialize::*;
+pub use internal_transfer::*;
+pub use lock_account::*;
+pub use match_and_settle::*;
 pub use match_orders::*;
+pub use modify_order::*;
 pub use pause_market::*;
+pub use place_multiple_orders::*;
 pub use place_order::*;
+pub use propose_authority::*;
+pub use propose_withdrawal_allowlist::*;
+pub use prune_expired_orders::*;
+pub use record_competition_score::*;
+pub use refresh_data_feed::*;
+pub use register_integrator::*;
+pub use replay_events::*;
+pub use request_withdrawal::*;
+pub use reset_circuit_breaker::*;
+pub use revoke_seat::*;
+pub use roll_epoch::*;
+pub use seed_liquidity::*;
+pub use set_crank_reward::*;
+pub use set_fee_pass::*;
+pub use set_guardian::*;
+pub use set_market_operator::*;
+pub use set_market_oracle::*;
+pub use set_market_status::*;
+pub use set_peg_protection::*;
+pub use set_points_schedule::*;
+pub use set_risk_authority::*;
+pub use set_seat_requirement::*;
+pub use set_settlement_program::*;
+pub use set_withdrawal_co_signer::*;
+pub use set_withdrawal_cooldown::*;
 pub use settle::*;
+pub use swap::*;
+pub use sweep_competition_prize::*;
 pub use update_market_params::*;
 pub use update_protocol_fees::*;
+pub use update_risk_params::*;
 pub use withdraw::*;
+pub use withdraw_multi::*;
+pub use withdraw_placement_deposits::*;
+pub use withdraw_sol::*;
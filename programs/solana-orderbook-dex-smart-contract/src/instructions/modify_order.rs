@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, OpenOrders, Seat, TraderState};
+use crate::orderbook::{Orderbook, Side};
+use crate::errors::DexError;
+use crate::events::{event_sink, OrderModified};
+use crate::oracle::{is_within_oracle_band, load_band_price};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ModifyOrderParams {
+    pub order_id: u128,
+    pub new_price: Option<u64>, // None = keep the current price
+    pub new_size: Option<u64>,  // New remaining size; None = keep
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ModifyOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    /// Slot index of the trader's orders; used for the lookup and updated on re-queue
+    #[account(
+        mut,
+        seeds = [b"open_orders", trader_state.key().as_ref()],
+        bump = open_orders.bump
+    )]
+    pub open_orders: Option<Account<'info, OpenOrders>>,
+    
+    /// CHECK: Market oracle, required to move the price on a market that enforces an oracle band
+    pub oracle: Option<UncheckedAccount<'info>>,
+    
+    /// Trading seat, required when the market is permissioned
+    #[account(
+        seeds = [b"seat", market.key().as_ref(), trader.key().as_ref()],
+        bump = seat.bump
+    )]
+    pub seat: Option<Account<'info, Seat>>,
+    
+    pub trader: Signer<'info>,
+}
+
+/// Atomically change the price and/or remaining size of a resting order
+///
+/// Reducing the size at the same price keeps the order's time priority.
+/// Any other change re-queues it at the back of its (new) price level; a
+/// re-queued order must not cross the spread. Locked funds are topped up or
+/// released to match the new order.
+pub fn handler(ctx: Context<ModifyOrder>, params: ModifyOrderParams) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    let trader_state = &mut ctx.accounts.trader_state;
+    
+    require!(!market.paused, DexError::MarketPaused);
+    require!(!trader_state.is_locked(clock.unix_timestamp), DexError::AccountLocked);
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    
+    let (slot, mut order) = ctx.accounts.open_orders.as_ref()
+        .and_then(|open_orders| open_orders.lookup(&orderbook, &orderbook_data, params.order_id))
+        .or_else(|| orderbook.find_order(&orderbook_data, params.order_id))
+        .filter(|(_, order)| order.trader == ctx.accounts.trader.key())
+        .ok_or(DexError::OrderNotFound)?;
+    
+    let old_price = order.price;
+    let old_size = order.remaining_size;
+    let new_price = params.new_price.unwrap_or(old_price);
+    let new_size = params.new_size.unwrap_or(old_size);
+    require!(new_price != old_price || new_size != old_size, DexError::InvalidOrderParams);
+    
+    let keeps_priority = new_price == old_price && new_size < old_size;
+    
+    // Cancel-only markets accept reductions but nothing that adds risk
+    require!(market.accepts_new_orders() || keeps_priority, DexError::MarketCancelOnly);
+    require!(!market.is_halted(clock.unix_timestamp) || keeps_priority, DexError::MarketHalted);
+    
+    // Re-queuing pulls a quote and posts a new one, so it counts as a cancel
+    require!(
+        keeps_priority || !trader_state.exceeds_cancel_ratio(market.max_cancel_to_fill_ratio, clock.unix_timestamp),
+        DexError::CancelRatioExceeded
+    );
+    
+    // Permissioned markets: like a cancel, a reduction never needs the seat
+    require!(
+        !market.requires_seat || ctx.accounts.seat.is_some() || keeps_priority,
+        DexError::SeatRequired
+    );
+    
+    // Validate the new order like place_order does
+    require!(market.is_valid_tick(new_price), DexError::PriceNotOnTick);
+    require!(market.is_within_peg_band(new_price), DexError::PriceOutsidePegBand);
+    if new_price != old_price {
+        let band_price = load_band_price(market, ctx.accounts.oracle.as_deref(), clock.unix_timestamp)?;
+        require!(
+            is_within_oracle_band(new_price, band_price),
+            DexError::OraclePriceDeviationTooLarge
+        );
+    }
+    require!(market.is_valid_lot(new_size), DexError::OrderSizeTooSmall);
+    require!(
+        new_size <= 1_000_000_000_000, // Reasonable upper bound
+        DexError::OrderSizeTooLarge
+    );
+    let notional = (new_price as u128)
+        .checked_mul(new_size as u128)
+        .and_then(|v| v.checked_div(market.lot_size as u128))
+        .ok_or(DexError::MathOverflow)?;
+    market.check_order_notional(notional)?;
+    
+    // Re-lock or release the difference
+    if order.is_bid() {
+        let old_locked = old_price
+            .checked_mul(old_size)
+            .and_then(|v| v.checked_div(market.lot_size))
+            .ok_or(DexError::MathOverflow)?;
+        let new_locked = new_price
+            .checked_mul(new_size)
+            .and_then(|v| v.checked_div(market.lot_size))
+            .ok_or(DexError::MathOverflow)?;
+        if new_locked > old_locked {
+            trader_state.lock_quote(new_locked - old_locked)?;
+        } else {
+            trader_state.unlock_quote(old_locked - new_locked)?;
+        }
+    } else if new_size > old_size {
+        trader_state.lock_base(new_size - old_size)?;
+    } else {
+        trader_state.unlock_base(old_size - new_size)?;
+    }
+    
+    // Keep the original size minus what is no longer open
+    order.size = order.size
+        .checked_sub(old_size)
+        .and_then(|v| v.checked_add(new_size))
+        .ok_or(DexError::MathOverflow)?;
+    order.remaining_size = new_size;
+    
+    if keeps_priority {
+        orderbook.set_order(&mut orderbook_data, slot, &order)?;
+    } else {
+        orderbook.remove_order(&mut orderbook_data, slot)?;
+        trader_state.record_order_activity(1, 0, clock.unix_timestamp);
+        
+        // A re-queued order rests; taking liquidity goes through place_order
+        if order.is_bid() {
+            require!(
+                orderbook.best_ask == 0 || new_price < orderbook.best_ask,
+                DexError::PostOnlyWouldCross
+            );
+        } else {
+            require!(
+                orderbook.best_bid == 0 || new_price > orderbook.best_bid,
+                DexError::PostOnlyWouldCross
+            );
+        }
+        
+        order.price = new_price;
+        order.timestamp = clock.unix_timestamp;
+        order.slot = clock.slot;
+        let new_slot = orderbook.insert_order(&mut orderbook_data, &order)?;
+        if let Some(open_orders) = ctx.accounts.open_orders.as_mut() {
+            open_orders.remove(order.order_id);
+            open_orders.insert(&orderbook, &orderbook_data, order.order_id, new_slot);
+        }
+        
+        let side = if order.is_bid() { Side::Bid } else { Side::Ask };
+        market.check_exposure(&orderbook, side)?;
+    }
+    
+    // Save orderbook
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    
+    // Update market
+    let market_mut = &mut ctx.accounts.market;
+    market_mut.sync_book(&orderbook, &orderbook_data, &sink)?;
+    
+    if market_mut.logs_l3() {
+        sink.emit(OrderModified {
+            market: market_mut.key(),
+            trader: order.trader,
+            order_id: order.order_id,
+            client_order_id: order.client_order_id,
+            old_price,
+            new_price,
+            old_size,
+            new_size,
+            priority_kept: keeps_priority,
+            timestamp: clock.unix_timestamp,
+        })?;
+    }
+    
+    msg!("Order modified: id={}, price={}->{}, size={}->{}, priority_kept={}", 
+         order.order_id, old_price, new_price, old_size, new_size, keeps_priority);
+    
+    Ok(())
+}
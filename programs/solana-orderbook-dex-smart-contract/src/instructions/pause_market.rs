@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::state::Market;
 use crate::errors::DexError;
-use crate::events::MarketPauseUpdated;
+use crate::events::{event_sink, MarketPauseUpdated};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(paused: bool)]
 pub struct PauseMarket<'info> {
@@ -11,7 +12,8 @@ pub struct PauseMarket<'info> {
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
         bump = market.bump,
         constraint = authority.key() == market.authority || 
-                     authority.key() == global_config.authority @ DexError::Unauthorized
+                     authority.key() == global_config.authority ||
+                     market.is_operator(&authority.key(), Market::OPERATOR_CAN_PAUSE) @ DexError::Unauthorized
     )]
     pub market: Account<'info, Market>,
     
@@ -25,14 +27,15 @@ pub struct PauseMarket<'info> {
 }
 
 pub fn handler(ctx: Context<PauseMarket>, paused: bool) -> Result<()> {
+    let sink = event_sink!(ctx);
     let market = &mut ctx.accounts.market;
     market.paused = paused;
     
-    emit!(MarketPauseUpdated {
+    sink.emit(MarketPauseUpdated {
         market: market.key(),
         paused,
         timestamp: Clock::get()?.unix_timestamp,
-    });
+    })?;
     
     msg!("Market {}: market={}", if paused { "paused" } else { "unpaused" }, market.key());
     
@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, OpenOrders, Seat, TraderState};
+use crate::orderbook::{Order, Orderbook, Side, TimeInForce};
+use crate::errors::DexError;
+use crate::events::{event_sink, MultipleOrdersPlaced, OrderPlaced};
+use crate::oracle::{is_within_oracle_band, load_band_price};
+
+/// Maximum number of orders per place_multiple_orders call
+pub const MAX_MULTIPLE_ORDERS: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OrderEntry {
+    pub side: u8, // 0 = bid, 1 = ask
+    pub price: u64,
+    pub size: u64,
+    pub client_order_id: u64, // Caller-chosen ID, 0 = none
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PlaceMultipleOrdersParams {
+    pub orders: Vec<OrderEntry>,
+    pub expiry_timestamp: i64, // Good-till-date expiry for every order (0 = none)
+    pub origin: u32, // Origin tag for surveillance, as in place_order
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct PlaceMultipleOrders<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    pub trader: Signer<'info>,
+    
+    /// CHECK: Market oracle, required when the market enforces an oracle band
+    pub oracle: Option<UncheckedAccount<'info>>,
+    
+    /// Slot index of the trader's orders; records every posted order
+    #[account(
+        mut,
+        seeds = [b"open_orders", trader_state.key().as_ref()],
+        bump = open_orders.bump
+    )]
+    pub open_orders: Option<Account<'info, OpenOrders>>,
+    
+    /// Trading seat, required when the market is permissioned
+    #[account(
+        seeds = [b"seat", market.key().as_ref(), trader.key().as_ref()],
+        bump = seat.bump
+    )]
+    pub seat: Option<Account<'info, Seat>>,
+}
+
+/// Post up to `MAX_MULTIPLE_ORDERS` resting orders with one book load
+///
+/// Every entry is a post-only order: it must not cross the book, including
+/// the entries posted before it. Funds for the whole batch are locked at once
+/// and the call fails as a unit if any entry is invalid.
+pub fn handler(ctx: Context<PlaceMultipleOrders>, params: PlaceMultipleOrdersParams) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    
+    require!(!market.paused, DexError::MarketPaused);
+    require!(market.accepts_new_orders(), DexError::MarketCancelOnly);
+    require!(!market.is_halted(clock.unix_timestamp), DexError::MarketHalted);
+    require!(!market.requires_seat || ctx.accounts.seat.is_some(), DexError::SeatRequired);
+    require!(
+        !params.orders.is_empty() && params.orders.len() <= MAX_MULTIPLE_ORDERS,
+        DexError::InvalidOrderParams
+    );
+    require!(
+        params.expiry_timestamp == 0 || params.expiry_timestamp > clock.unix_timestamp,
+        DexError::InvalidOrderParams
+    );
+    require!(Order::is_valid_origin(params.origin), DexError::InvalidOrderParams);
+    
+    // Validate every entry and total the funds to lock
+    let band_price = load_band_price(market, ctx.accounts.oracle.as_deref(), clock.unix_timestamp)?;
+    let mut entries = Vec::with_capacity(params.orders.len());
+    let mut base_required = 0u64;
+    let mut quote_required = 0u64;
+    for entry in params.orders.iter() {
+        let side = Side::from_u8(entry.side).ok_or(DexError::InvalidOrderParams)?;
+        require!(market.is_valid_tick(entry.price), DexError::PriceNotOnTick);
+        require!(market.is_within_peg_band(entry.price), DexError::PriceOutsidePegBand);
+        require!(
+            is_within_oracle_band(entry.price, band_price),
+            DexError::OraclePriceDeviationTooLarge
+        );
+        require!(market.is_valid_lot(entry.size), DexError::OrderSizeTooSmall);
+        require!(
+            entry.size <= 1_000_000_000_000, // Reasonable upper bound
+            DexError::OrderSizeTooLarge
+        );
+        
+        let notional = entry.price
+            .checked_mul(entry.size)
+            .and_then(|v| v.checked_div(market.lot_size))
+            .ok_or(DexError::MathOverflow)?;
+        market.check_order_notional(notional as u128)?;
+        
+        if side == Side::Bid {
+            quote_required = quote_required
+                .checked_add(notional)
+                .ok_or(DexError::MathOverflow)?;
+        } else {
+            base_required = base_required
+                .checked_add(entry.size)
+                .ok_or(DexError::MathOverflow)?;
+        }
+        entries.push((side, entry));
+    }
+    
+    let trader_state = &mut ctx.accounts.trader_state;
+    require!(!trader_state.is_locked(clock.unix_timestamp), DexError::AccountLocked);
+    require!(
+        !trader_state.exceeds_cancel_ratio(market.max_cancel_to_fill_ratio, clock.unix_timestamp),
+        DexError::CancelRatioExceeded
+    );
+    trader_state.lock_quote(quote_required)?;
+    trader_state.lock_base(base_required)?;
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    
+    for (index, (side, entry)) in entries.iter().enumerate() {
+        // Post-only against the book as it stands, earlier entries included
+        if *side == Side::Bid {
+            require!(
+                orderbook.best_ask == 0 || entry.price < orderbook.best_ask,
+                DexError::PostOnlyWouldCross
+            );
+        } else {
+            require!(
+                orderbook.best_bid == 0 || entry.price > orderbook.best_bid,
+                DexError::PostOnlyWouldCross
+            );
+        }
+        
+        let order_id = market.order_id_at(&market.key(), index as u64)?;
+        let mut order = Order::new(
+            order_id,
+            trader_state.trader,
+            *side,
+            entry.price,
+            entry.size,
+            TimeInForce::PostOnly,
+            &clock,
+        );
+        order.client_order_id = entry.client_order_id;
+        order.expiry_timestamp = params.expiry_timestamp;
+        order.origin = params.origin;
+        order.deposit_lamports = market.placement_deposit_lamports;
+        trader_state.hold_deposit(order.deposit_lamports as u64)?;
+        let slot = orderbook.insert_order(&mut orderbook_data, &order)?;
+        
+        if let Some(open_orders) = ctx.accounts.open_orders.as_mut() {
+            if !open_orders.insert(&orderbook, &orderbook_data, order_id, slot) {
+                msg!("Open orders full; order {} not indexed", order_id);
+            }
+        }
+        
+        if market.logs_l3() {
+            sink.emit(OrderPlaced {
+                market: market.key(),
+                trader: order.trader,
+                order_id,
+                client_order_id: entry.client_order_id,
+                side: entry.side,
+                price: entry.price,
+                size: entry.size,
+                time_in_force: TimeInForce::PostOnly as u8,
+                origin: params.origin,
+                timestamp: clock.unix_timestamp,
+            })?;
+        }
+    }
+    market.check_exposure(&orderbook, Side::Bid)?;
+    market.check_exposure(&orderbook, Side::Ask)?;
+    
+    trader_state.open_order_count = trader_state.open_order_count
+        .checked_add(entries.len() as u16)
+        .ok_or(DexError::MathOverflow)?;
+    
+    // Save orderbook
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    
+    let market_key = market.key();
+    let market_mut = &mut ctx.accounts.market;
+    market_mut.sync_book(&orderbook, &orderbook_data, &sink)?;
+    market_mut.advance_order_sequence(entries.len() as u64)?;
+    
+    sink.emit(MultipleOrdersPlaced {
+        market: market_key,
+        trader: ctx.accounts.trader.key(),
+        count: entries.len() as u8,
+        base_locked: base_required,
+        quote_locked: quote_required,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Multiple orders placed: market={}, count={}, base_locked={}, quote_locked={}",
+         market_key, entries.len(), base_required, quote_required);
+    
+    Ok(())
+}
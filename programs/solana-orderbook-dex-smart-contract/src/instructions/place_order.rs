@@ -1,9 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
-use crate::state::{Market, TraderState, Orderbook};
-use crate::orderbook::{Order, Side, TimeInForce};
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::{GlobalConfig, IntegratorFees, Market, OpenOrders, Seat, TraderState};
+use crate::orderbook::{Order, Orderbook, Side, TimeInForce};
 use crate::errors::DexError;
-use crate::events::OrderPlaced;
+use crate::event_queue::{EventQueue, QueueEvent};
+use crate::events::{event_sink, BookImpactGuarded, EventSink, OrderCancelled, OrderPlaced};
+use crate::fee_pass::load_fee_pass_discount;
+use crate::instructions::cancel_order::queue_removed_orders;
+use crate::matching::{fillable_size, match_taker_order, walk_taker_order, TakerLimits};
+use crate::oracle::{deviation_bps, is_within_oracle_band, load_band_price};
+use crate::trade_tape::{record_trades, TradeRecord};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct PlaceOrderParams {
@@ -11,12 +18,49 @@ pub struct PlaceOrderParams {
     pub price: u64,
     pub size: u64,
     pub time_in_force: u8, // 0 = GTC, 1 = IOC, 2 = FOK, 3 = PostOnly
+    pub client_order_id: u64, // Caller-chosen ID, 0 = none
+    pub dry_run: bool, // Validate and match without committing; result in return data
+    pub price_ui: Option<u64>, // Alternative to price: quote per base token, 9 decimals (price must be 0)
+    pub size_ui: Option<u64>, // Alternative to size: base tokens, 9 decimals (size must be 0)
+    pub oracle_band_bps: u16, // Cancel if the oracle moves further than this from the price (0 = off)
+    pub expiry_timestamp: i64, // Good-till-date expiry (0 = none, GTC/PostOnly only)
+    pub quote_budget: Option<u64>, // IOC bids only: spend up to this much quote, price = worst price (size must be 0)
+    pub max_book_impact_bps: u16, // IOC only: stop filling this far from the pre-trade mid (0 = off)
+    pub quote_proceeds: Option<u64>, // Asks only: sell enough base to raise this much quote at the limit price (size must be 0)
+    pub integrator_fee_bps: u16, // Share of the taker fee routed to the integrator account (0 = none)
+    pub origin: u32, // Origin tag for surveillance: 0 = none, 1 = UI, 2 = API, >= 256 = CPI program tag
+    pub reduce_only: bool, // Only decrease exposure; the size is trimmed to what can be reduced
 }
 
+/// A fill projected by a dry run
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PreviewFill {
+    pub maker_order_id: u128,
+    pub price: u64,
+    pub size: u64,
+}
+
+/// Projected outcome of a dry-run order, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OrderPreview {
+    pub fills: Vec<PreviewFill>,
+    pub filled_size: u64,
+    pub quote_amount: u64,
+    pub fee: u64, // Bids: base units, asks: quote units
+    pub resting_size: u64, // Remainder that would rest on the book
+    pub cancelled_size: u64, // IOC/FOK remainder that would be cancelled
+    pub base_available: u64,
+    pub base_locked: u64,
+    pub quote_available: u64,
+    pub quote_locked: u64,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(params: PlaceOrderParams)]
 pub struct PlaceOrder<'info> {
     #[account(
+        mut,
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
         bump = market.bump
     )]
@@ -26,7 +70,22 @@ pub struct PlaceOrder<'info> {
     #[account(mut)]
     pub orderbook: UncheckedAccount<'info>,
     
+    /// CHECK: Event queue account (must be the market's)
+    #[account(mut, address = market.event_queue @ DexError::InvalidAccountState)]
+    pub event_queue: UncheckedAccount<'info>,
+    
+    /// CHECK: Trade tape, required when the market has one
+    #[account(mut)]
+    pub trade_tape: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
     #[account(
+        mut,
         seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
         bump = trader_state.bump
     )]
@@ -35,15 +94,98 @@ pub struct PlaceOrder<'info> {
     #[account(mut)]
     pub trader: Signer<'info>,
     
+    /// CHECK: Market oracle, required when the market enforces an oracle band
+    pub oracle: Option<UncheckedAccount<'info>>,
+    
+    /// Slot index of the trader's orders; records the order if it rests
+    #[account(
+        mut,
+        seeds = [b"open_orders", trader_state.key().as_ref()],
+        bump = open_orders.bump
+    )]
+    pub open_orders: Option<Account<'info, OpenOrders>>,
+    
+    /// Integrator (wallet, frontend) routing the order; earns a share of its taker fees
+    #[account(mut, has_one = market @ DexError::InvalidAccountState)]
+    pub integrator_fees: Option<Account<'info, IntegratorFees>>,
+    
+    /// Trading seat, required when the market is permissioned
+    #[account(
+        seeds = [b"seat", market.key().as_ref(), trader.key().as_ref()],
+        bump = seat.bump
+    )]
+    pub seat: Option<Account<'info, Seat>>,
+    
+    /// Fee-discount pass: the trader's token account holding the market's pass token or NFT
+    pub fee_pass: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Metaplex metadata of the pass NFT, required when the pass is a collection
+    pub fee_pass_metadata: Option<UncheckedAccount<'info>>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
+/// Accounts an order is placed with, borrowed from place_order or deposit_and_place
+pub struct PlaceOrderAccounts<'a, 'info> {
+    pub market: &'a mut Account<'info, Market>,
+    pub orderbook: &'a UncheckedAccount<'info>,
+    pub event_queue: &'a UncheckedAccount<'info>,
+    pub trade_tape: Option<&'a AccountInfo<'info>>,
+    pub global_config: &'a Account<'info, GlobalConfig>,
+    pub trader_state: &'a mut Account<'info, TraderState>,
+    pub trader: &'a Signer<'info>,
+    pub oracle: Option<&'a AccountInfo<'info>>,
+    pub open_orders: Option<&'a mut Account<'info, OpenOrders>>,
+    pub integrator_fees: Option<&'a mut Account<'info, IntegratorFees>>,
+    pub seat: Option<&'a Account<'info, Seat>>,
+    pub fee_pass: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub fee_pass_metadata: Option<&'a AccountInfo<'info>>,
+}
+
 pub fn handler(ctx: Context<PlaceOrder>, params: PlaceOrderParams) -> Result<()> {
-    let market = &ctx.accounts.market;
+    let sink = event_sink!(ctx);
+    let accounts = &mut *ctx.accounts;
+    place(
+        PlaceOrderAccounts {
+            market: &mut accounts.market,
+            orderbook: &accounts.orderbook,
+            event_queue: &accounts.event_queue,
+            trade_tape: accounts.trade_tape.as_deref(),
+            global_config: &accounts.global_config,
+            trader_state: &mut accounts.trader_state,
+            trader: &accounts.trader,
+            oracle: accounts.oracle.as_deref(),
+            open_orders: accounts.open_orders.as_mut(),
+            integrator_fees: accounts.integrator_fees.as_mut(),
+            seat: accounts.seat.as_ref(),
+            fee_pass: accounts.fee_pass.as_ref(),
+            fee_pass_metadata: accounts.fee_pass_metadata.as_deref(),
+        },
+        params,
+        &sink,
+    )
+}
+
+/// Validate, lock funds for, match and rest (or cancel) an order
+pub fn place(mut accounts: PlaceOrderAccounts, mut params: PlaceOrderParams, sink: &EventSink) -> Result<()> {
+    let market = &*accounts.market;
+    
+    // Convert UI units to ticks/lots using the market's recorded decimals
+    if let Some(price_ui) = params.price_ui {
+        require!(params.price == 0, DexError::InvalidOrderParams);
+        params.price = market.price_from_ui(price_ui)?;
+    }
+    if let Some(size_ui) = params.size_ui {
+        require!(params.size == 0, DexError::InvalidOrderParams);
+        params.size = market.size_from_ui(size_ui)?;
+    }
     
     // Check if market is paused
     require!(!market.paused, DexError::MarketPaused);
+    require!(market.accepts_new_orders(), DexError::MarketCancelOnly);
+    require!(!market.is_halted(Clock::get()?.unix_timestamp), DexError::MarketHalted);
+    require!(!market.requires_seat || accounts.seat.is_some(), DexError::SeatRequired);
     
     // Validate side
     let side = Side::from_u8(params.side)
@@ -53,12 +195,112 @@ pub fn handler(ctx: Context<PlaceOrder>, params: PlaceOrderParams) -> Result<()>
     let tif = TimeInForce::from_u8(params.time_in_force)
         .ok_or(DexError::InvalidTimeInForce)?;
     
+    // Market buys by quote amount: lock the budget and cap the size at what it
+    // could buy at one tick, the lowest possible price
+    if let Some(quote_budget) = params.quote_budget {
+        require!(
+            side == Side::Bid && tif == TimeInForce::IOC && params.size == 0 && quote_budget > 0,
+            DexError::InvalidOrderParams
+        );
+        let max_size = (quote_budget as u128)
+            .checked_mul(market.lot_size as u128)
+            .and_then(|v| v.checked_div(market.tick_size as u128))
+            .ok_or(DexError::MathOverflow)?
+            .min(1_000_000_000_000) as u64;
+        params.size = max_size - max_size % market.lot_size;
+    }
+    
+    // Sell-to-quote: size the ask so that it raises at least the target at its
+    // limit price (before fees), rounding up to whole lots
+    if let Some(quote_proceeds) = params.quote_proceeds {
+        require!(
+            side == Side::Ask && params.size == 0 && quote_proceeds > 0 && params.price > 0,
+            DexError::InvalidOrderParams
+        );
+        let lots = (quote_proceeds as u128)
+            .checked_mul(market.lot_size as u128)
+            .map(|v| v.div_ceil(params.price as u128))
+            .map(|v| v.div_ceil(market.lot_size as u128))
+            .ok_or(DexError::MathOverflow)?;
+        params.size = lots
+            .checked_mul(market.lot_size as u128)
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(DexError::OrderSizeTooLarge)?;
+    }
+    
+    // Reduce-only orders are trimmed to the exposure they can close
+    if params.reduce_only {
+        params.size = accounts.trader_state.reduce_only_size(side, params.size, market.lot_size)?;
+    }
+    
+    // Markets that defer matching to the crank cannot honour immediate-only orders
+    if market.min_resting_slots > 0 || market.batch_matching {
+        require!(
+            tif == TimeInForce::GTC || tif == TimeInForce::PostOnly,
+            DexError::InvalidTimeInForce
+        );
+    }
+    
     // Validate price is on tick
     require!(market.is_valid_tick(params.price), DexError::PriceNotOnTick);
     
+    // Validate price is inside the peg band (stable-pair markets)
+    require!(market.is_within_peg_band(params.price), DexError::PriceOutsidePegBand);
+    
+    // Validate price is inside the oracle band (markets with an oracle deviation limit)
+    let band_price = load_band_price(market, accounts.oracle, Clock::get()?.unix_timestamp)?;
+    require!(
+        is_within_oracle_band(params.price, band_price),
+        DexError::OraclePriceDeviationTooLarge
+    );
+    
     // Validate size is valid lot
     require!(market.is_valid_lot(params.size), DexError::OrderSizeTooSmall);
     
+    // Good-till-date orders must expire in the future and be able to rest
+    if params.expiry_timestamp != 0 {
+        require!(
+            tif == TimeInForce::GTC || tif == TimeInForce::PostOnly,
+            DexError::InvalidTimeInForce
+        );
+        require!(
+            params.expiry_timestamp > Clock::get()?.unix_timestamp,
+            DexError::InvalidOrderParams
+        );
+    }
+    
+    // The book impact guard cancels what it cannot fill, so it only fits IOC orders
+    if params.max_book_impact_bps > 0 {
+        require!(tif == TimeInForce::IOC, DexError::InvalidTimeInForce);
+        require!(params.max_book_impact_bps <= 10_000, DexError::InvalidOrderParams);
+    }
+    
+    // Oracle protection needs an oracle to compare against
+    if params.oracle_band_bps > 0 {
+        require!(market.has_oracle(), DexError::OraclePriceNotAvailable);
+        require!(params.oracle_band_bps <= 10_000, DexError::InvalidOrderParams);
+    }
+    
+    // Integrator shares need an integrator to credit and stay under the protocol cap
+    if params.integrator_fee_bps > 0 {
+        require!(accounts.integrator_fees.is_some(), DexError::InvalidAccountState);
+        require!(
+            params.integrator_fee_bps <= accounts.global_config.max_integrator_fee_bps,
+            DexError::InvalidFeeCalculation
+        );
+    }
+    
+    require!(Order::is_valid_origin(params.origin), DexError::InvalidOrderParams);
+    
+    // Holders of the market's fee-discount pass take liquidity at a lower fee
+    let discount_bps = load_fee_pass_discount(
+        market,
+        &accounts.trader.key(),
+        accounts.fee_pass,
+        accounts.fee_pass_metadata,
+    )?;
+    let taker_fee_bps = accounts.global_config.discounted_taker_fee_bps(discount_bps, params.integrator_fee_bps);
+    
     // Validate size bounds
     require!(params.size >= market.lot_size, DexError::OrderSizeTooSmall);
     require!(
@@ -66,17 +308,26 @@ pub fn handler(ctx: Context<PlaceOrder>, params: PlaceOrderParams) -> Result<()>
         DexError::OrderSizeTooLarge
     );
     
+    // Validate order notional against the market's minimum and risk limit
+    let notional = match params.quote_budget {
+        Some(quote_budget) => quote_budget as u128,
+        None => (params.price as u128)
+            .checked_mul(params.size as u128)
+            .and_then(|v| v.checked_div(market.lot_size as u128))
+            .ok_or(DexError::MathOverflow)?,
+    };
+    market.check_order_notional(notional)?;
+    
     // Load orderbook
-    let orderbook_account_info = &ctx.accounts.orderbook;
+    let orderbook_account_info = &accounts.orderbook;
     require!(
         orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
         DexError::InvalidOrderbookState
     );
     
     let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
-    let orderbook = Account::<Orderbook>::try_deserialize(
-        &mut &orderbook_account_info.data.borrow()[..Orderbook::HEADER_SIZE]
-    )?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
     
     // Check if order would cross spread (for PostOnly)
     if tif == TimeInForce::PostOnly {
@@ -88,15 +339,51 @@ pub fn handler(ctx: Context<PlaceOrder>, params: PlaceOrderParams) -> Result<()>
         }
     }
     
+    // Worst fill price allowed by the book impact guard, measured from the pre-trade mid
+    let mut mid_price = 0;
+    let mut impact_price = None;
+    if params.max_book_impact_bps > 0 {
+        require!(orderbook.best_bid > 0 && orderbook.best_ask > 0, DexError::InsufficientLiquidity);
+        mid_price = ((orderbook.best_bid as u128 + orderbook.best_ask as u128) / 2) as u64;
+        let band = (mid_price as u128 * params.max_book_impact_bps as u128 / 10_000) as u64;
+        impact_price = Some(if side == Side::Bid {
+            mid_price.saturating_add(band)
+        } else {
+            mid_price - band
+        });
+    }
+    
     // Calculate required tokens and lock them
-    let mut trader_state = ctx.accounts.trader_state.clone();
+    // (dry runs work on a copy of the trader state and only read the book, so nothing is committed)
+    let mut simulated_state;
+    let trader_state: &mut TraderState = if params.dry_run {
+        simulated_state = (**accounts.trader_state).clone();
+        &mut simulated_state
+    } else {
+        accounts.trader_state
+    };
+    require!(
+        !trader_state.is_locked(Clock::get()?.unix_timestamp),
+        DexError::AccountLocked
+    );
+    
+    // Orders that can rest are quotes; a trader flickering them is throttled
+    // until it fills or its window rolls over (taking liquidity stays open)
+    let may_rest = tif == TimeInForce::GTC || tif == TimeInForce::PostOnly;
+    require!(
+        !may_rest || !trader_state.exceeds_cancel_ratio(market.max_cancel_to_fill_ratio, Clock::get()?.unix_timestamp),
+        DexError::CancelRatioExceeded
+    );
     
     if side == Side::Bid {
-        // Bids need quote tokens: price * size
-        let quote_required = params.price
-            .checked_mul(params.size)
-            .and_then(|v| v.checked_div(market.lot_size))
-            .ok_or(DexError::MathOverflow)?;
+        // Bids need quote tokens: price * size, or the whole budget for market buys
+        let quote_required = match params.quote_budget {
+            Some(quote_budget) => quote_budget,
+            None => params.price
+                .checked_mul(params.size)
+                .and_then(|v| v.checked_div(market.lot_size))
+                .ok_or(DexError::MathOverflow)?,
+        };
         
         trader_state.lock_quote(quote_required)?;
     } else {
@@ -104,76 +391,289 @@ pub fn handler(ctx: Context<PlaceOrder>, params: PlaceOrderParams) -> Result<()>
         trader_state.lock_base(params.size)?;
     }
     
-    // Generate order ID (in production, use a more sophisticated method)
+    // Order IDs are bound to this market and book generation
     let clock = Clock::get()?;
-    let order_id = u128::from(clock.unix_timestamp)
-        .checked_mul(1_000_000)
-        .and_then(|v| v.checked_add(u128::from(clock.slot)))
-        .ok_or(DexError::MathOverflow)?;
+    let order_id = market.order_id_at(&market.key(), 0)?;
     
     // Create order
-    let order = Order::new(
+    let mut order = Order::new(
         order_id,
-        ctx.accounts.trader.key(),
+        accounts.trader.key(),
         side,
         params.price,
         params.size,
         tif,
-        clock.unix_timestamp,
+        &clock,
     );
+    order.client_order_id = params.client_order_id;
+    order.oracle_band_bps = params.oracle_band_bps;
+    order.expiry_timestamp = params.expiry_timestamp;
+    order.origin = params.origin;
+    if params.reduce_only {
+        order.flags |= Order::FLAG_REDUCE_ONLY;
+    }
     
-    // Allocate slot in orderbook
-    let mut orderbook_mut = Account::<Orderbook>::try_deserialize(
-        &mut &orderbook_account_info.data.borrow()[..Orderbook::HEADER_SIZE]
-    )?;
+    if !params.dry_run && market.logs_l3() {
+        sink.emit(OrderPlaced {
+            market: market.key(),
+            trader: accounts.trader.key(),
+            order_id,
+            client_order_id: params.client_order_id,
+            side: params.side,
+            price: params.price,
+            size: params.size,
+            time_in_force: params.time_in_force,
+            origin: params.origin,
+            timestamp: clock.unix_timestamp,
+        })?;
+        
+        msg!("Order placed: id={}, side={:?}, price={}, size={}", 
+             order_id, side, params.price, params.size);
+    }
     
-    let slot = orderbook_mut.allocate_slot(&mut orderbook_data)?;
-    orderbook_mut.set_order(&mut orderbook_data, slot, &order)?;
+    // Take liquidity immediately if the order is marketable
+    // (skipped on speed-bump and batch markets, where orders rest until the crank)
+    let mut base_fees = 0u64;
+    let mut quote_fees = 0u64;
+    let mut base_rebates = 0u64;
+    let mut quote_rebates = 0u64;
+    let mut quote_filled = 0u64;
+    let mut preview_fills = Vec::new();
+    let mut worst_fill_price = 0;
+    let mut first_fill_price = 0;
+    let mut last_fill_size = 0;
+    let mut breaker_price = None;
+    let mut crossed = false;
+    let mut fill_count = 0u64;
+    let limits = TakerLimits {
+        quote_budget: params.quote_budget,
+        impact_price,
+        band_price,
+        taker_fee_bps: Some(taker_fee_bps),
+        ..TakerLimits::default()
+    };
     
-    // Update orderbook metadata
-    orderbook_mut.order_count = orderbook_mut.order_count
-        .checked_add(1)
-        .ok_or(DexError::MathOverflow)?;
-    orderbook_mut.update_best_prices(&orderbook_data);
-    orderbook_mut.market = market.key();
+    // Fill-or-kill: cancel outright unless the whole size can fill now
+    let fok_killed = tif == TimeInForce::FOK &&
+        fillable_size(&orderbook, &orderbook_data, &order, market, accounts.global_config, limits)? < order.size;
+    if fok_killed && !params.dry_run {
+        msg!("Fill-or-kill order {} killed: insufficient liquidity for size {}", order_id, order.size);
+    }
     
-    // Save orderbook
-    orderbook_mut.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    if tif != TimeInForce::PostOnly && market.min_resting_slots == 0 && !market.batch_matching && !fok_killed {
+        let taker_match = if params.dry_run {
+            walk_taker_order(&orderbook, &orderbook_data, &mut order, market, accounts.global_config, limits)?
+        } else {
+            match_taker_order(&mut orderbook, &mut orderbook_data, &mut order, market, accounts.global_config, limits)?
+        };
+        quote_filled = taker_match.quote_amount;
+        fill_count = taker_match.fill_count as u64;
+        breaker_price = taker_match.breaker_price;
+        crossed = taker_match.crossed;
+        
+        // Apply the taker side now; makers are settled by consume_events
+        if !taker_match.events.is_empty() || !taker_match.expired.is_empty() {
+            let mut event_queue_data = accounts.event_queue.try_borrow_mut_data()?;
+            let mut event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE])?;
+            
+            first_fill_price = taker_match.events.first().map_or(0, |event| event.price);
+            worst_fill_price = taker_match.events.last().map_or(0, |event| event.price);
+            last_fill_size = taker_match.events.last().map_or(0, |event| event.size);
+            let trades: Vec<TradeRecord> = taker_match.events.iter()
+                .map(|event| TradeRecord::from_fill(event, Some(side)))
+                .collect();
+            for mut event in taker_match.events {
+                if params.dry_run {
+                    preview_fills.push(PreviewFill {
+                        maker_order_id: if side == Side::Bid { event.ask_order_id } else { event.bid_order_id },
+                        price: event.price,
+                        size: event.size,
+                    });
+                }
+                
+                if params.quote_budget.is_some() {
+                    event.bid_price = event.price; // Budget orders lock exactly what they pay
+                }
+                
+                if side == Side::Bid {
+                    event.flags &= !QueueEvent::BID_DONE; // The taker never rested
+                    let (fee, _) = event.apply_bid(trader_state, market.lot_size)?; // Takers earn no rebate
+                    base_fees = base_fees
+                        .checked_add(fee)
+                        .ok_or(DexError::MathOverflow)?;
+                    base_rebates = base_rebates
+                        .checked_add(event.ask_fee(market.lot_size)?.1)
+                        .ok_or(DexError::MathOverflow)?;
+                } else {
+                    event.flags &= !QueueEvent::ASK_DONE;
+                    let (fee, _) = event.apply_ask(trader_state, market.lot_size)?;
+                    quote_fees = quote_fees
+                        .checked_add(fee)
+                        .ok_or(DexError::MathOverflow)?;
+                    quote_rebates = quote_rebates
+                        .checked_add(event.bid_fee(market.lot_size)?.1)
+                        .ok_or(DexError::MathOverflow)?;
+                }
+                let quote_amount = event.quote_amount(market.lot_size)?;
+                let points = market.points_for(quote_amount, None, event.timestamp);
+                trader_state.points = trader_state.points.saturating_add(points);
+                trader_state.record_competition_volume(market, quote_amount, event.timestamp);
+                if !params.dry_run {
+                    event_queue.push(&mut event_queue_data, event)?;
+                }
+            }
+            
+            // Expired makers met on the way, and makers left with dust, are
+            // released like crank cancellations
+            if !params.dry_run {
+                for removed in [&taker_match.expired, &taker_match.dust] {
+                    queue_removed_orders(
+                        market,
+                        &mut event_queue,
+                        &mut event_queue_data,
+                        removed,
+                        clock.unix_timestamp,
+                        sink,
+                    )?;
+                }
+            }
+            
+            if !params.dry_run {
+                event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE])?;
+                record_trades(market, accounts.trade_tape, &trades)?;
+                for matched in taker_match.matched {
+                    sink.emit(matched)?;
+                }
+                for filled in taker_match.filled {
+                    sink.emit(filled)?;
+                }
+            }
+        }
+        
+        if taker_match.fill_count > 0 && !params.dry_run {
+            msg!("Order matched on placement: filled={}, fills={}", 
+                 taker_match.filled_size, taker_match.fill_count);
+        }
+    }
     
-    // Update trader state
-    ctx.accounts.trader_state.base_available = trader_state.base_available;
-    ctx.accounts.trader_state.quote_available = trader_state.quote_available;
-    ctx.accounts.trader_state.base_locked = trader_state.base_locked;
-    ctx.accounts.trader_state.quote_locked = trader_state.quote_locked;
-    ctx.accounts.trader_state.open_order_count = ctx.accounts.trader_state.open_order_count
-        .checked_add(1)
-        .ok_or(DexError::MathOverflow)? as u16;
+    // Return the unspent part of a market buy's budget
+    if let Some(quote_budget) = params.quote_budget {
+        trader_state.unlock_quote(quote_budget - quote_filled)?;
+    }
     
-    // Update market
-    let mut market_mut = ctx.accounts.market.as_mut();
-    market_mut.best_bid = orderbook_mut.best_bid;
-    market_mut.best_ask = orderbook_mut.best_ask;
-    market_mut.order_count = orderbook_mut.order_count;
-    
-    emit!(OrderPlaced {
-        market: market.key(),
-        trader: ctx.accounts.trader.key(),
-        order_id,
-        side: params.side,
-        price: params.price,
-        size: params.size,
-        time_in_force: params.time_in_force,
-        timestamp: clock.unix_timestamp,
-    });
+    if order.is_filled() {
+        // Fully filled as taker, nothing to rest on the book
+    } else if tif == TimeInForce::IOC || tif == TimeInForce::FOK || crossed || market.is_dust(&order) {
+        // Cancel the unfilled IOC/FOK remainder and release its locked funds
+        // (a remainder stopped at the trader's own order, the oracle band, the circuit
+        // breaker or the fill cap is still marketable, so it cannot rest without crossing
+        // the book, and a remainder below the dust threshold is not worth resting)
+        if params.quote_budget.is_some() {
+            // Unspent budget already released above
+        } else if order.is_bid() {
+            let quote_unlocked = order.price
+                .checked_mul(order.remaining_size)
+                .and_then(|v| v.checked_div(market.lot_size))
+                .ok_or(DexError::MathOverflow)?;
+            trader_state.unlock_quote(quote_unlocked)?;
+        } else {
+            trader_state.unlock_base(order.remaining_size)?;
+        }
+        
+        if !params.dry_run && market.logs_l3() {
+            sink.emit(OrderCancelled {
+                market: market.key(),
+                trader: order.trader,
+                order_id,
+                client_order_id: order.client_order_id,
+                remaining_size: order.remaining_size,
+                timestamp: clock.unix_timestamp,
+            })?;
+        }
+    } else if !params.dry_run {
+        // Rest the remainder on the book (a dry run only reports the resting size)
+        order.deposit_lamports = market.placement_deposit_lamports;
+        trader_state.hold_deposit(order.deposit_lamports as u64)?;
+        let slot = orderbook.insert_order(&mut orderbook_data, &order)?;
+        market.check_exposure(&orderbook, side)?;
+        
+        // A full index only costs this order its fast cancel path
+        if let Some(open_orders) = accounts.open_orders.as_mut() {
+            if !open_orders.insert(&orderbook, &orderbook_data, order_id, slot) {
+                msg!("Open orders full; order {} not indexed", order_id);
+            }
+        }
+        
+        trader_state.open_order_count = trader_state.open_order_count
+            .checked_add(1)
+            .ok_or(DexError::MathOverflow)?;
+    }
+    
+    if impact_price.is_some() && !params.dry_run {
+        sink.emit(BookImpactGuarded {
+            market: market.key(),
+            trader: order.trader,
+            order_id,
+            mid_price,
+            max_book_impact_bps: params.max_book_impact_bps,
+            worst_fill_price,
+            realized_impact_bps: if worst_fill_price > 0 { deviation_bps(mid_price, worst_fill_price) } else { 0 },
+            filled_size: order.size - order.remaining_size,
+            cancelled_size: order.remaining_size,
+            timestamp: clock.unix_timestamp,
+        })?;
+    }
+    
+    if params.dry_run {
+        let filled_size = order.size - order.remaining_size;
+        let resting = !order.is_filled() &&
+            (tif == TimeInForce::GTC || tif == TimeInForce::PostOnly) &&
+            !crossed &&
+            !market.is_dust(&order);
+        let preview = OrderPreview {
+            fills: preview_fills,
+            filled_size,
+            quote_amount: quote_filled,
+            fee: if side == Side::Bid { base_fees } else { quote_fees },
+            resting_size: if resting { order.remaining_size } else { 0 },
+            cancelled_size: if resting { 0 } else { order.remaining_size },
+            base_available: trader_state.base_available,
+            base_locked: trader_state.base_locked,
+            quote_available: trader_state.quote_available,
+            quote_locked: trader_state.quote_locked,
+        };
+        anchor_lang::solana_program::program::set_return_data(&preview.try_to_vec()?);
+        
+        msg!("Dry run: filled={}, resting={}", filled_size, preview.resting_size);
+        return Ok(());
+    }
     
-    msg!("Order placed: id={}, side={:?}, price={}, size={}", 
-         order_id, side, params.price, params.size);
+    // Save orderbook
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    
+    // Update market
+    let market_mut = &mut accounts.market;
+    market_mut.sync_book(&orderbook, &orderbook_data, sink)?;
+    market_mut.record_fills(quote_filled, fill_count, first_fill_price, worst_fill_price, last_fill_size, clock.unix_timestamp)?;
+    market_mut.advance_order_sequence(1)?;
+    if let Some(price) = breaker_price {
+        let market_key = market_mut.key();
+        market_mut.trip_breaker(market_key, price, clock.unix_timestamp, sink)?;
+    }
     
-    // If IOC or FOK, try to match immediately
-    if tif == TimeInForce::IOC || tif == TimeInForce::FOK {
-        // In a full implementation, we'd call match_orders here
-        // For now, we'll let the match_orders instruction handle it
+    // Carve the integrator's share out of the taker fees before the protocol accrues them
+    if let Some(integrator_fees) = accounts.integrator_fees.as_mut() {
+        let fee = if side == Side::Bid { &mut base_fees } else { &mut quote_fees };
+        *fee -= integrator_fees.accrue_share(*fee, params.integrator_fee_bps, side)?;
     }
+    market_mut.base_fees_accrued = market_mut.base_fees_accrued
+        .checked_add(base_fees)
+        .ok_or(DexError::MathOverflow)?;
+    market_mut.quote_fees_accrued = market_mut.quote_fees_accrued
+        .checked_add(quote_fees)
+        .ok_or(DexError::MathOverflow)?;
+    // The makers' rebates are paid out of these fees when consume_events settles them
+    market_mut.reserve_rebates(base_rebates, quote_rebates)?;
     
     Ok(())
 }
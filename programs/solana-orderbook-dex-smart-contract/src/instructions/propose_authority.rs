@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::GlobalConfig;
+use crate::errors::DexError;
+use crate::events::{event_sink, AuthorityProposed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Propose `new_authority` as the next protocol authority
+///
+/// Nothing changes hands until the proposed key signs accept_authority, so a
+/// mistyped key can't brick the protocol. Proposing again replaces the pending
+/// key; Pubkey::default() withdraws the proposal.
+pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let global_config = &mut ctx.accounts.global_config;
+    require!(new_authority != global_config.authority, DexError::InvalidAuthority);
+    
+    global_config.pending_authority = new_authority;
+    
+    sink.emit(AuthorityProposed {
+        authority: global_config.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Authority proposed: {}", new_authority);
+    
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState, WithdrawalAllowlist};
+use crate::errors::DexError;
+use crate::events::{event_sink, WithdrawalAllowlistChangeProposed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProposeWithdrawalAllowlist<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal_allowlist", trader_state.key().as_ref()],
+        bump = withdrawal_allowlist.bump,
+        has_one = trader_state
+    )]
+    pub withdrawal_allowlist: Account<'info, WithdrawalAllowlist>,
+    
+    pub trader: Signer<'info>,
+}
+
+/// Queue a replacement destination list (empty removes the allowlist)
+/// A new proposal replaces any pending one and restarts the delay
+pub fn handler(ctx: Context<ProposeWithdrawalAllowlist>, destinations: Vec<Pubkey>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
+    let allowlist = &mut ctx.accounts.withdrawal_allowlist;
+    
+    allowlist.pending_destinations = WithdrawalAllowlist::pack(&destinations)?;
+    allowlist.pending_effective_at = clock.unix_timestamp
+        .checked_add(WithdrawalAllowlist::CHANGE_DELAY_SECONDS)
+        .ok_or(DexError::MathOverflow)?;
+    
+    sink.emit(WithdrawalAllowlistChangeProposed {
+        trader: ctx.accounts.trader.key(),
+        market: ctx.accounts.market.key(),
+        destinations,
+        effective_at: allowlist.pending_effective_at,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Withdrawal allowlist change proposed: trader={}, effective_at={}", 
+         ctx.accounts.trader.key(), allowlist.pending_effective_at);
+    
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::orderbook::{Order, Orderbook, Side};
+use crate::errors::DexError;
+use crate::event_queue::EventQueue;
+use crate::instructions::cancel_order::evict_resting_orders;
+use crate::events::event_sink;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct PruneExpiredOrders<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    /// CHECK: Event queue account (must be the market's)
+    #[account(mut, address = market.event_queue @ DexError::InvalidAccountState)]
+    pub event_queue: UncheckedAccount<'info>,
+}
+
+/// Remove up to `limit` expired good-till-date orders
+///
+/// Permissionless crank. Funds are released through Out events on the
+/// event queue, since the owners' trader states are not passed in.
+pub fn handler(ctx: Context<PruneExpiredOrders>, limit: u8) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(limit > 0, DexError::InvalidInstruction);
+    
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    
+    let expired: Vec<(u64, Order)> = orderbook.iter_side(&orderbook_data, Side::Bid)
+        .chain(orderbook.iter_side(&orderbook_data, Side::Ask))
+        .filter(|(_, order)| order.is_expired(clock.unix_timestamp))
+        .take(limit as usize)
+        .collect();
+    
+    let mut event_queue_data = ctx.accounts.event_queue.try_borrow_mut_data()?;
+    let mut event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE])?;
+    
+    evict_resting_orders(
+        market,
+        &mut orderbook,
+        &mut orderbook_data,
+        &mut event_queue,
+        &mut event_queue_data,
+        &expired,
+        &sink,
+    )?;
+    
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE])?;
+    
+    let market_key = market.key();
+    ctx.accounts.market.sync_book(&orderbook, &orderbook_data, &sink)?;
+    
+    msg!("Expired orders pruned: market={}, pruned={}", market_key, expired.len());
+    
+    Ok(())
+}
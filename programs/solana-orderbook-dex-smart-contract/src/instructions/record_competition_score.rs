@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use crate::state::{Competition, CompetitionEntry, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, CompetitionScoreRecorded};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RecordCompetitionScore<'info> {
+    #[account(
+        mut,
+        seeds = [b"competition", competition.competition_id.to_le_bytes().as_ref()],
+        bump = competition.bump
+    )]
+    pub competition: Account<'info, Competition>,
+    
+    #[account(
+        mut,
+        seeds = [b"competition_entry", competition.key().as_ref(), entry.trader.as_ref()],
+        bump = entry.bump,
+        has_one = competition
+    )]
+    pub entry: Account<'info, CompetitionEntry>,
+    
+    // Remaining accounts: the entrant's TraderState accounts carrying competition volume (writable)
+}
+
+/// Move an entrant's competition volume from its trader states into its score
+///
+/// Permissionless crank, open from the end of the window until scoring
+/// closes. Each trader state is released from the competition as it is
+/// recorded, so volume is never counted twice and the state is free to
+/// enter the next one.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RecordCompetitionScore<'info>>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let now = Clock::get()?.unix_timestamp;
+    let competition = &mut ctx.accounts.competition;
+    require!(
+        now >= competition.end && now < competition.scoring_ends_at(),
+        DexError::WrongCompetitionPhase
+    );
+    
+    let competition_key = competition.key();
+    let entry = &mut ctx.accounts.entry;
+    let mut volume = 0u64;
+    for trader_state_info in ctx.remaining_accounts.iter() {
+        let mut trader_state = Account::<TraderState>::try_from(trader_state_info)?;
+        require_keys_eq!(trader_state.trader, entry.trader, DexError::Unauthorized);
+        require_keys_eq!(trader_state.competition, competition_key, DexError::InvalidCompetition);
+        
+        volume = volume
+            .checked_add(trader_state.competition_volume)
+            .ok_or(DexError::MathOverflow)?;
+        trader_state.competition = Pubkey::default();
+        trader_state.competition_volume = 0;
+        trader_state.exit(&crate::ID)?;
+    }
+    
+    entry.score = entry.score.checked_add(volume).ok_or(DexError::MathOverflow)?;
+    competition.total_score = competition.total_score
+        .checked_add(volume)
+        .ok_or(DexError::MathOverflow)?;
+    
+    sink.emit(CompetitionScoreRecorded {
+        competition: competition_key,
+        trader: entry.trader,
+        volume,
+        score: entry.score,
+        total_score: competition.total_score,
+        timestamp: now,
+    })?;
+    
+    msg!("Competition score recorded: trader={}, volume={}, score={}",
+         entry.trader, volume, entry.score);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{account_infos, install_clock, load, zeroed, TestAccount, Venue, NOW};
+    
+    /// Record the entry after `competition` from the trader states in `trader_states`
+    fn record<'info>(
+        infos: &'info [AccountInfo<'info>],
+        competition: usize,
+        trader_states: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        install_clock();
+        let mut accounts = RecordCompetitionScore {
+            competition: Account::try_from(&infos[competition])?,
+            entry: Account::try_from(&infos[competition + 1])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, trader_states, Default::default()))?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn volume_is_recorded_once_after_the_window_and_shares_the_prize_pro_rata() {
+        let mut venue = Venue::new(|_| {});
+        let competition_key = Pubkey::new_unique();
+        let mut competition: Competition = zeroed(Competition::SIZE);
+        competition.end = NOW + 1;
+        competition.prize_amount = 1_000;
+        competition.total_score = 10_000; // Another entrant's score
+        let mut entry: CompetitionEntry = zeroed(CompetitionEntry::SIZE);
+        entry.competition = competition_key;
+        entry.trader = Pubkey::new_unique();
+        let mut trader_state: TraderState = zeroed(TraderState::SIZE);
+        trader_state.trader = entry.trader;
+        trader_state.competition = competition_key;
+        trader_state.competition_volume = 30_000;
+        let competition_index = venue.accounts.len();
+        venue.accounts.extend([
+            TestAccount::program_owned(&competition).at(competition_key),
+            TestAccount::program_owned(&entry),
+            TestAccount::program_owned(&trader_state),
+        ]);
+        let infos = account_infos(&mut venue.accounts);
+        let trader_states = &infos[competition_index + 2..];
+        
+        // Scores are recorded only once the window is over
+        assert_eq!(record(&infos, competition_index, trader_states).unwrap_err(), DexError::WrongCompetitionPhase.into());
+        let mut competition = Account::<Competition>::try_from(&infos[competition_index]).unwrap();
+        competition.end = NOW;
+        competition.exit(&crate::ID).unwrap();
+        
+        record(&infos, competition_index, trader_states).unwrap();
+        let trader_state: TraderState = load(&trader_states[0]);
+        assert_eq!((trader_state.competition, trader_state.competition_volume), (Pubkey::default(), 0));
+        assert_eq!(
+            record(&infos, competition_index, trader_states).unwrap_err(),
+            DexError::InvalidCompetition.into()
+        );
+        
+        let competition: Competition = load(&infos[competition_index]);
+        let entry: CompetitionEntry = load(&infos[competition_index + 1]);
+        assert_eq!((entry.score, competition.total_score), (30_000, 40_000));
+        assert_eq!(competition.prize_for(entry.score), 750);
+        assert_eq!(competition.prize_for(10_000), 250);
+    }
+}
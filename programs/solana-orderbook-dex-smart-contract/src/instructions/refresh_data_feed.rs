@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use crate::state::{DataFeed, Market};
+use crate::orderbook::{Orderbook, Side};
+use crate::errors::DexError;
+use crate::events::{event_sink, DataFeedRefreshed};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RefreshDataFeed<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account (validated against market in instruction)
+    pub orderbook: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"data_feed", market.key().as_ref()],
+        bump = data_feed.bump,
+        has_one = market
+    )]
+    pub data_feed: Account<'info, DataFeed>,
+    
+    /// Receives the refresh reward
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+}
+
+/// Rewrite a data feed's L3 snapshot and pay the cranker from subscriber deposits
+///
+/// Refreshes are rate limited to one per `REFRESH_INTERVAL_SLOTS`. The reward
+/// never dips into the feed's rent-exempt minimum; once deposits run out the
+/// feed stops refreshing until a subscriber tops it up.
+pub fn handler(ctx: Context<RefreshDataFeed>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
+    let data_feed = &mut ctx.accounts.data_feed;
+    
+    require!(
+        data_feed.last_refresh_slot == 0 ||
+            clock.slot >= data_feed.last_refresh_slot.saturating_add(DataFeed::REFRESH_INTERVAL_SLOTS),
+        DexError::OperationNotSupported
+    );
+    
+    let feed_info = data_feed.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(DataFeed::SIZE);
+    require!(
+        feed_info.lamports() >= rent_floor.saturating_add(DataFeed::REFRESH_REWARD_LAMPORTS),
+        DexError::InsufficientFunds
+    );
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let orderbook_data = orderbook_account_info.try_borrow_data()?;
+    let orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == data_feed.market, DexError::InvalidOrderbookState);
+    
+    data_feed.bids = DataFeed::snapshot_side(&orderbook, &orderbook_data, Side::Bid);
+    data_feed.asks = DataFeed::snapshot_side(&orderbook, &orderbook_data, Side::Ask);
+    data_feed.last_refresh_slot = clock.slot;
+    data_feed.refresh_count = data_feed.refresh_count
+        .checked_add(1)
+        .ok_or(DexError::MathOverflow)?;
+    
+    // The feed is program-owned, so its lamports can be moved directly
+    **feed_info.try_borrow_mut_lamports()? -= DataFeed::REFRESH_REWARD_LAMPORTS;
+    **ctx.accounts.cranker.to_account_info().try_borrow_mut_lamports()? += DataFeed::REFRESH_REWARD_LAMPORTS;
+    
+    sink.emit(DataFeedRefreshed {
+        market: data_feed.market,
+        cranker: ctx.accounts.cranker.key(),
+        reward: DataFeed::REFRESH_REWARD_LAMPORTS,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Data feed refreshed: market={}, refresh={}, cranker={}",
+         data_feed.market, data_feed.refresh_count, ctx.accounts.cranker.key());
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::fund_data_feed::{self, FundDataFeed};
+    use crate::orderbook::TimeInForce;
+    use crate::state::FeedOrder;
+    use crate::test_utils::{account_infos, install_clock, load, zeroed, TestAccount, Venue, SLOT};
+    
+    /// Fund the data feed at `feed` from the subscriber after it, whose
+    /// system program follows
+    fn fund<'info>(infos: &'info [AccountInfo<'info>], feed: usize, subscriber: usize, lamports: u64) -> Result<()> {
+        install_clock();
+        let mut accounts = FundDataFeed {
+            data_feed: Account::try_from(&infos[feed])?,
+            subscriber: Signer::try_from(&infos[subscriber])?,
+            system_program: Program::try_from(&infos[subscriber + 1])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        fund_data_feed::handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), lamports)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    fn refresh<'info>(infos: &'info [AccountInfo<'info>], feed: usize, cranker: usize) -> Result<()> {
+        install_clock();
+        let mut accounts = RefreshDataFeed {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            orderbook: UncheckedAccount::try_from(&infos[Venue::ORDERBOOK]),
+            data_feed: Account::try_from(&infos[feed])?,
+            cranker: Signer::try_from(&infos[cranker])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()))?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn subscriber_deposits_pay_for_rate_limited_refreshes() {
+        let mut venue = Venue::new(|_| {});
+        let maker = venue.add_trader(10_000, 40_000);
+        let mut data_feed: DataFeed = zeroed(DataFeed::SIZE);
+        data_feed.market = venue.accounts[Venue::MARKET].key;
+        let rent_floor = Rent::default().minimum_balance(DataFeed::SIZE);
+        venue.accounts.extend([
+            TestAccount { lamports: rent_floor, ..TestAccount::program_owned(&data_feed) },
+            TestAccount::signer(),
+            TestAccount::signer(),
+            TestAccount::program(anchor_lang::system_program::ID),
+        ]);
+        let feed = venue.accounts.len() - 4;
+        let (cranker, subscriber) = (feed + 1, feed + 2);
+        let infos = account_infos(&mut venue.accounts);
+        Venue::place(&infos, maker, Venue::order(Side::Bid, 1_900, 10_000, TimeInForce::GTC)).unwrap();
+        Venue::place(&infos, maker, Venue::order(Side::Bid, 2_000, 10_000, TimeInForce::GTC)).unwrap();
+        Venue::place(&infos, maker, Venue::order(Side::Ask, 2_100, 10_000, TimeInForce::GTC)).unwrap();
+        
+        // Rent alone pays for nothing
+        assert_eq!(refresh(&infos, feed, cranker).unwrap_err(), DexError::InsufficientFunds.into());
+        
+        let reward = DataFeed::REFRESH_REWARD_LAMPORTS;
+        fund(&infos, feed, subscriber, reward * 3 / 2).unwrap();
+        assert_eq!(infos[feed].lamports(), rent_floor + reward * 3 / 2);
+        assert_eq!(load::<DataFeed>(&infos[feed]).total_funded, reward * 3 / 2);
+        
+        let cranker_before = infos[cranker].lamports();
+        refresh(&infos, feed, cranker).unwrap();
+        assert_eq!(infos[cranker].lamports(), cranker_before + reward);
+        let data_feed: DataFeed = load(&infos[feed]);
+        let prices = |side: &[FeedOrder]| side.iter().map(|order| (order.price, order.size)).collect::<Vec<_>>();
+        assert_eq!(prices(&data_feed.bids[..3]), vec![(2_000, 10_000), (1_900, 10_000), (0, 0)]);
+        assert_eq!(prices(&data_feed.asks[..2]), vec![(2_100, 10_000), (0, 0)]);
+        assert_eq!((data_feed.last_refresh_slot, data_feed.refresh_count), (SLOT, 1));
+        
+        // A second refresh waits out the interval, then finds half a reward left
+        assert_eq!(refresh(&infos, feed, cranker).unwrap_err(), DexError::OperationNotSupported.into());
+        let mut data_feed = Account::<DataFeed>::try_from(&infos[feed]).unwrap();
+        data_feed.last_refresh_slot = SLOT - DataFeed::REFRESH_INTERVAL_SLOTS;
+        data_feed.exit(&crate::ID).unwrap();
+        assert_eq!(refresh(&infos, feed, cranker).unwrap_err(), DexError::InsufficientFunds.into());
+        assert_eq!(infos[feed].lamports(), rent_floor + reward / 2);
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::{IntegratorFees, Market};
+use crate::events::{event_sink, IntegratorRegistered};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RegisterIntegrator<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = platform,
+        space = IntegratorFees::SIZE,
+        seeds = [b"integrator", market.key().as_ref(), platform.key().as_ref()],
+        bump
+    )]
+    pub integrator_fees: Account<'info, IntegratorFees>,
+    
+    #[account(mut)]
+    pub platform: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Open the fee account a platform's orders credit on this market
+pub fn handler(ctx: Context<RegisterIntegrator>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let integrator_fees = &mut ctx.accounts.integrator_fees;
+    integrator_fees.market = ctx.accounts.market.key();
+    integrator_fees.platform = ctx.accounts.platform.key();
+    integrator_fees.bump = ctx.bumps.integrator_fees;
+    
+    sink.emit(IntegratorRegistered {
+        market: integrator_fees.market,
+        platform: integrator_fees.platform,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Integrator registered: market={}, platform={}", 
+         integrator_fees.market, integrator_fees.platform);
+    
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::DexError;
+use crate::event_queue::EventQueue;
+use crate::events::{event_sink, QueueEventReplayed};
+
+/// Maximum number of events replayed by a single call
+pub const MAX_REPLAY_EVENTS: u64 = 32;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ReplayEvents<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Event queue account (must be the market's)
+    #[account(address = market.event_queue @ DexError::InvalidAccountState)]
+    pub event_queue: UncheckedAccount<'info>,
+}
+
+/// Re-emit the queue events with sequence numbers in `from_seq..to_seq`
+///
+/// Read-only and permissionless, so indexers that fell behind can recover
+/// recent fills without an archive node. Only the last `capacity` events are
+/// retained; asking for anything older fails with EventNotRetained.
+pub fn handler(ctx: Context<ReplayEvents>, from_seq: u64, to_seq: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(
+        from_seq < to_seq && to_seq - from_seq <= MAX_REPLAY_EVENTS,
+        DexError::InvalidInstruction
+    );
+    
+    let market_key = ctx.accounts.market.key();
+    let event_queue_data = ctx.accounts.event_queue.try_borrow_data()?;
+    let event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE])?;
+    require!(event_queue.market == market_key, DexError::InvalidAccountState);
+    
+    for seq_num in from_seq..to_seq {
+        let event = event_queue.get_by_seq(&event_queue_data, seq_num)
+            .ok_or(DexError::EventNotRetained)?;
+        
+        sink.emit(QueueEventReplayed {
+            market: market_key,
+            seq_num,
+            event_type: event.event_type,
+            bid_order_id: event.bid_order_id,
+            ask_order_id: event.ask_order_id,
+            bid_trader: event.bid_trader,
+            ask_trader: event.ask_trader,
+            price: event.price,
+            size: event.size,
+            bid_fee_bps: event.bid_fee_bps,
+            ask_fee_bps: event.ask_fee_bps,
+            consumed: event_queue.is_consumed(seq_num),
+            timestamp: event.timestamp,
+        })?;
+    }
+    
+    msg!("Events replayed: market={}, seq={}..{}", market_key, from_seq, to_seq);
+    
+    Ok(())
+}
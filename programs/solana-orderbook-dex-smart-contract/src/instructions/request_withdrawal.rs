@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::{Market, PendingWithdrawal, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, WithdrawalRequested};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(
+        init,
+        payer = trader,
+        space = PendingWithdrawal::SIZE,
+        seeds = [b"pending_withdrawal", trader_state.key().as_ref()],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Reserve funds for a withdrawal that the co-signer must approve
+pub fn handler(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(amount > 0, DexError::InvalidOrderParams);
+    
+    let market = &ctx.accounts.market;
+    let trader_state = &mut ctx.accounts.trader_state;
+    require!(
+        trader_state.withdrawal_co_signer != Pubkey::default(),
+        DexError::InvalidCoSigner
+    );
+    require!(
+        !trader_state.is_locked(Clock::get()?.unix_timestamp),
+        DexError::AccountLocked
+    );
+    
+    // Validate mint matches market
+    let is_base = ctx.accounts.mint.key() == market.base_mint;
+    let is_quote = ctx.accounts.mint.key() == market.quote_mint;
+    require!(is_base || is_quote, DexError::InvalidMint);
+    
+    // Reserve the funds so they cannot be traded or withdrawn meanwhile
+    if is_base {
+        require!(trader_state.base_available >= amount, DexError::InsufficientFunds);
+        trader_state.base_available = trader_state.base_available
+            .checked_sub(amount)
+            .ok_or(DexError::MathUnderflow)?;
+    } else {
+        require!(trader_state.quote_available >= amount, DexError::InsufficientFunds);
+        trader_state.quote_available = trader_state.quote_available
+            .checked_sub(amount)
+            .ok_or(DexError::MathUnderflow)?;
+    }
+    
+    let clock = Clock::get()?;
+    let pending = &mut ctx.accounts.pending_withdrawal;
+    pending.trader_state = trader_state.key();
+    pending.market = market.key();
+    pending.mint = ctx.accounts.mint.key();
+    pending.amount = amount;
+    pending.requested_at = clock.unix_timestamp;
+    pending.bump = ctx.bumps.pending_withdrawal;
+    
+    sink.emit(WithdrawalRequested {
+        trader: trader_state.trader,
+        market: market.key(),
+        mint: pending.mint,
+        amount,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Withdrawal requested: trader={}, mint={}, amount={}", 
+         trader_state.trader, pending.mint, amount);
+    
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, CircuitBreakerUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ResetCircuitBreaker<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority ||
+                     authority.key() == global_config.risk_authority ||
+                     authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Resume a market halted by the circuit breaker before its cooldown ends
+///
+/// Also restarts the measurement window, so the next fill sets a fresh
+/// reference price. The breaker's limits are risk parameters staged with
+/// update_risk_params; lifting a halt is an incident response and takes
+/// effect at once.
+pub fn handler(ctx: Context<ResetCircuitBreaker>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &mut ctx.accounts.market;
+    
+    market.breaker_reference_price = 0;
+    market.breaker_window_start = 0;
+    market.halted_until = 0;
+    
+    sink.emit(CircuitBreakerUpdated {
+        market: market.key(),
+        move_bps: market.breaker_move_bps,
+        window_seconds: market.breaker_window_seconds,
+        cooldown_seconds: market.breaker_cooldown_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Circuit breaker reset: market={}", market.key());
+    
+    Ok(())
+}
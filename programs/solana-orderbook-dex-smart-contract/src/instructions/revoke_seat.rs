@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, Seat};
+use crate::errors::DexError;
+use crate::events::{event_sink, SeatRevoked};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RevokeSeat<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"seat", market.key().as_ref(), seat.trader.as_ref()],
+        bump = seat.bump
+    )]
+    pub seat: Account<'info, Seat>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Revoke a trader's seat and return its rent to the market authority
+/// The trader can still cancel and withdraw; only new orders and deposits need a seat
+pub fn handler(ctx: Context<RevokeSeat>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let seat = &ctx.accounts.seat;
+    
+    sink.emit(SeatRevoked {
+        market: seat.market,
+        trader: seat.trader,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Seat revoked: market={}, trader={}", seat.market, seat.trader);
+    
+    Ok(())
+}
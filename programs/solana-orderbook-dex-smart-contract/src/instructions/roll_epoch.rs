@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::DexError;
+use crate::events::{event_sink, EpochRolled};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RollEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+}
+
+/// Finalize the market's statistics epoch and start the next one
+///
+/// Permissionless crank, callable once the epoch has run its full length.
+/// The finished epoch's counters are published in `EpochRolled` and kept as
+/// `last_epoch_volume`; the new epoch starts at the current aligned boundary,
+/// so epochs missed while nobody cranked are skipped rather than replayed.
+pub fn handler(ctx: Context<RollEpoch>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let now = Clock::get()?.unix_timestamp;
+    let market = &mut ctx.accounts.market;
+    
+    require!(market.is_epoch_over(now), DexError::EpochNotOver);
+    
+    let next_epoch_started_at = now - now.rem_euclid(Market::EPOCH_SECONDS);
+    
+    sink.emit(EpochRolled {
+        market: market.key(),
+        epoch: market.epoch,
+        started_at: market.epoch_started_at,
+        volume: market.epoch_volume,
+        fill_count: market.epoch_fill_count,
+        next_epoch_started_at,
+        timestamp: now,
+    })?;
+    
+    msg!("Epoch rolled: market={}, epoch={}, volume={}, fills={}", 
+         market.key(), market.epoch, market.epoch_volume, market.epoch_fill_count);
+    
+    market.last_epoch_volume = market.epoch_volume;
+    market.epoch_volume = 0;
+    market.epoch_fill_count = 0;
+    market.epoch = market.epoch.checked_add(1).ok_or(DexError::MathOverflow)?;
+    market.epoch_started_at = next_epoch_started_at;
+    
+    Ok(())
+}
@@ -0,0 +1,252 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Market, TraderState};
+use crate::orderbook::{Order, Orderbook, Side, TimeInForce};
+use crate::errors::DexError;
+use crate::events::{event_sink, LiquiditySeeded, OrderPlaced};
+
+/// Maximum number of ladder levels per side
+pub const MAX_SEED_LEVELS: u8 = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SeedLiquidityParams {
+    pub start_price: u64,    // Mid price of the ladder (must be on tick)
+    pub levels: u8,          // Levels per side
+    pub spacing_ticks: u64,  // Ticks between consecutive levels
+    pub size_per_level: u64, // Base size of every order
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SeedLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TraderState::SIZE,
+        seeds = [b"trader_state", authority.key().as_ref(), market.key().as_ref()],
+        bump
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = base_token_account.mint == market.base_mint @ DexError::InvalidMint
+    )]
+    pub base_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = quote_token_account.mint == market.quote_mint @ DexError::InvalidMint
+    )]
+    pub quote_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = market.base_vault @ DexError::InvalidMint)]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = market.quote_vault @ DexError::InvalidMint)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = market.base_mint @ DexError::InvalidMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(address = market.quote_mint @ DexError::InvalidMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+    
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit the creator's inventory and post a symmetric ladder around
+/// `start_price` on an empty book
+///
+/// Bids sit at `start_price - i * spacing`, asks at `start_price + i * spacing`
+/// for i in 1..=levels. The orders are ordinary GTC orders owned by the
+/// creator and can be cancelled like any other, but hold no placement deposit.
+pub fn handler(ctx: Context<SeedLiquidity>, params: SeedLiquidityParams) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &ctx.accounts.market;
+    require!(!market.paused, DexError::MarketPaused);
+    require!(market.accepts_new_orders(), DexError::MarketCancelOnly);
+    require!(
+        params.levels > 0 && params.levels <= MAX_SEED_LEVELS,
+        DexError::InvalidOrderParams
+    );
+    require!(params.spacing_ticks > 0, DexError::InvalidOrderParams);
+    require!(market.is_valid_tick(params.start_price), DexError::PriceNotOnTick);
+    require!(market.is_valid_lot(params.size_per_level), DexError::OrderSizeTooSmall);
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    require!(orderbook.order_count == 0, DexError::InvalidOrderbookState);
+    
+    // Build the ladder, best levels first
+    let spacing = market.tick_size
+        .checked_mul(params.spacing_ticks)
+        .ok_or(DexError::MathOverflow)?;
+    let mut ladder = Vec::with_capacity(params.levels as usize * 2);
+    let mut base_required = 0u64;
+    let mut quote_required = 0u64;
+    for i in 1..=params.levels as u64 {
+        let offset = spacing.checked_mul(i).ok_or(DexError::MathOverflow)?;
+        let bid_price = params.start_price
+            .checked_sub(offset)
+            .filter(|price| *price > 0)
+            .ok_or(DexError::InvalidPrice)?;
+        let ask_price = params.start_price
+            .checked_add(offset)
+            .ok_or(DexError::MathOverflow)?;
+        require!(
+            market.is_within_peg_band(bid_price) && market.is_within_peg_band(ask_price),
+            DexError::PriceOutsidePegBand
+        );
+        
+        quote_required = bid_price
+            .checked_mul(params.size_per_level)
+            .and_then(|v| v.checked_div(market.lot_size))
+            .and_then(|v| quote_required.checked_add(v))
+            .ok_or(DexError::MathOverflow)?;
+        base_required = base_required
+            .checked_add(params.size_per_level)
+            .ok_or(DexError::MathOverflow)?;
+        
+        ladder.push((Side::Bid, bid_price));
+        ladder.push((Side::Ask, ask_price));
+    }
+    
+    // Deposit the inventory
+    let base_vault_before = ctx.accounts.base_vault.amount;
+    let quote_vault_before = ctx.accounts.quote_vault.amount;
+    for (from, to, mint, token_program, amount) in [
+        (&ctx.accounts.base_token_account, &ctx.accounts.base_vault, &ctx.accounts.base_mint, &ctx.accounts.base_token_program, base_required),
+        (&ctx.accounts.quote_token_account, &ctx.accounts.quote_vault, &ctx.accounts.quote_mint, &ctx.accounts.quote_token_program, quote_required),
+    ] {
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            mint: mint.to_account_info(),
+            to: to.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
+    }
+    
+    // The ladder locks exact amounts, so the vaults must receive them in full
+    ctx.accounts.base_vault.reload()?;
+    ctx.accounts.quote_vault.reload()?;
+    require!(
+        ctx.accounts.base_vault.amount.saturating_sub(base_vault_before) == base_required &&
+            ctx.accounts.quote_vault.amount.saturating_sub(quote_vault_before) == quote_required,
+        DexError::TransferFeeNotSupported
+    );
+    
+    let trader_state = &mut ctx.accounts.trader_state;
+    if trader_state.trader == Pubkey::default() {
+        trader_state.trader = ctx.accounts.authority.key();
+        trader_state.market = market.key();
+        trader_state.bump = ctx.bumps.trader_state;
+    }
+    trader_state.last_deposit_at = Clock::get()?.unix_timestamp;
+    trader_state.base_available = trader_state.base_available
+        .checked_add(base_required)
+        .ok_or(DexError::MathOverflow)?;
+    trader_state.quote_available = trader_state.quote_available
+        .checked_add(quote_required)
+        .ok_or(DexError::MathOverflow)?;
+    
+    // Post the ladder
+    let clock = Clock::get()?;
+    for (index, (side, price)) in ladder.iter().enumerate() {
+        if *side == Side::Bid {
+            let quote_locked = price
+                .checked_mul(params.size_per_level)
+                .and_then(|v| v.checked_div(market.lot_size))
+                .ok_or(DexError::MathOverflow)?;
+            trader_state.lock_quote(quote_locked)?;
+        } else {
+            trader_state.lock_base(params.size_per_level)?;
+        }
+        
+        let order_id = market.order_id_at(&market.key(), index as u64)?;
+        let order = Order::new(
+            order_id,
+            trader_state.trader,
+            *side,
+            *price,
+            params.size_per_level,
+            TimeInForce::GTC,
+            &clock,
+        );
+        orderbook.insert_order(&mut orderbook_data, &order)?;
+        
+        if market.logs_l3() {
+            sink.emit(OrderPlaced {
+                market: market.key(),
+                trader: order.trader,
+                order_id,
+                client_order_id: 0,
+                side: *side as u8,
+                price: *price,
+                size: params.size_per_level,
+                time_in_force: TimeInForce::GTC as u8,
+            origin: order.origin,
+                timestamp: clock.unix_timestamp,
+            })?;
+        }
+    }
+    market.check_exposure(&orderbook, Side::Bid)?;
+    market.check_exposure(&orderbook, Side::Ask)?;
+    
+    trader_state.open_order_count = trader_state.open_order_count
+        .checked_add(ladder.len() as u16)
+        .ok_or(DexError::MathOverflow)?;
+    
+    // Save orderbook
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    
+    let market_key = market.key();
+    let market_mut = &mut ctx.accounts.market;
+    market_mut.sync_book(&orderbook, &orderbook_data, &sink)?;
+    market_mut.advance_order_sequence(ladder.len() as u64)?;
+    market_mut.credit_vault(true, base_required)?;
+    market_mut.credit_vault(false, quote_required)?;
+    
+    sink.emit(LiquiditySeeded {
+        market: market_key,
+        start_price: params.start_price,
+        levels: params.levels,
+        spacing,
+        size_per_level: params.size_per_level,
+        base_deposited: base_required,
+        quote_deposited: quote_required,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Liquidity seeded: market={}, start_price={}, levels={}", 
+         market_key, params.start_price, params.levels);
+    
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, CrankRewardUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetCrankReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Set the reward per fill paid to whoever runs match_orders
+///
+/// The reward comes out of the market's accrued quote fees, so it is the
+/// protocol's to set. Callers are paid only what the accrued fees cover.
+pub fn handler(ctx: Context<SetCrankReward>, reward_per_fill: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &mut ctx.accounts.market;
+    market.crank_reward_per_fill = reward_per_fill;
+    
+    sink.emit(CrankRewardUpdated {
+        market: market.key(),
+        reward_per_fill,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Crank reward updated: market={}, reward_per_fill={}", market.key(), reward_per_fill);
+    
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, FeePassUpdated};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetFeePassParams {
+    pub mint: Option<Pubkey>, // Pass token or NFT collection mint, None = no pass
+    pub is_collection: bool, // Any NFT verified in the `mint` collection is a pass
+    pub min_amount: u64, // Smallest holding that counts (0 = any)
+    pub discount_bps: u16, // Taker fee discount
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetFeePass<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Set the token or NFT collection whose holders take liquidity at a discount
+///
+/// The discount comes out of the protocol's taker fee, so it is the
+/// protocol's to set. It takes effect at once rather than behind the
+/// timelock: it only ever lowers what a pass holder pays, and never below
+/// what the maker rebate and integrator share need (see
+/// `GlobalConfig::discounted_taker_fee_bps`).
+pub fn handler(ctx: Context<SetFeePass>, params: SetFeePassParams) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &mut ctx.accounts.market;
+    
+    match params.mint {
+        Some(mint) => {
+            require!(mint != Pubkey::default(), DexError::InvalidMarketParams);
+            require!(
+                params.discount_bps > 0 && params.discount_bps <= 10_000,
+                DexError::InvalidFeeCalculation
+            );
+            market.fee_pass_mint = mint;
+            market.fee_pass_is_collection = params.is_collection;
+            market.fee_pass_min_amount = params.min_amount;
+            market.fee_pass_discount_bps = params.discount_bps;
+        }
+        None => {
+            market.fee_pass_mint = Pubkey::default();
+            market.fee_pass_is_collection = false;
+            market.fee_pass_min_amount = 0;
+            market.fee_pass_discount_bps = 0;
+        }
+    }
+    
+    sink.emit(FeePassUpdated {
+        market: market.key(),
+        mint: market.fee_pass_mint,
+        is_collection: market.fee_pass_is_collection,
+        min_amount: market.fee_pass_min_amount,
+        discount_bps: market.fee_pass_discount_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Fee pass updated: market={}, mint={}, discount_bps={}",
+         market.key(), market.fee_pass_mint, market.fee_pass_discount_bps);
+    
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, GuardianUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    pub trader: Signer<'info>,
+}
+
+/// Pre-authorize (or remove) a key that may lock the account on the trader's behalf
+pub fn handler(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let trader_state = &mut ctx.accounts.trader_state;
+    
+    // A compromised key must not be able to drop the guardian during a lockdown
+    require!(
+        !trader_state.is_locked(Clock::get()?.unix_timestamp),
+        DexError::AccountLocked
+    );
+    
+    match guardian {
+        Some(guardian) => {
+            require!(guardian != Pubkey::default(), DexError::InvalidAuthority);
+            require!(guardian != trader_state.trader, DexError::InvalidAuthority);
+            trader_state.guardian = guardian;
+        }
+        None => {
+            trader_state.guardian = Pubkey::default();
+        }
+    }
+    
+    sink.emit(GuardianUpdated {
+        trader: trader_state.trader,
+        market: ctx.accounts.market.key(),
+        guardian: trader_state.guardian,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Guardian updated: trader={}, guardian={}", 
+         trader_state.trader, trader_state.guardian);
+    
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, MarketOperatorUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetMarketOperator<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority || 
+                     authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Delegate limited market powers to an operator key, or revoke them
+///
+/// `permissions` is a set of `Market::OPERATOR_*` flags. Risk parameters are
+/// governed by the protocol, so only the protocol or risk authority may hand
+/// that power to an operator.
+pub fn handler(ctx: Context<SetMarketOperator>, operator: Option<Pubkey>, permissions: u8) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let authority = ctx.accounts.authority.key();
+    let global_config = &ctx.accounts.global_config;
+    let market = &mut ctx.accounts.market;
+    
+    match operator {
+        Some(operator) => {
+            require!(operator != Pubkey::default(), DexError::InvalidAuthority);
+            require!(
+                permissions != 0 &&
+                    permissions & !(Market::OPERATOR_CAN_PAUSE | Market::OPERATOR_CAN_UPDATE_RISK) == 0,
+                DexError::InvalidInstruction
+            );
+            if permissions & Market::OPERATOR_CAN_UPDATE_RISK != 0 {
+                require!(
+                    authority == global_config.authority || authority == global_config.risk_authority,
+                    DexError::Unauthorized
+                );
+            }
+            market.operator = operator;
+            market.operator_permissions = permissions;
+        }
+        None => {
+            market.operator = Pubkey::default();
+            market.operator_permissions = 0;
+        }
+    }
+    
+    sink.emit(MarketOperatorUpdated {
+        market: market.key(),
+        operator: market.operator,
+        permissions: market.operator_permissions,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Market operator updated: market={}, operator={}, permissions={}", 
+         market.key(), market.operator, market.operator_permissions);
+    
+    Ok(())
+}
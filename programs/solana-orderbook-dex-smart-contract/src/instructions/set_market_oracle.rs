@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, MarketOracleUpdateStaged};
+use crate::oracle::OracleType;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetMarketOracleParams {
+    /// New oracle account (None detaches the current oracle)
+    pub oracle: Option<Pubkey>,
+    pub oracle_type: u8, // 0 = Pyth, 1 = Switchboard
+    pub max_staleness: u64,
+    pub max_deviation_bps: u16,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(params: SetMarketOracleParams)]
+pub struct SetMarketOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority || 
+                     authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Stage a new oracle configuration behind the update timelock
+///
+/// The oracle decides which orders the band rejects, so it changes on the
+/// same schedule as the other market parameters: validated now, activated
+/// by apply_pending_update once `UPDATE_DELAY_SECONDS` have passed. Staging
+/// again replaces the pending change and restarts the delay.
+pub fn handler(ctx: Context<SetMarketOracle>, params: SetMarketOracleParams) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+    
+    match params.oracle {
+        Some(oracle) => {
+            require!(oracle != Pubkey::default(), DexError::InvalidMarketParams);
+            let oracle_type = OracleType::from_u8(params.oracle_type)
+                .ok_or(DexError::InvalidMarketParams)?;
+            require!(params.max_staleness > 0, DexError::InvalidMarketParams);
+            require!(
+                params.max_deviation_bps > 0 && params.max_deviation_bps <= 10_000,
+                DexError::InvalidMarketParams
+            );
+            market.pending_oracle = oracle;
+            market.pending_oracle_type = oracle_type as u8;
+            market.pending_oracle_max_staleness = params.max_staleness;
+            market.pending_oracle_max_deviation_bps = params.max_deviation_bps;
+        }
+        None => {
+            market.pending_oracle = Pubkey::default();
+            market.pending_oracle_type = OracleType::Pyth as u8;
+            market.pending_oracle_max_staleness = 0;
+            market.pending_oracle_max_deviation_bps = 0;
+        }
+    }
+    
+    market.oracle_effective_at = clock.unix_timestamp
+        .checked_add(GlobalConfig::UPDATE_DELAY_SECONDS)
+        .ok_or(DexError::MathOverflow)?;
+    
+    sink.emit(MarketOracleUpdateStaged {
+        market: market.key(),
+        oracle: market.pending_oracle,
+        oracle_type: market.pending_oracle_type,
+        max_staleness: market.pending_oracle_max_staleness,
+        max_deviation_bps: market.pending_oracle_max_deviation_bps,
+        effective_at: market.oracle_effective_at,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Market oracle update staged: market={}, oracle={}, effective_at={}", 
+         market.key(), market.pending_oracle, market.oracle_effective_at);
+    
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market, MarketStatus};
+use crate::errors::DexError;
+use crate::events::{event_sink, MarketStatusUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetMarketStatus<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority || 
+                     authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Move a market through its wind-down lifecycle: Active -> CancelOnly -> Delisted
+///
+/// A cancel-only market can be reactivated; delisting is final, since the
+/// force_cancel_orders crank may already have emptied the book.
+pub fn handler(ctx: Context<SetMarketStatus>, status: u8) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &mut ctx.accounts.market;
+    
+    let next = MarketStatus::from_u8(status).ok_or(DexError::InvalidMarketStatus)?;
+    let current = MarketStatus::from_u8(market.status).ok_or(DexError::InvalidMarketStatus)?;
+    require!(current.can_transition_to(next), DexError::InvalidMarketStatus);
+    
+    market.status = status;
+    
+    sink.emit(MarketStatusUpdated {
+        market: market.key(),
+        previous_status: current as u8,
+        status,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Market status updated: market={}, {:?} -> {:?}", market.key(), current, next);
+    
+    Ok(())
+}
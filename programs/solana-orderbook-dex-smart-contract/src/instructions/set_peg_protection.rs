@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::DexError;
+use crate::events::{event_sink, PegProtectionUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetPegProtection<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority || 
+                     authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, crate::state::GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Configure (or disable with band_bps = 0) the peg band and re-arm a broken peg
+pub fn handler(ctx: Context<SetPegProtection>, peg_price: u64, band_bps: u16) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &mut ctx.accounts.market;
+    
+    if band_bps > 0 {
+        require!(market.is_valid_tick(peg_price), DexError::PriceNotOnTick);
+        require!(band_bps <= 10_000, DexError::InvalidMarketParams);
+        market.peg_price = peg_price;
+        market.peg_band_bps = band_bps;
+    } else {
+        market.peg_price = 0;
+        market.peg_band_bps = 0;
+    }
+    market.peg_cancel_only = false;
+    
+    sink.emit(PegProtectionUpdated {
+        market: market.key(),
+        peg_price: market.peg_price,
+        peg_band_bps: market.peg_band_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Peg protection updated: market={}, peg={}, band={}bps", 
+         market.key(), market.peg_price, market.peg_band_bps);
+    
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, PointsScheduleUpdated};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetPointsScheduleParams {
+    pub taker_weight: u16, // Points per 10_000 quote units taken
+    pub maker_weight: u16, // Points per 10_000 quote units filled after a full hour resting
+    pub start: i64, // Fills from this time on earn points
+    pub end: i64, // Fills from this time on stop earning (0 = open-ended)
+    pub halving_seconds: i64, // Emission halves every period after `start` (0 = flat)
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetPointsSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Set how many loyalty points the market's fills earn, and over what window
+///
+/// Points are protocol perks and move no funds, so a new schedule applies
+/// to the next fill. Points already earned are kept; zero weights stop
+/// emission.
+pub fn handler(ctx: Context<SetPointsSchedule>, params: SetPointsScheduleParams) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(params.start >= 0 && params.halving_seconds >= 0, DexError::InvalidMarketParams);
+    require!(params.end == 0 || params.end > params.start, DexError::InvalidMarketParams);
+    
+    let market = &mut ctx.accounts.market;
+    market.points_taker_weight = params.taker_weight;
+    market.points_maker_weight = params.maker_weight;
+    market.points_start = params.start;
+    market.points_end = params.end;
+    market.points_halving_seconds = params.halving_seconds;
+    
+    sink.emit(PointsScheduleUpdated {
+        market: market.key(),
+        taker_weight: params.taker_weight,
+        maker_weight: params.maker_weight,
+        start: params.start,
+        end: params.end,
+        halving_seconds: params.halving_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Points schedule updated: market={}, taker_weight={}, maker_weight={}",
+         market.key(), params.taker_weight, params.maker_weight);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{account_infos, install_clock, load, TestAccount, Venue, NOW};
+    
+    fn run<'info>(infos: &'info [AccountInfo<'info>], authority: usize, params: SetPointsScheduleParams) -> Result<()> {
+        install_clock();
+        let mut accounts = SetPointsSchedule {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            global_config: Account::try_from(&infos[Venue::GLOBAL_CONFIG])?,
+            authority: Signer::try_from(&infos[authority])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), params)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn emission_runs_inside_the_window_and_halves_each_period() {
+        let mut venue = Venue::new(|_| {});
+        venue.accounts.push(TestAccount::signer());
+        let authority = venue.accounts.len() - 1;
+        let infos = account_infos(&mut venue.accounts);
+        let schedule = |end| SetPointsScheduleParams {
+            taker_weight: 10_000,
+            maker_weight: 20_000,
+            start: NOW,
+            end,
+            halving_seconds: 86_400,
+        };
+        
+        assert_eq!(
+            run(&infos, authority, schedule(NOW)).err().unwrap(),
+            DexError::InvalidMarketParams.into()
+        );
+        run(&infos, authority, schedule(NOW + 3 * 86_400)).unwrap();
+        let market: Market = load(&infos[Venue::MARKET]);
+        
+        assert_eq!(market.points_for(1_000, None, NOW - 1), 0);
+        assert_eq!(market.points_for(1_000, None, NOW), 1_000);
+        assert_eq!(market.points_for(1_000, Some(900), NOW), 500);
+        assert_eq!(market.points_for(1_000, Some(7_200), NOW), 2_000); // Uptime counts up to an hour
+        assert_eq!(market.points_for(1_000, None, NOW + 86_400), 500);
+        assert_eq!(market.points_for(1_000, None, NOW + 2 * 86_400), 250);
+        assert_eq!(market.points_for(1_000, None, NOW + 3 * 86_400), 0);
+    }
+}
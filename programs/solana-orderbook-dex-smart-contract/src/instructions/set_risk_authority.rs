@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::GlobalConfig;
+use crate::errors::DexError;
+
+#[derive(Accounts)]
+pub struct SetRiskAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetRiskAuthority>, risk_authority: Pubkey) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.risk_authority = risk_authority;
+    
+    msg!("Risk authority updated: {}", risk_authority);
+    
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::DexError;
+use crate::events::{event_sink, SeatRequirementUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetSeatRequirement<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Turn seat gating on or off for a market
+/// Turning it on does not touch resting orders; it only gates new orders, swaps and deposits
+pub fn handler(ctx: Context<SetSeatRequirement>, requires_seat: bool) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &mut ctx.accounts.market;
+    market.requires_seat = requires_seat;
+    
+    sink.emit(SeatRequirementUpdated {
+        market: market.key(),
+        requires_seat,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Seat requirement updated: market={}, requires_seat={}", market.key(), requires_seat);
+    
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::DexError;
+use crate::events::{event_sink, SettlementProgramUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetSettlementProgram<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == market.authority || 
+                     authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, crate::state::GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Detach a market's external settlement program
+///
+/// Attaching one is refused for now. Fills settle through the event queue:
+/// place_order and swap apply the taker side as they match, and
+/// consume_events or match_and_settle credit the makers. None of those
+/// forward fills to the settlement program yet. A veto raised only when a
+/// maker is credited could not undo the taker side, and it would stall the
+/// queue behind that fill. An attached program would therefore be bypassed
+/// without anyone noticing. Detaching stays possible, so markets configured
+/// earlier can be cleaned up.
+pub fn handler(ctx: Context<SetSettlementProgram>, settlement_program: Option<Pubkey>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &mut ctx.accounts.market;
+    
+    require!(settlement_program.is_none(), DexError::InvalidSettlementProgram);
+    market.settlement_program = Pubkey::default();
+    
+    sink.emit(SettlementProgramUpdated {
+        market: market.key(),
+        settlement_program: market.settlement_program,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Settlement program updated: market={}, program={}", 
+         market.key(), market.settlement_program);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{account_infos, install_clock, load, TestAccount, Venue};
+    
+    fn run<'info>(infos: &'info [AccountInfo<'info>], authority: usize, program: Option<Pubkey>) -> Result<()> {
+        install_clock();
+        let mut accounts = SetSettlementProgram {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            global_config: Account::try_from(&infos[Venue::GLOBAL_CONFIG])?,
+            authority: Signer::try_from(&infos[authority])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), program)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn refuses_to_attach_a_program_but_detaches_one_set_earlier() {
+        let legacy = Pubkey::new_unique();
+        let mut venue = Venue::new(|market| market.settlement_program = legacy);
+        venue.accounts.push(TestAccount::signer());
+        let authority = venue.accounts.len() - 1;
+        let infos = account_infos(&mut venue.accounts);
+        
+        assert_eq!(
+            run(&infos, authority, Some(Pubkey::new_unique())).err().unwrap(),
+            DexError::InvalidSettlementProgram.into()
+        );
+        run(&infos, authority, None).unwrap();
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert!(!market.has_settlement_program());
+    }
+}
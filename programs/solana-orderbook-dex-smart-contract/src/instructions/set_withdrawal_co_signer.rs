@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, WithdrawalCoSignerUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetWithdrawalCoSigner<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    pub trader: Signer<'info>,
+    
+    /// Current co-signer; required to change or remove an existing one
+    pub current_co_signer: Option<Signer<'info>>,
+}
+
+pub fn handler(
+    ctx: Context<SetWithdrawalCoSigner>,
+    co_signer: Option<Pubkey>,
+    threshold: u64,
+) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let trader_state = &mut ctx.accounts.trader_state;
+    
+    // The hot key alone must not be able to weaken an existing co-signer setup
+    if trader_state.withdrawal_co_signer != Pubkey::default() {
+        let current = ctx.accounts.current_co_signer
+            .as_ref()
+            .ok_or(DexError::CoSignatureRequired)?;
+        require!(
+            current.key() == trader_state.withdrawal_co_signer,
+            DexError::InvalidCoSigner
+        );
+    }
+    
+    match co_signer {
+        Some(co_signer) => {
+            require!(co_signer != Pubkey::default(), DexError::InvalidCoSigner);
+            require!(co_signer != trader_state.trader, DexError::InvalidCoSigner);
+            trader_state.withdrawal_co_signer = co_signer;
+            trader_state.co_sign_threshold = threshold;
+        }
+        None => {
+            trader_state.withdrawal_co_signer = Pubkey::default();
+            trader_state.co_sign_threshold = 0;
+        }
+    }
+    
+    sink.emit(WithdrawalCoSignerUpdated {
+        trader: trader_state.trader,
+        market: ctx.accounts.market.key(),
+        co_signer: trader_state.withdrawal_co_signer,
+        threshold: trader_state.co_sign_threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Withdrawal co-signer updated: trader={}, co_signer={}", 
+         trader_state.trader, trader_state.withdrawal_co_signer);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{account_infos, install_clock, load, TestAccount, Venue};
+    
+    /// Set the co-signer of the trader whose signer is at `trader`, signed
+    /// by the current co-signer at `current` if any
+    fn set<'info>(
+        infos: &'info [AccountInfo<'info>],
+        trader: usize,
+        current: Option<usize>,
+        co_signer: Option<Pubkey>,
+    ) -> Result<()> {
+        install_clock();
+        let mut accounts = SetWithdrawalCoSigner {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            trader_state: Account::try_from(&infos[trader + 1])?,
+            trader: Signer::try_from(&infos[trader])?,
+            current_co_signer: current.map(|index| Signer::try_from(&infos[index])).transpose()?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), co_signer, 1_000)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn the_hot_key_alone_cannot_drop_or_replace_the_co_signer() {
+        let mut venue = Venue::new(|_| {});
+        let trader = venue.add_trader(0, 0);
+        venue.accounts.push(TestAccount::signer());
+        let cold = venue.accounts.len() - 1;
+        let cold_key = venue.accounts[cold].key;
+        let infos = account_infos(&mut venue.accounts);
+        
+        assert_eq!(
+            set(&infos, trader, None, Some(*infos[trader].key)).unwrap_err(),
+            DexError::InvalidCoSigner.into()
+        );
+        set(&infos, trader, None, Some(cold_key)).unwrap();
+        let trader_state: TraderState = load(&infos[trader + 1]);
+        assert_eq!((trader_state.withdrawal_co_signer, trader_state.co_sign_threshold), (cold_key, 1_000));
+        
+        assert_eq!(set(&infos, trader, None, None).unwrap_err(), DexError::CoSignatureRequired.into());
+        assert_eq!(
+            set(&infos, trader, Some(trader), Some(Pubkey::new_unique())).unwrap_err(),
+            DexError::InvalidCoSigner.into()
+        );
+        set(&infos, trader, Some(cold), None).unwrap();
+        assert_eq!(load::<TraderState>(&infos[trader + 1]).withdrawal_co_signer, Pubkey::default());
+    }
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, WithdrawalCooldownUpdated};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetWithdrawalCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Set the minimum holding period between a deposit and a withdrawal
+///
+/// Blunts flash-loan style balance inflation. With a non-zero fee, early
+/// withdrawals go through and pay it; otherwise they are refused until the
+/// cooldown ends. A zero cooldown disables the check.
+pub fn handler(ctx: Context<SetWithdrawalCooldown>, cooldown_seconds: u32, early_withdrawal_fee_bps: u16) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(
+        cooldown_seconds <= 86_400, // Max 1 day
+        DexError::InvalidMarketParams
+    );
+    require!(
+        early_withdrawal_fee_bps <= 100, // Max 1%
+        DexError::InvalidFeeCalculation
+    );
+    
+    let market = &mut ctx.accounts.market;
+    market.withdrawal_cooldown_seconds = cooldown_seconds;
+    market.early_withdrawal_fee_bps = early_withdrawal_fee_bps;
+    
+    sink.emit(WithdrawalCooldownUpdated {
+        market: market.key(),
+        cooldown_seconds,
+        early_withdrawal_fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Withdrawal cooldown updated: market={}, cooldown={}s, fee={}bps", 
+         market.key(), cooldown_seconds, early_withdrawal_fee_bps);
+    
+    Ok(())
+}
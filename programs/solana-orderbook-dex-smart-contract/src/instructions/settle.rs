@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 use crate::state::{Market, TraderState, PendingFill, GlobalConfig};
 use crate::errors::DexError;
-use crate::events::FillSettled;
+use crate::events::{event_sink, FillSettled};
+use crate::settlement_hook::{forward_fill, SettlementHookFill};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
-#[instruction(fill_ids: Vec<u128>)]
 pub struct Settle<'info> {
     #[account(
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
@@ -19,57 +20,97 @@ pub struct Settle<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
     
-    pub base_vault: Account<'info, TokenAccount>,
-    pub quote_vault: Account<'info, TokenAccount>,
+    #[account(address = market.base_vault @ DexError::InvalidMint)]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = market.quote_vault @ DexError::InvalidMint)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: Bid trader state (validated in instruction)
-    #[account(mut)]
-    pub bid_trader_state: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = market,
+        constraint = !pending_fill.settled @ DexError::FillAlreadySettled
+    )]
+    pub pending_fill: Account<'info, PendingFill>,
+    
+    /// Trader state of the fill's buyer in this market
+    #[account(
+        mut,
+        seeds = [b"trader_state", pending_fill.bid_trader.as_ref(), market.key().as_ref()],
+        bump = bid_trader_state.bump,
+        has_one = market,
+        constraint = bid_trader_state.trader == pending_fill.bid_trader @ DexError::Unauthorized
+    )]
+    pub bid_trader_state: Account<'info, TraderState>,
     
-    /// CHECK: Ask trader state (validated in instruction)
-    #[account(mut)]
-    pub ask_trader_state: UncheckedAccount<'info>,
+    /// Trader state of the fill's seller in this market
+    #[account(
+        mut,
+        seeds = [b"trader_state", pending_fill.ask_trader.as_ref(), market.key().as_ref()],
+        bump = ask_trader_state.bump,
+        has_one = market,
+        constraint = ask_trader_state.trader == pending_fill.ask_trader @ DexError::Unauthorized
+    )]
+    pub ask_trader_state: Account<'info, TraderState>,
     
-    #[account(mut)]
-    pub fee_recipient: Signer<'info>,
+    /// Any caller; settlement is a permissionless crank
+    pub keeper: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<Settle>, fill_ids: Vec<u128>) -> Result<()> {
+/// Settle one recorded fill between the trader states it names
+///
+/// Deprecated: fills now settle through the event queue (`consume_events`)
+/// and no new `PendingFill` accounts are recorded. Kept so fills recorded
+/// before the queue can still be marked settled and archived; it moves no
+/// balances and pays no crank reward.
+///
+/// The trader states are bound to the fill's `bid_trader`/`ask_trader` and
+/// market by PDA seeds, so a caller cannot redirect a fill's proceeds to
+/// other accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Settle<'info>>,
+) -> Result<()> {
+    let sink = event_sink!(ctx);
     let market = &ctx.accounts.market;
-    let global_config = &ctx.accounts.global_config;
-    
-    // In a full implementation, we'd load fills from account data
-    // For now, this is a simplified version that assumes fills are passed
-    // In production, you'd store fills in a separate account and iterate
-    
-    // This is a placeholder - in reality, you'd:
-    // 1. Load fills from account data
-    // 2. Group by trader to batch transfers
-    // 3. Transfer base tokens from ask traders to bid traders
-    // 4. Transfer quote tokens from bid traders to ask traders
-    // 5. Collect fees to protocol treasury
-    // 6. Update trader states
-    
-    // For now, we'll emit an event indicating settlement
+    let fill = &ctx.accounts.pending_fill;
     let clock = Clock::get()?;
     
-    for fill_id in fill_ids {
-        emit!(FillSettled {
-            market: market.key(),
-            fill_id,
-            bid_trader: ctx.accounts.bid_trader_state.key(),
-            ask_trader: ctx.accounts.ask_trader_state.key(),
-            base_amount: 0, // Would be calculated from fill
-            quote_amount: 0, // Would be calculated from fill
-            maker_fee: 0,
-            taker_fee: 0,
-            timestamp: clock.unix_timestamp,
-        });
+    // Regulated markets: the external settlement program must approve the fill
+    // (remaining accounts: [settlement_program, ...accounts it needs])
+    if market.has_settlement_program() {
+        forward_fill(
+            &market.settlement_program,
+            &market.to_account_info(),
+            ctx.remaining_accounts,
+            &SettlementHookFill {
+                market: market.key(),
+                fill_id: fill.fill_id,
+                bid_trader: fill.bid_trader,
+                ask_trader: fill.ask_trader,
+                base_amount: fill.size,
+                quote_amount: fill.quote_amount,
+                maker_fee: fill.maker_fee,
+                taker_fee: fill.taker_fee,
+            },
+        )?;
     }
     
-    msg!("Settled {} fills", fill_ids.len());
+    sink.emit(FillSettled {
+        market: market.key(),
+        fill_id: fill.fill_id,
+        bid_trader: fill.bid_trader,
+        ask_trader: fill.ask_trader,
+        base_amount: fill.size,
+        quote_amount: fill.quote_amount,
+        maker_fee: fill.maker_fee,
+        taker_fee: fill.taker_fee,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Fill settled: id={}", fill.fill_id);
+    
+    ctx.accounts.pending_fill.settled = true;
     
     Ok(())
 }
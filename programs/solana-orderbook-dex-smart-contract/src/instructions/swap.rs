@@ -0,0 +1,466 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{GlobalConfig, IntegratorFees, Market, Seat};
+use crate::orderbook::{Order, Orderbook, Side, TimeInForce};
+use crate::errors::DexError;
+use crate::event_queue::{EventQueue, QueueEvent};
+use crate::events::{event_sink, SwapExecuted};
+use crate::fee_pass::load_fee_pass_discount;
+use crate::instructions::cancel_order::queue_removed_orders;
+use crate::matching::{calculate_fee, match_taker_order, TakerLimits};
+use crate::oracle::{is_within_oracle_band, load_band_price};
+use crate::trade_tape::{record_trades, TradeRecord};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SwapParams {
+    pub side: u8, // 0 = buy base with quote, 1 = sell base for quote
+    pub price: u64, // Worst acceptable price
+    pub size: u64, // Base size (0 with quote_budget)
+    pub quote_budget: Option<u64>, // Buys only: spend up to this much quote instead of a base size
+    pub min_amount_out: u64, // Base received on buys, quote received on sells (after fees)
+    pub integrator_fee_bps: u16, // Share of the taker fee routed to the integrator account (0 = none)
+    pub origin: u32, // Origin tag for surveillance: 0 = none, 1 = UI, 2 = API, >= 256 = CPI program tag
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// CHECK: Orderbook account
+    #[account(mut)]
+    pub orderbook: UncheckedAccount<'info>,
+    
+    /// CHECK: Event queue account (must be the market's)
+    #[account(mut, address = market.event_queue @ DexError::InvalidAccountState)]
+    pub event_queue: UncheckedAccount<'info>,
+    
+    /// CHECK: Trade tape, required when the market has one
+    #[account(mut)]
+    pub trade_tape: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub trader: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = trader_base_account.mint == market.base_mint @ DexError::InvalidMint,
+        constraint = trader_base_account.owner == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_base_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = trader_quote_account.mint == market.quote_mint @ DexError::InvalidMint,
+        constraint = trader_quote_account.owner == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_quote_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = market.base_vault @ DexError::InvalidMint)]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = market.quote_vault @ DexError::InvalidMint)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = market.base_mint @ DexError::InvalidMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(address = market.quote_mint @ DexError::InvalidMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Market authority for vault signer
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Market oracle, required when the market enforces an oracle band
+    pub oracle: Option<UncheckedAccount<'info>>,
+    
+    /// Integrator (wallet, frontend) routing the swap; earns a share of its fees
+    #[account(mut, has_one = market @ DexError::InvalidAccountState)]
+    pub integrator_fees: Option<Account<'info, IntegratorFees>>,
+    
+    /// Trading seat, required when the market is permissioned
+    #[account(
+        seeds = [b"seat", market.key().as_ref(), trader.key().as_ref()],
+        bump = seat.bump
+    )]
+    pub seat: Option<Account<'info, Seat>>,
+    
+    /// Fee-discount pass: the trader's token account holding the market's pass token or NFT
+    pub fee_pass: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Metaplex metadata of the pass NFT, required when the pass is a collection
+    pub fee_pass_metadata: Option<UncheckedAccount<'info>>,
+    
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Swap wallet tokens against the book in one instruction
+///
+/// The swap is an IOC taker order owned by no trader state: input tokens
+/// move from the trader's wallet into the vault and output tokens back out,
+/// while the makers' side of each fill goes through the event queue as
+/// usual. Only what actually fills is transferred.
+pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let market = &ctx.accounts.market;
+    
+    require!(!market.paused, DexError::MarketPaused);
+    require!(market.accepts_new_orders(), DexError::MarketCancelOnly);
+    require!(!market.is_halted(Clock::get()?.unix_timestamp), DexError::MarketHalted);
+    require!(!market.requires_seat || ctx.accounts.seat.is_some(), DexError::SeatRequired);
+    require!(
+        market.min_resting_slots == 0 && !market.batch_matching,
+        DexError::OperationNotSupported
+    );
+    
+    let side = Side::from_u8(params.side)
+        .ok_or(DexError::InvalidOrderParams)?;
+    require!(market.is_valid_tick(params.price), DexError::PriceNotOnTick);
+    require!(Order::is_valid_origin(params.origin), DexError::InvalidOrderParams);
+    
+    let band_price = load_band_price(market, ctx.accounts.oracle.as_deref(), Clock::get()?.unix_timestamp)?;
+    require!(
+        is_within_oracle_band(params.price, band_price),
+        DexError::OraclePriceDeviationTooLarge
+    );
+    
+    // Buys by quote budget cap the size at what the budget could buy at one tick
+    let size = match params.quote_budget {
+        Some(quote_budget) => {
+            require!(
+                side == Side::Bid && params.size == 0 && quote_budget > 0,
+                DexError::InvalidOrderParams
+            );
+            let max_size = (quote_budget as u128)
+                .checked_mul(market.lot_size as u128)
+                .and_then(|v| v.checked_div(market.tick_size as u128))
+                .ok_or(DexError::MathOverflow)?
+                .min(1_000_000_000_000) as u64;
+            max_size - max_size % market.lot_size
+        }
+        None => params.size,
+    };
+    require!(market.is_valid_lot(size), DexError::OrderSizeTooSmall);
+    
+    if params.integrator_fee_bps > 0 {
+        require!(ctx.accounts.integrator_fees.is_some(), DexError::InvalidAccountState);
+        require!(
+            params.integrator_fee_bps <= ctx.accounts.global_config.max_integrator_fee_bps,
+            DexError::InvalidFeeCalculation
+        );
+    }
+    
+    // Holders of the market's fee-discount pass swap at a lower fee
+    let discount_bps = load_fee_pass_discount(
+        market,
+        &ctx.accounts.trader.key(),
+        ctx.accounts.fee_pass.as_ref(),
+        ctx.accounts.fee_pass_metadata.as_deref(),
+    )?;
+    let taker_fee_bps = ctx.accounts.global_config.discounted_taker_fee_bps(discount_bps, params.integrator_fee_bps);
+    
+    // Load orderbook
+    let orderbook_account_info = &ctx.accounts.orderbook;
+    require!(
+        orderbook_account_info.data_len() >= Orderbook::HEADER_SIZE,
+        DexError::InvalidOrderbookState
+    );
+    
+    let mut orderbook_data = orderbook_account_info.try_borrow_mut_data()?;
+    let mut orderbook = Orderbook::try_deserialize(&mut &orderbook_data[..Orderbook::HEADER_SIZE])?;
+    require!(orderbook.market == market.key(), DexError::InvalidOrderbookState);
+    
+    let clock = Clock::get()?;
+    let order_id = market.order_id_at(&market.key(), 0)?;
+    let mut order = Order::new(
+        order_id,
+        ctx.accounts.trader.key(),
+        side,
+        params.price,
+        size,
+        TimeInForce::IOC,
+        &clock,
+    );
+    order.origin = params.origin;
+    
+    let taker_match = match_taker_order(
+        &mut orderbook,
+        &mut orderbook_data,
+        &mut order,
+        market,
+        &ctx.accounts.global_config,
+        TakerLimits {
+            quote_budget: params.quote_budget,
+            band_price,
+            taker_fee_bps: Some(taker_fee_bps),
+            ..TakerLimits::default()
+        },
+    )?;
+    require!(taker_match.fill_count > 0, DexError::NoMatchingOrders);
+    
+    // Queue the makers' side; the taker side is settled by the transfers below
+    let mut fee = 0u64;
+    let mut rebates = 0u64;
+    let mut event_queue_data = ctx.accounts.event_queue.try_borrow_mut_data()?;
+    let mut event_queue = EventQueue::try_deserialize(&mut &event_queue_data[..EventQueue::HEADER_SIZE])?;
+    let first_price = taker_match.events.first().map_or(0, |event| event.price);
+    let last_price = taker_match.events.last().map_or(0, |event| event.price);
+    let last_size = taker_match.events.last().map_or(0, |event| event.size);
+    let trades: Vec<TradeRecord> = taker_match.events.iter()
+        .map(|event| TradeRecord::from_fill(event, Some(side)))
+        .collect();
+    for mut event in taker_match.events {
+        if side == Side::Bid {
+            fee = fee
+                .checked_add(calculate_fee(event.size, taker_fee_bps))
+                .ok_or(DexError::MathOverflow)?;
+            rebates = rebates
+                .checked_add(event.ask_fee(market.lot_size)?.1)
+                .ok_or(DexError::MathOverflow)?;
+            event.flags &= !(QueueEvent::BID_PENDING | QueueEvent::BID_DONE);
+        } else {
+            fee = fee
+                .checked_add(calculate_fee(event.quote_amount(market.lot_size)?, taker_fee_bps))
+                .ok_or(DexError::MathOverflow)?;
+            rebates = rebates
+                .checked_add(event.bid_fee(market.lot_size)?.1)
+                .ok_or(DexError::MathOverflow)?;
+            event.flags &= !(QueueEvent::ASK_PENDING | QueueEvent::ASK_DONE);
+        }
+        event_queue.push(&mut event_queue_data, event)?;
+    }
+    for removed in [&taker_match.expired, &taker_match.dust] {
+        queue_removed_orders(
+            market,
+            &mut event_queue,
+            &mut event_queue_data,
+            removed,
+            clock.unix_timestamp,
+            &sink,
+        )?;
+    }
+    event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE])?;
+    record_trades(market, ctx.accounts.trade_tape.as_deref(), &trades)?;
+    
+    for matched in taker_match.matched {
+        sink.emit(matched)?;
+    }
+    for filled in taker_match.filled {
+        sink.emit(filled)?;
+    }
+    
+    orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE])?;
+    
+    // Buyers pay quote and receive base net of fees; sellers the reverse
+    let (amount_in, amount_out) = if side == Side::Bid {
+        (taker_match.quote_amount, taker_match.filled_size - fee)
+    } else {
+        (taker_match.filled_size, taker_match.quote_amount - fee)
+    };
+    require!(amount_out >= params.min_amount_out, DexError::SlippageExceeded);
+    
+    let accounts = &ctx.accounts;
+    let (from_wallet, into_vault, in_mint, in_program) = if side == Side::Bid {
+        (&accounts.trader_quote_account, &accounts.quote_vault, &accounts.quote_mint, &accounts.quote_token_program)
+    } else {
+        (&accounts.trader_base_account, &accounts.base_vault, &accounts.base_mint, &accounts.base_token_program)
+    };
+    let (from_vault, to_wallet, out_mint, out_program) = if side == Side::Bid {
+        (&accounts.base_vault, &accounts.trader_base_account, &accounts.base_mint, &accounts.base_token_program)
+    } else {
+        (&accounts.quote_vault, &accounts.trader_quote_account, &accounts.quote_mint, &accounts.quote_token_program)
+    };
+    
+    // Makers are owed the full input, so it must arrive without transfer fees
+    let into_vault_info = into_vault.to_account_info();
+    let vault_before = into_vault.amount;
+    let cpi_accounts = TransferChecked {
+        from: from_wallet.to_account_info(),
+        mint: in_mint.to_account_info(),
+        to: into_vault_info.clone(),
+        authority: accounts.trader.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(in_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, amount_in, in_mint.decimals)?;
+    
+    let vault_after = TokenAccount::try_deserialize(&mut &into_vault_info.try_borrow_data()?[..])?.amount;
+    require!(
+        vault_after.saturating_sub(vault_before) == amount_in,
+        DexError::TransferFeeNotSupported
+    );
+    
+    let from_vault_amount = from_vault.amount;
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"market",
+        &market_id_bytes,
+        &[market.bump],
+    ];
+    let signer = &[seeds];
+    
+    let cpi_accounts = TransferChecked {
+        from: from_vault.to_account_info(),
+        mint: out_mint.to_account_info(),
+        to: to_wallet.to_account_info(),
+        authority: accounts.market_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(out_program.to_account_info(), cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount_out, out_mint.decimals)?;
+    
+    sink.emit(SwapExecuted {
+        market: market.key(),
+        trader: ctx.accounts.trader.key(),
+        side: params.side,
+        amount_in,
+        amount_out,
+        fee,
+        fill_count: taker_match.fill_count,
+        origin: params.origin,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Swap executed: side={:?}, in={}, out={}, fills={}",
+         side, amount_in, amount_out, taker_match.fill_count);
+    
+    // Update market
+    let market_mut = &mut ctx.accounts.market;
+    market_mut.sync_book(&orderbook, &orderbook_data, &sink)?;
+    market_mut.record_fills(
+        taker_match.quote_amount,
+        taker_match.fill_count as u64,
+        first_price,
+        last_price,
+        last_size,
+        clock.unix_timestamp,
+    )?;
+    market_mut.advance_order_sequence(1)?;
+    if let Some(price) = taker_match.breaker_price {
+        let market_key = market_mut.key();
+        market_mut.trip_breaker(market_key, price, clock.unix_timestamp, &sink)?;
+    }
+    if let Some(integrator_fees) = ctx.accounts.integrator_fees.as_mut() {
+        fee -= integrator_fees.accrue_share(fee, params.integrator_fee_bps, side)?;
+    }
+    if side == Side::Bid {
+        market_mut.base_fees_accrued = market_mut.base_fees_accrued
+            .checked_add(fee)
+            .ok_or(DexError::MathOverflow)?;
+        market_mut.reserve_rebates(rebates, 0)?;
+    } else {
+        market_mut.quote_fees_accrued = market_mut.quote_fees_accrued
+            .checked_add(fee)
+            .ok_or(DexError::MathOverflow)?;
+        market_mut.reserve_rebates(0, rebates)?;
+    }
+    
+    // Input credited in full above; output leaves from the pre-transfer vault balance
+    market_mut.credit_vault(side == Side::Ask, amount_in)?;
+    market_mut.debit_vault(side == Side::Bid, amount_out, from_vault_amount)?;
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::TraderState;
+    use crate::test_utils::{account_infos, install_clock, load, token_balance, TestAccount, Venue, VenueVaults};
+    
+    /// Swap for the signer at `trader`, whose base and quote wallets follow it
+    fn swap<'info>(infos: &'info [AccountInfo<'info>], vaults: &VenueVaults, trader: usize, params: SwapParams) -> Result<()> {
+        install_clock();
+        let mut accounts = Swap {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            orderbook: UncheckedAccount::try_from(&infos[Venue::ORDERBOOK]),
+            event_queue: UncheckedAccount::try_from(&infos[Venue::EVENT_QUEUE]),
+            trade_tape: None,
+            global_config: Account::try_from(&infos[Venue::GLOBAL_CONFIG])?,
+            trader: Signer::try_from(&infos[trader])?,
+            trader_base_account: InterfaceAccount::try_from(&infos[trader + 1])?,
+            trader_quote_account: InterfaceAccount::try_from(&infos[trader + 2])?,
+            base_vault: InterfaceAccount::try_from(&infos[vaults.base_vault])?,
+            quote_vault: InterfaceAccount::try_from(&infos[vaults.quote_vault])?,
+            base_mint: InterfaceAccount::try_from(&infos[vaults.base_mint])?,
+            quote_mint: InterfaceAccount::try_from(&infos[vaults.quote_mint])?,
+            market_authority: UncheckedAccount::try_from(&infos[Venue::MARKET]),
+            oracle: None,
+            integrator_fees: None,
+            seat: None,
+            fee_pass: None,
+            fee_pass_metadata: None,
+            base_token_program: Interface::try_from(&infos[vaults.token_program])?,
+            quote_token_program: Interface::try_from(&infos[vaults.token_program])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), params)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    /// Buy `size` base at up to `price`, receiving at least `min_amount_out`
+    fn buy(price: u64, size: u64, min_amount_out: u64) -> SwapParams {
+        SwapParams {
+            side: Side::Bid as u8,
+            price,
+            size,
+            quote_budget: None,
+            min_amount_out,
+            integrator_fee_bps: 0,
+            origin: 0,
+        }
+    }
+    
+    #[test]
+    fn a_swap_settles_the_taker_wallet_and_queues_only_the_maker_side() {
+        let mut venue = Venue::new(|_| {});
+        let vaults = venue.add_vaults(20_000, 0);
+        let maker = venue.add_trader(20_000, 0);
+        venue.accounts.push(TestAccount::signer());
+        let taker = venue.accounts.len() - 1;
+        venue.add_wallet(taker, true, 0);
+        venue.add_wallet(taker, false, 50_000);
+        let infos = account_infos(&mut venue.accounts);
+        Venue::place(&infos, maker, Venue::order(Side::Ask, 2_000, 20_000, TimeInForce::GTC)).unwrap();
+        
+        // Ten lots at 2_000 cost 20_000 quote; the taker keeps the base net of its 30 bps fee
+        swap(&infos, &vaults, taker, buy(2_100, 10_000, 9_970)).unwrap();
+        assert_eq!((token_balance(&infos[taker + 1]), token_balance(&infos[taker + 2])), (9_970, 30_000));
+        assert_eq!((token_balance(&infos[vaults.base_vault]), token_balance(&infos[vaults.quote_vault])), (10_030, 20_000));
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!((market.base_vault_ledger, market.quote_vault_ledger), (10_030, 20_000));
+        assert_eq!((market.base_fees_accrued, market.base_rebates_reserved), (30, 10));
+        
+        let event_queue: EventQueue = load(&infos[Venue::EVENT_QUEUE]);
+        assert_eq!(event_queue.count, 1);
+        let data = infos[Venue::EVENT_QUEUE].try_borrow_data().unwrap();
+        let fill = event_queue.peek_front(&data).unwrap();
+        assert_eq!((fill.size, fill.flags & QueueEvent::BID_PENDING), (10_000, 0));
+        assert_ne!(fill.flags & QueueEvent::ASK_PENDING, 0);
+        drop(data);
+        assert_eq!(load::<TraderState>(&infos[maker + 1]).base_locked, 20_000);
+        
+        // Nothing moves when the fill nets less than the taker's minimum
+        assert_eq!(
+            swap(&infos, &vaults, taker, buy(2_100, 10_000, 9_971)).unwrap_err(),
+            DexError::SlippageExceeded.into()
+        );
+        assert_eq!(token_balance(&infos[taker + 2]), 30_000);
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Competition, GlobalConfig};
+use crate::errors::DexError;
+use crate::events::{event_sink, CompetitionPrizeSwept};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SweepCompetitionPrize<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = authority.key() == global_config.authority @ DexError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        seeds = [b"competition", competition.competition_id.to_le_bytes().as_ref()],
+        bump = competition.bump
+    )]
+    pub competition: Account<'info, Competition>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = recipient_account.mint == competition.prize_mint @ DexError::InvalidMint,
+        constraint = recipient_account.owner == global_config.fee_recipient @ DexError::Unauthorized
+    )]
+    pub recipient_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = competition.prize_vault @ DexError::InvalidMint)]
+    pub prize_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = competition.prize_mint @ DexError::InvalidMint)]
+    pub prize_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Sweep what is left of a competition prize to the fee recipient
+///
+/// Allowed once the claim period is over, or as soon as scoring closes if
+/// nobody recorded any volume, so an unwon prize is never stuck in the vault.
+pub fn handler(ctx: Context<SweepCompetitionPrize>) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let now = Clock::get()?.unix_timestamp;
+    let competition = &ctx.accounts.competition;
+    let scoring_ends_at = competition.scoring_ends_at();
+    require!(
+        now >= scoring_ends_at.saturating_add(Competition::CLAIM_PERIOD_SECONDS) ||
+            (now >= scoring_ends_at && competition.total_score == 0),
+        DexError::WrongCompetitionPhase
+    );
+    let amount = ctx.accounts.prize_vault.amount;
+    require!(amount > 0, DexError::InsufficientFunds);
+    
+    let competition_id_bytes = competition.competition_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"competition",
+        &competition_id_bytes,
+        &[competition.bump],
+    ];
+    let signer = &[seeds];
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.prize_vault.to_account_info(),
+        mint: ctx.accounts.prize_mint.to_account_info(),
+        to: ctx.accounts.recipient_account.to_account_info(),
+        authority: competition.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.prize_mint.decimals)?;
+    
+    sink.emit(CompetitionPrizeSwept {
+        competition: competition.key(),
+        recipient: ctx.accounts.recipient_account.key(),
+        amount,
+        timestamp: now,
+    })?;
+    
+    msg!("Competition prize swept: competition={}, amount={}", competition.key(), amount);
+    
+    Ok(())
+}
@@ -1,14 +1,19 @@
 use anchor_lang::prelude::*;
-use crate::state::Market;
+use crate::state::{EventVerbosity, GlobalConfig, Market, MatchPriceModel};
 use crate::errors::DexError;
-use crate::events::MarketParamsUpdated;
+use crate::events::{event_sink, MarketParamsUpdateStaged};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct UpdateMarketParamsParams {
     pub tick_size: Option<u64>,
     pub lot_size: Option<u64>,
+    pub min_resting_slots: Option<u64>,
+    pub batch_matching: Option<bool>,
+    pub event_verbosity: Option<u8>, // 0 = full, 1 = fills + top of book, 2 = fills only
+    pub match_price_model: Option<u8>, // 0 = maker price, 1 = taker price, 2 = midpoint
 }
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(params: UpdateMarketParamsParams)]
 pub struct UpdateMarketParams<'info> {
@@ -25,21 +30,47 @@ pub struct UpdateMarketParams<'info> {
         seeds = [b"global_config"],
         bump = global_config.bump
     )]
-    pub global_config: Account<'info, crate::state::GlobalConfig>,
+    pub global_config: Account<'info, GlobalConfig>,
     
     pub authority: Signer<'info>,
 }
 
+/// Stage new market parameters behind the update timelock
+///
+/// Unset fields keep their current value. The change is validated now and
+/// activated by apply_pending_update once `UPDATE_DELAY_SECONDS` have passed.
+/// Staging again replaces the pending change and restarts the delay; a call
+/// with no fields set cancels it.
 pub fn handler(ctx: Context<UpdateMarketParams>, params: UpdateMarketParamsParams) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
     let market = &mut ctx.accounts.market;
     
+    if params.tick_size.is_none() &&
+        params.lot_size.is_none() &&
+        params.min_resting_slots.is_none() &&
+        params.batch_matching.is_none() &&
+        params.event_verbosity.is_none() &&
+        params.match_price_model.is_none() {
+        market.params_effective_at = 0;
+        msg!("Pending market params update cancelled: market={}", market.key());
+        return Ok(());
+    }
+    
+    market.pending_tick_size = market.tick_size;
+    market.pending_lot_size = market.lot_size;
+    market.pending_min_resting_slots = market.min_resting_slots;
+    market.pending_batch_matching = market.batch_matching;
+    market.pending_event_verbosity = market.event_verbosity;
+    market.pending_match_price_model = market.match_price_model;
+    
     if let Some(tick_size) = params.tick_size {
         require!(tick_size > 0, DexError::InvalidMarketParams);
         require!(
             tick_size <= 1_000_000_000,
             DexError::InvalidMarketParams
         );
-        market.tick_size = tick_size;
+        market.pending_tick_size = tick_size;
     }
     
     if let Some(lot_size) = params.lot_size {
@@ -48,17 +79,54 @@ pub fn handler(ctx: Context<UpdateMarketParams>, params: UpdateMarketParamsParam
             lot_size <= 1_000_000_000_000,
             DexError::InvalidMarketParams
         );
-        market.lot_size = lot_size;
+        market.pending_lot_size = lot_size;
     }
     
-    emit!(MarketParamsUpdated {
+    if let Some(min_resting_slots) = params.min_resting_slots {
+        require!(
+            min_resting_slots <= Market::MAX_RESTING_SLOTS,
+            DexError::InvalidMarketParams
+        );
+        market.pending_min_resting_slots = min_resting_slots;
+    }
+    
+    if let Some(batch_matching) = params.batch_matching {
+        market.pending_batch_matching = batch_matching;
+    }
+    
+    if let Some(event_verbosity) = params.event_verbosity {
+        require!(
+            EventVerbosity::from_u8(event_verbosity).is_some(),
+            DexError::InvalidMarketParams
+        );
+        market.pending_event_verbosity = event_verbosity;
+    }
+    
+    if let Some(match_price_model) = params.match_price_model {
+        require!(
+            MatchPriceModel::from_u8(match_price_model).is_some(),
+            DexError::InvalidMarketParams
+        );
+        market.pending_match_price_model = match_price_model;
+    }
+    
+    market.params_effective_at = clock.unix_timestamp
+        .checked_add(GlobalConfig::UPDATE_DELAY_SECONDS)
+        .ok_or(DexError::MathOverflow)?;
+    
+    sink.emit(MarketParamsUpdateStaged {
         market: market.key(),
         tick_size: params.tick_size,
         lot_size: params.lot_size,
-        timestamp: Clock::get()?.unix_timestamp,
-    });
+        min_resting_slots: params.min_resting_slots,
+        batch_matching: params.batch_matching,
+        event_verbosity: params.event_verbosity,
+        match_price_model: params.match_price_model,
+        effective_at: market.params_effective_at,
+        timestamp: clock.unix_timestamp,
+    })?;
     
-    msg!("Market params updated: market={}", market.key());
+    msg!("Market params update staged: market={}, effective_at={}", market.key(), market.params_effective_at);
     
     Ok(())
 }
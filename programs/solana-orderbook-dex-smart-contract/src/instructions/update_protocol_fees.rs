@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::state::GlobalConfig;
 use crate::errors::DexError;
+use crate::events::{event_sink, ProtocolFeesUpdateStaged};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 pub struct UpdateProtocolFees<'info> {
     #[account(
@@ -15,25 +17,52 @@ pub struct UpdateProtocolFees<'info> {
     pub authority: Signer<'info>,
 }
 
+/// Stage a new fee schedule behind the update timelock
+///
+/// Unset fields keep their current value. The change is validated now and
+/// activated by apply_pending_update once `UPDATE_DELAY_SECONDS` have passed.
+/// Staging again replaces the pending schedule and restarts the delay; a call
+/// with no fields set cancels it.
 pub fn handler(
     ctx: Context<UpdateProtocolFees>,
-    maker_fee_bps: Option<u16>,
+    maker_fee_bps: Option<i16>,
     taker_fee_bps: Option<u16>,
+    max_integrator_fee_bps: Option<u16>,
 ) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
     let global_config = &mut ctx.accounts.global_config;
     
-    if let Some(fee) = maker_fee_bps {
-        require!(fee <= 1000, DexError::InvalidFeeCalculation); // Max 10%
-        global_config.maker_fee_bps = fee;
+    if maker_fee_bps.is_none() && taker_fee_bps.is_none() && max_integrator_fee_bps.is_none() {
+        global_config.fees_effective_at = 0;
+        msg!("Pending protocol fee update cancelled");
+        return Ok(());
     }
     
-    if let Some(fee) = taker_fee_bps {
-        require!(fee <= 1000, DexError::InvalidFeeCalculation); // Max 10%
-        global_config.taker_fee_bps = fee;
-    }
+    let maker_fee_bps = maker_fee_bps.unwrap_or(global_config.maker_fee_bps);
+    let taker_fee_bps = taker_fee_bps.unwrap_or(global_config.taker_fee_bps);
+    let max_integrator_fee_bps = max_integrator_fee_bps.unwrap_or(global_config.max_integrator_fee_bps);
+    
+    // Max 10% each; rebates plus integrator shares must fit in the taker fee
+    GlobalConfig::validate_fee_schedule(maker_fee_bps, taker_fee_bps, max_integrator_fee_bps)?;
+    
+    global_config.pending_maker_fee_bps = maker_fee_bps;
+    global_config.pending_taker_fee_bps = taker_fee_bps;
+    global_config.pending_max_integrator_fee_bps = max_integrator_fee_bps;
+    global_config.fees_effective_at = clock.unix_timestamp
+        .checked_add(GlobalConfig::UPDATE_DELAY_SECONDS)
+        .ok_or(DexError::MathOverflow)?;
+    
+    sink.emit(ProtocolFeesUpdateStaged {
+        maker_fee_bps,
+        taker_fee_bps,
+        max_integrator_fee_bps,
+        effective_at: global_config.fees_effective_at,
+        timestamp: clock.unix_timestamp,
+    })?;
     
-    msg!("Protocol fees updated: maker={}bps, taker={}bps", 
-         global_config.maker_fee_bps, global_config.taker_fee_bps);
+    msg!("Protocol fee update staged: maker={}bps, taker={}bps, max_integrator={}bps, effective_at={}", 
+         maker_fee_bps, taker_fee_bps, max_integrator_fee_bps, global_config.fees_effective_at);
     
     Ok(())
 }
@@ -0,0 +1,215 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalConfig, Market};
+use crate::errors::DexError;
+use crate::events::{event_sink, RiskParamsUpdateStaged};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateRiskParamsParams {
+    pub oracle_max_deviation_bps: Option<u16>,
+    pub oracle_max_staleness: Option<u64>,
+    pub peg_band_bps: Option<u16>,
+    pub max_order_notional: Option<u64>, // 0 = unlimited
+    pub min_notional: Option<u64>, // Quote units, 0 = no minimum
+    pub dust_threshold: Option<u64>, // Base units, 0 = off
+    pub max_side_notional: Option<u64>, // 0 = unlimited
+    pub max_open_notional: Option<u64>, // 0 = unlimited
+    pub breaker_move_bps: Option<u16>, // 0 = off
+    pub breaker_window_seconds: Option<i64>,
+    pub breaker_cooldown_seconds: Option<i64>,
+    pub max_cancel_to_fill_ratio: Option<u16>, // Cancels per fill, 0 = off
+    pub placement_deposit_lamports: Option<u32>, // Refundable deposit per resting order, 0 = off
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(params: UpdateRiskParamsParams)]
+pub struct UpdateRiskParams<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = authority.key() == global_config.risk_authority || 
+                     authority.key() == global_config.authority ||
+                     market.is_operator(&authority.key(), Market::OPERATOR_CAN_UPDATE_RISK) @ DexError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Stage new risk limits and circuit breaker settings behind the update timelock
+///
+/// Unset fields keep their current value. The change is validated now and
+/// activated by apply_pending_update once `UPDATE_DELAY_SECONDS` have passed.
+/// Staging again replaces the pending change and restarts the delay; a call
+/// with no fields set cancels it. A halted market is resumed early with
+/// reset_circuit_breaker, which is not timelocked.
+pub fn handler(ctx: Context<UpdateRiskParams>, params: UpdateRiskParamsParams) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+    
+    let stages_oracle = params.oracle_max_deviation_bps.is_some() ||
+        params.oracle_max_staleness.is_some();
+    let stages_risk = params.peg_band_bps.is_some() ||
+        params.max_order_notional.is_some() ||
+        params.min_notional.is_some() ||
+        params.dust_threshold.is_some() ||
+        params.max_side_notional.is_some() ||
+        params.max_open_notional.is_some() ||
+        params.breaker_move_bps.is_some() ||
+        params.breaker_window_seconds.is_some() ||
+        params.breaker_cooldown_seconds.is_some() ||
+        params.max_cancel_to_fill_ratio.is_some() ||
+        params.placement_deposit_lamports.is_some();
+    
+    if !stages_oracle && !stages_risk {
+        market.risk_effective_at = 0;
+        msg!("Pending risk params update cancelled: market={}", market.key());
+        return Ok(());
+    }
+    
+    let effective_at = clock.unix_timestamp
+        .checked_add(GlobalConfig::UPDATE_DELAY_SECONDS)
+        .ok_or(DexError::MathOverflow)?;
+    
+    // The oracle band is part of the oracle configuration, so it is staged
+    // there, on top of any oracle change already pending
+    if stages_oracle {
+        if market.oracle_effective_at == 0 {
+            market.pending_oracle = market.oracle;
+            market.pending_oracle_type = market.oracle_type;
+            market.pending_oracle_max_staleness = market.oracle_max_staleness;
+            market.pending_oracle_max_deviation_bps = market.oracle_max_deviation_bps;
+        }
+        require!(market.pending_oracle != Pubkey::default(), DexError::OraclePriceNotAvailable);
+        
+        if let Some(deviation_bps) = params.oracle_max_deviation_bps {
+            require!(
+                deviation_bps > 0 && deviation_bps <= 10_000,
+                DexError::InvalidMarketParams
+            );
+            market.pending_oracle_max_deviation_bps = deviation_bps;
+        }
+        
+        if let Some(staleness) = params.oracle_max_staleness {
+            require!(staleness > 0, DexError::InvalidMarketParams);
+            market.pending_oracle_max_staleness = staleness;
+        }
+        
+        market.oracle_effective_at = effective_at;
+    }
+    
+    if stages_risk {
+        market.pending_peg_band_bps = market.peg_band_bps;
+        market.pending_max_order_notional = market.max_order_notional;
+        market.pending_min_notional = market.min_notional;
+        market.pending_dust_threshold = market.dust_threshold;
+        market.pending_max_side_notional = market.max_side_notional;
+        market.pending_max_open_notional = market.max_open_notional;
+        market.pending_breaker_move_bps = market.breaker_move_bps;
+        market.pending_breaker_window_seconds = market.breaker_window_seconds;
+        market.pending_breaker_cooldown_seconds = market.breaker_cooldown_seconds;
+        market.pending_max_cancel_to_fill_ratio = market.max_cancel_to_fill_ratio;
+        market.pending_placement_deposit_lamports = market.placement_deposit_lamports;
+        
+        if let Some(band_bps) = params.peg_band_bps {
+            require!(market.peg_price > 0, DexError::InvalidMarketParams);
+            require!(
+                band_bps > 0 && band_bps <= 10_000,
+                DexError::InvalidMarketParams
+            );
+            market.pending_peg_band_bps = band_bps;
+        }
+        
+        if let Some(max_notional) = params.max_order_notional {
+            market.pending_max_order_notional = max_notional;
+        }
+        
+        if let Some(min_notional) = params.min_notional {
+            market.pending_min_notional = min_notional;
+        }
+        require!(
+            market.pending_max_order_notional == 0 ||
+                market.pending_min_notional <= market.pending_max_order_notional,
+            DexError::InvalidMarketParams
+        );
+        
+        // Applies to remainders left by later fills; resting orders are not swept
+        if let Some(dust_threshold) = params.dust_threshold {
+            market.pending_dust_threshold = dust_threshold;
+        }
+        
+        // Caps only gate new resting orders; orders already on the book stay
+        if let Some(max_side_notional) = params.max_side_notional {
+            market.pending_max_side_notional = max_side_notional;
+        }
+        
+        if let Some(max_open_notional) = params.max_open_notional {
+            market.pending_max_open_notional = max_open_notional;
+        }
+        
+        // A fill further than `breaker_move_bps` from the first fill of the
+        // window halts trading for the cooldown; a zero move turns the breaker off
+        if let Some(move_bps) = params.breaker_move_bps {
+            market.pending_breaker_move_bps = move_bps;
+        }
+        if let Some(window_seconds) = params.breaker_window_seconds {
+            market.pending_breaker_window_seconds = window_seconds;
+        }
+        if let Some(cooldown_seconds) = params.breaker_cooldown_seconds {
+            market.pending_breaker_cooldown_seconds = cooldown_seconds;
+        }
+        if market.pending_breaker_move_bps > 0 {
+            require!(market.pending_breaker_move_bps <= 10_000, DexError::InvalidMarketParams);
+            require!(
+                market.pending_breaker_window_seconds > 0 && market.pending_breaker_cooldown_seconds > 0,
+                DexError::InvalidMarketParams
+            );
+        } else {
+            market.pending_breaker_window_seconds = 0;
+            market.pending_breaker_cooldown_seconds = 0;
+        }
+        
+        // Only gates new resting orders; cancelling is never refused
+        if let Some(max_ratio) = params.max_cancel_to_fill_ratio {
+            market.pending_max_cancel_to_fill_ratio = max_ratio;
+        }
+        
+        // Orders already resting keep the deposit they were placed with
+        if let Some(deposit) = params.placement_deposit_lamports {
+            market.pending_placement_deposit_lamports = deposit;
+        }
+        
+        market.risk_effective_at = effective_at;
+    }
+    
+    sink.emit(RiskParamsUpdateStaged {
+        market: market.key(),
+        oracle_max_deviation_bps: params.oracle_max_deviation_bps,
+        oracle_max_staleness: params.oracle_max_staleness,
+        peg_band_bps: params.peg_band_bps,
+        max_order_notional: params.max_order_notional,
+        min_notional: params.min_notional,
+        dust_threshold: params.dust_threshold,
+        max_side_notional: params.max_side_notional,
+        max_open_notional: params.max_open_notional,
+        breaker_move_bps: params.breaker_move_bps,
+        breaker_window_seconds: params.breaker_window_seconds,
+        breaker_cooldown_seconds: params.breaker_cooldown_seconds,
+        max_cancel_to_fill_ratio: params.max_cancel_to_fill_ratio,
+        placement_deposit_lamports: params.placement_deposit_lamports,
+        effective_at,
+        timestamp: clock.unix_timestamp,
+    })?;
+    
+    msg!("Risk params update staged: market={}, effective_at={}", market.key(), effective_at);
+    
+    Ok(())
+}
@@ -1,35 +1,45 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, Mint};
-use crate::state::{Market, TraderState};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Market, TraderState, WithdrawalAllowlist};
 use crate::errors::DexError;
-use crate::events::WithdrawEvent;
+use crate::events::{event_sink, WithdrawEvent};
 
+#[cfg_attr(feature = "event-cpi", event_cpi)]
 #[derive(Accounts)]
 #[instruction(amount: u64)]
 pub struct Withdraw<'info> {
     #[account(
+        mut,
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
         bump = market.bump
     )]
     pub market: Account<'info, Market>,
     
     #[account(
+        mut,
         seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
         bump = trader_state.bump,
         constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
     )]
     pub trader_state: Account<'info, TraderState>,
     
+    /// Required when the trader has enabled a withdrawal allowlist
+    #[account(
+        seeds = [b"withdrawal_allowlist", trader_state.key().as_ref()],
+        bump = withdrawal_allowlist.bump
+    )]
+    pub withdrawal_allowlist: Option<Account<'info, WithdrawalAllowlist>>,
+    
     #[account(mut)]
     pub trader: Signer<'info>,
     
     #[account(mut)]
-    pub trader_token_account: Account<'info, TokenAccount>,
+    pub trader_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
@@ -38,10 +48,11 @@ pub struct Withdraw<'info> {
     /// CHECK: Market authority for vault signer
     pub market_authority: UncheckedAccount<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
     require!(amount > 0, DexError::InvalidOrderParams);
     
     let market = &ctx.accounts.market;
@@ -72,8 +83,31 @@ pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
     
     require!(available >= amount, DexError::InsufficientFunds);
     
+    // Locked-down accounts cannot withdraw until the cooldown ends
+    require!(
+        !trader_state.is_locked(Clock::get()?.unix_timestamp),
+        DexError::AccountLocked
+    );
+    
+    // Large withdrawals from co-signed accounts go through request_withdrawal
+    require!(
+        !trader_state.requires_co_signature(amount),
+        DexError::CoSignatureRequired
+    );
+    
+    // Locked accounts may only withdraw to allowlisted destinations
+    if trader_state.withdrawal_allowlist_enabled {
+        let allowlist = ctx.accounts.withdrawal_allowlist
+            .as_ref()
+            .ok_or(DexError::WithdrawalDestinationNotAllowed)?;
+        require!(
+            allowlist.allows(&ctx.accounts.trader_token_account.key()),
+            DexError::WithdrawalDestinationNotAllowed
+        );
+    }
+    
     // Update trader state
-    let mut trader_state_mut = ctx.accounts.trader_state.as_mut();
+    let trader_state_mut = &mut ctx.accounts.trader_state;
     
     if is_base {
         trader_state_mut.base_available = trader_state_mut.base_available
@@ -85,38 +119,179 @@ pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
             .ok_or(DexError::MathUnderflow)?;
     }
     
+    // Withdrawals soon after a deposit pay the early-withdrawal fee (or wait)
+    let fee = market.early_withdrawal_fee(trader_state_mut.last_deposit_at, amount, Clock::get()?.unix_timestamp)?;
+    let payout = amount - fee;
+    
     // Transfer tokens from vault to trader
-    let seeds = &[
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
         b"market",
-        &market.market_id.to_le_bytes(),
+        &market_id_bytes,
         &[market.bump],
     ];
-    let signer = &[&seeds[..]];
+    let signer = &[seeds];
     
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
         to: ctx.accounts.trader_token_account.to_account_info(),
         authority: ctx.accounts.market_authority.to_account_info(),
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    anchor_spl::token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, payout, ctx.accounts.mint.decimals)?;
     
-    emit!(WithdrawEvent {
+    sink.emit(WithdrawEvent {
         trader: ctx.accounts.trader.key(),
         market: market.key(),
         mint: ctx.accounts.mint.key(),
         amount,
+        fee,
         new_balance: if is_base {
             trader_state_mut.base_available
         } else {
             trader_state_mut.quote_available
         },
         timestamp: Clock::get()?.unix_timestamp,
-    });
+    })?;
     
     msg!("Withdraw: trader={}, mint={}, amount={}", 
          ctx.accounts.trader.key(), ctx.accounts.mint.key(), amount);
     
+    // Vault amount is still the pre-transfer balance
+    let market = &mut ctx.accounts.market;
+    market.debit_vault(is_base, payout, ctx.accounts.vault.amount)?;
+    market.accrue_fee(is_base, fee)?;
+    
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{account_infos, install_clock, load, token_balance, zeroed, TestAccount, Venue, VenueVaults, NOW};
+    
+    /// Withdraw `amount` of base or quote for the trader whose signer is at
+    /// `trader` into the token account at `wallet`
+    fn withdraw<'info>(
+        infos: &'info [AccountInfo<'info>],
+        vaults: &VenueVaults,
+        trader: usize,
+        wallet: usize,
+        allowlist: Option<usize>,
+        amount: u64,
+    ) -> Result<()> {
+        install_clock();
+        let trader_token_account = InterfaceAccount::<TokenAccount>::try_from(&infos[wallet])?;
+        let (vault, mint) = if trader_token_account.mint == *infos[vaults.base_mint].key {
+            (vaults.base_vault, vaults.base_mint)
+        } else {
+            (vaults.quote_vault, vaults.quote_mint)
+        };
+        let mut accounts = Withdraw {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            trader_state: Account::try_from(&infos[trader + 1])?,
+            withdrawal_allowlist: allowlist.map(|index| Account::try_from(&infos[index])).transpose()?,
+            trader: Signer::try_from(&infos[trader])?,
+            trader_token_account,
+            vault: InterfaceAccount::try_from(&infos[vault])?,
+            mint: InterfaceAccount::try_from(&infos[mint])?,
+            market_authority: UncheckedAccount::try_from(&infos[Venue::MARKET]),
+            token_program: Interface::try_from(&infos[vaults.token_program])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), amount)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn withdrawals_at_the_threshold_need_the_co_signer() {
+        let mut venue = Venue::new(|_| {});
+        let vaults = venue.add_vaults(0, 10_000);
+        let trader = venue.add_trader(0, 10_000);
+        let wallet = venue.add_wallet(trader, false, 0);
+        let infos = account_infos(&mut venue.accounts);
+        let mut trader_state = Account::<TraderState>::try_from(&infos[trader + 1]).unwrap();
+        trader_state.withdrawal_co_signer = Pubkey::new_unique();
+        trader_state.co_sign_threshold = 5_000;
+        trader_state.exit(&crate::ID).unwrap();
+        
+        // The hot key alone moves small amounts; larger ones go through request_withdrawal
+        withdraw(&infos, &vaults, trader, wallet, None, 4_999).unwrap();
+        assert_eq!(
+            withdraw(&infos, &vaults, trader, wallet, None, 5_000).unwrap_err(),
+            DexError::CoSignatureRequired.into()
+        );
+        assert_eq!(load::<TraderState>(&infos[trader + 1]).quote_available, 5_001);
+        assert_eq!(load::<Market>(&infos[Venue::MARKET]).quote_vault_ledger, 5_001);
+    }
+    
+    #[test]
+    fn an_allowlisted_account_only_withdraws_to_its_destinations() {
+        let mut venue = Venue::new(|_| {});
+        let vaults = venue.add_vaults(0, 10_000);
+        let trader = venue.add_trader(0, 10_000);
+        let allowed = venue.add_wallet(trader, false, 0);
+        let other = venue.add_wallet(trader, false, 0);
+        let mut allowlist: WithdrawalAllowlist = zeroed(WithdrawalAllowlist::SIZE);
+        allowlist.trader_state = venue.accounts[trader + 1].key;
+        allowlist.destinations[0] = venue.accounts[allowed].key;
+        venue.accounts.push(TestAccount::program_owned(&allowlist));
+        let allowlist = venue.accounts.len() - 1;
+        let infos = account_infos(&mut venue.accounts);
+        let mut trader_state = Account::<TraderState>::try_from(&infos[trader + 1]).unwrap();
+        trader_state.withdrawal_allowlist_enabled = true;
+        trader_state.exit(&crate::ID).unwrap();
+        
+        for (wallet, allowlist) in [(allowed, None), (other, Some(allowlist))] {
+            assert_eq!(
+                withdraw(&infos, &vaults, trader, wallet, allowlist, 1_000).unwrap_err(),
+                DexError::WithdrawalDestinationNotAllowed.into()
+            );
+        }
+        withdraw(&infos, &vaults, trader, allowed, Some(allowlist), 1_000).unwrap();
+        assert_eq!(load::<TraderState>(&infos[trader + 1]).quote_available, 9_000);
+    }
+    
+    #[test]
+    fn withdrawals_inside_the_cooldown_pay_the_early_fee_or_wait() {
+        let mut venue = Venue::new(|market| market.withdrawal_cooldown_seconds = 3_600);
+        let vaults = venue.add_vaults(0, 30_000);
+        let trader = venue.add_trader(0, 30_000);
+        let wallet = venue.add_wallet(trader, false, 0);
+        let infos = account_infos(&mut venue.accounts);
+        let set_last_deposit = |at: i64| {
+            let mut trader_state = Account::<TraderState>::try_from(&infos[trader + 1]).unwrap();
+            trader_state.last_deposit_at = at;
+            trader_state.exit(&crate::ID).unwrap();
+        };
+        let set_fee = |bps: u16| {
+            let mut market = Account::<Market>::try_from(&infos[Venue::MARKET]).unwrap();
+            market.early_withdrawal_fee_bps = bps;
+            market.exit(&crate::ID).unwrap();
+        };
+        set_last_deposit(NOW - 600);
+        
+        // Without a fee the funds stay until the cooldown runs out
+        assert_eq!(
+            withdraw(&infos, &vaults, trader, wallet, None, 10_000).unwrap_err(),
+            DexError::WithdrawalCooldownActive.into()
+        );
+        
+        // With one, 50 bps of the amount stays behind as protocol fees
+        set_fee(50);
+        withdraw(&infos, &vaults, trader, wallet, None, 10_000).unwrap();
+        assert_eq!(token_balance(&infos[wallet]), 9_950);
+        let market: Market = load(&infos[Venue::MARKET]);
+        assert_eq!((market.quote_fees_accrued, market.quote_vault_ledger), (50, 20_050));
+        
+        set_last_deposit(NOW - 3_600);
+        withdraw(&infos, &vaults, trader, wallet, None, 10_000).unwrap();
+        assert_eq!(token_balance(&infos[wallet]), 19_950);
+        assert_eq!(load::<TraderState>(&infos[trader + 1]).quote_available, 10_000);
+    }
+}
@@ -0,0 +1,301 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Market, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, WithdrawEvent};
+
+/// Remaining accounts per leg: market, trader_state, vault, mint, trader_token_account, token_program
+pub const WITHDRAW_LEG_ACCOUNTS: usize = 6;
+
+/// Maximum number of legs in a single withdraw_multi
+pub const MAX_WITHDRAW_LEGS: usize = 8;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct WithdrawMulti<'info> {
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    // Remaining accounts: WITHDRAW_LEG_ACCOUNTS per leg, see above
+}
+
+/// Sweep the trader's whole available balance from several markets at once
+///
+/// Each leg withdraws one token of one market; pass two legs to empty both
+/// sides. Legs are applied one after another, so a market may appear twice.
+/// Legs with nothing available are skipped. The same restrictions as
+/// withdraw apply, except that accounts with a withdrawal allowlist must use
+/// withdraw, which takes the allowlist.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawMulti<'info>>,
+) -> Result<()> {
+    let sink = event_sink!(ctx);
+    let legs = ctx.remaining_accounts.chunks_exact(WITHDRAW_LEG_ACCOUNTS);
+    require!(
+        legs.len() > 0 &&
+            legs.remainder().is_empty() &&
+            legs.len() <= MAX_WITHDRAW_LEGS,
+        DexError::InvalidInstruction
+    );
+    
+    let trader = ctx.accounts.trader.key();
+    let now = Clock::get()?.unix_timestamp;
+    let mut swept = 0u8;
+    
+    for leg in legs.clone() {
+        let mut market = Account::<Market>::try_from(&leg[0])?;
+        let mut trader_state = Account::<TraderState>::try_from(&leg[1])?;
+        let vault = InterfaceAccount::<TokenAccount>::try_from(&leg[2])?;
+        let mint = InterfaceAccount::<Mint>::try_from(&leg[3])?;
+        let token_program = Interface::<TokenInterface>::try_from(&leg[5])?;
+        
+        require!(
+            trader_state.trader == trader && trader_state.market == market.key(),
+            DexError::Unauthorized
+        );
+        
+        // Validate mint and vault
+        let is_base = mint.key() == market.base_mint;
+        require!(is_base || mint.key() == market.quote_mint, DexError::InvalidMint);
+        let expected_vault = if is_base { market.base_vault } else { market.quote_vault };
+        require!(vault.key() == expected_vault, DexError::InvalidMint);
+        
+        let available = if is_base {
+            trader_state.base_available
+        } else {
+            trader_state.quote_available
+        };
+        if available == 0 {
+            continue;
+        }
+        
+        require!(!trader_state.is_locked(now), DexError::AccountLocked);
+        require!(
+            !trader_state.requires_co_signature(available),
+            DexError::CoSignatureRequired
+        );
+        require!(
+            !trader_state.withdrawal_allowlist_enabled,
+            DexError::WithdrawalDestinationNotAllowed
+        );
+        
+        if is_base {
+            trader_state.base_available = 0;
+        } else {
+            trader_state.quote_available = 0;
+        }
+        
+        // Withdrawals soon after a deposit pay the early-withdrawal fee (or wait)
+        let fee = market.early_withdrawal_fee(trader_state.last_deposit_at, available, now)?;
+        let payout = available - fee;
+        
+        // The market PDA owns its vaults
+        let market_id_bytes = market.market_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            b"market",
+            &market_id_bytes,
+            &[market.bump],
+        ];
+        let signer = &[seeds];
+        
+        let cpi_accounts = TransferChecked {
+            from: vault.to_account_info(),
+            mint: mint.to_account_info(),
+            to: leg[4].clone(),
+            authority: leg[0].clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, payout, mint.decimals)?;
+        
+        // Vault amount is still the pre-transfer balance
+        market.debit_vault(is_base, payout, vault.amount)?;
+        market.accrue_fee(is_base, fee)?;
+        
+        sink.emit(WithdrawEvent {
+            trader,
+            market: market.key(),
+            mint: mint.key(),
+            amount: available,
+            fee,
+            new_balance: 0,
+            timestamp: now,
+        })?;
+        
+        // Persist before the next leg, which may load the same accounts
+        market.exit(&crate::ID)?;
+        trader_state.exit(&crate::ID)?;
+        swept += 1;
+    }
+    
+    msg!("Withdraw multi: trader={}, legs={}, swept={}", 
+         trader, legs.len(), swept);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{account_data, install_clock, token_balance, zeroed};
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token;
+    
+    /// The accounts of one quote leg, in remaining-account order
+    struct Leg {
+        keys: [Pubkey; WITHDRAW_LEG_ACCOUNTS],
+        owners: [Pubkey; WITHDRAW_LEG_ACCOUNTS],
+        lamports: [u64; WITHDRAW_LEG_ACCOUNTS],
+        data: [Vec<u8>; WITHDRAW_LEG_ACCOUNTS],
+    }
+    
+    impl Leg {
+        fn new(trader: Pubkey, quote_available: u64) -> Self {
+            let keys = [
+                Pubkey::new_unique(), // market
+                Pubkey::new_unique(), // trader_state
+                Pubkey::new_unique(), // vault
+                Pubkey::new_unique(), // mint
+                Pubkey::new_unique(), // trader_token_account
+                spl_token::ID,
+            ];
+            
+            let mut market: Market = zeroed(Market::SIZE);
+            market.base_mint = Pubkey::new_unique();
+            market.quote_mint = keys[3];
+            market.quote_vault = keys[2];
+            market.quote_vault_ledger = quote_available;
+            
+            let mut trader_state: TraderState = zeroed(TraderState::SIZE);
+            trader_state.trader = trader;
+            trader_state.market = keys[0];
+            trader_state.quote_available = quote_available;
+            
+            let mut vault = vec![0u8; spl_token::state::Account::LEN];
+            spl_token::state::Account {
+                mint: keys[3],
+                owner: keys[0],
+                amount: quote_available,
+                state: spl_token::state::AccountState::Initialized,
+                ..Default::default()
+            }.pack_into_slice(&mut vault);
+            
+            let mut wallet = vec![0u8; spl_token::state::Account::LEN];
+            spl_token::state::Account {
+                mint: keys[3],
+                owner: trader,
+                state: spl_token::state::AccountState::Initialized,
+                ..Default::default()
+            }.pack_into_slice(&mut wallet);
+            
+            let mut mint = vec![0u8; spl_token::state::Mint::LEN];
+            spl_token::state::Mint {
+                decimals: 6,
+                is_initialized: true,
+                ..Default::default()
+            }.pack_into_slice(&mut mint);
+            
+            Self {
+                keys,
+                owners: [crate::ID, crate::ID, spl_token::ID, spl_token::ID, spl_token::ID, Pubkey::default()],
+                lamports: [1_000_000; WITHDRAW_LEG_ACCOUNTS],
+                data: [account_data(&market), account_data(&trader_state), vault, mint, wallet, Vec::new()],
+            }
+        }
+        
+        fn account_infos(&mut self) -> Vec<AccountInfo<'_>> {
+            let mut infos = Vec::new();
+            for (i, (lamports, data)) in self.lamports.iter_mut().zip(self.data.iter_mut()).enumerate() {
+                let executable = i == WITHDRAW_LEG_ACCOUNTS - 1;
+                infos.push(AccountInfo::new(&self.keys[i], false, true, lamports, data, &self.owners[i], executable, 0));
+            }
+            infos
+        }
+    }
+    
+    /// The signing trader, then the event authority and program `event-cpi` builds expect
+    struct Caller {
+        keys: [Pubkey; 3],
+        lamports: [u64; 3],
+        data: [Vec<u8>; 3],
+    }
+    
+    impl Caller {
+        fn new() -> Self {
+            Self {
+                keys: [Pubkey::new_unique(), Pubkey::new_unique(), crate::ID],
+                lamports: [1_000_000; 3],
+                data: [Vec::new(), Vec::new(), Vec::new()],
+            }
+        }
+        
+        fn account_infos(&mut self) -> Vec<AccountInfo<'_>> {
+            let [trader, event_authority, program] = &self.keys;
+            let [trader_lamports, event_lamports, program_lamports] = &mut self.lamports;
+            let [trader_data, event_data, program_data] = &mut self.data;
+            vec![
+                AccountInfo::new(trader, true, true, trader_lamports, trader_data, &SYSTEM_PROGRAM, false, 0),
+                AccountInfo::new(event_authority, false, false, event_lamports, event_data, &crate::ID, false, 0),
+                AccountInfo::new(program, false, false, program_lamports, program_data, &SYSTEM_PROGRAM, true, 0),
+            ]
+        }
+    }
+    
+    const SYSTEM_PROGRAM: Pubkey = Pubkey::new_from_array([0; 32]);
+    
+    fn run<'info>(caller: &'info [AccountInfo<'info>], legs: &'info [AccountInfo<'info>]) -> Result<()> {
+        install_clock();
+        let mut accounts = WithdrawMulti {
+            trader: Signer::try_from(&caller[0])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: caller[1].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: caller[2].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, legs, Default::default()))
+    }
+    
+    #[test]
+    fn sweeps_available_balances_and_skips_empty_legs() {
+        let mut caller = Caller::new();
+        let caller = caller.account_infos();
+        let mut funded = Leg::new(*caller[0].key, 500);
+        let mut empty = Leg::new(*caller[0].key, 0);
+        let mut legs = funded.account_infos();
+        legs.extend(empty.account_infos());
+        
+        run(&caller, &legs).unwrap();
+        
+        let trader_state = TraderState::try_deserialize(&mut &legs[1].data.borrow()[..]).unwrap();
+        assert_eq!(trader_state.quote_available, 0);
+        let market = Market::try_deserialize(&mut &legs[0].data.borrow()[..]).unwrap();
+        assert_eq!(market.quote_vault_ledger, 0);
+        assert_eq!(token_balance(&legs[4]), 500);
+    }
+    
+    #[test]
+    fn rejects_incomplete_or_too_many_legs() {
+        let mut caller = Caller::new();
+        let caller = caller.account_infos();
+        let mut leg = Leg::new(*caller[0].key, 500);
+        let legs = leg.account_infos();
+        let too_many: Vec<AccountInfo> = legs.iter()
+            .cycle()
+            .take(WITHDRAW_LEG_ACCOUNTS * (MAX_WITHDRAW_LEGS + 1))
+            .cloned()
+            .collect();
+        
+        for legs in [&legs[..0], &legs[..WITHDRAW_LEG_ACCOUNTS - 1], &too_many[..]] {
+            assert_eq!(run(&caller, legs).err().unwrap(), DexError::InvalidInstruction.into());
+        }
+    }
+    
+    #[test]
+    fn rejects_another_traders_balance() {
+        let mut caller = Caller::new();
+        let caller = caller.account_infos();
+        let mut leg = Leg::new(Pubkey::new_unique(), 500);
+        let legs = leg.account_infos();
+        
+        assert_eq!(run(&caller, &legs).err().unwrap(), DexError::Unauthorized.into());
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TraderState};
+use crate::errors::DexError;
+use crate::events::{event_sink, PlacementDepositsWithdrawn};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct WithdrawPlacementDeposits<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    #[account(mut)]
+    pub trader: Signer<'info>,
+}
+
+/// Take back prepaid placement-deposit lamports not held by resting orders
+pub fn handler(ctx: Context<WithdrawPlacementDeposits>, lamports: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(lamports > 0, DexError::InvalidInstruction);
+    
+    let trader_state = &mut ctx.accounts.trader_state;
+    require!(trader_state.deposit_balance >= lamports, DexError::InsufficientFunds);
+    trader_state.deposit_balance -= lamports;
+    
+    // The trader state is program-owned, so its lamports can be moved directly
+    **trader_state.to_account_info().try_borrow_mut_lamports()? -= lamports;
+    **ctx.accounts.trader.to_account_info().try_borrow_mut_lamports()? += lamports;
+    
+    sink.emit(PlacementDepositsWithdrawn {
+        market: ctx.accounts.market.key(),
+        trader: ctx.accounts.trader.key(),
+        lamports,
+        deposit_balance: trader_state.deposit_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Placement deposits withdrawn: trader={}, lamports={}, balance={}",
+         ctx.accounts.trader.key(), lamports, trader_state.deposit_balance);
+    
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Market, TraderState, WithdrawalAllowlist};
+use crate::errors::DexError;
+use crate::events::{event_sink, WithdrawEvent};
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"trader_state", trader.key().as_ref(), market.key().as_ref()],
+        bump = trader_state.bump,
+        constraint = trader_state.trader == trader.key() @ DexError::Unauthorized
+    )]
+    pub trader_state: Account<'info, TraderState>,
+    
+    /// Required when the trader has enabled a withdrawal allowlist
+    #[account(
+        seeds = [b"withdrawal_allowlist", trader_state.key().as_ref()],
+        bump = withdrawal_allowlist.bump
+    )]
+    pub withdrawal_allowlist: Option<Account<'info, WithdrawalAllowlist>>,
+    
+    /// Receives the unwrapped lamports
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    /// The market's wSOL vault
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = native_mint::ID @ DexError::InvalidMint)]
+    pub native_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Temporary wSOL account, closed to the trader within the instruction
+    #[account(
+        init,
+        payer = trader,
+        token::mint = native_mint,
+        token::authority = market,
+        token::token_program = token_program,
+        seeds = [b"unwrap", trader_state.key().as_ref()],
+        bump
+    )]
+    pub unwrap_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Market authority for vault signer
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Withdraw from a market's wSOL side as native SOL
+///
+/// The wSOL moves from the vault into a temporary account that is closed to
+/// the trader, which unwraps it (and refunds the account's rent) in one step.
+pub fn handler(ctx: Context<WithdrawSol>, amount: u64) -> Result<()> {
+    let sink = event_sink!(ctx);
+    require!(amount > 0, DexError::InvalidOrderParams);
+    
+    let market = &ctx.accounts.market;
+    
+    // One side of the market must be wrapped SOL
+    let is_base = market.base_mint == native_mint::ID;
+    require!(is_base || market.quote_mint == native_mint::ID, DexError::InvalidMint);
+    
+    let expected_vault = if is_base {
+        market.base_vault
+    } else {
+        market.quote_vault
+    };
+    require!(
+        ctx.accounts.vault.key() == expected_vault,
+        DexError::InvalidMint
+    );
+    
+    // Same restrictions as withdraw
+    let trader_state = &mut ctx.accounts.trader_state;
+    require!(
+        !trader_state.is_locked(Clock::get()?.unix_timestamp),
+        DexError::AccountLocked
+    );
+    require!(
+        !trader_state.requires_co_signature(amount),
+        DexError::CoSignatureRequired
+    );
+    
+    // With an allowlist the trader's wallet itself must be allowlisted
+    if trader_state.withdrawal_allowlist_enabled {
+        let allowlist = ctx.accounts.withdrawal_allowlist
+            .as_ref()
+            .ok_or(DexError::WithdrawalDestinationNotAllowed)?;
+        require!(
+            allowlist.allows(&ctx.accounts.trader.key()),
+            DexError::WithdrawalDestinationNotAllowed
+        );
+    }
+    
+    if is_base {
+        trader_state.base_available = trader_state.base_available
+            .checked_sub(amount)
+            .ok_or(DexError::InsufficientFunds)?;
+    } else {
+        trader_state.quote_available = trader_state.quote_available
+            .checked_sub(amount)
+            .ok_or(DexError::InsufficientFunds)?;
+    }
+    
+    // Withdrawals soon after a deposit pay the early-withdrawal fee (or wait)
+    let fee = market.early_withdrawal_fee(trader_state.last_deposit_at, amount, Clock::get()?.unix_timestamp)?;
+    let payout = amount - fee;
+    
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"market",
+        &market_id_bytes,
+        &[market.bump],
+    ];
+    let signer = &[seeds];
+    
+    // Move the wSOL out of the vault, then close the temporary account to unwrap it
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.native_mint.to_account_info(),
+        to: ctx.accounts.unwrap_account.to_account_info(),
+        authority: ctx.accounts.market_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, payout, ctx.accounts.native_mint.decimals)?;
+    
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.unwrap_account.to_account_info(),
+        destination: ctx.accounts.trader.to_account_info(),
+        authority: ctx.accounts.market_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::close_account(cpi_ctx)?;
+    
+    sink.emit(WithdrawEvent {
+        trader: ctx.accounts.trader.key(),
+        market: market.key(),
+        mint: native_mint::ID,
+        amount,
+        fee,
+        new_balance: if is_base {
+            trader_state.base_available
+        } else {
+            trader_state.quote_available
+        },
+        timestamp: Clock::get()?.unix_timestamp,
+    })?;
+    
+    msg!("Withdraw SOL: trader={}, lamports={}", ctx.accounts.trader.key(), amount);
+    
+    // Vault amount is still the pre-transfer balance
+    let market = &mut ctx.accounts.market;
+    market.debit_vault(is_base, payout, ctx.accounts.vault.amount)?;
+    market.accrue_fee(is_base, fee)?;
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::deposit_sol::{self, DepositSol};
+    use crate::test_utils::{account_data, account_infos, install_clock, load, load_data, token_balance, TestAccount, Venue, VenueVaults, NOW};
+    
+    /// A venue quoted in wrapped SOL and a trader holding nothing yet, whose
+    /// signer and state are followed by its unwrap account and the system program
+    fn sol_quoted_venue() -> (Venue, VenueVaults, usize) {
+        let mut venue = Venue::new(|_| {});
+        let vaults = venue.add_vaults(0, 0);
+        let market_key = venue.accounts[Venue::MARKET].key;
+        let quote_vault_key = venue.accounts[vaults.quote_vault].key;
+        venue.accounts[vaults.quote_mint] = TestAccount::mint(9).at(native_mint::ID);
+        venue.accounts[vaults.quote_vault] = TestAccount::wrapped_sol(market_key, 0).at(quote_vault_key);
+        let mut market: Market = load_data(&venue.accounts[Venue::MARKET].data);
+        market.quote_mint = native_mint::ID;
+        venue.accounts[Venue::MARKET].data = account_data(&market);
+        let trader = venue.add_trader(0, 0);
+        venue.accounts.push(TestAccount::wrapped_sol(market_key, 0));
+        venue.accounts.push(TestAccount::program(anchor_lang::system_program::ID));
+        (venue, vaults, trader)
+    }
+    
+    fn deposit<'info>(infos: &'info [AccountInfo<'info>], vaults: &VenueVaults, trader: usize, amount: u64) -> Result<()> {
+        install_clock();
+        let mut accounts = DepositSol {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            trader_state: Account::try_from(&infos[trader + 1])?,
+            trader: Signer::try_from(&infos[trader])?,
+            seat: None,
+            vault: InterfaceAccount::try_from(&infos[vaults.quote_vault])?,
+            token_program: Interface::try_from(&infos[vaults.token_program])?,
+            system_program: Program::try_from(&infos[trader + 3])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        deposit_sol::handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), amount)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    fn withdraw<'info>(infos: &'info [AccountInfo<'info>], vaults: &VenueVaults, trader: usize, amount: u64) -> Result<()> {
+        install_clock();
+        let mut accounts = WithdrawSol {
+            market: Account::try_from(&infos[Venue::MARKET])?,
+            trader_state: Account::try_from(&infos[trader + 1])?,
+            withdrawal_allowlist: None,
+            trader: Signer::try_from(&infos[trader])?,
+            vault: InterfaceAccount::try_from(&infos[vaults.quote_vault])?,
+            native_mint: InterfaceAccount::try_from(&infos[vaults.quote_mint])?,
+            unwrap_account: InterfaceAccount::try_from(&infos[trader + 2])?,
+            market_authority: UncheckedAccount::try_from(&infos[Venue::MARKET]),
+            token_program: Interface::try_from(&infos[vaults.token_program])?,
+            system_program: Program::try_from(&infos[trader + 3])?,
+            #[cfg(feature = "event-cpi")]
+            event_authority: infos[Venue::EVENT_AUTHORITY].clone(),
+            #[cfg(feature = "event-cpi")]
+            program: infos[Venue::PROGRAM].clone(),
+        };
+        handler(Context::new(&crate::ID, &mut accounts, &[], Default::default()), amount)?;
+        accounts.exit(&crate::ID)
+    }
+    
+    #[test]
+    fn lamports_wrap_into_the_vault_and_unwrap_back_to_the_wallet() {
+        let (mut venue, vaults, trader) = sol_quoted_venue();
+        let (wallet_before, unwrap_rent) = (venue.accounts[trader].lamports, venue.accounts[trader + 2].lamports);
+        let infos = account_infos(&mut venue.accounts);
+        
+        deposit(&infos, &vaults, trader, 400_000).unwrap();
+        assert_eq!(infos[trader].lamports(), wallet_before - 400_000);
+        assert_eq!(token_balance(&infos[vaults.quote_vault]), 400_000);
+        let trader_state: TraderState = load(&infos[trader + 1]);
+        assert_eq!((trader_state.quote_available, trader_state.last_deposit_at), (400_000, NOW));
+        
+        // The unwrap account closes into the wallet, returning its rent with the withdrawal
+        withdraw(&infos, &vaults, trader, 150_000).unwrap();
+        assert_eq!(infos[trader].lamports(), wallet_before - 250_000 + unwrap_rent);
+        assert_eq!((infos[trader + 2].lamports(), *infos[trader + 2].owner), (0, anchor_lang::system_program::ID));
+        assert_eq!(token_balance(&infos[vaults.quote_vault]), 250_000);
+        assert_eq!(load::<TraderState>(&infos[trader + 1]).quote_available, 250_000);
+        assert_eq!(load::<Market>(&infos[Venue::MARKET]).quote_vault_ledger, 250_000);
+    }
+}
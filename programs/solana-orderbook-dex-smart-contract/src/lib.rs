@@ -1,10 +1,20 @@
 use anchor_lang::prelude::*;
 
 pub mod errors;
+pub mod event_queue;
 pub mod events;
+pub mod fee_pass;
 pub mod instructions;
+pub mod matching;
+pub mod oracle;
 pub mod orderbook;
+#[cfg(feature = "serde")]
+pub mod schema;
+pub mod settlement_hook;
 pub mod state;
+#[cfg(test)]
+mod test_utils;
+pub mod trade_tape;
 
 use instructions::*;
 
@@ -37,8 +47,18 @@ pub mod solana_orderbook_dex {
         instructions::create_market::handler(ctx, params)
     }
 
+    /// Market creator: Deposit inventory and post a symmetric order ladder
+    /// Optional launch step on an empty book, right after create_market
+    pub fn seed_liquidity(
+        ctx: Context<SeedLiquidity>,
+        params: SeedLiquidityParams,
+    ) -> Result<()> {
+        instructions::seed_liquidity::handler(ctx, params)
+    }
+
     /// Place a limit or market order
     /// Supports IOC, FOK, Post-only, and GTC time-in-force options
+    /// Marketable orders fill against resting liquidity immediately
     pub fn place_order(
         ctx: Context<PlaceOrder>,
         params: PlaceOrderParams,
@@ -46,6 +66,45 @@ pub mod solana_orderbook_dex {
         instructions::place_order::handler(ctx, params)
     }
 
+    /// Deposit what an order needs and place it in one transaction
+    /// Creates the trader state if needed; only the shortfall is transferred
+    pub fn deposit_and_place(
+        ctx: Context<DepositAndPlace>,
+        params: PlaceOrderParams,
+    ) -> Result<()> {
+        instructions::deposit_and_place::handler(ctx, params)
+    }
+
+    /// Post several resting orders in one transaction
+    /// Post-only quotes for market makers; one book load and one funds lock
+    pub fn place_multiple_orders(
+        ctx: Context<PlaceMultipleOrders>,
+        params: PlaceMultipleOrdersParams,
+    ) -> Result<()> {
+        instructions::place_multiple_orders::handler(ctx, params)
+    }
+
+    /// Swap directly from the trader's wallet against the book
+    /// No deposit or trader state needed; fills settle to the wallet atomically
+    pub fn swap(
+        ctx: Context<Swap>,
+        params: SwapParams,
+    ) -> Result<()> {
+        instructions::swap::handler(ctx, params)
+    }
+
+    /// Open a platform's integrator fee account on a market
+    /// Orders and swaps naming it share their taker fees with the platform
+    pub fn register_integrator(ctx: Context<RegisterIntegrator>) -> Result<()> {
+        instructions::register_integrator::handler(ctx)
+    }
+
+    /// Claim a platform's accrued integrator fees
+    /// Pays base and quote shares from the market vaults
+    pub fn claim_integrator_fees(ctx: Context<ClaimIntegratorFees>) -> Result<()> {
+        instructions::claim_integrator_fees::handler(ctx)
+    }
+
     /// Cancel an existing order
     /// Returns unfilled portion to trader's account
     pub fn cancel_order(
@@ -55,6 +114,34 @@ pub mod solana_orderbook_dex {
         instructions::cancel_order::handler(ctx, order_id)
     }
 
+    /// Cancel an order by the client order ID given at placement
+    /// Lets market makers cancel without tracking program-assigned IDs
+    pub fn cancel_order_by_client_id(
+        ctx: Context<CancelOrderByClientId>,
+        client_order_id: u64,
+    ) -> Result<()> {
+        instructions::cancel_order_by_client_id::handler(ctx, client_order_id)
+    }
+
+    /// Cancel up to `limit` of the trader's bids or asks in one call
+    /// Lets market makers pull one side of their quotes at once
+    pub fn cancel_orders_by_side(
+        ctx: Context<CancelOrdersBySide>,
+        side: u8,
+        limit: u8,
+    ) -> Result<()> {
+        instructions::cancel_orders_by_side::handler(ctx, side, limit)
+    }
+
+    /// Change the price and/or size of a resting order in place
+    /// Size reductions at the same price keep time priority
+    pub fn modify_order(
+        ctx: Context<ModifyOrder>,
+        params: ModifyOrderParams,
+    ) -> Result<()> {
+        instructions::modify_order::handler(ctx, params)
+    }
+
     /// Match orders in the orderbook
     /// Can be called by anyone to trigger matching engine
     pub fn match_orders(
@@ -64,10 +151,151 @@ pub mod solana_orderbook_dex {
         instructions::match_orders::handler(ctx, max_iterations)
     }
 
-    /// Settle matched orders and transfer tokens
-    /// Handles atomic token swaps and fee collection
-    pub fn settle(ctx: Context<Settle>, fill_ids: Vec<u128>) -> Result<()> {
-        instructions::settle::handler(ctx, fill_ids)
+    /// Cancel oracle-protected orders once the oracle leaves their band
+    /// Permissionless crank; funds are released through the event queue
+    pub fn cancel_diverged_orders(
+        ctx: Context<CancelDivergedOrders>,
+        limit: u8,
+    ) -> Result<()> {
+        instructions::cancel_diverged_orders::handler(ctx, limit)
+    }
+
+    /// Remove expired good-till-date orders
+    /// Permissionless crank; funds are released through the event queue
+    pub fn prune_expired_orders(
+        ctx: Context<PruneExpiredOrders>,
+        limit: u8,
+    ) -> Result<()> {
+        instructions::prune_expired_orders::handler(ctx, limit)
+    }
+
+    /// Cancel remaining orders on a delisted market
+    /// Permissionless crank; funds are released through the event queue
+    pub fn force_cancel_orders(
+        ctx: Context<ForceCancelOrders>,
+        limit: u8,
+    ) -> Result<()> {
+        instructions::force_cancel_orders::handler(ctx, limit)
+    }
+
+    /// Match crossing orders and settle both sides inline, bypassing the event queue
+    /// Permissionless crank; pass both sides' trader states as remaining accounts
+    pub fn match_and_settle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MatchAndSettle<'info>>,
+        max_iterations: u8,
+    ) -> Result<()> {
+        instructions::match_and_settle::handler(ctx, max_iterations)
+    }
+
+    /// Apply queued fill/out events to the traders' balances
+    /// Permissionless crank; pass the makers' trader states as remaining accounts, earns the crank reward
+    pub fn consume_events<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConsumeEvents<'info>>,
+        max_events: u8,
+    ) -> Result<()> {
+        instructions::consume_events::handler(ctx, max_events)
+    }
+
+    /// Re-emit retained event queue entries in a sequence range
+    /// Read-only; lets indexers that fell behind recover recent fills
+    pub fn replay_events(
+        ctx: Context<ReplayEvents>,
+        from_seq: u64,
+        to_seq: u64,
+    ) -> Result<()> {
+        instructions::replay_events::handler(ctx, from_seq, to_seq)
+    }
+
+    /// Admin: Attach a pre-allocated event queue account to a market
+    /// The account must be owned by the program, zeroed and sized for the ring buffer
+    pub fn init_event_queue(ctx: Context<InitEventQueue>) -> Result<()> {
+        instructions::init_event_queue::handler(ctx)
+    }
+
+    /// Admin: Bind a pre-allocated orderbook account to a market
+    /// The account must be owned by the program, zeroed and sized for `Orderbook::MAX_SIZE`
+    pub fn init_orderbook(ctx: Context<InitOrderbook>) -> Result<()> {
+        instructions::init_orderbook::handler(ctx)
+    }
+
+    /// Admin: Attach a pre-allocated trade tape account to a market
+    /// The account must be owned by the program, zeroed and sized for the ring buffer
+    pub fn init_trade_tape(ctx: Context<InitTradeTape>) -> Result<()> {
+        instructions::init_trade_tape::handler(ctx)
+    }
+
+    /// Deprecated: mark a legacy recorded fill settled (fills now settle via consume_events)
+    /// Trader states are bound to the fill's traders; moves no balances
+    pub fn settle<'info>(
+        ctx: Context<'_, '_, '_, 'info, Settle<'info>>,
+    ) -> Result<()> {
+        instructions::settle::handler(ctx)
+    }
+
+    /// Admin: Archive settled fills into the market's merkle archive root
+    /// Closes the fill accounts and returns their rent to the authority
+    pub fn archive_fills<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ArchiveFills<'info>>,
+    ) -> Result<()> {
+        instructions::archive_fills::handler(ctx)
+    }
+
+    /// Finalize the market's daily statistics epoch and reset its counters
+    /// Permissionless crank, callable once the epoch has ended
+    pub fn roll_epoch(ctx: Context<RollEpoch>) -> Result<()> {
+        instructions::roll_epoch::handler(ctx)
+    }
+
+    /// Emit aggregated book depth at coarse price buckets
+    /// Permissionless crank, rate limited per market
+    pub fn emit_depth_snapshot(
+        ctx: Context<EmitDepthSnapshot>,
+        bucket_ticks: u64,
+        max_buckets: u8,
+    ) -> Result<()> {
+        instructions::emit_depth_snapshot::handler(ctx, bucket_ticks, max_buckets)
+    }
+
+    /// Grow a market's orderbook by `additional_orders` slots via realloc
+    /// Permissionless; the caller pays the added rent
+    pub fn expand_orderbook(
+        ctx: Context<ExpandOrderbook>,
+        additional_orders: u32,
+    ) -> Result<()> {
+        instructions::expand_orderbook::handler(ctx, additional_orders)
+    }
+
+    /// Report a market's book depth, crank backlog and vault/ledger drift
+    /// Read-only view, returned as a Borsh-encoded `MarketHealth` in return data
+    pub fn get_market_health(ctx: Context<GetMarketHealth>) -> Result<()> {
+        instructions::get_market_health::handler(ctx)
+    }
+
+    /// Open a market's premium data feed (L3 snapshot account)
+    /// Permissionless; the payer covers the account's rent
+    pub fn create_data_feed(ctx: Context<CreateDataFeed>) -> Result<()> {
+        instructions::create_data_feed::handler(ctx)
+    }
+
+    /// Deposit lamports that pay for a data feed's refreshes
+    /// Anyone subscribing to the feed can top it up
+    pub fn fund_data_feed(
+        ctx: Context<FundDataFeed>,
+        lamports: u64,
+    ) -> Result<()> {
+        instructions::fund_data_feed::handler(ctx, lamports)
+    }
+
+    /// Rewrite a data feed's snapshot and pay the cranker from its deposits
+    /// Permissionless crank, rate limited per feed
+    pub fn refresh_data_feed(ctx: Context<RefreshDataFeed>) -> Result<()> {
+        instructions::refresh_data_feed::handler(ctx)
+    }
+
+    /// Open the trader's slot index for O(1) cancels
+    /// Optional; pass it to place_order, modify_order and cancel_order
+    pub fn init_open_orders(ctx: Context<InitOpenOrders>) -> Result<()> {
+        instructions::init_open_orders::handler(ctx)
     }
 
     /// Deposit tokens into the DEX for trading
@@ -79,6 +307,15 @@ pub mod solana_orderbook_dex {
         instructions::deposit::handler(ctx, amount)
     }
 
+    /// Deposit native SOL into a market with a wSOL side
+    /// Lamports are wrapped into the vault; no wSOL account needed
+    pub fn deposit_sol(
+        ctx: Context<DepositSol>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_sol::handler(ctx, amount)
+    }
+
     /// Withdraw tokens from the DEX
     /// Transfers available balance back to trader
     pub fn withdraw(
@@ -88,7 +325,128 @@ pub mod solana_orderbook_dex {
         instructions::withdraw::handler(ctx, amount)
     }
 
-    /// Admin: Update market parameters
+    /// Sweep available balances from several markets in one instruction
+    /// Legs (market, trader state, vault, mint, destination, token program) go in remaining accounts
+    pub fn withdraw_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawMulti<'info>>,
+    ) -> Result<()> {
+        instructions::withdraw_multi::handler(ctx)
+    }
+
+    /// Withdraw from a market's wSOL side as native SOL
+    /// Unwraps through a temporary account closed to the trader
+    pub fn withdraw_sol(
+        ctx: Context<WithdrawSol>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_sol::handler(ctx, amount)
+    }
+
+    /// Prepay lamports for the refundable deposit held per resting order
+    /// Deposits return to this balance when their orders leave the book
+    pub fn fund_placement_deposits(
+        ctx: Context<FundPlacementDeposits>,
+        lamports: u64,
+    ) -> Result<()> {
+        instructions::fund_placement_deposits::handler(ctx, lamports)
+    }
+
+    /// Withdraw prepaid placement-deposit lamports
+    /// Deposits held by resting orders stay until the orders leave the book
+    pub fn withdraw_placement_deposits(
+        ctx: Context<WithdrawPlacementDeposits>,
+        lamports: u64,
+    ) -> Result<()> {
+        instructions::withdraw_placement_deposits::handler(ctx, lamports)
+    }
+
+    /// Withdraw, shrinking or cancelling resting orders to cover any shortfall
+    /// Least aggressive orders are reduced first
+    pub fn downsize_and_withdraw(
+        ctx: Context<DownsizeAndWithdraw>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::downsize_and_withdraw::handler(ctx, amount)
+    }
+
+    /// Transfer available balance to another trader in the same market
+    /// Sender-authorized; no token transfer since funds stay in the vault
+    pub fn internal_transfer(
+        ctx: Context<InternalTransfer>,
+        amount: u64,
+        is_base: bool,
+    ) -> Result<()> {
+        instructions::internal_transfer::handler(ctx, amount, is_base)
+    }
+
+    /// Require a co-signer for withdrawals at or above a threshold
+    /// Changing an existing co-signer needs the current co-signer's signature
+    pub fn set_withdrawal_co_signer(
+        ctx: Context<SetWithdrawalCoSigner>,
+        co_signer: Option<Pubkey>,
+        threshold: u64,
+    ) -> Result<()> {
+        instructions::set_withdrawal_co_signer::handler(ctx, co_signer, threshold)
+    }
+
+    /// Queue a withdrawal for co-signer approval
+    /// Reserves the funds from the available balance
+    pub fn request_withdrawal(
+        ctx: Context<RequestWithdrawal>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::request_withdrawal::handler(ctx, amount)
+    }
+
+    /// Co-signer: Approve a pending withdrawal
+    /// Transfers the reserved funds to the trader's wallet
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        instructions::approve_withdrawal::handler(ctx)
+    }
+
+    /// Cancel a pending withdrawal
+    /// Returns the reserved funds to the available balance
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        instructions::cancel_withdrawal::handler(ctx)
+    }
+
+    /// Restrict withdrawals to a set of destination token accounts
+    /// Takes effect immediately
+    pub fn enable_withdrawal_allowlist(
+        ctx: Context<EnableWithdrawalAllowlist>,
+        destinations: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::enable_withdrawal_allowlist::handler(ctx, destinations)
+    }
+
+    /// Propose a new withdrawal allowlist (empty to remove it)
+    /// Applies only after a 24 hour delay
+    pub fn propose_withdrawal_allowlist(
+        ctx: Context<ProposeWithdrawalAllowlist>,
+        destinations: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::propose_withdrawal_allowlist::handler(ctx, destinations)
+    }
+
+    /// Apply a proposed withdrawal allowlist change
+    /// Only once the delay has elapsed
+    pub fn apply_withdrawal_allowlist(ctx: Context<ApplyWithdrawalAllowlist>) -> Result<()> {
+        instructions::apply_withdrawal_allowlist::handler(ctx)
+    }
+
+    /// Pre-authorize a guardian key that may lock the account
+    /// Cannot be changed while the account is locked
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+        instructions::set_guardian::handler(ctx, guardian)
+    }
+
+    /// Panic button: cancel all orders and block orders and withdrawals for a cooldown
+    /// Callable by the trader or their guardian
+    pub fn lock_account(ctx: Context<LockAccount>) -> Result<()> {
+        instructions::lock_account::handler(ctx)
+    }
+
+    /// Admin: Stage a market parameter update (applied after the timelock)
     /// Only callable by market or protocol authority
     pub fn update_market_params(
         ctx: Context<UpdateMarketParams>,
@@ -97,6 +455,12 @@ pub mod solana_orderbook_dex {
         instructions::update_market_params::handler(ctx, params)
     }
 
+    /// Activate staged fee, market parameter and oracle changes whose timelock has passed
+    /// Permissionless: the change was authorized when it was staged
+    pub fn apply_pending_update(ctx: Context<ApplyPendingUpdate>) -> Result<()> {
+        instructions::apply_pending_update::handler(ctx)
+    }
+
     /// Admin: Pause/unpause a market
     /// Prevents new orders during pause
     pub fn pause_market(
@@ -106,13 +470,222 @@ pub mod solana_orderbook_dex {
         instructions::pause_market::handler(ctx, paused)
     }
 
-    /// Admin: Update protocol fees
+    /// Admin: Move a market to cancel-only (wind-down), back to active, or delisted
+    /// Only callable by market or protocol authority
+    pub fn set_market_status(
+        ctx: Context<SetMarketStatus>,
+        status: u8,
+    ) -> Result<()> {
+        instructions::set_market_status::handler(ctx, status)
+    }
+
+    /// Admin: Require (or stop requiring) a trading seat to trade or deposit on a market
+    /// Only callable by market authority
+    pub fn set_seat_requirement(
+        ctx: Context<SetSeatRequirement>,
+        requires_seat: bool,
+    ) -> Result<()> {
+        instructions::set_seat_requirement::handler(ctx, requires_seat)
+    }
+
+    /// Admin: Grant a trader a seat on a permissioned market
+    /// Only callable by market authority
+    pub fn grant_seat(ctx: Context<GrantSeat>) -> Result<()> {
+        instructions::grant_seat::handler(ctx)
+    }
+
+    /// Admin: Revoke a trader's seat, returning its rent
+    /// Only callable by market authority
+    pub fn revoke_seat(ctx: Context<RevokeSeat>) -> Result<()> {
+        instructions::revoke_seat::handler(ctx)
+    }
+
+    /// Admin: Set the per-fill reward paid to match_orders callers
+    /// Only callable by protocol authority; paid out of the market's accrued quote fees
+    pub fn set_crank_reward(
+        ctx: Context<SetCrankReward>,
+        reward_per_fill: u64,
+    ) -> Result<()> {
+        instructions::set_crank_reward::handler(ctx, reward_per_fill)
+    }
+
+    /// Admin: Set the token or NFT collection whose holders pay a discounted taker fee
+    /// Only callable by protocol authority; None removes the pass
+    pub fn set_fee_pass(
+        ctx: Context<SetFeePass>,
+        params: SetFeePassParams,
+    ) -> Result<()> {
+        instructions::set_fee_pass::handler(ctx, params)
+    }
+
+    /// Admin: Set the loyalty points a market's taker volume and maker uptime earn
+    /// Only callable by protocol authority; applies from the next fill
+    pub fn set_points_schedule(
+        ctx: Context<SetPointsSchedule>,
+        params: SetPointsScheduleParams,
+    ) -> Result<()> {
+        instructions::set_points_schedule::handler(ctx, params)
+    }
+
+    /// Admin: Delegate pause or risk-param powers to an operator key (None revokes)
+    /// Callable by the market authority; risk powers need the protocol or risk authority
+    pub fn set_market_operator(
+        ctx: Context<SetMarketOperator>,
+        operator: Option<Pubkey>,
+        permissions: u8,
+    ) -> Result<()> {
+        instructions::set_market_operator::handler(ctx, operator, permissions)
+    }
+
+    /// Admin: Stage a protocol fee and integrator fee cap update (applied after the timelock)
     /// Only callable by protocol authority
     pub fn update_protocol_fees(
         ctx: Context<UpdateProtocolFees>,
-        maker_fee_bps: Option<u16>,
+        maker_fee_bps: Option<i16>,
         taker_fee_bps: Option<u16>,
+        max_integrator_fee_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::update_protocol_fees::handler(ctx, maker_fee_bps, taker_fee_bps, max_integrator_fee_bps)
+    }
+
+    /// Admin: Sweep a market's accrued protocol fees to the fee recipient
+    /// Only callable by protocol authority
+    pub fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+        instructions::claim_protocol_fees::handler(ctx)
+    }
+
+    /// Admin: Split a new market's fees between partners per an immutable table
+    /// Only callable by protocol authority, before the market's first fill
+    pub fn init_revenue_share(
+        ctx: Context<InitRevenueShare>,
+        splits: Vec<RevenueSplit>,
+    ) -> Result<()> {
+        instructions::init_revenue_share::handler(ctx, splits)
+    }
+
+    /// Pay a revenue-share beneficiary its cut of the market's fees
+    /// Only callable by the beneficiary; each claims independently
+    pub fn claim_revenue_share(ctx: Context<ClaimRevenueShare>) -> Result<()> {
+        instructions::claim_revenue_share::handler(ctx)
+    }
+
+    /// Admin: Set up a trading competition over up to four markets and fund its prize
+    /// Only callable by protocol authority; markets are passed as remaining accounts
+    pub fn create_competition<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateCompetition<'info>>,
+        params: CreateCompetitionParams,
+    ) -> Result<()> {
+        instructions::create_competition::handler(ctx, params)
+    }
+
+    /// Enter a trading competition with the trader's states on its markets
+    /// Only volume filled after entering counts towards the score
+    pub fn enter_competition<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EnterCompetition<'info>>,
+    ) -> Result<()> {
+        instructions::enter_competition::handler(ctx)
+    }
+
+    /// Record an entrant's competition volume as its score after the window
+    /// Permissionless crank; trader states are passed as remaining accounts
+    pub fn record_competition_score<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordCompetitionScore<'info>>,
+    ) -> Result<()> {
+        instructions::record_competition_score::handler(ctx)
+    }
+
+    /// Claim a pro-rata share of a competition prize once scoring has closed
+    /// Only callable by the entrant; closes its entry
+    pub fn claim_competition_prize(ctx: Context<ClaimCompetitionPrize>) -> Result<()> {
+        instructions::claim_competition_prize::handler(ctx)
+    }
+
+    /// Admin: Sweep an unclaimed competition prize to the fee recipient
+    /// Only callable by protocol authority, after the claim period or an unscored competition
+    pub fn sweep_competition_prize(ctx: Context<SweepCompetitionPrize>) -> Result<()> {
+        instructions::sweep_competition_prize::handler(ctx)
+    }
+
+    /// Admin: Sweep placement deposits forfeited by orders that expired untouched
+    /// Only callable by protocol authority; paid to the fee recipient
+    pub fn claim_forfeited_deposits(ctx: Context<ClaimForfeitedDeposits>) -> Result<()> {
+        instructions::claim_forfeited_deposits::handler(ctx)
+    }
+
+    /// Admin: Stage attaching, replacing, or detaching a market's price oracle (applied after the timelock)
+    /// Only callable by market or protocol authority
+    pub fn set_market_oracle(
+        ctx: Context<SetMarketOracle>,
+        params: SetMarketOracleParams,
+    ) -> Result<()> {
+        instructions::set_market_oracle::handler(ctx, params)
+    }
+
+    /// Admin: Configure the stable-pair peg band
+    /// Also re-arms a market flipped to cancel-only by a de-peg
+    pub fn set_peg_protection(
+        ctx: Context<SetPegProtection>,
+        peg_price: u64,
+        band_bps: u16,
+    ) -> Result<()> {
+        instructions::set_peg_protection::handler(ctx, peg_price, band_bps)
+    }
+
+    /// Admin: Resume a market halted by the circuit breaker before its cooldown ends
+    /// Breaker limits are set with update_risk_params
+    pub fn reset_circuit_breaker(ctx: Context<ResetCircuitBreaker>) -> Result<()> {
+        instructions::reset_circuit_breaker::handler(ctx)
+    }
+
+    /// Risk admin: Stage per-market risk limits and circuit breaker settings (applied after the timelock)
+    /// Only callable by risk authority or protocol authority
+    pub fn update_risk_params(
+        ctx: Context<UpdateRiskParams>,
+        params: UpdateRiskParamsParams,
+    ) -> Result<()> {
+        instructions::update_risk_params::handler(ctx, params)
+    }
+
+    /// Admin: Set a market's deposit-to-withdrawal cooldown and early-withdrawal fee
+    /// Only callable by protocol authority
+    pub fn set_withdrawal_cooldown(
+        ctx: Context<SetWithdrawalCooldown>,
+        cooldown_seconds: u32,
+        early_withdrawal_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_withdrawal_cooldown::handler(ctx, cooldown_seconds, early_withdrawal_fee_bps)
+    }
+
+    /// Admin: Set the risk authority
+    /// Only callable by protocol authority
+    pub fn set_risk_authority(
+        ctx: Context<SetRiskAuthority>,
+        risk_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::set_risk_authority::handler(ctx, risk_authority)
+    }
+
+    /// Admin: Propose a new protocol authority
+    /// Takes effect only once the proposed key signs accept_authority; default key cancels
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    /// Admin: Accept a pending protocol authority transfer
+    /// Must be signed by the proposed authority
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    /// Admin: Detach an external settlement program
+    /// Attaching is refused until the queue's settlement paths forward fills to it
+    pub fn set_settlement_program(
+        ctx: Context<SetSettlementProgram>,
+        settlement_program: Option<Pubkey>,
     ) -> Result<()> {
-        instructions::update_protocol_fees::handler(ctx, maker_fee_bps, taker_fee_bps)
+        instructions::set_settlement_program::handler(ctx, settlement_program)
     }
 }
@@ -0,0 +1,675 @@
+use anchor_lang::prelude::*;
+use crate::errors::DexError;
+use crate::event_queue::QueueEvent;
+use crate::events::{EventSink, OrderFilled, OrderMatched};
+use crate::oracle::{is_within_oracle_band, BandPrice};
+use crate::orderbook::{Order, Orderbook, Side};
+use crate::state::{GlobalConfig, Market};
+
+/// Maximum number of resting orders an incoming order can fill in one instruction
+pub const MAX_TAKER_FILLS: u8 = 16;
+
+/// Calculate a fee for the given rate in basis points
+pub fn calculate_fee(amount: u64, fee_bps: u16) -> u64 {
+    amount
+        .checked_mul(fee_bps as u64)
+        .and_then(|v| v.checked_div(10000))
+        .unwrap_or(0)
+}
+
+/// Bounds on how far an incoming (taker) order may walk the book
+#[derive(Clone, Copy)]
+pub struct TakerLimits {
+    /// Maximum number of resting orders to fill
+    pub max_fills: u8,
+    
+    /// Market buys by quote amount: total quote the taker may spend
+    pub quote_budget: Option<u64>,
+    
+    /// Book impact guard: worst fill price allowed, tighter than the order's limit
+    pub impact_price: Option<u64>,
+    
+    /// Price the market's deviation band is measured from (None if it has no band)
+    pub band_price: Option<BandPrice>,
+    
+    /// Taker fee charged instead of the protocol's (a fee-discount pass)
+    pub taker_fee_bps: Option<u16>,
+}
+
+impl Default for TakerLimits {
+    fn default() -> Self {
+        Self {
+            max_fills: MAX_TAKER_FILLS,
+            quote_budget: None,
+            impact_price: None,
+            band_price: None,
+            taker_fee_bps: None,
+        }
+    }
+}
+
+/// Summary of an incoming (taker) order's fills against the book
+#[derive(Default)]
+pub struct TakerMatch {
+    /// Base size filled
+    pub filled_size: u64,
+    
+    /// Quote amount exchanged across all fills
+    pub quote_amount: u64,
+    
+    /// Number of resting orders filled against
+    pub fill_count: u8,
+    
+    /// Fill events for the event queue (taker side still pending)
+    pub events: Vec<QueueEvent>,
+    
+    /// Match events for the caller to emit (nothing is logged on dry runs)
+    pub matched: Vec<OrderMatched>,
+    
+    /// Per-side fill events for the caller to emit after `matched`
+    pub filled: Vec<OrderFilled>,
+    
+    /// Expired makers removed during the walk, for the caller to queue as Out events
+    pub expired: Vec<Order>,
+    
+    /// Makers left with a dust remainder and removed, for the caller to queue as Out events
+    pub dust: Vec<Order>,
+    
+    /// Book slots touched by the walk: the maker to write back, or `None` to remove it
+    pub book_updates: Vec<(u64, Option<Order>)>,
+    
+    /// Fill price that tripped the circuit breaker (not executed), for the caller to halt on
+    pub breaker_price: Option<u64>,
+    
+    /// The walk stopped at a maker the taker's price still crosses, so the
+    /// remainder must not rest
+    pub crossed: bool,
+}
+
+/// Build the OrderFilled events of a fill for its bid and ask, in that order
+///
+/// `bid` and `ask` are the orders after the fill. `taker_side` is `None`
+/// for batch auction fills, where both sides are makers.
+pub fn order_fills(
+    market: Pubkey,
+    event: &QueueEvent,
+    bid: &Order,
+    ask: &Order,
+    taker_side: Option<Side>,
+    fill_id: u128,
+    lot_size: u64,
+) -> Result<[OrderFilled; 2]> {
+    let (bid_fee, bid_rebate) = event.bid_fee(lot_size)?;
+    let (ask_fee, ask_rebate) = event.ask_fee(lot_size)?;
+    let filled = |order: &Order, side: Side, fee: u64, rebate: u64| OrderFilled {
+        market,
+        trader: order.trader,
+        order_id: order.order_id,
+        client_order_id: order.client_order_id,
+        side: side as u8,
+        maker: taker_side != Some(side),
+        fill_id,
+        price: event.price,
+        size: event.size,
+        cumulative_filled: order.cumulative_filled,
+        remaining_size: order.remaining_size,
+        fee,
+        rebate,
+        timestamp: event.timestamp,
+    };
+    Ok([
+        filled(bid, Side::Bid, bid_fee, bid_rebate),
+        filled(ask, Side::Ask, ask_fee, ask_rebate),
+    ])
+}
+
+/// Write a resting order back after a fill, or take it off the book once it
+/// is filled or its remainder has fallen below the market's dust threshold
+///
+/// Returns the order when it was removed as dust, so the caller can release
+/// its remaining funds.
+pub fn store_filled_order(
+    orderbook: &mut Orderbook,
+    orderbook_data: &mut [u8],
+    slot: u64,
+    order: &Order,
+    market: &Market,
+) -> Result<Option<Order>> {
+    if order.is_filled() {
+        orderbook.remove_order(orderbook_data, slot)?;
+        Ok(None)
+    } else if market.is_dust(order) {
+        orderbook.remove_order(orderbook_data, slot)?;
+        Ok(Some(*order))
+    } else {
+        orderbook.set_order(orderbook_data, slot, order)?;
+        Ok(None)
+    }
+}
+
+/// Fill the incoming order against the book and write the touched makers back
+///
+/// See `walk_taker_order` for how the walk proceeds.
+pub fn match_taker_order(
+    orderbook: &mut Orderbook,
+    orderbook_data: &mut [u8],
+    taker: &mut Order,
+    market: &Account<Market>,
+    global_config: &GlobalConfig,
+    limits: TakerLimits,
+) -> Result<TakerMatch> {
+    let result = walk_taker_order(orderbook, orderbook_data, taker, market, global_config, limits)?;
+    for (slot, maker) in result.book_updates.iter() {
+        match maker {
+            Some(maker) => orderbook.set_order(orderbook_data, *slot, maker)?,
+            None => orderbook.remove_order(orderbook_data, *slot)?,
+        }
+    }
+    Ok(result)
+}
+
+/// Walk the opposite side of the book and fill the incoming order against
+/// resting orders at or better than its limit price (price-time priority)
+///
+/// The book itself is only read: the makers' new state is returned in
+/// `book_updates`, so dry runs can preview a match without a copy of the book.
+/// Fills execute at the price set by the market's match-price model. Expired good-till-date
+/// makers are removed instead of filled and count towards `max_fills`; makers
+/// left with a dust remainder are removed after their fill.
+/// Matching stops at the first resting order that cannot match (price,
+/// self-trade), beyond the `impact_price` guard, at a fill price outside the
+/// market's oracle band, past the market's circuit breaker (reported in
+/// `breaker_price`), or after `max_fills` fills. All but the price stop leave
+/// the taker crossing the book, reported in `crossed`.
+/// With a `quote_budget` (market buys by quote amount) fills are also capped
+/// to whole lots the remaining budget can pay for, and matching stops once
+/// not even one lot is affordable. The incoming order is never written to the book here;
+/// the caller decides whether to post or cancel its remainder, applies the
+/// taker side of each fill, pushes the events to the event queue and emits
+/// the match events.
+pub fn walk_taker_order(
+    orderbook: &Orderbook,
+    orderbook_data: &[u8],
+    taker: &mut Order,
+    market: &Account<Market>,
+    global_config: &GlobalConfig,
+    limits: TakerLimits,
+) -> Result<TakerMatch> {
+    let clock = Clock::get()?;
+    let mut result = TakerMatch::default();
+    let mut breaker_reference = market.breaker_reference(clock.unix_timestamp);
+    let side = if taker.is_bid() { Side::Ask } else { Side::Bid };
+    
+    // Every maker met is either removed or ends the walk, so the side's
+    // price-time order is the order repeated best-match lookups would give
+    for (maker_slot, mut maker) in orderbook.iter_side(orderbook_data, side) {
+        if taker.remaining_size == 0 {
+            break;
+        }
+        if result.fill_count as usize + result.expired.len() >= limits.max_fills as usize {
+            result.crossed = taker.crosses(&maker);
+            break;
+        }
+        
+        if maker.is_expired(clock.unix_timestamp) {
+            result.book_updates.push((maker_slot, None));
+            result.expired.push(maker);
+            continue;
+        }
+        
+        if !taker.crosses(&maker) {
+            break; // Not marketable
+        }
+        
+        // Every stop from here on leaves a maker the taker still crosses
+        if !taker.can_match(&maker) {
+            result.crossed = true;
+            break; // Self-trade
+        }
+        
+        if let Some(impact_price) = limits.impact_price {
+            let beyond_guard = if taker.is_bid() {
+                maker.price > impact_price
+            } else {
+                maker.price < impact_price
+            };
+            if beyond_guard {
+                result.crossed = true;
+                break; // Filling further would move the price past the impact guard
+            }
+        }
+        
+        let match_price = market.match_price(maker.price, taker.price);
+        if !is_within_oracle_band(match_price, limits.band_price) {
+            result.crossed = true;
+            break; // Fills stay inside the band, as in match_orders
+        }
+        if breaker_reference == 0 {
+            breaker_reference = match_price;
+        }
+        if market.breaker_exceeded(breaker_reference, match_price) {
+            result.breaker_price = Some(match_price);
+            result.crossed = true;
+            break; // The caller halts the market
+        }
+        
+        let mut fill_size = taker.remaining_size.min(maker.remaining_size);
+        
+        if let Some(budget) = limits.quote_budget {
+            let affordable = ((budget - result.quote_amount) as u128)
+                .checked_mul(market.lot_size as u128)
+                .and_then(|v| v.checked_div(match_price as u128))
+                .ok_or(DexError::MathOverflow)?;
+            let affordable = u64::try_from(affordable).unwrap_or(u64::MAX);
+            fill_size = fill_size.min(affordable - affordable % market.lot_size);
+            if fill_size == 0 {
+                break; // Budget spent
+            }
+        }
+        
+        taker.fill(fill_size)?;
+        maker.fill(fill_size)?;
+        
+        let quote_amount = match_price
+            .checked_mul(fill_size)
+            .and_then(|v| v.checked_div(market.lot_size))
+            .ok_or(DexError::MathOverflow)?;
+        
+        // Update or remove the resting order
+        if maker.is_filled() {
+            result.book_updates.push((maker_slot, None));
+        } else if market.is_dust(&maker) {
+            result.book_updates.push((maker_slot, None));
+            result.dust.push(maker);
+        } else {
+            result.book_updates.push((maker_slot, Some(maker)));
+        }
+        
+        let fill_id = market.fill_id_at(result.fill_count as u64)?;
+        
+        let taker_fee_bps = limits.taker_fee_bps.unwrap_or(global_config.taker_fee_bps) as i16;
+        let (bid, ask, bid_fee_bps, ask_fee_bps) = if taker.is_bid() {
+            (&*taker, &maker, taker_fee_bps, global_config.maker_fee_bps)
+        } else {
+            (&maker, &*taker, global_config.maker_fee_bps, taker_fee_bps)
+        };
+        let event = QueueEvent::fill(
+            bid,
+            ask,
+            match_price,
+            fill_size,
+            bid_fee_bps,
+            ask_fee_bps,
+            clock.unix_timestamp,
+        );
+        let taker_side = if taker.is_bid() { Side::Bid } else { Side::Ask };
+        result.filled.extend(order_fills(market.key(), &event, bid, ask, Some(taker_side), fill_id, market.lot_size)?);
+        result.events.push(event);
+        
+        result.matched.push(OrderMatched {
+            market: market.key(),
+            bid_order_id: bid.order_id,
+            ask_order_id: ask.order_id,
+            bid_client_order_id: bid.client_order_id,
+            ask_client_order_id: ask.client_order_id,
+            price: match_price,
+            size: fill_size,
+            bid_trader: bid.trader,
+            ask_trader: ask.trader,
+            bid_origin: bid.origin,
+            ask_origin: ask.origin,
+            fill_id,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        result.filled_size = result.filled_size
+            .checked_add(fill_size)
+            .ok_or(DexError::MathOverflow)?;
+        result.quote_amount = result.quote_amount
+            .checked_add(quote_amount)
+            .ok_or(DexError::MathOverflow)?;
+        result.fill_count += 1;
+    }
+    
+    Ok(result)
+}
+
+/// Size the incoming order could fill right now, without touching the book
+///
+/// Runs the same walk as match_taker_order on a copy of the order, so every
+/// limit that stops matching (price, impact guard, quote budget, oracle band,
+/// circuit breaker, `max_fills`) also bounds the result. Used as the fill-or-kill
+/// pre-pass, so a FOK order either fills in full or not at all.
+pub fn fillable_size(
+    orderbook: &Orderbook,
+    orderbook_data: &[u8],
+    taker: &Order,
+    market: &Account<Market>,
+    global_config: &GlobalConfig,
+    limits: TakerLimits,
+) -> Result<u64> {
+    let mut taker = *taker;
+    let walk = walk_taker_order(orderbook, orderbook_data, &mut taker, market, global_config, limits)?;
+    Ok(walk.filled_size)
+}
+
+/// Maximum number of same-slot orders considered at one price level in batch mode
+pub const MAX_BATCH_LEVEL_ORDERS: usize = 16;
+
+/// Collect the next batch on one side of the book for batch-per-slot matching
+///
+/// A batch is the best-priced resting orders from the earliest slot at that
+/// price. Orders younger than `min_age` slots are excluded, so the current
+/// slot's batch only clears once the slot has closed. Arrival order inside
+/// the batch is ignored.
+pub fn collect_batch_level(
+    orderbook: &Orderbook,
+    data: &[u8],
+    side: Side,
+    current_slot: u64,
+    min_age: u64,
+) -> Vec<(u64, Order)> {
+    // The book is in price-time order, so the first eligible order fixes the
+    // batch's price and slot and the rest of the batch follows it directly
+    let mut orders = orderbook
+        .iter_side(data, side)
+        .skip_while(|(_, order)| !order.has_rested(current_slot, min_age))
+        .peekable();
+    
+    let (price, slot) = match orders.peek() {
+        Some((_, first)) => (first.price, first.slot),
+        None => return Vec::new(),
+    };
+    
+    orders
+        .take_while(|(_, order)| order.price == price && order.slot == slot)
+        .take(MAX_BATCH_LEVEL_ORDERS)
+        .collect()
+}
+
+/// Split `total` across orders in proportion to their remaining size
+///
+/// Shares are rounded down to whole lots; leftover lots are handed out one
+/// at a time in slab order until `total` is exhausted.
+pub fn allocate_pro_rata(orders: &[(u64, Order)], total: u64, lot_size: u64) -> Vec<u64> {
+    let level_total: u128 = orders.iter().map(|(_, o)| o.remaining_size as u128).sum();
+    if level_total == 0 || lot_size == 0 {
+        return vec![0; orders.len()];
+    }
+    
+    let mut allocations: Vec<u64> = orders
+        .iter()
+        .map(|(_, o)| {
+            let share = (total as u128 * o.remaining_size as u128 / level_total) as u64;
+            (share - share % lot_size).min(o.remaining_size)
+        })
+        .collect();
+    
+    let mut leftover = total.saturating_sub(allocations.iter().sum());
+    while leftover > 0 {
+        let mut progressed = false;
+        for (allocation, (_, order)) in allocations.iter_mut().zip(orders) {
+            let step = lot_size.min(order.remaining_size - *allocation).min(leftover);
+            if step > 0 {
+                *allocation += step;
+                leftover -= step;
+                progressed = true;
+            }
+            if leftover == 0 {
+                break;
+            }
+        }
+        if !progressed {
+            break;
+        }
+    }
+    
+    allocations
+}
+
+/// Clear one crossing pair of batches at a single price
+///
+/// The batch clears at the lower of the two batch prices, whatever the
+/// market's match-price model. The matched size is the smaller side's total; each side is allocated
+/// pro-rata and allocations are paired into fills (skipping self-trades).
+/// Nobody takes liquidity in a batch, so both sides pay the maker fee
+/// (or nothing when the maker fee is a rebate, as no taker fee funds it).
+/// `fills_before` counts the fills already made in this instruction, which
+/// the market's fill sequence has not yet recorded.
+/// The orders in `bids` and `asks` are filled in place, for the caller to
+/// write back to the book. Returns the fill events for the event queue.
+pub fn match_batch(
+    bids: &mut [(u64, Order)],
+    asks: &mut [(u64, Order)],
+    market: &Account<Market>,
+    global_config: &GlobalConfig,
+    fills_before: u64,
+    sink: &EventSink,
+) -> Result<Vec<QueueEvent>> {
+    let clock = Clock::get()?;
+    let match_price = match (bids.first(), asks.first()) {
+        (Some((_, bid)), Some((_, ask))) => bid.price.min(ask.price),
+        _ => return Ok(Vec::new()),
+    };
+    
+    let bid_total: u64 = bids.iter().map(|(_, o)| o.remaining_size).sum();
+    let ask_total: u64 = asks.iter().map(|(_, o)| o.remaining_size).sum();
+    let matched = bid_total.min(ask_total);
+    
+    let mut bid_alloc = allocate_pro_rata(bids, matched, market.lot_size);
+    let mut ask_alloc = allocate_pro_rata(asks, matched, market.lot_size);
+    let mut events = Vec::new();
+    let batch_fee_bps = global_config.maker_fee_bps.max(0);
+    
+    for (i, (_, bid)) in bids.iter_mut().enumerate() {
+        for (j, (_, ask)) in asks.iter_mut().enumerate() {
+            if bid_alloc[i] == 0 {
+                break;
+            }
+            if ask_alloc[j] == 0 || bid.trader == ask.trader {
+                continue; // Exhausted or self-trade
+            }
+            
+            let fill_size = bid_alloc[i].min(ask_alloc[j]);
+            bid.fill(fill_size)?;
+            ask.fill(fill_size)?;
+            bid_alloc[i] -= fill_size;
+            ask_alloc[j] -= fill_size;
+            
+            let fill_id = market.fill_id_at(fills_before + events.len() as u64)?;
+            
+            sink.emit(OrderMatched {
+                market: market.key(),
+                bid_order_id: bid.order_id,
+                ask_order_id: ask.order_id,
+                bid_client_order_id: bid.client_order_id,
+                ask_client_order_id: ask.client_order_id,
+                price: match_price,
+                size: fill_size,
+                bid_trader: bid.trader,
+                ask_trader: ask.trader,
+                bid_origin: bid.origin,
+                ask_origin: ask.origin,
+                fill_id,
+                timestamp: clock.unix_timestamp,
+            })?;
+            
+            let event = QueueEvent::fill(
+                bid,
+                ask,
+                match_price,
+                fill_size,
+                batch_fee_bps,
+                batch_fee_bps,
+                clock.unix_timestamp,
+            );
+            for filled in order_fills(market.key(), &event, bid, ask, None, fill_id, market.lot_size)? {
+                sink.emit(filled)?;
+            }
+            events.push(event);
+        }
+    }
+    
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::TimeInForce;
+    use crate::state::MatchPriceModel;
+    use crate::test_utils::{account_data, install_clock, zeroed, NOW};
+    
+    const LOT_SIZE: u64 = 1_000;
+    
+    fn global_config() -> GlobalConfig {
+        let mut global_config: GlobalConfig = zeroed(GlobalConfig::SIZE);
+        global_config.taker_fee_bps = 30;
+        global_config.maker_fee_bps = -10;
+        global_config
+    }
+    
+    fn order(order_id: u128, trader: Pubkey, side: Side, price: u64, size: u64) -> Order {
+        Order::new(order_id, trader, side, price, size, TimeInForce::GTC, &Clock::default())
+    }
+    
+    fn book(orders: &[Order]) -> (Orderbook, Vec<u8>) {
+        let mut orderbook: Orderbook = zeroed(Orderbook::HEADER_SIZE);
+        orderbook.capacity = 16;
+        let mut data = vec![0u8; Orderbook::size_for(16)];
+        for order in orders {
+            orderbook.insert_order(&mut data, order).unwrap();
+        }
+        (orderbook, data)
+    }
+    
+    /// Run `test` against a market account configured by `configure`
+    fn with_market(configure: impl FnOnce(&mut Market), test: impl FnOnce(&Account<Market>)) {
+        install_clock();
+        let mut market: Market = zeroed(Market::SIZE);
+        market.tick_size = 1;
+        market.lot_size = LOT_SIZE;
+        configure(&mut market);
+        
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = account_data(&market);
+        let account_info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        test(&Account::try_from(&account_info).unwrap());
+    }
+    
+    #[test]
+    fn match_price_follows_the_market_model() {
+        with_market(|_| {}, |market| {
+            assert_eq!(market.match_price(100, 110), 100);
+        });
+        with_market(|market| market.match_price_model = MatchPriceModel::TakerPrice as u8, |market| {
+            assert_eq!(market.match_price(100, 110), 110);
+        });
+        with_market(|market| {
+            market.match_price_model = MatchPriceModel::Midpoint as u8;
+            market.tick_size = 4;
+        }, |market| {
+            assert_eq!(market.match_price(100, 110), 104); // 105 rounded down to the tick
+        });
+    }
+    
+    #[test]
+    fn taker_fills_makers_in_price_order_and_pays_the_taker_fee() {
+        let maker = Pubkey::new_unique();
+        let (mut orderbook, mut data) = book(&[
+            order(1, maker, Side::Ask, 101, 5_000),
+            order(2, maker, Side::Ask, 100, 5_000),
+        ]);
+        let mut taker = order(3, Pubkey::new_unique(), Side::Bid, 101, 8_000);
+        
+        with_market(|market| market.fill_sequence = 41, |market| {
+            let taker_match = match_taker_order(
+                &mut orderbook,
+                &mut data,
+                &mut taker,
+                market,
+                &global_config(),
+                TakerLimits::default(),
+            ).unwrap();
+            
+            assert_eq!(taker_match.fill_count, 2);
+            assert_eq!(taker_match.filled_size, 8_000);
+            assert_eq!(taker_match.quote_amount, 500 + 303); // 5 lots at 100, 3 at 101
+            assert_eq!(taker_match.events[0].price, 100);
+            assert_eq!(taker_match.events[1].price, 101);
+            assert_eq!(taker_match.events[0].bid_fee_bps, 30);
+            assert_eq!(taker_match.events[0].ask_fee_bps, -10);
+            assert_eq!(taker_match.matched[0].fill_id, 41);
+            assert_eq!(taker_match.matched[1].fill_id, 42);
+            assert!(!taker_match.crossed);
+        });
+        
+        assert!(taker.is_filled());
+        assert_eq!(orderbook.best_ask, 101);
+        let (_, resting) = orderbook.find_best_ask(&data).unwrap();
+        assert_eq!((resting.order_id, resting.remaining_size), (1, 2_000));
+    }
+    
+    #[test]
+    fn dry_run_walk_leaves_the_book_untouched() {
+        let maker = Pubkey::new_unique();
+        let (orderbook, data) = book(&[order(1, maker, Side::Ask, 100, 5_000)]);
+        let before = data.clone();
+        let mut taker = order(2, Pubkey::new_unique(), Side::Bid, 100, 2_000);
+        
+        with_market(|_| {}, |market| {
+            let walk = walk_taker_order(&orderbook, &data, &mut taker, market, &global_config(), TakerLimits::default()).unwrap();
+            assert_eq!(walk.filled_size, 2_000);
+            assert_eq!(walk.book_updates.len(), 1);
+            assert_eq!(walk.book_updates[0].1.unwrap().remaining_size, 3_000);
+        });
+        
+        assert_eq!(data, before);
+        assert_eq!(orderbook.find_best_ask(&data).unwrap().1.remaining_size, 5_000);
+    }
+    
+    #[test]
+    fn fill_or_kill_sizing_stops_at_the_circuit_breaker() {
+        let maker = Pubkey::new_unique();
+        let (orderbook, data) = book(&[
+            order(1, maker, Side::Ask, 100, 5_000),
+            order(2, maker, Side::Ask, 105, 5_000),
+        ]);
+        let taker = order(3, Pubkey::new_unique(), Side::Bid, 105, 10_000);
+        
+        with_market(|_| {}, |market| {
+            let size = fillable_size(&orderbook, &data, &taker, market, &global_config(), TakerLimits::default()).unwrap();
+            assert_eq!(size, 10_000);
+        });
+        
+        // A 1% breaker window opened at 100 stops the walk before the fill at 105
+        with_market(|market| {
+            market.breaker_move_bps = 100;
+            market.breaker_window_seconds = 60;
+            market.breaker_reference_price = 100;
+            market.breaker_window_start = NOW - 1;
+        }, |market| {
+            let size = fillable_size(&orderbook, &data, &taker, market, &global_config(), TakerLimits::default()).unwrap();
+            assert_eq!(size, 5_000);
+        });
+    }
+    
+    #[test]
+    fn walk_reports_a_remainder_stopped_at_the_traders_own_order() {
+        let trader = Pubkey::new_unique();
+        let (orderbook, data) = book(&[order(1, trader, Side::Ask, 100, 5_000)]);
+        
+        with_market(|_| {}, |market| {
+            let mut own = order(2, trader, Side::Bid, 100, 5_000);
+            let walk = walk_taker_order(&orderbook, &data, &mut own, market, &global_config(), TakerLimits::default()).unwrap();
+            assert_eq!(walk.fill_count, 0);
+            assert!(walk.crossed);
+            
+            let mut below = order(3, Pubkey::new_unique(), Side::Bid, 99, 5_000);
+            let walk = walk_taker_order(&orderbook, &data, &mut below, market, &global_config(), TakerLimits::default()).unwrap();
+            assert_eq!(walk.fill_count, 0);
+            assert!(!walk.crossed);
+        });
+    }
+}
@@ -0,0 +1,351 @@
+use anchor_lang::prelude::*;
+use crate::errors::DexError;
+use crate::state::Market;
+
+/// Oracle account formats a market can read its reference price from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OracleType {
+    /// Pyth v2 price account
+    Pyth = 0,
+    /// Switchboard v2 aggregator account
+    Switchboard = 1,
+}
+
+impl OracleType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(OracleType::Pyth),
+            1 => Some(OracleType::Switchboard),
+            _ => None,
+        }
+    }
+}
+
+/// Raw oracle reading: `mantissa * 10^exponent` quote tokens per base token
+struct OracleReading {
+    mantissa: i128,
+    exponent: i64,
+    publish_time: i64,
+}
+
+/// Pyth price account magic number
+pub const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+
+/// Pyth account type of a price account
+const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+
+/// Pyth aggregate status while the price is actively published
+const PYTH_STATUS_TRADING: u32 = 1;
+
+// Byte offsets inside a Pyth v2 price account
+const MAGIC_OFFSET: usize = 0;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPONENT_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_STATUS_OFFSET: usize = 224;
+const MIN_ACCOUNT_LEN: usize = 240;
+
+/// Anchor discriminator of a Switchboard v2 `AggregatorAccountData`
+const SWITCHBOARD_AGGREGATOR_DISCRIMINATOR: [u8; 8] = [217, 230, 65, 101, 201, 162, 27, 125];
+
+// Byte offsets inside a Switchboard v2 aggregator (packed `latest_confirmed_round`)
+const SB_NUM_SUCCESS_OFFSET: usize = 341;
+const SB_ROUND_OPEN_TIMESTAMP_OFFSET: usize = 358;
+const SB_RESULT_MANTISSA_OFFSET: usize = 366;
+const SB_RESULT_SCALE_OFFSET: usize = 382;
+const SB_MIN_ACCOUNT_LEN: usize = 386;
+
+/// Read the market's oracle price, converted to quote units per lot
+///
+/// The oracle is a Pyth v2 price account or a Switchboard v2 aggregator
+/// (per `market.oracle_type`) quoting one whole base token in whole quote
+/// tokens. Fails if the account is not the market's oracle, has no current
+/// price, or the price is older than the market's `oracle_max_staleness`.
+pub fn load_oracle_price(market: &Market, oracle: &AccountInfo, now: i64) -> Result<u64> {
+    require!(market.has_oracle(), DexError::OraclePriceNotAvailable);
+    require!(oracle.key() == market.oracle, DexError::OraclePriceNotAvailable);
+    
+    let data = oracle.try_borrow_data()?;
+    let reading = match OracleType::from_u8(market.oracle_type) {
+        Some(OracleType::Pyth) => read_pyth(&data)?,
+        Some(OracleType::Switchboard) => read_switchboard(&data)?,
+        None => return Err(DexError::OraclePriceNotAvailable.into()),
+    };
+    
+    if market.oracle_max_staleness > 0 {
+        require!(
+            now.saturating_sub(reading.publish_time) <= market.oracle_max_staleness as i64,
+            DexError::OraclePriceStale
+        );
+    }
+    require!(reading.mantissa > 0, DexError::OraclePriceNotAvailable);
+    
+    // mantissa * 10^exponent quote tokens per base token, re-expressed per lot in atoms
+    let value = (reading.mantissa as u128)
+        .checked_mul(market.lot_size as u128)
+        .ok_or(DexError::MathOverflow)?;
+    let scale = reading.exponent + market.quote_decimals as i64 - market.base_decimals as i64;
+    let pow10 = 10u128
+        .checked_pow(scale.unsigned_abs() as u32)
+        .ok_or(DexError::MathOverflow)?;
+    let converted = if scale >= 0 {
+        value.checked_mul(pow10).ok_or(DexError::MathOverflow)?
+    } else {
+        value / pow10
+    };
+    u64::try_from(converted).map_err(|_| DexError::MathOverflow.into())
+}
+
+/// How much wider the band is while it is measured from the market's own TWAP
+pub const FALLBACK_BAND_MULTIPLIER: u64 = 2;
+
+/// Reference price of a market's deviation band and how far prices may stray from it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BandPrice {
+    /// Oracle price, or the market's recent TWAP while the oracle is down
+    pub price: u64,
+    
+    /// Largest deviation allowed from `price`, in bps
+    pub max_deviation_bps: u64,
+}
+
+/// Price to enforce the market's deviation band against, if it has one
+///
+/// Markets with an oracle and a non-zero `oracle_max_deviation_bps` must be
+/// given the oracle account. While the oracle is stale or has no current
+/// price, the band is measured from the market's own recent TWAP instead,
+/// `FALLBACK_BAND_MULTIPLIER` times as wide, so the protection degrades rather
+/// than halting trading or switching off. With no fills to take a TWAP from,
+/// the oracle error fails the instruction.
+pub fn load_band_price(market: &Market, oracle: Option<&AccountInfo>, now: i64) -> Result<Option<BandPrice>> {
+    if !market.has_oracle() || market.oracle_max_deviation_bps == 0 {
+        return Ok(None);
+    }
+    let oracle = oracle.ok_or(DexError::OraclePriceNotAvailable)?;
+    require!(oracle.key() == market.oracle, DexError::OraclePriceNotAvailable);
+    
+    let max_deviation_bps = market.oracle_max_deviation_bps as u64;
+    match load_oracle_price(market, oracle, now) {
+        Ok(price) => Ok(Some(BandPrice { price, max_deviation_bps })),
+        Err(err) => {
+            let twap = market.recent_twap(now);
+            if twap == 0 {
+                return Err(err);
+            }
+            msg!("Oracle unavailable, banding against the market TWAP: twap={}", twap);
+            Ok(Some(BandPrice {
+                price: twap,
+                max_deviation_bps: max_deviation_bps.saturating_mul(FALLBACK_BAND_MULTIPLIER),
+            }))
+        }
+    }
+}
+
+/// Whether `price` lies within the deviation band around `band_price`
+pub fn is_within_oracle_band(price: u64, band_price: Option<BandPrice>) -> bool {
+    match band_price {
+        Some(band) => deviation_bps(band.price, price) <= band.max_deviation_bps,
+        None => true, // No band enforced
+    }
+}
+
+/// Distance between `price` and the oracle price in bps of `price`
+pub fn deviation_bps(price: u64, oracle_price: u64) -> u64 {
+    if price == 0 {
+        return u64::MAX;
+    }
+    let diff = price.abs_diff(oracle_price) as u128;
+    (diff * 10_000 / price as u128).min(u64::MAX as u128) as u64
+}
+
+fn read_pyth(data: &[u8]) -> Result<OracleReading> {
+    require!(data.len() >= MIN_ACCOUNT_LEN, DexError::OraclePriceNotAvailable);
+    require!(
+        read_u32(data, MAGIC_OFFSET) == PYTH_MAGIC &&
+            read_u32(data, ACCOUNT_TYPE_OFFSET) == PYTH_PRICE_ACCOUNT_TYPE,
+        DexError::OraclePriceNotAvailable
+    );
+    require!(
+        read_u32(data, AGG_STATUS_OFFSET) == PYTH_STATUS_TRADING,
+        DexError::OraclePriceNotAvailable
+    );
+    
+    Ok(OracleReading {
+        mantissa: read_i64(data, AGG_PRICE_OFFSET) as i128,
+        exponent: read_i32(data, EXPONENT_OFFSET) as i64,
+        publish_time: read_i64(data, TIMESTAMP_OFFSET),
+    })
+}
+
+fn read_switchboard(data: &[u8]) -> Result<OracleReading> {
+    require!(data.len() >= SB_MIN_ACCOUNT_LEN, DexError::OraclePriceNotAvailable);
+    require!(
+        data[..8] == SWITCHBOARD_AGGREGATOR_DISCRIMINATOR,
+        DexError::OraclePriceNotAvailable
+    );
+    // A confirmed round needs at least one oracle response
+    require!(
+        read_u32(data, SB_NUM_SUCCESS_OFFSET) > 0,
+        DexError::OraclePriceNotAvailable
+    );
+    
+    Ok(OracleReading {
+        mantissa: i128::from_le_bytes(data[SB_RESULT_MANTISSA_OFFSET..SB_RESULT_MANTISSA_OFFSET + 16].try_into().unwrap()),
+        exponent: -(read_u32(data, SB_RESULT_SCALE_OFFSET) as i64),
+        publish_time: read_i64(data, SB_ROUND_OPEN_TIMESTAMP_OFFSET),
+    })
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_i32(data: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_i64(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::{Side, TimeInForce};
+    use crate::test_utils::{account_infos, zeroed, TestAccount, Venue, NOW};
+    
+    /// Largest staleness the test markets accept
+    const MAX_STALENESS: u64 = 60;
+    
+    /// A Pyth account quoting 1.50 quote tokens per base token (1_500 per lot)
+    fn pyth(publish_time: i64) -> TestAccount {
+        let mut data = vec![0u8; MIN_ACCOUNT_LEN];
+        data[MAGIC_OFFSET..MAGIC_OFFSET + 4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+        data[ACCOUNT_TYPE_OFFSET..ACCOUNT_TYPE_OFFSET + 4].copy_from_slice(&PYTH_PRICE_ACCOUNT_TYPE.to_le_bytes());
+        data[EXPONENT_OFFSET..EXPONENT_OFFSET + 4].copy_from_slice(&(-2i32).to_le_bytes());
+        data[TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 8].copy_from_slice(&publish_time.to_le_bytes());
+        data[AGG_PRICE_OFFSET..AGG_PRICE_OFFSET + 8].copy_from_slice(&150i64.to_le_bytes());
+        data[AGG_STATUS_OFFSET..AGG_STATUS_OFFSET + 4].copy_from_slice(&PYTH_STATUS_TRADING.to_le_bytes());
+        TestAccount::new(Pubkey::new_unique(), data)
+    }
+    
+    /// Point `market` at `oracle` with a 100 bps band
+    fn attach(market: &mut Market, oracle: Pubkey) {
+        market.oracle = oracle;
+        market.oracle_type = OracleType::Pyth as u8;
+        market.oracle_max_staleness = MAX_STALENESS;
+        market.oracle_max_deviation_bps = 100;
+    }
+    
+    /// Fills closing two hours at 1_400 and 1_600 (a 1_500 TWAP)
+    fn trade(market: &mut Market) {
+        market.record_fills(1_400, 1, 1_400, 1_400, 1_000, NOW - 7_200).unwrap();
+        market.record_fills(1_600, 1, 1_600, 1_600, 1_000, NOW).unwrap();
+    }
+    
+    #[test]
+    fn a_fresh_oracle_price_bands_at_the_configured_width() {
+        let mut oracle = pyth(NOW);
+        let info = oracle.info();
+        let mut market: Market = zeroed(Market::SIZE);
+        market.lot_size = Venue::LOT_SIZE;
+        attach(&mut market, info.key());
+        trade(&mut market);
+        
+        let band = load_band_price(&market, Some(&info), NOW).unwrap();
+        assert_eq!(band, Some(BandPrice { price: 1_500, max_deviation_bps: 100 }));
+        assert!(is_within_oracle_band(1_515, band));
+        assert!(!is_within_oracle_band(1_516, band));
+        assert!(!is_within_oracle_band(1_484, band));
+    }
+    
+    #[test]
+    fn a_stale_oracle_falls_back_to_a_wider_band_around_the_twap() {
+        let mut oracle = pyth(NOW - MAX_STALENESS as i64 - 1);
+        let info = oracle.info();
+        let mut market: Market = zeroed(Market::SIZE);
+        market.lot_size = Venue::LOT_SIZE;
+        attach(&mut market, info.key());
+        trade(&mut market);
+        
+        let band = load_band_price(&market, Some(&info), NOW).unwrap();
+        assert_eq!(band, Some(BandPrice { price: 1_500, max_deviation_bps: 200 }));
+        assert!(is_within_oracle_band(1_530, band));
+        assert!(!is_within_oracle_band(1_531, band));
+        
+        // Fills that fell out of the 24 hour window no longer count
+        assert_eq!(market.recent_twap(NOW + 24 * 3_600), 0);
+    }
+    
+    #[test]
+    fn a_stale_oracle_with_no_fills_to_fall_back_on_fails() {
+        let mut oracle = pyth(NOW - MAX_STALENESS as i64 - 1);
+        let info = oracle.info();
+        let mut market: Market = zeroed(Market::SIZE);
+        market.lot_size = Venue::LOT_SIZE;
+        attach(&mut market, info.key());
+        
+        assert_eq!(
+            load_band_price(&market, Some(&info), NOW).err().unwrap(),
+            DexError::OraclePriceStale.into()
+        );
+    }
+    
+    #[test]
+    fn the_fallback_needs_the_markets_own_oracle_account() {
+        let mut oracle = pyth(NOW);
+        let info = oracle.info();
+        let mut market: Market = zeroed(Market::SIZE);
+        market.lot_size = Venue::LOT_SIZE;
+        attach(&mut market, Pubkey::new_unique());
+        trade(&mut market);
+        
+        assert_eq!(
+            load_band_price(&market, Some(&info), NOW).err().unwrap(),
+            DexError::OraclePriceNotAvailable.into()
+        );
+        assert_eq!(
+            load_band_price(&market, None, NOW).err().unwrap(),
+            DexError::OraclePriceNotAvailable.into()
+        );
+    }
+    
+    #[test]
+    fn orders_are_checked_against_the_band_the_oracle_allows() {
+        let fresh = pyth(NOW);
+        let stale = pyth(NOW - MAX_STALENESS as i64 - 1);
+        let (fresh_key, stale_key) = (fresh.key, stale.key);
+        let mut venue = Venue::new(|market| {
+            attach(market, fresh_key);
+            trade(market);
+        });
+        let trader = venue.add_trader(0, 1_000_000);
+        venue.accounts.push(fresh);
+        venue.accounts.push(stale);
+        let (fresh, stale) = (venue.accounts.len() - 2, venue.accounts.len() - 1);
+        let infos = account_infos(&mut venue.accounts);
+        let bid = |price| Venue::order(Side::Bid, price, Venue::LOT_SIZE, TimeInForce::GTC);
+        
+        assert_eq!(
+            Venue::place_with(&infos, trader, bid(1_525), Some(fresh), None).err().unwrap(),
+            DexError::OraclePriceDeviationTooLarge.into()
+        );
+        Venue::place_with(&infos, trader, bid(1_515), Some(fresh), None).unwrap();
+        assert_eq!(
+            Venue::place(&infos, trader, bid(1_515)).err().unwrap(),
+            DexError::OraclePriceNotAvailable.into()
+        );
+        
+        // Once the market's oracle goes stale the TWAP band lets the wider price through
+        let mut market = Account::<Market>::try_from(&infos[Venue::MARKET]).unwrap();
+        market.oracle = stale_key;
+        market.exit(&crate::ID).unwrap();
+        Venue::place_with(&infos, trader, bid(1_525), Some(stale), None).unwrap();
+        assert_eq!(
+            Venue::place_with(&infos, trader, bid(1_531), Some(stale), None).err().unwrap(),
+            DexError::OraclePriceDeviationTooLarge.into()
+        );
+    }
+}
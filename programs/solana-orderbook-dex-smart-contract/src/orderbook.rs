@@ -47,18 +47,20 @@ impl TimeInForce {
 
 /// Order structure stored in the orderbook
 /// Uses a slab-based data structure for efficient insertion/deletion
+///
+/// Fields are ordered so the `repr(C)` layout has no implicit padding and
+/// matches `Order::SIZE` exactly (required for the `Pod` byte casts).
+/// Slab links store `slot + 1` so that zeroed data means "no link".
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(C)]
 pub struct Order {
-    /// Unique order identifier (128-bit for collision resistance)
+    /// Unique order identifier (128-bit for collision resistance, never 0)
     pub order_id: u128,
     
     /// Trader's public key
     pub trader: Pubkey,
     
-    /// Order side (0 = bid, 1 = ask)
-    pub side: u8,
-    
     /// Limit price (in quote units, must be on tick)
     pub price: u64,
     
@@ -68,23 +70,54 @@ pub struct Order {
     /// Remaining size (decreases as order is filled)
     pub remaining_size: u64,
     
-    /// Time-in-force
-    pub time_in_force: u8,
-    
     /// Timestamp when order was placed
     pub timestamp: i64,
     
-    /// Next order at same price (linked list for price level)
+    /// Slot when order was placed (used by the speed bump)
+    pub slot: u64,
+    
+    /// Caller-chosen order ID (0 if none)
+    pub client_order_id: u64,
+    
+    /// Next order at same price (FIFO within the price level)
+    /// Also links free slots together in the free list
     pub next_at_price: u64,
     
-    /// Previous order at same price
+    /// Previous order at same price (on the level head: the level's tail)
     pub prev_at_price: u64,
     
-    /// Next order in price-sorted order (for orderbook traversal)
+    /// Level heads only: head of the next (worse) price level
     pub next_in_book: u64,
     
-    /// Previous order in price-sorted order
+    /// Level heads only: head of the previous (better) price level
     pub prev_in_book: u64,
+    
+    /// Good-till-date expiry (unix timestamp, 0 if none)
+    pub expiry_timestamp: i64,
+    
+    /// Order side (0 = bid, 1 = ask)
+    pub side: u8,
+    
+    /// Time-in-force
+    pub time_in_force: u8,
+    
+    /// Oracle protection band in bps (0 if unprotected)
+    /// The order is cancelled by the crank once the oracle moves further away
+    pub oracle_band_bps: u16,
+    
+    /// Where the order came from (see `ORIGIN_*`; 0 if untagged)
+    pub origin: u32,
+    
+    /// Order behaviour flags (see `FLAG_*`)
+    pub flags: u8,
+    
+    pub _padding: [u8; 3],
+    
+    /// Placement deposit held for the order, in lamports (0 if none)
+    pub deposit_lamports: u32,
+    
+    /// Base size filled over the order's lifetime (kept across modify_order)
+    pub cumulative_filled: u64,
 }
 
 unsafe impl Pod for Order {}
@@ -93,18 +126,39 @@ unsafe impl Zeroable for Order {}
 impl Order {
     pub const SIZE: usize = 16 + // order_id
         32 + // trader
-        1 +  // side
         8 +  // price
         8 +  // size
         8 +  // remaining_size
-        1 +  // time_in_force
         8 +  // timestamp
+        8 +  // slot
+        8 +  // client_order_id
         8 +  // next_at_price
         8 +  // prev_at_price
         8 +  // next_in_book
-        8;   // prev_in_book
-    
-    /// Create a new order
+        8 +  // prev_in_book
+        8 +  // expiry_timestamp
+        1 +  // side
+        1 +  // time_in_force
+        2 +  // oracle_band_bps
+        4 +  // origin
+        1 +  // flags
+        3 +  // padding
+        4 +  // deposit_lamports
+        8;   // cumulative_filled
+    
+    /// Origin tag: not tagged by the sender
+    pub const ORIGIN_NONE: u32 = 0;
+    /// Origin tag: a retail user interface
+    pub const ORIGIN_UI: u32 = 1;
+    /// Origin tag: an API / bot client
+    pub const ORIGIN_API: u32 = 2;
+    /// Tags below this are reserved for well-known origins; higher ones come from `cpi_origin`
+    pub const CPI_ORIGIN_MIN: u32 = 256;
+    
+    /// Flag: the order may only reduce the trader's exposure on the market
+    pub const FLAG_REDUCE_ONLY: u8 = 1 << 0;
+    
+    /// Create a new order, stamped with the clock's time and slot
     pub fn new(
         order_id: u128,
         trader: Pubkey,
@@ -112,24 +166,49 @@ impl Order {
         price: u64,
         size: u64,
         time_in_force: TimeInForce,
-        timestamp: i64,
+        clock: &Clock,
     ) -> Self {
         Self {
             order_id,
             trader,
-            side: side as u8,
             price,
             size,
             remaining_size: size,
-            time_in_force: time_in_force as u8,
-            timestamp,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+            client_order_id: 0,
             next_at_price: 0,
             prev_at_price: 0,
             next_in_book: 0,
             prev_in_book: 0,
+            expiry_timestamp: 0,
+            side: side as u8,
+            time_in_force: time_in_force as u8,
+            oracle_band_bps: 0,
+            origin: Self::ORIGIN_NONE,
+            flags: 0,
+            _padding: [0; 3],
+            deposit_lamports: 0,
+            cumulative_filled: 0,
         }
     }
     
+    /// Origin tag for orders sent through another program via CPI
+    pub fn cpi_origin(program_id: &Pubkey) -> u32 {
+        let digest = anchor_lang::solana_program::hash::hash(program_id.as_ref()).to_bytes();
+        u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]]).max(Self::CPI_ORIGIN_MIN)
+    }
+    
+    /// Whether a sender-supplied origin tag is valid
+    pub fn is_valid_origin(origin: u32) -> bool {
+        origin <= Self::ORIGIN_API || origin >= Self::CPI_ORIGIN_MIN
+    }
+    
+    /// Whether the order is reduce-only
+    pub fn is_reduce_only(&self) -> bool {
+        self.flags & Self::FLAG_REDUCE_ONLY != 0
+    }
+    
     /// Check if order is a bid
     pub fn is_bid(&self) -> bool {
         self.side == Side::Bid as u8
@@ -146,6 +225,11 @@ impl Order {
             return false; // Self-trade prevention
         }
         
+        self.crosses(other)
+    }
+    
+    /// Check if the two orders' prices cross, whoever owns them
+    pub fn crosses(&self, other: &Order) -> bool {
         match (self.is_bid(), other.is_bid()) {
             (true, false) => self.price >= other.price, // Bid can match if price >= ask
             (false, true) => self.price <= other.price, // Ask can match if price <= bid
@@ -162,6 +246,9 @@ impl Order {
         self.remaining_size = self.remaining_size
             .checked_sub(fill_size)
             .ok_or(crate::errors::DexError::MathUnderflow)?;
+        self.cumulative_filled = self.cumulative_filled
+            .checked_add(fill_size)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
         Ok(())
     }
     
@@ -169,21 +256,44 @@ impl Order {
     pub fn is_filled(&self) -> bool {
         self.remaining_size == 0
     }
+    
+    /// Whether a good-till-date order has expired at `now`
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expiry_timestamp > 0 && now >= self.expiry_timestamp
+    }
+    
+    /// Whether the oracle has moved outside this order's protection band
+    pub fn is_oracle_diverged(&self, oracle_price: u64) -> bool {
+        self.oracle_band_bps > 0 &&
+            crate::oracle::deviation_bps(self.price, oracle_price) > self.oracle_band_bps as u64
+    }
+    
+    /// Check if order has aged past the market's speed bump
+    pub fn has_rested(&self, current_slot: u64, min_resting_slots: u64) -> bool {
+        current_slot >= self.slot.saturating_add(min_resting_slots)
+    }
 }
 
+const _: () = assert!(std::mem::size_of::<Order>() == Order::SIZE);
+
 /// Orderbook data structure
 /// Uses a slab allocator pattern stored in account data
 /// 
 /// Structure:
-/// - Header: metadata (best_bid, best_ask, order_count, free_list_head)
+/// - Header: metadata (best prices, side heads, order_count, free list)
 /// - Slab: array of orders indexed by slot number
-/// - Price levels: linked lists of orders at each price point
+/// - Price levels: each side is a list of level heads sorted best price
+///   first (`next_in_book`), and each level is a FIFO list of orders
+///   (`next_at_price`); the level head's `prev_at_price` points at its tail
 /// 
 /// Matching algorithm:
 /// 1. For bids: highest price first (price-time priority)
 /// 2. For asks: lowest price first (price-time priority)
 /// 3. Within same price: FIFO (first-in-first-out)
+///
+/// Best-price lookup and removal are O(1); insertion walks price levels only.
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Orderbook {
     /// Market this orderbook belongs to
     pub market: Pubkey,
@@ -191,21 +301,68 @@ pub struct Orderbook {
     /// Best bid price (0 if empty)
     pub best_bid: u64,
     
-    /// Best ask price (u64::MAX if empty)
+    /// Best ask price (0 if empty)
     pub best_ask: u64,
     
     /// Number of active orders
     pub order_count: u64,
     
-    /// Head of free list (for slab allocation)
+    /// Head of free list (slot + 1, 0 if empty)
     pub free_list_head: u64,
     
-    /// Reserved space for future extensions
-    pub _reserved: [u8; 64],
+    /// Best bid order (slot + 1, 0 if no bids)
+    pub bid_head: u64,
+    
+    /// Best ask order (slot + 1, 0 if no asks)
+    pub ask_head: u64,
+    
+    /// Number of slab slots ever handed out (slots below this are live or free-listed)
+    pub slab_len: u64,
+    
+    /// Sum of price * remaining_size over resting bids (divide by lot size for quote)
+    pub bid_value: u128,
     
-    /// Order slab data follows (stored as raw bytes)
-    /// Each order is 128 bytes, max ~5000 orders per orderbook
-    /// (limited by account size constraints)
+    /// Sum of price * remaining_size over resting asks (divide by lot size for quote)
+    pub ask_value: u128,
+    
+    /// Slab capacity in orders (0 = the original `MAX_ORDERS`), raised by expand_orderbook
+    pub capacity: u64,
+    
+    // Order slab data follows (stored as raw bytes)
+    // Each order is Order::SIZE bytes; the account is grown with realloc
+    // as the capacity is expanded
+}
+
+/// Iterator over one side of the book in price-time priority
+pub struct BookSideIter<'a> {
+    book: &'a Orderbook,
+    data: &'a [u8],
+    level: u64,
+    next: u64,
+}
+
+impl<'a> Iterator for BookSideIter<'a> {
+    type Item = (u64, Order);
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == 0 {
+            return None;
+        }
+        let slot = self.next - 1;
+        let order = self.book.get_order(self.data, slot)?;
+        
+        if order.next_at_price != 0 {
+            self.next = order.next_at_price;
+        } else {
+            // End of this level: move on to the next level head
+            self.level = self.book.get_order(self.data, self.level - 1)
+                .map(|head| head.next_in_book)
+                .unwrap_or(0);
+            self.next = self.level;
+        }
+        
+        Some((slot, order))
+    }
 }
 
 impl Orderbook {
@@ -215,109 +372,295 @@ impl Orderbook {
         8 +  // best_ask
         8 +  // order_count
         8 +  // free_list_head
-        64;  // reserved
-    
-    pub const MAX_ORDERS: usize = 1000; // Conservative limit for account size
+        8 +  // bid_head
+        8 +  // ask_head
+        8 +  // slab_len
+        16 + // bid_value
+        16 + // ask_value
+        8;   // capacity
+    
+    pub const MAX_ORDERS: usize = 1000; // Initial capacity, before any expand_orderbook
     pub const ORDER_SIZE: usize = Order::SIZE;
     pub const MAX_SIZE: usize = Self::HEADER_SIZE + (Self::MAX_ORDERS * Self::ORDER_SIZE);
     
+    /// Number of order slots the slab may use
+    pub fn capacity(&self) -> usize {
+        if self.capacity == 0 {
+            Self::MAX_ORDERS
+        } else {
+            self.capacity as usize
+        }
+    }
+    
+    /// Account size needed for a slab of `capacity` orders
+    pub fn size_for(capacity: usize) -> usize {
+        Self::HEADER_SIZE + capacity * Self::ORDER_SIZE
+    }
+    
     /// Get order at a specific slot index
     /// Returns None if slot is free or invalid
     pub fn get_order(&self, data: &[u8], slot: u64) -> Option<Order> {
-        if slot as usize >= Self::MAX_ORDERS {
+        if slot as usize >= self.capacity() {
             return None;
         }
         
-        let offset = Self::HEADER_SIZE + (slot as usize * Self::ORDER_SIZE);
-        if offset + Self::ORDER_SIZE > data.len() {
-            return None;
-        }
-        
-        let order_bytes = &data[offset..offset + Self::ORDER_SIZE];
-        if order_bytes.iter().all(|&b| b == 0) {
+        let order = Self::read_slot(data, slot)?;
+        if order.order_id == 0 {
             return None; // Free slot
         }
         
-        bytemuck::try_from_bytes::<Order>(order_bytes).ok().copied()
+        Some(order)
     }
     
-    /// Write order to a specific slot
+    /// Update an order in place
+    /// Slab links are owned by the book and are preserved from the stored copy
     pub fn set_order(&mut self, data: &mut [u8], slot: u64, order: &Order) -> Result<()> {
-        require!(
-            slot as usize < Self::MAX_ORDERS,
-            crate::errors::DexError::OrderbookFull
-        );
+        let stored = self.get_order(data, slot)
+            .ok_or(crate::errors::DexError::InvalidOrderbookState)?;
         
-        let offset = Self::HEADER_SIZE + (slot as usize * Self::ORDER_SIZE);
-        require!(
-            offset + Self::ORDER_SIZE <= data.len(),
-            crate::errors::DexError::OrderbookFull
-        );
+        let mut updated = *order;
+        updated.next_at_price = stored.next_at_price;
+        updated.prev_at_price = stored.prev_at_price;
+        updated.next_in_book = stored.next_in_book;
+        updated.prev_in_book = stored.prev_in_book;
         
-        let order_bytes = bytemuck::bytes_of(order);
-        data[offset..offset + Self::ORDER_SIZE].copy_from_slice(order_bytes);
-        Ok(())
+        self.sub_value(&stored);
+        self.add_value(&updated);
+        Self::write_slot(data, slot, &updated)
     }
     
-    /// Allocate a new slot for an order
-    pub fn allocate_slot(&mut self, data: &mut [u8]) -> Result<u64> {
-        // Try free list first
-        if self.free_list_head != 0 && self.free_list_head < Self::MAX_ORDERS as u64 {
-            let slot = self.free_list_head;
-            // Read next free slot from order's next_at_price field (repurposed for free list)
-            let offset = Self::HEADER_SIZE + (slot as usize * Self::ORDER_SIZE);
-            if offset + 8 <= data.len() {
-                let next_free = u64::from_le_bytes(
-                    data[offset..offset + 8].try_into().unwrap_or([0; 8])
-                );
-                self.free_list_head = next_free;
-            } else {
-                self.free_list_head = 0;
+    /// Quote notional resting on one side of the book
+    pub fn open_notional(&self, side: Side, lot_size: u64) -> u64 {
+        let value = match side {
+            Side::Bid => self.bid_value,
+            Side::Ask => self.ask_value,
+        };
+        (value / lot_size.max(1) as u128).min(u64::MAX as u128) as u64
+    }
+    
+    fn add_value(&mut self, order: &Order) {
+        let value = order.price as u128 * order.remaining_size as u128;
+        if order.is_bid() {
+            self.bid_value = self.bid_value.saturating_add(value);
+        } else {
+            self.ask_value = self.ask_value.saturating_add(value);
+        }
+    }
+    
+    fn sub_value(&mut self, order: &Order) {
+        let value = order.price as u128 * order.remaining_size as u128;
+        if order.is_bid() {
+            self.bid_value = self.bid_value.saturating_sub(value);
+        } else {
+            self.ask_value = self.ask_value.saturating_sub(value);
+        }
+    }
+    
+    /// Insert a new order at the back of its price level
+    /// Returns the slot the order was stored in
+    pub fn insert_order(&mut self, data: &mut [u8], order: &Order) -> Result<u64> {
+        require!(order.order_id != 0, crate::errors::DexError::InvalidOrderParams);
+        
+        let slot = self.allocate_slot(data)?;
+        let link = slot + 1;
+        let mut new_order = *order;
+        new_order.next_at_price = 0;
+        
+        // Walk level heads until we reach this price or a worse one
+        let mut prev_level = 0u64;
+        let mut cursor = self.side_head(order.is_bid());
+        while cursor != 0 {
+            let head = self.link_order(data, cursor)?;
+            let better = if order.is_bid() { head.price > order.price } else { head.price < order.price };
+            if !better {
+                break;
             }
-            return Ok(slot);
+            prev_level = cursor;
+            cursor = head.next_in_book;
         }
         
-        // Allocate new slot
-        require!(
-            self.order_count < Self::MAX_ORDERS as u64,
-            crate::errors::DexError::OrderbookFull
-        );
+        let existing_level = if cursor != 0 {
+            let head = self.link_order(data, cursor)?;
+            (head.price == order.price).then_some(head)
+        } else {
+            None
+        };
         
-        // Find first free slot by scanning
-        for i in 0..Self::MAX_ORDERS {
-            let offset = Self::HEADER_SIZE + (i * Self::ORDER_SIZE);
-            if offset + Self::ORDER_SIZE <= data.len() {
-                if data[offset..offset + Self::ORDER_SIZE].iter().all(|&b| b == 0) {
-                    return Ok(i as u64);
+        match existing_level {
+            Some(mut head) => {
+                // Append to the level's tail
+                let tail = head.prev_at_price;
+                new_order.prev_at_price = tail;
+                new_order.next_in_book = 0;
+                new_order.prev_in_book = 0;
+                Self::write_slot(data, slot, &new_order)?;
+                
+                let mut tail_order = self.link_order(data, tail)?;
+                tail_order.next_at_price = link;
+                Self::write_slot(data, tail - 1, &tail_order)?;
+                
+                head = self.link_order(data, cursor)?;
+                head.prev_at_price = link;
+                Self::write_slot(data, cursor - 1, &head)?;
+            }
+            None => {
+                // Start a new level between prev_level and cursor
+                new_order.prev_at_price = link;
+                new_order.next_in_book = cursor;
+                new_order.prev_in_book = prev_level;
+                Self::write_slot(data, slot, &new_order)?;
+                
+                if prev_level != 0 {
+                    let mut prev = self.link_order(data, prev_level)?;
+                    prev.next_in_book = link;
+                    Self::write_slot(data, prev_level - 1, &prev)?;
+                } else {
+                    self.set_side_head(order.is_bid(), link);
+                }
+                if cursor != 0 {
+                    let mut next = self.link_order(data, cursor)?;
+                    next.prev_in_book = link;
+                    Self::write_slot(data, cursor - 1, &next)?;
                 }
             }
         }
         
-        Err(crate::errors::DexError::OrderbookFull.into())
+        self.order_count = self.order_count
+            .checked_add(1)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        self.add_value(order);
+        self.update_best_prices(data);
+        
+        Ok(slot)
     }
     
-    /// Free a slot (add to free list)
-    pub fn free_slot(&mut self, data: &mut [u8], slot: u64) -> Result<()> {
-        require!(
-            slot as usize < Self::MAX_ORDERS,
-            crate::errors::DexError::InvalidOrderbookState
-        );
+    /// Unlink an order from its price level and free its slot
+    pub fn remove_order(&mut self, data: &mut [u8], slot: u64) -> Result<()> {
+        let order = self.get_order(data, slot)
+            .ok_or(crate::errors::DexError::InvalidOrderbookState)?;
+        let link = slot + 1;
         
-        let offset = Self::HEADER_SIZE + (slot as usize * Self::ORDER_SIZE);
-        require!(
-            offset + Self::ORDER_SIZE <= data.len(),
-            crate::errors::DexError::InvalidOrderbookState
-        );
+        // A level head's prev_at_price is the tail, whose next_at_price is 0
+        let prev = self.link_order(data, order.prev_at_price)?;
+        let is_head = prev.next_at_price != link;
         
-        // Clear the slot
-        data[offset..offset + Self::ORDER_SIZE].fill(0);
+        if !is_head {
+            // Middle or tail of a level
+            let mut prev = prev;
+            prev.next_at_price = order.next_at_price;
+            Self::write_slot(data, order.prev_at_price - 1, &prev)?;
+            
+            if order.next_at_price != 0 {
+                let mut next = self.link_order(data, order.next_at_price)?;
+                next.prev_at_price = order.prev_at_price;
+                Self::write_slot(data, order.next_at_price - 1, &next)?;
+            } else {
+                // Removed the tail: find the head and point it at the new tail
+                let mut head_link = order.prev_at_price;
+                loop {
+                    let candidate = self.link_order(data, head_link)?;
+                    let before = self.link_order(data, candidate.prev_at_price)?;
+                    if before.next_at_price != head_link {
+                        break;
+                    }
+                    head_link = candidate.prev_at_price;
+                }
+                let mut head = self.link_order(data, head_link)?;
+                head.prev_at_price = order.prev_at_price;
+                Self::write_slot(data, head_link - 1, &head)?;
+            }
+        } else {
+            // Level head: promote the next order at this price, or drop the level
+            let replacement = order.next_at_price;
+            if replacement != 0 {
+                let mut new_head = self.link_order(data, replacement)?;
+                new_head.prev_at_price = order.prev_at_price; // Tail is unchanged
+                new_head.next_in_book = order.next_in_book;
+                new_head.prev_in_book = order.prev_in_book;
+                Self::write_slot(data, replacement - 1, &new_head)?;
+            }
+            
+            if order.prev_in_book != 0 {
+                let mut prev_level = self.link_order(data, order.prev_in_book)?;
+                prev_level.next_in_book = if replacement != 0 { replacement } else { order.next_in_book };
+                Self::write_slot(data, order.prev_in_book - 1, &prev_level)?;
+            } else {
+                self.set_side_head(order.is_bid(), if replacement != 0 { replacement } else { order.next_in_book });
+            }
+            if order.next_in_book != 0 {
+                let mut next_level = self.link_order(data, order.next_in_book)?;
+                next_level.prev_in_book = if replacement != 0 { replacement } else { order.prev_in_book };
+                Self::write_slot(data, order.next_in_book - 1, &next_level)?;
+            }
+        }
+        
+        self.free_slot(data, slot)?;
+        self.order_count = self.order_count
+            .checked_sub(1)
+            .ok_or(crate::errors::DexError::MathUnderflow)?;
+        self.sub_value(&order);
+        self.update_best_prices(data);
         
-        // Add to free list
+        Ok(())
+    }
+    
+    /// Iterate one side of the book in price-time priority
+    pub fn iter_side<'a>(&'a self, data: &'a [u8], side: Side) -> BookSideIter<'a> {
+        let head = self.side_head(side == Side::Bid);
+        BookSideIter { book: self, data, level: head, next: head }
+    }
+    
+    /// Find an order by ID on either side of the book
+    pub fn find_order(&self, data: &[u8], order_id: u128) -> Option<(u64, Order)> {
+        self.iter_side(data, Side::Bid)
+            .chain(self.iter_side(data, Side::Ask))
+            .find(|(_, order)| order.order_id == order_id)
+    }
+    
+    /// Find a trader's order by client order ID on either side of the book
+    /// Returns the oldest match if the trader reused the ID
+    pub fn find_order_by_client_id(
+        &self,
+        data: &[u8],
+        trader: &Pubkey,
+        client_order_id: u64,
+    ) -> Option<(u64, Order)> {
+        self.iter_side(data, Side::Bid)
+            .chain(self.iter_side(data, Side::Ask))
+            .filter(|(_, order)| order.trader == *trader && order.client_order_id == client_order_id)
+            .min_by_key(|(_, order)| (order.timestamp, order.order_id))
+    }
+    
+    /// Allocate a new slot for an order
+    fn allocate_slot(&mut self, data: &mut [u8]) -> Result<u64> {
+        // Try free list first
         if self.free_list_head != 0 {
-            // Write current free_list_head to slot's next_at_price
-            data[offset..offset + 8].copy_from_slice(&self.free_list_head.to_le_bytes());
+            let slot = self.free_list_head - 1;
+            let free = Self::read_slot(data, slot)
+                .ok_or(crate::errors::DexError::InvalidOrderbookState)?;
+            self.free_list_head = free.next_at_price;
+            return Ok(slot);
         }
-        self.free_list_head = slot;
+        
+        // Grow the slab
+        let slot = self.slab_len;
+        require!(
+            (slot as usize) < self.capacity() &&
+                Self::HEADER_SIZE + (slot as usize + 1) * Self::ORDER_SIZE <= data.len(),
+            crate::errors::DexError::OrderbookFull
+        );
+        self.slab_len += 1;
+        
+        Ok(slot)
+    }
+    
+    /// Free a slot (add to free list)
+    fn free_slot(&mut self, data: &mut [u8], slot: u64) -> Result<()> {
+        // A free slot is zeroed (order_id == 0) apart from the free-list link
+        let mut free = Order::zeroed();
+        free.next_at_price = self.free_list_head;
+        Self::write_slot(data, slot, &free)?;
+        self.free_list_head = slot + 1;
         
         Ok(())
     }
@@ -338,78 +681,57 @@ impl Orderbook {
         }
     }
     
-    /// Find best bid (highest price)
-    fn find_best_bid(&self, data: &[u8]) -> Option<(u64, Order)> {
-        if self.best_bid == 0 {
-            return None;
-        }
-        
-        // Start from best_bid price level and find first order
-        // In a full implementation, we'd maintain price level pointers
-        // For now, we scan (inefficient but functional)
-        let mut best_price = 0u64;
-        let mut best_slot = None;
-        let mut best_order = None;
-        
-        for i in 0..Self::MAX_ORDERS {
-            if let Some(order) = self.get_order(data, i as u64) {
-                if order.is_bid() && order.remaining_size > 0 {
-                    if order.price > best_price {
-                        best_price = order.price;
-                        best_slot = Some(i as u64);
-                        best_order = Some(order);
-                    }
-                }
-            }
-        }
-        
-        best_slot.zip(best_order)
+    /// Find best bid (highest price, oldest first)
+    pub fn find_best_bid(&self, data: &[u8]) -> Option<(u64, Order)> {
+        self.iter_side(data, Side::Bid).next()
     }
     
-    /// Find best ask (lowest price)
-    fn find_best_ask(&self, data: &[u8]) -> Option<(u64, Order)> {
-        if self.best_ask == u64::MAX {
-            return None;
-        }
-        
-        let mut best_price = u64::MAX;
-        let mut best_slot = None;
-        let mut best_order = None;
-        
-        for i in 0..Self::MAX_ORDERS {
-            if let Some(order) = self.get_order(data, i as u64) {
-                if order.is_ask() && order.remaining_size > 0 {
-                    if order.price < best_price {
-                        best_price = order.price;
-                        best_slot = Some(i as u64);
-                        best_order = Some(order);
-                    }
-                }
-            }
-        }
-        
-        best_slot.zip(best_order)
+    /// Find best ask (lowest price, oldest first)
+    pub fn find_best_ask(&self, data: &[u8]) -> Option<(u64, Order)> {
+        self.iter_side(data, Side::Ask).next()
     }
     
-    /// Update best bid/ask after order changes
+    /// Update best bid/ask from the side heads
     pub fn update_best_prices(&mut self, data: &[u8]) {
-        let mut best_bid = 0u64;
-        let mut best_ask = u64::MAX;
-        
-        for i in 0..Self::MAX_ORDERS {
-            if let Some(order) = self.get_order(data, i as u64) {
-                if order.remaining_size > 0 {
-                    if order.is_bid() && order.price > best_bid {
-                        best_bid = order.price;
-                    } else if order.is_ask() && order.price < best_ask {
-                        best_ask = order.price;
-                    }
-                }
-            }
+        self.best_bid = self.find_best_bid(data).map(|(_, o)| o.price).unwrap_or(0);
+        self.best_ask = self.find_best_ask(data).map(|(_, o)| o.price).unwrap_or(0);
+    }
+    
+    fn side_head(&self, is_bid: bool) -> u64 {
+        if is_bid { self.bid_head } else { self.ask_head }
+    }
+    
+    fn set_side_head(&mut self, is_bid: bool, link: u64) {
+        if is_bid {
+            self.bid_head = link;
+        } else {
+            self.ask_head = link;
         }
+    }
+    
+    /// Load the live order a slab link points at
+    fn link_order(&self, data: &[u8], link: u64) -> Result<Order> {
+        require!(link != 0, crate::errors::DexError::InvalidOrderbookState);
+        self.get_order(data, link - 1)
+            .ok_or(crate::errors::DexError::InvalidOrderbookState.into())
+    }
+    
+    fn read_slot(data: &[u8], slot: u64) -> Option<Order> {
+        let offset = Self::HEADER_SIZE + (slot as usize * Self::ORDER_SIZE);
+        let order_bytes = data.get(offset..offset + Self::ORDER_SIZE)?;
+        Some(bytemuck::pod_read_unaligned::<Order>(order_bytes))
+    }
+    
+    /// Slots past the capacity are never handed out by allocate_slot
+    fn write_slot(data: &mut [u8], slot: u64, order: &Order) -> Result<()> {
+        let offset = Self::HEADER_SIZE + (slot as usize * Self::ORDER_SIZE);
+        require!(
+            offset + Self::ORDER_SIZE <= data.len(),
+            crate::errors::DexError::OrderbookFull
+        );
         
-        self.best_bid = best_bid;
-        self.best_ask = if best_ask == u64::MAX { 0 } else { best_ask };
+        data[offset..offset + Self::ORDER_SIZE].copy_from_slice(bytemuck::bytes_of(order));
+        Ok(())
     }
 }
 
@@ -0,0 +1,525 @@
+use anchor_lang::Discriminator;
+use serde::Serialize;
+use std::mem::{offset_of, size_of};
+use crate::event_queue::{EventQueue, QueueEvent};
+use crate::orderbook::{Order, Orderbook};
+use crate::trade_tape::{TradeRecord, TradeTape};
+use crate::state::{Competition, CompetitionEntry, DataFeed, GlobalConfig, IntegratorFees, Market, OpenOrders, PendingFill, PendingWithdrawal, RevenueShare, Seat, TraderState, WithdrawalAllowlist};
+
+/// Layout of a single field inside an account
+#[derive(Serialize)]
+pub struct FieldLayout {
+    pub name: &'static str,
+    pub ty: &'static str,
+    pub offset: usize,
+    pub size: usize,
+}
+
+/// Layout of an account (or slab element) as stored on-chain
+/// Offsets are byte offsets from the start of the account data
+#[derive(Serialize)]
+pub struct AccountLayout {
+    pub name: &'static str,
+    pub encoding: &'static str,
+    pub discriminator: Option<[u8; 8]>,
+    pub size: usize,
+    pub fields: Vec<FieldLayout>,
+}
+
+/// Build a Borsh layout from (name, type, size) triples
+/// Fields are packed back to back after the 8-byte discriminator
+fn borsh_layout(
+    name: &'static str,
+    discriminator: [u8; 8],
+    size: usize,
+    fields: &[(&'static str, &'static str, usize)],
+) -> AccountLayout {
+    let mut offset = 8;
+    let fields = fields
+        .iter()
+        .map(|&(name, ty, size)| {
+            let field = FieldLayout { name, ty, offset, size };
+            offset += size;
+            field
+        })
+        .collect();
+    
+    AccountLayout {
+        name,
+        encoding: "borsh",
+        discriminator: Some(discriminator),
+        size,
+        fields,
+    }
+}
+
+/// Layouts for every account type owned by the program
+/// Keep in sync with the `SIZE` constants in state.rs and orderbook.rs
+pub fn account_layouts() -> Vec<AccountLayout> {
+    vec![
+        borsh_layout(
+            "GlobalConfig",
+            GlobalConfig::DISCRIMINATOR,
+            GlobalConfig::SIZE,
+            &[
+                ("authority", "pubkey", 32),
+                ("fee_recipient", "pubkey", 32),
+                ("maker_fee_bps", "i16", 2),
+                ("taker_fee_bps", "u16", 2),
+                ("permissionless_markets", "bool", 1),
+                ("market_creation_fee", "u64", 8),
+                ("risk_authority", "pubkey", 32),
+                ("max_integrator_fee_bps", "u16", 2),
+                ("pending_authority", "pubkey", 32),
+                ("pending_maker_fee_bps", "i16", 2),
+                ("pending_taker_fee_bps", "u16", 2),
+                ("pending_max_integrator_fee_bps", "u16", 2),
+                ("fees_effective_at", "i64", 8),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 64]", 64),
+            ],
+        ),
+        borsh_layout(
+            "Market",
+            Market::DISCRIMINATOR,
+            Market::SIZE,
+            &[
+                ("market_id", "u64", 8),
+                ("base_mint", "pubkey", 32),
+                ("quote_mint", "pubkey", 32),
+                ("base_vault", "pubkey", 32),
+                ("quote_vault", "pubkey", 32),
+                ("tick_size", "u64", 8),
+                ("lot_size", "u64", 8),
+                ("authority", "pubkey", 32),
+                ("paused", "bool", 1),
+                ("best_bid", "u64", 8),
+                ("best_ask", "u64", 8),
+                ("order_count", "u64", 8),
+                ("total_volume", "u128", 16),
+                ("oracle", "pubkey", 32),
+                ("oracle_max_staleness", "u64", 8),
+                ("oracle_max_deviation_bps", "u16", 2),
+                ("last_depth_snapshot_slot", "u64", 8),
+                ("peg_price", "u64", 8),
+                ("peg_band_bps", "u16", 2),
+                ("peg_cancel_only", "bool", 1),
+                ("template", "u8", 1),
+                ("max_order_notional", "u64", 8),
+                ("min_resting_slots", "u64", 8),
+                ("batch_matching", "bool", 1),
+                ("settlement_program", "pubkey", 32),
+                ("fill_archive_root", "[u8; 32]", 32),
+                ("archived_fill_count", "u64", 8),
+                ("event_queue", "pubkey", 32),
+                ("base_fees_accrued", "u64", 8),
+                ("quote_fees_accrued", "u64", 8),
+                ("base_decimals", "u8", 1),
+                ("quote_decimals", "u8", 1),
+                ("max_side_notional", "u64", 8),
+                ("max_open_notional", "u64", 8),
+                ("bid_open_notional", "u64", 8),
+                ("ask_open_notional", "u64", 8),
+                ("epoch", "u64", 8),
+                ("epoch_started_at", "i64", 8),
+                ("epoch_volume", "u128", 16),
+                ("epoch_fill_count", "u64", 8),
+                ("last_epoch_volume", "u128", 16),
+                ("oracle_type", "u8", 1),
+                ("base_vault_ledger", "u64", 8),
+                ("quote_vault_ledger", "u64", 8),
+                ("operator", "pubkey", 32),
+                ("operator_permissions", "u8", 1),
+                ("best_bid_order_id", "u128", 16),
+                ("best_ask_order_id", "u128", 16),
+                ("withdrawal_cooldown_seconds", "u32", 4),
+                ("early_withdrawal_fee_bps", "u16", 2),
+                ("last_match_slot", "u64", 8),
+                ("last_consume_slot", "u64", 8),
+                ("order_sequence", "u64", 8),
+                ("book_generation", "u32", 4),
+                ("event_verbosity", "u8", 1),
+                ("pending_tick_size", "u64", 8),
+                ("pending_lot_size", "u64", 8),
+                ("pending_min_resting_slots", "u64", 8),
+                ("pending_batch_matching", "bool", 1),
+                ("pending_event_verbosity", "u8", 1),
+                ("params_effective_at", "i64", 8),
+                ("status", "u8", 1),
+                ("requires_seat", "bool", 1),
+                ("crank_reward_per_fill", "u64", 8),
+                ("trade_tape", "pubkey", 32),
+                ("stats_hour", "i64", 8),
+                ("hourly_volume", "[u64; 24]", 192),
+                ("hourly_fill_count", "[u32; 24]", 96),
+                ("hourly_open_price", "[u64; 24]", 192),
+                ("hourly_close_price", "[u64; 24]", 192),
+                ("min_notional", "u64", 8),
+                ("last_trade_price", "u64", 8),
+                ("last_trade_size", "u64", 8),
+                ("last_trade_timestamp", "i64", 8),
+                ("breaker_move_bps", "u16", 2),
+                ("breaker_window_seconds", "i64", 8),
+                ("breaker_cooldown_seconds", "i64", 8),
+                ("breaker_reference_price", "u64", 8),
+                ("breaker_window_start", "i64", 8),
+                ("halted_until", "i64", 8),
+                ("dust_threshold", "u64", 8),
+                ("match_price_model", "u8", 1),
+                ("pending_match_price_model", "u8", 1),
+                ("pending_oracle", "Pubkey", 32),
+                ("pending_oracle_type", "u8", 1),
+                ("pending_oracle_max_staleness", "u64", 8),
+                ("pending_oracle_max_deviation_bps", "u16", 2),
+                ("oracle_effective_at", "i64", 8),
+                ("pending_peg_band_bps", "u16", 2),
+                ("pending_max_order_notional", "u64", 8),
+                ("pending_min_notional", "u64", 8),
+                ("pending_dust_threshold", "u64", 8),
+                ("pending_max_side_notional", "u64", 8),
+                ("pending_max_open_notional", "u64", 8),
+                ("pending_breaker_move_bps", "u16", 2),
+                ("pending_breaker_window_seconds", "i64", 8),
+                ("pending_breaker_cooldown_seconds", "i64", 8),
+                ("risk_effective_at", "i64", 8),
+                ("fill_sequence", "u64", 8),
+                ("base_rebates_reserved", "u64", 8),
+                ("quote_rebates_reserved", "u64", 8),
+                ("max_cancel_to_fill_ratio", "u16", 2),
+                ("pending_max_cancel_to_fill_ratio", "u16", 2),
+                ("placement_deposit_lamports", "u32", 4),
+                ("pending_placement_deposit_lamports", "u32", 4),
+                ("forfeited_deposits", "u64", 8),
+                ("fee_pass_mint", "pubkey", 32),
+                ("fee_pass_is_collection", "bool", 1),
+                ("fee_pass_min_amount", "u64", 8),
+                ("fee_pass_discount_bps", "u16", 2),
+                ("points_taker_weight", "u16", 2),
+                ("points_maker_weight", "u16", 2),
+                ("points_start", "i64", 8),
+                ("points_end", "i64", 8),
+                ("points_halving_seconds", "i64", 8),
+                ("revenue_share", "pubkey", 32),
+                ("competition", "pubkey", 32),
+                ("competition_start", "i64", 8),
+                ("competition_end", "i64", 8),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 128]", 128),
+            ],
+        ),
+        borsh_layout(
+            "TraderState",
+            TraderState::DISCRIMINATOR,
+            TraderState::SIZE,
+            &[
+                ("trader", "pubkey", 32),
+                ("market", "pubkey", 32),
+                ("base_available", "u64", 8),
+                ("quote_available", "u64", 8),
+                ("base_locked", "u64", 8),
+                ("quote_locked", "u64", 8),
+                ("open_order_count", "u16", 2),
+                ("withdrawal_co_signer", "pubkey", 32),
+                ("co_sign_threshold", "u64", 8),
+                ("withdrawal_allowlist_enabled", "bool", 1),
+                ("guardian", "pubkey", 32),
+                ("locked_until", "i64", 8),
+                ("last_deposit_at", "i64", 8),
+                ("ratio_window_start", "i64", 8),
+                ("window_cancels", "u32", 4),
+                ("window_fills", "u32", 4),
+                ("points", "u64", 8),
+                ("deposit_balance", "u64", 8),
+                ("deposits_held", "u64", 8),
+                ("competition", "pubkey", 32),
+                ("competition_volume", "u64", 8),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        borsh_layout(
+            "PendingFill",
+            PendingFill::DISCRIMINATOR,
+            PendingFill::SIZE,
+            &[
+                ("fill_id", "u128", 16),
+                ("market", "pubkey", 32),
+                ("bid_order_id", "u128", 16),
+                ("ask_order_id", "u128", 16),
+                ("bid_trader", "pubkey", 32),
+                ("ask_trader", "pubkey", 32),
+                ("price", "u64", 8),
+                ("size", "u64", 8),
+                ("quote_amount", "u64", 8),
+                ("maker_fee", "u64", 8),
+                ("taker_fee", "u64", 8),
+                ("settled", "bool", 1),
+                ("timestamp", "i64", 8),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        borsh_layout(
+            "PendingWithdrawal",
+            PendingWithdrawal::DISCRIMINATOR,
+            PendingWithdrawal::SIZE,
+            &[
+                ("trader_state", "pubkey", 32),
+                ("market", "pubkey", 32),
+                ("mint", "pubkey", 32),
+                ("amount", "u64", 8),
+                ("requested_at", "i64", 8),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        borsh_layout(
+            "WithdrawalAllowlist",
+            WithdrawalAllowlist::DISCRIMINATOR,
+            WithdrawalAllowlist::SIZE,
+            &[
+                ("trader_state", "pubkey", 32),
+                ("destinations", "[pubkey; 4]", 128),
+                ("pending_destinations", "[pubkey; 4]", 128),
+                ("pending_effective_at", "i64", 8),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        borsh_layout(
+            "IntegratorFees",
+            IntegratorFees::DISCRIMINATOR,
+            IntegratorFees::SIZE,
+            &[
+                ("market", "pubkey", 32),
+                ("platform", "pubkey", 32),
+                ("base_accrued", "u64", 8),
+                ("quote_accrued", "u64", 8),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        borsh_layout(
+            "OpenOrders",
+            OpenOrders::DISCRIMINATOR,
+            OpenOrders::SIZE,
+            &[
+                ("trader_state", "pubkey", 32),
+                ("orders", "[OpenOrderRef; 32]", 768),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        borsh_layout(
+            "DataFeed",
+            DataFeed::DISCRIMINATOR,
+            DataFeed::SIZE,
+            &[
+                ("market", "pubkey", 32),
+                ("last_refresh_slot", "u64", 8),
+                ("refresh_count", "u64", 8),
+                ("total_funded", "u64", 8),
+                ("bids", "[FeedOrder; 16]", 1024),
+                ("asks", "[FeedOrder; 16]", 1024),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        borsh_layout(
+            "Seat",
+            Seat::DISCRIMINATOR,
+            Seat::SIZE,
+            &[
+                ("market", "pubkey", 32),
+                ("trader", "pubkey", 32),
+                ("granted_at", "i64", 8),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        borsh_layout(
+            "RevenueShare",
+            RevenueShare::DISCRIMINATOR,
+            RevenueShare::SIZE,
+            &[
+                ("market", "pubkey", 32),
+                ("entry_count", "u8", 1),
+                ("entries", "[RevenueShareEntry; 8]", 400),
+                ("base_collected", "u64", 8),
+                ("quote_collected", "u64", 8),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        borsh_layout(
+            "Competition",
+            Competition::DISCRIMINATOR,
+            Competition::SIZE,
+            &[
+                ("competition_id", "u64", 8),
+                ("market_count", "u8", 1),
+                ("markets", "[pubkey; 4]", 128),
+                ("quote_mint", "pubkey", 32),
+                ("start", "i64", 8),
+                ("end", "i64", 8),
+                ("prize_mint", "pubkey", 32),
+                ("prize_vault", "pubkey", 32),
+                ("prize_amount", "u64", 8),
+                ("total_score", "u64", 8),
+                ("entrant_count", "u32", 4),
+                ("bump", "u8", 1),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        borsh_layout(
+            "CompetitionEntry",
+            CompetitionEntry::DISCRIMINATOR,
+            CompetitionEntry::SIZE,
+            &[
+                ("competition", "pubkey", 32),
+                ("trader", "pubkey", 32),
+                ("score", "u64", 8),
+                ("bump", "u8", 1),
+            ],
+        ),
+        borsh_layout(
+            "Orderbook",
+            Orderbook::DISCRIMINATOR,
+            Orderbook::HEADER_SIZE,
+            &[
+                ("market", "pubkey", 32),
+                ("best_bid", "u64", 8),
+                ("best_ask", "u64", 8),
+                ("order_count", "u64", 8),
+                ("free_list_head", "u64", 8),
+                ("bid_head", "u64", 8),
+                ("ask_head", "u64", 8),
+                ("slab_len", "u64", 8),
+                ("bid_value", "u128", 16),
+                ("ask_value", "u128", 16),
+                ("capacity", "u64", 8),
+            ],
+        ),
+        order_layout(),
+        borsh_layout(
+            "EventQueue",
+            EventQueue::DISCRIMINATOR,
+            EventQueue::HEADER_SIZE,
+            &[
+                ("market", "pubkey", 32),
+                ("head", "u64", 8),
+                ("count", "u64", 8),
+                ("seq_num", "u64", 8),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        queue_event_layout(),
+        borsh_layout(
+            "TradeTape",
+            TradeTape::DISCRIMINATOR,
+            TradeTape::HEADER_SIZE,
+            &[
+                ("market", "pubkey", 32),
+                ("seq_num", "u64", 8),
+                ("_reserved", "[u8; 32]", 32),
+            ],
+        ),
+        trade_record_layout(),
+    ]
+}
+
+/// Field of a `repr(C)` struct at its real offset
+macro_rules! field {
+    ($ty_name:ident, $name:ident, $ty:expr, $size:expr) => {
+        FieldLayout {
+            name: stringify!($name),
+            ty: $ty,
+            offset: offset_of!($ty_name, $name),
+            size: $size,
+        }
+    };
+}
+
+/// Layout of a slab order (raw `repr(C)` bytes following the orderbook header)
+fn order_layout() -> AccountLayout {
+    AccountLayout {
+        name: "Order",
+        encoding: "repr(C)",
+        discriminator: None,
+        size: size_of::<Order>(),
+        fields: vec![
+            field!(Order, order_id, "u128", 16),
+            field!(Order, trader, "pubkey", 32),
+            field!(Order, price, "u64", 8),
+            field!(Order, size, "u64", 8),
+            field!(Order, remaining_size, "u64", 8),
+            field!(Order, timestamp, "i64", 8),
+            field!(Order, slot, "u64", 8),
+            field!(Order, client_order_id, "u64", 8),
+            field!(Order, next_at_price, "u64", 8),
+            field!(Order, prev_at_price, "u64", 8),
+            field!(Order, next_in_book, "u64", 8),
+            field!(Order, prev_in_book, "u64", 8),
+            field!(Order, expiry_timestamp, "i64", 8),
+            field!(Order, side, "u8", 1),
+            field!(Order, time_in_force, "u8", 1),
+            field!(Order, oracle_band_bps, "u16", 2),
+            field!(Order, origin, "u32", 4),
+            field!(Order, flags, "u8", 1),
+            field!(Order, deposit_lamports, "u32", 4),
+            field!(Order, cumulative_filled, "u64", 8),
+        ],
+    }
+}
+
+/// Layout of an event queue entry (raw `repr(C)` bytes following the queue header)
+fn queue_event_layout() -> AccountLayout {
+    AccountLayout {
+        name: "QueueEvent",
+        encoding: "repr(C)",
+        discriminator: None,
+        size: size_of::<QueueEvent>(),
+        fields: vec![
+            field!(QueueEvent, bid_order_id, "u128", 16),
+            field!(QueueEvent, ask_order_id, "u128", 16),
+            field!(QueueEvent, bid_trader, "pubkey", 32),
+            field!(QueueEvent, ask_trader, "pubkey", 32),
+            field!(QueueEvent, seq_num, "u64", 8),
+            field!(QueueEvent, price, "u64", 8),
+            field!(QueueEvent, size, "u64", 8),
+            field!(QueueEvent, bid_price, "u64", 8),
+            field!(QueueEvent, timestamp, "i64", 8),
+            field!(QueueEvent, bid_placed_at, "i64", 8),
+            field!(QueueEvent, ask_placed_at, "i64", 8),
+            field!(QueueEvent, bid_deposit, "u64", 8),
+            field!(QueueEvent, ask_deposit, "u64", 8),
+            field!(QueueEvent, bid_fee_bps, "i16", 2),
+            field!(QueueEvent, ask_fee_bps, "i16", 2),
+            field!(QueueEvent, event_type, "u8", 1),
+            field!(QueueEvent, flags, "u8", 1),
+        ],
+    }
+}
+
+/// Layout of a trade tape entry (raw `repr(C)` bytes following the tape header)
+fn trade_record_layout() -> AccountLayout {
+    AccountLayout {
+        name: "TradeRecord",
+        encoding: "repr(C)",
+        discriminator: None,
+        size: size_of::<TradeRecord>(),
+        fields: vec![
+            field!(TradeRecord, seq_num, "u64", 8),
+            field!(TradeRecord, price, "u64", 8),
+            field!(TradeRecord, size, "u64", 8),
+            field!(TradeRecord, timestamp, "i64", 8),
+            field!(TradeRecord, maker, "pubkey", 32),
+            field!(TradeRecord, taker, "pubkey", 32),
+            field!(TradeRecord, taker_side, "u8", 1),
+        ],
+    }
+}
+
+/// Export all account layouts as pretty-printed JSON
+pub fn export_json() -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&account_layouts())
+}
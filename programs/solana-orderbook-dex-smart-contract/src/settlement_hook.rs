@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use crate::errors::DexError;
+
+/// Instruction name the external settlement program must implement
+///
+/// The hook is called Anchor-style: 8-byte `global:on_settle` sighash
+/// followed by the Borsh-encoded `SettlementHookFill`.
+pub const ON_SETTLE_IX_NAME: &str = "global:on_settle";
+
+/// Fill details forwarded to the external settlement program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SettlementHookFill {
+    pub market: Pubkey,
+    pub fill_id: u128,
+    pub bid_trader: Pubkey,
+    pub ask_trader: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub maker_fee: u64,
+    pub taker_fee: u64,
+}
+
+/// Forward a fill to the market's settlement program
+///
+/// `accounts[0]` must be the configured program; the remaining accounts are
+/// passed through to it unchanged (escrow state, compliance registries, ...).
+/// The external program vetoes a settlement by returning an error, which
+/// aborts the whole settle instruction.
+pub fn forward_fill<'info>(
+    settlement_program: &Pubkey,
+    market_info: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    fill: &SettlementHookFill,
+) -> Result<()> {
+    let program_info = accounts.first().ok_or(DexError::InvalidSettlementProgram)?;
+    require!(
+        program_info.key() == *settlement_program && program_info.executable,
+        DexError::InvalidSettlementProgram
+    );
+    let passthrough = &accounts[1..];
+    
+    let mut data = hash(ON_SETTLE_IX_NAME.as_bytes()).to_bytes()[..8].to_vec();
+    fill.serialize(&mut data)?;
+    
+    let mut metas = vec![AccountMeta::new_readonly(market_info.key(), false)];
+    metas.extend(passthrough.iter().map(|info| {
+        if info.is_writable {
+            AccountMeta::new(info.key(), info.is_signer)
+        } else {
+            AccountMeta::new_readonly(info.key(), info.is_signer)
+        }
+    }));
+    
+    let mut infos = vec![market_info.clone()];
+    infos.extend(passthrough.iter().cloned());
+    infos.push(program_info.clone());
+    
+    invoke(
+        &Instruction {
+            program_id: *settlement_program,
+            accounts: metas,
+            data,
+        },
+        &infos,
+    )?;
+    
+    Ok(())
+}
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
-use crate::orderbook::{Orderbook, OrderbookSide};
+use anchor_lang::solana_program::hash::hashv;
+use crate::events::EventSink;
+use crate::orderbook::{Order, Orderbook, Side};
 
 /// Global DEX configuration account
 /// Stores protocol-wide settings, fee parameters, and authority
 #[account]
-#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct GlobalConfig {
     /// Protocol authority (can update fees, pause markets, etc.)
     pub authority: Pubkey,
@@ -13,8 +14,8 @@ pub struct GlobalConfig {
     /// Protocol fee recipient (treasury)
     pub fee_recipient: Pubkey,
     
-    /// Maker fee in basis points (e.g., 2 = 0.02%)
-    pub maker_fee_bps: u16,
+    /// Maker fee in basis points (e.g., 2 = 0.02%; negative = rebate paid from the taker fee)
+    pub maker_fee_bps: i16,
     
     /// Taker fee in basis points (e.g., 4 = 0.04%)
     pub taker_fee_bps: u16,
@@ -25,10 +26,29 @@ pub struct GlobalConfig {
     /// Market creation fee (in lamports) if permissioned
     pub market_creation_fee: u64,
     
+    /// Risk admin (can tune per-market risk parameters)
+    pub risk_authority: Pubkey,
+    
+    /// Maximum share of the taker fee an order may route to an integrator (in bps of the fee)
+    pub max_integrator_fee_bps: u16,
+    
+    /// Authority proposed by propose_authority; takes over once it signs accept_authority
+    /// Pubkey::default() when no transfer is pending
+    pub pending_authority: Pubkey,
+    
+    /// Staged fee schedule, activated by apply_pending_update at `fees_effective_at`
+    pub pending_maker_fee_bps: i16,
+    pub pending_taker_fee_bps: u16,
+    pub pending_max_integrator_fee_bps: u16,
+    
+    /// When the staged fee schedule can be applied (0 if nothing is pending)
+    pub fees_effective_at: i64,
+    
     /// Bump seed for PDA derivation
     pub bump: u8,
     
     /// Reserved space for future upgrades
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub _reserved: [u8; 64],
 }
 
@@ -40,12 +60,170 @@ impl GlobalConfig {
         2 +  // taker_fee_bps
         1 +  // permissionless_markets
         8 +  // market_creation_fee
+        32 + // risk_authority
+        2 +  // max_integrator_fee_bps
+        32 + // pending_authority
+        2 +  // pending_maker_fee_bps
+        2 +  // pending_taker_fee_bps
+        2 +  // pending_max_integrator_fee_bps
+        8 +  // fees_effective_at
         1 +  // bump
         64;  // reserved
+    
+    /// Delay between staging a fee or market parameter change and applying it (24 hours)
+    pub const UPDATE_DELAY_SECONDS: i64 = 86_400;
+    
+    /// Check the fee schedule
+    pub fn validate_fees(&self) -> Result<()> {
+        Self::validate_fee_schedule(self.maker_fee_bps, self.taker_fee_bps, self.max_integrator_fee_bps)
+    }
+    
+    /// Taker fee after a `discount_bps` fee-pass discount
+    ///
+    /// The maker rebate and the integrator's `integrator_fee_bps` share are paid
+    /// out of the taker fee, so the discount stops where the fee would no longer
+    /// cover them (see `validate_fee_schedule`).
+    pub fn discounted_taker_fee_bps(&self, discount_bps: u16, integrator_fee_bps: u16) -> u16 {
+        let rebate_bps = if self.maker_fee_bps < 0 { self.maker_fee_bps.unsigned_abs() as u32 } else { 0 };
+        let floor_bps = if rebate_bps == 0 {
+            0
+        } else {
+            // validate_fee_schedule rules out a rebate next to a 100% integrator share
+            let kept_share = 10_000 - (integrator_fee_bps as u32).min(9_999);
+            (rebate_bps * 10_000).div_ceil(kept_share)
+        };
+        let discounted = (self.taker_fee_bps as u32).saturating_sub(discount_bps as u32);
+        discounted.max(floor_bps).min(self.taker_fee_bps as u32) as u16
+    }
+    
+    /// Check a fee schedule before it is stored
+    /// Maker rebates and integrator shares are both paid out of the taker fee,
+    /// so together they may not exceed it
+    pub fn validate_fee_schedule(maker_fee_bps: i16, taker_fee_bps: u16, max_integrator_fee_bps: u16) -> Result<()> {
+        require!(
+            maker_fee_bps <= 1000 && taker_fee_bps <= 1000, // Max 10%
+            crate::errors::DexError::InvalidFeeCalculation
+        );
+        require!(max_integrator_fee_bps <= 10_000, crate::errors::DexError::InvalidFeeCalculation); // Share of the taker fee
+        let rebate_bps = if maker_fee_bps < 0 { maker_fee_bps.unsigned_abs() as u32 } else { 0 };
+        let taker_bps = taker_fee_bps as u32;
+        require!(
+            rebate_bps * 10_000 + taker_bps * max_integrator_fee_bps as u32 <= taker_bps * 10_000,
+            crate::errors::DexError::InvalidFeeCalculation
+        );
+        Ok(())
+    }
+}
+
+/// How much order-flow detail a market logs
+/// Fills and non-market-data events are always logged
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum EventVerbosity {
+    /// Fills, top-of-book changes and every order placement, change and cancel
+    Full = 0,
+    /// Fills and top-of-book changes
+    FillsAndBbo = 1,
+    /// Fills only
+    FillsOnly = 2,
+}
+
+impl EventVerbosity {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(EventVerbosity::Full),
+            1 => Some(EventVerbosity::FillsAndBbo),
+            2 => Some(EventVerbosity::FillsOnly),
+            _ => None,
+        }
+    }
+}
+
+/// Price a continuous-matching fill executes at
+/// Batch auctions clear each level at a single price and ignore this
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MatchPriceModel {
+    /// The resting (maker) order's price
+    MakerPrice = 0,
+    /// The incoming (taker) order's limit price
+    TakerPrice = 1,
+    /// Midpoint of both prices, rounded down to the tick
+    Midpoint = 2,
+}
+
+impl MatchPriceModel {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(MatchPriceModel::MakerPrice),
+            1 => Some(MatchPriceModel::TakerPrice),
+            2 => Some(MatchPriceModel::Midpoint),
+            _ => None,
+        }
+    }
+}
+
+/// Lifecycle stage of a market; markets only move forward, except that a
+/// cancel-only market can be reactivated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MarketStatus {
+    /// Trading normally
+    Active = 0,
+    /// Winding down: new orders are rejected, cancels and withdrawals still work
+    CancelOnly = 1,
+    /// Delisted: anyone can force-cancel the remaining orders
+    Delisted = 2,
+}
+
+impl MarketStatus {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(MarketStatus::Active),
+            1 => Some(MarketStatus::CancelOnly),
+            2 => Some(MarketStatus::Delisted),
+            _ => None,
+        }
+    }
+    
+    /// Whether a market may move from `self` to `next`
+    pub fn can_transition_to(self, next: MarketStatus) -> bool {
+        matches!(
+            (self, next),
+            (MarketStatus::Active, MarketStatus::CancelOnly) |
+            (MarketStatus::Active, MarketStatus::Delisted) |
+            (MarketStatus::CancelOnly, MarketStatus::Active) |
+            (MarketStatus::CancelOnly, MarketStatus::Delisted)
+        )
+    }
+}
+
+/// Rolling 24h trading statistics derived from a market's hourly buckets
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct RollingStats {
+    /// Quote volume traded
+    pub volume: u128,
+    
+    /// Number of fills
+    pub fill_count: u64,
+    
+    /// First fill price in the window (0 if no fills)
+    pub open_price: u64,
+    
+    /// Last fill price in the window (0 if no fills)
+    pub close_price: u64,
+}
+
+impl RollingStats {
+    /// Price move over the window (close minus open)
+    pub fn price_change(&self) -> i128 {
+        self.close_price as i128 - self.open_price as i128
+    }
 }
 
 /// Market account storing spot market configuration and orderbook state
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Market {
     /// Market identifier (unique)
     pub market_id: u64,
@@ -86,10 +264,301 @@ pub struct Market {
     /// Total volume traded (in quote units)
     pub total_volume: u128,
     
+    /// Price oracle account (Pubkey::default() if none)
+    pub oracle: Pubkey,
+    
+    /// Maximum oracle price age in seconds before it is considered stale
+    pub oracle_max_staleness: u64,
+    
+    /// Maximum allowed deviation from the oracle price (in bps)
+    pub oracle_max_deviation_bps: u16,
+    
+    /// Slot of the last depth snapshot crank
+    pub last_depth_snapshot_slot: u64,
+    
+    /// Peg price for stable-pair markets (in quote units, 0 if disabled)
+    pub peg_price: u64,
+    
+    /// Allowed deviation from the peg price (in bps, 0 if disabled)
+    pub peg_band_bps: u16,
+    
+    /// Set when a trade breaks the peg band; only cancels allowed until re-armed
+    pub peg_cancel_only: bool,
+    
+    /// Template the market was created from (0 = Custom)
+    pub template: u8,
+    
+    /// Maximum notional per order (in quote units, 0 if unlimited)
+    pub max_order_notional: u64,
+    
+    /// Speed bump: slots an order must rest before it can match (0 if disabled)
+    pub min_resting_slots: u64,
+    
+    /// Batch-per-slot mode: same-slot orders are simultaneous and fill pro-rata
+    pub batch_matching: bool,
+    
+    /// External program that must approve each settlement (Pubkey::default() if none)
+    pub settlement_program: Pubkey,
+    
+    /// Cumulative root of archived fills: hash(previous_root || batch_merkle_root)
+    pub fill_archive_root: [u8; 32],
+    
+    /// Number of fills folded into the archive root
+    pub archived_fill_count: u64,
+    
+    /// Event queue account that matching appends fills to
+    pub event_queue: Pubkey,
+    
+    /// Protocol fees accrued in base units (charged to buyers)
+    pub base_fees_accrued: u64,
+    
+    /// Protocol fees accrued in quote units (charged to sellers)
+    pub quote_fees_accrued: u64,
+    
+    /// Base mint decimals (for UI unit conversion)
+    pub base_decimals: u8,
+    
+    /// Quote mint decimals (for UI unit conversion)
+    pub quote_decimals: u8,
+    
+    /// Cap on resting notional per side (in quote units, 0 if unlimited)
+    pub max_side_notional: u64,
+    
+    /// Cap on resting notional across both sides (in quote units, 0 if unlimited)
+    pub max_open_notional: u64,
+    
+    /// Resting bid notional (in quote units, mirrored from the orderbook)
+    pub bid_open_notional: u64,
+    
+    /// Resting ask notional (in quote units, mirrored from the orderbook)
+    pub ask_open_notional: u64,
+    
+    /// Current statistics epoch (advanced by roll_epoch)
+    pub epoch: u64,
+    
+    /// Start of the current epoch (unix timestamp, aligned to EPOCH_SECONDS)
+    pub epoch_started_at: i64,
+    
+    /// Volume traded in the current epoch (in quote units)
+    pub epoch_volume: u128,
+    
+    /// Fills in the current epoch
+    pub epoch_fill_count: u64,
+    
+    /// Volume traded in the last finalized epoch (in quote units)
+    pub last_epoch_volume: u128,
+    
+    /// Format of the oracle account (see `OracleType`, 0 = Pyth)
+    pub oracle_type: u8,
+    
+    /// Base the vault should hold per the ledger (deposits - withdrawals +/- swaps)
+    pub base_vault_ledger: u64,
+    
+    /// Quote the vault should hold per the ledger (deposits - withdrawals +/- swaps)
+    pub quote_vault_ledger: u64,
+    
+    /// Operator key with delegated powers (Pubkey::default() if none)
+    pub operator: Pubkey,
+    
+    /// Powers delegated to the operator (see `OPERATOR_*` flags)
+    pub operator_permissions: u8,
+    
+    /// Order first in line at the best bid (0 if none), for top-of-book change events
+    pub best_bid_order_id: u128,
+    
+    /// Order first in line at the best ask (0 if none), for top-of-book change events
+    pub best_ask_order_id: u128,
+    
+    /// Minimum time between a trader's last deposit and a withdrawal (in seconds, 0 if disabled)
+    pub withdrawal_cooldown_seconds: u32,
+    
+    /// Fee on withdrawals inside the cooldown (in bps, 0 = refuse them instead)
+    pub early_withdrawal_fee_bps: u16,
+    
+    /// Slot of the last match_orders crank
+    pub last_match_slot: u64,
+    
+    /// Slot of the last consume_events crank
+    pub last_consume_slot: u64,
+    
+    /// Orders ever assigned an ID on this market
+    pub order_sequence: u64,
+    
+    /// Book generation, bumped whenever the book is migrated or compacted
+    pub book_generation: u32,
+    
+    /// Order-flow detail logged by the market (see `EventVerbosity`)
+    pub event_verbosity: u8,
+    
+    /// Staged parameters, activated by apply_pending_update at `params_effective_at`
+    pub pending_tick_size: u64,
+    pub pending_lot_size: u64,
+    pub pending_min_resting_slots: u64,
+    pub pending_batch_matching: bool,
+    pub pending_event_verbosity: u8,
+    
+    /// When the staged parameters can be applied (0 if nothing is pending)
+    pub params_effective_at: i64,
+    
+    /// Lifecycle stage (see `MarketStatus`)
+    pub status: u8,
+    
+    /// Whether placing orders, swapping and depositing require a `Seat`
+    pub requires_seat: bool,
+    
+    /// Reward per fill paid to match_orders and consume_events callers out of accrued quote fees (in quote units, 0 = off)
+    pub crank_reward_per_fill: u64,
+    
+    /// Trade tape account that fills are recorded to (Pubkey::default() if none)
+    pub trade_tape: Pubkey,
+    
+    /// Hour (unix time / 3600) of the newest rolling-stats bucket
+    pub stats_hour: i64,
+    
+    /// Quote volume per hour over the last 24 hours, indexed by hour % 24
+    pub hourly_volume: [u64; 24],
+    
+    /// Fills per hour, indexed like `hourly_volume`
+    pub hourly_fill_count: [u32; 24],
+    
+    /// First fill price of each hour (0 if none), indexed like `hourly_volume`
+    pub hourly_open_price: [u64; 24],
+    
+    /// Last fill price of each hour (0 if none), indexed like `hourly_volume`
+    pub hourly_close_price: [u64; 24],
+    
+    /// Smallest order value accepted, in quote units (0 = no minimum)
+    pub min_notional: u64,
+    
+    /// Price of the most recent fill (0 before the first trade)
+    pub last_trade_price: u64,
+    
+    /// Base size of the most recent fill
+    pub last_trade_size: u64,
+    
+    /// Time of the most recent fill
+    pub last_trade_timestamp: i64,
+    
+    /// Circuit breaker: largest price move allowed within a window, in bps (0 = off)
+    pub breaker_move_bps: u16,
+    
+    /// Circuit breaker: length of a measurement window
+    pub breaker_window_seconds: i64,
+    
+    /// Circuit breaker: how long trading stays halted once tripped
+    pub breaker_cooldown_seconds: i64,
+    
+    /// First fill price of the current breaker window (0 if none is open)
+    pub breaker_reference_price: u64,
+    
+    /// Start of the current breaker window
+    pub breaker_window_start: i64,
+    
+    /// Trading is halted until this time (0 if not halted)
+    pub halted_until: i64,
+    
+    /// Remaining size below which a partially filled order is cancelled (in base units, 0 = off)
+    pub dust_threshold: u64,
+    
+    /// Price fills execute at (see `MatchPriceModel`)
+    pub match_price_model: u8,
+    
+    /// Staged `match_price_model`, activated with the other staged parameters
+    pub pending_match_price_model: u8,
+    
+    /// Staged oracle configuration, activated by apply_pending_update at `oracle_effective_at`
+    pub pending_oracle: Pubkey,
+    pub pending_oracle_type: u8,
+    pub pending_oracle_max_staleness: u64,
+    pub pending_oracle_max_deviation_bps: u16,
+    
+    /// When the staged oracle configuration can be applied (0 if nothing is pending)
+    pub oracle_effective_at: i64,
+    
+    /// Staged risk parameters, activated by apply_pending_update at `risk_effective_at`
+    pub pending_peg_band_bps: u16,
+    pub pending_max_order_notional: u64,
+    pub pending_min_notional: u64,
+    pub pending_dust_threshold: u64,
+    pub pending_max_side_notional: u64,
+    pub pending_max_open_notional: u64,
+    pub pending_breaker_move_bps: u16,
+    pub pending_breaker_window_seconds: i64,
+    pub pending_breaker_cooldown_seconds: i64,
+    
+    /// When the staged risk parameters can be applied (0 if nothing is pending)
+    pub risk_effective_at: i64,
+    
+    /// Fills ever executed on this market; the next fill's ID
+    pub fill_sequence: u64,
+    
+    /// Maker rebates owed to queued fills whose taker fee has accrued (in base units)
+    /// Held back from `base_fees_accrued` until consume_events pays them
+    pub base_rebates_reserved: u64,
+    
+    /// Maker rebates owed to queued fills whose taker fee has accrued (in quote units)
+    pub quote_rebates_reserved: u64,
+    
+    /// Cancels allowed per fill within a trader's window before new resting orders are refused (0 = off)
+    pub max_cancel_to_fill_ratio: u16,
+    
+    /// Staged `max_cancel_to_fill_ratio`, activated with the other staged risk parameters
+    pub pending_max_cancel_to_fill_ratio: u16,
+    
+    /// Lamports held from a trader's deposit balance for each order it rests (0 = off)
+    pub placement_deposit_lamports: u32,
+    
+    /// Staged `placement_deposit_lamports`, activated with the other staged risk parameters
+    pub pending_placement_deposit_lamports: u32,
+    
+    /// Deposits forfeited by orders that expired untouched, held in this account's lamports
+    pub forfeited_deposits: u64,
+    
+    /// Token (or NFT collection) whose holders pay a discounted taker fee (Pubkey::default() if none)
+    pub fee_pass_mint: Pubkey,
+    
+    /// `fee_pass_mint` is a Metaplex collection: any NFT verified in it is a pass
+    pub fee_pass_is_collection: bool,
+    
+    /// Smallest holding that counts as a pass (in the pass token's units)
+    pub fee_pass_min_amount: u64,
+    
+    /// Taker fee discount a pass earns, in bps
+    pub fee_pass_discount_bps: u16,
+    
+    /// Points a taker earns per 10_000 quote units of volume (0 = none)
+    pub points_taker_weight: u16,
+    
+    /// Points a maker earns per 10_000 quote units filled after resting a full
+    /// `POINTS_FULL_UPTIME_SECONDS` (0 = none)
+    pub points_maker_weight: u16,
+    
+    /// Fills from this time on earn points
+    pub points_start: i64,
+    
+    /// Fills from this time on no longer earn points (0 = open-ended)
+    pub points_end: i64,
+    
+    /// Emission halves every this many seconds after `points_start` (0 = flat)
+    pub points_halving_seconds: i64,
+    
+    /// Revenue share splitting this market's fees (Pubkey::default() if the protocol keeps them)
+    pub revenue_share: Pubkey,
+    
+    /// Trading competition scoring this market's volume (Pubkey::default() if none)
+    pub competition: Pubkey,
+    
+    /// Start of `competition`'s scoring window (unix timestamp)
+    pub competition_start: i64,
+    
+    /// End of `competition`'s scoring window (unix timestamp, exclusive)
+    pub competition_end: i64,
+    
     /// Bump seed for PDA derivation
     pub bump: u8,
     
     /// Reserved space for future extensions (perp, AMM, etc.)
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub _reserved: [u8; 128],
 }
 
@@ -108,17 +577,663 @@ impl Market {
         8 +  // best_ask
         8 +  // order_count
         16 + // total_volume
+        32 + // oracle
+        8 +  // oracle_max_staleness
+        2 +  // oracle_max_deviation_bps
+        8 +  // last_depth_snapshot_slot
+        8 +  // peg_price
+        2 +  // peg_band_bps
+        1 +  // peg_cancel_only
+        1 +  // template
+        8 +  // max_order_notional
+        8 +  // min_resting_slots
+        1 +  // batch_matching
+        32 + // settlement_program
+        32 + // fill_archive_root
+        8 +  // archived_fill_count
+        32 + // event_queue
+        8 +  // base_fees_accrued
+        8 +  // quote_fees_accrued
+        1 +  // base_decimals
+        1 +  // quote_decimals
+        8 +  // max_side_notional
+        8 +  // max_open_notional
+        8 +  // bid_open_notional
+        8 +  // ask_open_notional
+        8 +  // epoch
+        8 +  // epoch_started_at
+        16 + // epoch_volume
+        8 +  // epoch_fill_count
+        16 + // last_epoch_volume
+        1 +  // oracle_type
+        8 +  // base_vault_ledger
+        8 +  // quote_vault_ledger
+        32 + // operator
+        1 +  // operator_permissions
+        16 + // best_bid_order_id
+        16 + // best_ask_order_id
+        4 +  // withdrawal_cooldown_seconds
+        2 +  // early_withdrawal_fee_bps
+        8 +  // last_match_slot
+        8 +  // last_consume_slot
+        8 +  // order_sequence
+        4 +  // book_generation
+        1 +  // event_verbosity
+        8 +  // pending_tick_size
+        8 +  // pending_lot_size
+        8 +  // pending_min_resting_slots
+        1 +  // pending_batch_matching
+        1 +  // pending_event_verbosity
+        8 +  // params_effective_at
+        1 +  // status
+        1 +  // requires_seat
+        8 +  // crank_reward_per_fill
+        32 + // trade_tape
+        8 +  // stats_hour
+        8 * 24 + // hourly_volume
+        4 * 24 + // hourly_fill_count
+        8 * 24 + // hourly_open_price
+        8 * 24 + // hourly_close_price
+        8 +  // min_notional
+        8 +  // last_trade_price
+        8 +  // last_trade_size
+        8 +  // last_trade_timestamp
+        2 +  // breaker_move_bps
+        8 +  // breaker_window_seconds
+        8 +  // breaker_cooldown_seconds
+        8 +  // breaker_reference_price
+        8 +  // breaker_window_start
+        8 +  // halted_until
+        8 +  // dust_threshold
+        1 +  // match_price_model
+        1 +  // pending_match_price_model
+        32 + // pending_oracle
+        1 +  // pending_oracle_type
+        8 +  // pending_oracle_max_staleness
+        2 +  // pending_oracle_max_deviation_bps
+        8 +  // oracle_effective_at
+        2 +  // pending_peg_band_bps
+        8 +  // pending_max_order_notional
+        8 +  // pending_min_notional
+        8 +  // pending_dust_threshold
+        8 +  // pending_max_side_notional
+        8 +  // pending_max_open_notional
+        2 +  // pending_breaker_move_bps
+        8 +  // pending_breaker_window_seconds
+        8 +  // pending_breaker_cooldown_seconds
+        8 +  // risk_effective_at
+        8 +  // fill_sequence
+        8 +  // base_rebates_reserved
+        8 +  // quote_rebates_reserved
+        2 +  // max_cancel_to_fill_ratio
+        2 +  // pending_max_cancel_to_fill_ratio
+        4 +  // placement_deposit_lamports
+        4 +  // pending_placement_deposit_lamports
+        8 +  // forfeited_deposits
+        32 + // fee_pass_mint
+        1 +  // fee_pass_is_collection
+        8 +  // fee_pass_min_amount
+        2 +  // fee_pass_discount_bps
+        2 +  // points_taker_weight
+        2 +  // points_maker_weight
+        8 +  // points_start
+        8 +  // points_end
+        8 +  // points_halving_seconds
+        32 + // revenue_share
+        32 + // competition
+        8 +  // competition_start
+        8 +  // competition_end
         1 +  // bump
         128; // reserved
     
+    /// Operator may pause and unpause the market
+    pub const OPERATOR_CAN_PAUSE: u8 = 1;
+    /// Operator may update the market's risk parameters
+    pub const OPERATOR_CAN_UPDATE_RISK: u8 = 2;
+    
+    /// Upper bound for the speed bump (~1 minute)
+    pub const MAX_RESTING_SLOTS: u64 = 150;
+    
+    /// Number of hourly buckets in the rolling stats window
+    pub const STATS_BUCKETS: usize = 24;
+    
+    /// Length of a rolling stats bucket
+    pub const STATS_BUCKET_SECONDS: i64 = 3_600;
+    
+    /// Length of a statistics epoch (daily, aligned to UTC midnight)
+    pub const EPOCH_SECONDS: i64 = 86_400;
+    
+    /// Resting time after which a maker earns its full points weight (1 hour)
+    pub const POINTS_FULL_UPTIME_SECONDS: i64 = 3_600;
+    
+    /// Fixed-point scale of UI prices and sizes (1.5 == 1_500_000_000)
+    pub const UI_DECIMALS: u32 = 9;
+    
+    /// Convert a UI size (whole base tokens, `UI_DECIMALS` scaled) to base units
+    /// Fails unless the conversion is exact
+    pub fn size_from_ui(&self, size_ui: u64) -> Result<u64> {
+        Self::rescale(size_ui as u128, Self::pow10(self.base_decimals as u32)?, Self::pow10(Self::UI_DECIMALS)?)
+    }
+    
+    /// Convert a UI price (quote tokens per base token, `UI_DECIMALS` scaled)
+    /// to quote units per lot
+    /// Fails unless the conversion is exact
+    pub fn price_from_ui(&self, price_ui: u64) -> Result<u64> {
+        let numerator = Self::pow10(self.quote_decimals as u32)?
+            .checked_mul(self.lot_size as u128)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        let denominator = Self::pow10(Self::UI_DECIMALS)?
+            .checked_mul(Self::pow10(self.base_decimals as u32)?)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Self::rescale(price_ui as u128, numerator, denominator)
+    }
+    
+    fn pow10(exponent: u32) -> Result<u128> {
+        10u128
+            .checked_pow(exponent)
+            .ok_or(crate::errors::DexError::MathOverflow.into())
+    }
+    
+    /// Compute `value * numerator / denominator`, rejecting remainders
+    fn rescale(value: u128, numerator: u128, denominator: u128) -> Result<u64> {
+        let scaled = value
+            .checked_mul(numerator)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        require!(
+            scaled % denominator == 0,
+            crate::errors::DexError::UiAmountNotRepresentable
+        );
+        u64::try_from(scaled / denominator)
+            .map_err(|_| crate::errors::DexError::MathOverflow.into())
+    }
+    
+    /// Record tokens that entered a vault
+    pub fn credit_vault(&mut self, is_base: bool, amount: u64) -> Result<()> {
+        let ledger = if is_base { &mut self.base_vault_ledger } else { &mut self.quote_vault_ledger };
+        *ledger = ledger
+            .checked_add(amount)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Record tokens leaving a vault that holds `vault_amount` before the transfer
+    /// A vault holding less than the ledger means the accounting diverged, so
+    /// outflows halt instead of paying out tokens owed to other traders
+    pub fn debit_vault(&mut self, is_base: bool, amount: u64, vault_amount: u64) -> Result<()> {
+        let ledger = if is_base { &mut self.base_vault_ledger } else { &mut self.quote_vault_ledger };
+        require!(vault_amount >= *ledger, crate::errors::DexError::VaultBalanceMismatch);
+        *ledger = ledger
+            .checked_sub(amount)
+            .ok_or(crate::errors::DexError::VaultBalanceMismatch)?;
+        Ok(())
+    }
+    
+    /// Whether a price oracle is configured for this market
+    pub fn has_oracle(&self) -> bool {
+        self.oracle != Pubkey::default()
+    }
+    
+    /// Whether `key` is the market's operator and holds `permission`
+    pub fn is_operator(&self, key: &Pubkey, permission: u8) -> bool {
+        self.operator != Pubkey::default() &&
+            self.operator == *key &&
+            self.operator_permissions & permission != 0
+    }
+    
+    /// Fee on withdrawing `amount` at `now` given the trader's last deposit time
+    /// Inside the cooldown the withdrawal pays the early-withdrawal fee, or is
+    /// refused if the market charges none
+    pub fn early_withdrawal_fee(&self, last_deposit_at: i64, amount: u64, now: i64) -> Result<u64> {
+        if self.withdrawal_cooldown_seconds == 0 ||
+            now >= last_deposit_at.saturating_add(self.withdrawal_cooldown_seconds as i64) {
+            return Ok(0);
+        }
+        require!(
+            self.early_withdrawal_fee_bps > 0,
+            crate::errors::DexError::WithdrawalCooldownActive
+        );
+        Ok(crate::matching::calculate_fee(amount, self.early_withdrawal_fee_bps))
+    }
+    
+    /// Add withdrawal or trading fees to the protocol's accrued balance
+    pub fn accrue_fee(&mut self, is_base: bool, fee: u64) -> Result<()> {
+        let accrued = if is_base { &mut self.base_fees_accrued } else { &mut self.quote_fees_accrued };
+        *accrued = accrued
+            .checked_add(fee)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Accrued fees the protocol may take: everything not reserved for maker rebates
+    pub fn claimable_fees(&self, is_base: bool) -> u64 {
+        if is_base {
+            self.base_fees_accrued.saturating_sub(self.base_rebates_reserved)
+        } else {
+            self.quote_fees_accrued.saturating_sub(self.quote_rebates_reserved)
+        }
+    }
+    
+    /// Hold back maker rebates whose funding taker fees have just accrued
+    pub fn reserve_rebates(&mut self, base: u64, quote: u64) -> Result<()> {
+        self.base_rebates_reserved = self.base_rebates_reserved
+            .checked_add(base)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        self.quote_rebates_reserved = self.quote_rebates_reserved
+            .checked_add(quote)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Pay reserved maker rebates out of the accrued fees
+    pub fn pay_rebates(&mut self, base: u64, quote: u64) -> Result<()> {
+        self.base_rebates_reserved = self.base_rebates_reserved
+            .checked_sub(base)
+            .ok_or(crate::errors::DexError::MathUnderflow)?;
+        self.quote_rebates_reserved = self.quote_rebates_reserved
+            .checked_sub(quote)
+            .ok_or(crate::errors::DexError::MathUnderflow)?;
+        self.base_fees_accrued = self.base_fees_accrued
+            .checked_sub(base)
+            .ok_or(crate::errors::DexError::MathUnderflow)?;
+        self.quote_fees_accrued = self.quote_fees_accrued
+            .checked_sub(quote)
+            .ok_or(crate::errors::DexError::MathUnderflow)?;
+        Ok(())
+    }
+    
+    /// Take a crank's reward for `fills` matched or settled out of the accrued quote fees
+    /// Pays only what the claimable fees cover; returns the amount taken
+    pub fn take_crank_reward(&mut self, fills: u64) -> u64 {
+        let reward = self.crank_reward_per_fill
+            .saturating_mul(fills)
+            .min(self.claimable_fees(false));
+        self.quote_fees_accrued -= reward;
+        reward
+    }
+    
+    /// Whether the market's fees are split by a revenue share instead of swept by the protocol
+    pub fn has_revenue_share(&self) -> bool {
+        self.revenue_share != Pubkey::default()
+    }
+    
+    /// Whether fills at `now` count towards the market's trading competition
+    pub fn competition_is_live(&self, now: i64) -> bool {
+        self.competition != Pubkey::default() &&
+            now >= self.competition_start && now < self.competition_end
+    }
+    
+    /// Whether holders of a fee-discount pass pay a lower taker fee
+    pub fn has_fee_pass(&self) -> bool {
+        self.fee_pass_mint != Pubkey::default()
+    }
+    
+    /// Points earned for `quote_amount` of volume filled at `now`
+    ///
+    /// Takers (`rested_seconds` None) earn the taker weight. Makers earn the
+    /// maker weight scaled by how long their order rested, up to
+    /// `POINTS_FULL_UPTIME_SECONDS`, so quotes flashed in front of a taker earn
+    /// nothing. Outside the schedule's window nothing is earned, and each
+    /// halving period since its start halves the emission.
+    pub fn points_for(&self, quote_amount: u64, rested_seconds: Option<i64>, now: i64) -> u64 {
+        if now < self.points_start || (self.points_end > 0 && now >= self.points_end) {
+            return 0;
+        }
+        let weighted = match rested_seconds {
+            None => quote_amount as u128 * self.points_taker_weight as u128,
+            Some(rested) => {
+                let uptime = rested.clamp(0, Self::POINTS_FULL_UPTIME_SECONDS) as u128;
+                quote_amount as u128 * self.points_maker_weight as u128 * uptime /
+                    Self::POINTS_FULL_UPTIME_SECONDS as u128
+            }
+        };
+        let halvings = if self.points_halving_seconds > 0 {
+            (now - self.points_start) / self.points_halving_seconds
+        } else {
+            0
+        };
+        u64::try_from((weighted / 10_000) >> halvings.min(127)).unwrap_or(u64::MAX)
+    }
+    
+    /// Whether settlements are forwarded to an external program for approval
+    pub fn has_settlement_program(&self) -> bool {
+        self.settlement_program != Pubkey::default()
+    }
+    
+    /// Whether peg protection is enabled for this market
+    pub fn has_peg_band(&self) -> bool {
+        self.peg_price > 0 && self.peg_band_bps > 0
+    }
+    
+    /// Check that a price is inside the peg band (always true if disabled)
+    pub fn is_within_peg_band(&self, price: u64) -> bool {
+        if !self.has_peg_band() {
+            return true;
+        }
+        let deviation = price.abs_diff(self.peg_price) as u128;
+        deviation * 10_000 <= self.peg_price as u128 * self.peg_band_bps as u128
+    }
+    
+    /// Whether the market takes new orders (active and not in peg cancel-only mode)
+    pub fn accepts_new_orders(&self) -> bool {
+        self.status == MarketStatus::Active as u8 && !self.peg_cancel_only
+    }
+    
+    /// Mirror the orderbook's top of book, order count and open notional
+    /// Emits `TopOfBookChanged` for each side whose first-in-line order changed
+    pub fn sync_book(&mut self, orderbook: &Orderbook, data: &[u8], sink: &EventSink) -> Result<()> {
+        self.best_bid = orderbook.best_bid;
+        self.best_ask = orderbook.best_ask;
+        self.order_count = orderbook.order_count;
+        self.bid_open_notional = orderbook.open_notional(Side::Bid, self.lot_size);
+        self.ask_open_notional = orderbook.open_notional(Side::Ask, self.lot_size);
+        
+        for side in [Side::Bid, Side::Ask] {
+            let top = orderbook.iter_side(data, side).next().map(|(_, order)| order);
+            let top_order_id = top.map_or(0, |order| order.order_id);
+            let tracked = if side == Side::Bid { &mut self.best_bid_order_id } else { &mut self.best_ask_order_id };
+            if *tracked == top_order_id {
+                continue;
+            }
+            *tracked = top_order_id;
+            if !self.logs_bbo() {
+                continue;
+            }
+            
+            sink.emit(crate::events::TopOfBookChanged {
+                market: orderbook.market,
+                side: side as u8,
+                order_id: top_order_id,
+                trader: top.map_or(Pubkey::default(), |order| order.trader),
+                price: top.map_or(0, |order| order.price),
+                size: top.map_or(0, |order| order.remaining_size),
+                timestamp: Clock::get()?.unix_timestamp,
+            })?;
+        }
+        Ok(())
+    }
+    
+    /// Whether the market logs top-of-book changes
+    pub fn logs_bbo(&self) -> bool {
+        self.event_verbosity <= EventVerbosity::FillsAndBbo as u8
+    }
+    
+    /// Whether the market logs every order placement, change and cancel (L3 deltas)
+    pub fn logs_l3(&self) -> bool {
+        self.event_verbosity == EventVerbosity::Full as u8
+    }
+    
+    /// ID of the order `offset` places after the next unassigned one
+    /// Hashing in the market key and book generation means an ID (or an order
+    /// intent naming it) can never refer to an order on another market or book
+    pub fn order_id_at(&self, market_key: &Pubkey, offset: u64) -> Result<u128> {
+        let sequence = self.order_sequence
+            .checked_add(offset)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        let digest = hashv(&[
+            b"order",
+            market_key.as_ref(),
+            &self.book_generation.to_le_bytes(),
+            &sequence.to_le_bytes(),
+        ]).to_bytes();
+        let mut id = [0u8; 16];
+        id.copy_from_slice(&digest[..16]);
+        // 0 marks a free slab slot
+        Ok(u128::from_le_bytes(id).max(1))
+    }
+    
+    /// Consume `count` order IDs
+    pub fn advance_order_sequence(&mut self, count: u64) -> Result<()> {
+        self.order_sequence = self.order_sequence
+            .checked_add(count)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// ID of the fill `offset` fills after the next unassigned one
+    /// IDs count up per market, so they never repeat within a slot or across slots
+    pub fn fill_id_at(&self, offset: u64) -> Result<u128> {
+        let sequence = self.fill_sequence
+            .checked_add(offset)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Ok(sequence as u128)
+    }
+    
+    /// Add executed fills to the lifetime, current-epoch and rolling 24h counters
+    /// `first_price` and `last_price` are the prices of the first and last fill
+    pub fn record_fills(
+        &mut self,
+        quote_volume: u64,
+        fills: u64,
+        first_price: u64,
+        last_price: u64,
+        last_size: u64,
+        now: i64,
+    ) -> Result<()> {
+        self.total_volume = self.total_volume
+            .checked_add(quote_volume as u128)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        self.epoch_volume = self.epoch_volume
+            .checked_add(quote_volume as u128)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        self.epoch_fill_count = self.epoch_fill_count
+            .checked_add(fills)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        self.fill_sequence = self.fill_sequence
+            .checked_add(fills)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        if fills == 0 {
+            return Ok(());
+        }
+        
+        self.last_trade_price = last_price;
+        self.last_trade_size = last_size;
+        self.last_trade_timestamp = now;
+        
+        // The first fill after a breaker window closed opens the next one
+        if self.breaker_move_bps > 0 && self.breaker_reference(now) == 0 {
+            self.breaker_reference_price = first_price;
+            self.breaker_window_start = now;
+        }
+        
+        // Clear the buckets of hours skipped since the last fill before reusing them
+        let hour = now.div_euclid(Self::STATS_BUCKET_SECONDS);
+        if hour > self.stats_hour {
+            let skipped = (hour - self.stats_hour).min(Self::STATS_BUCKETS as i64);
+            for offset in 0..skipped {
+                let index = (hour - offset).rem_euclid(Self::STATS_BUCKETS as i64) as usize;
+                self.hourly_volume[index] = 0;
+                self.hourly_fill_count[index] = 0;
+                self.hourly_open_price[index] = 0;
+                self.hourly_close_price[index] = 0;
+            }
+            self.stats_hour = hour;
+        }
+        
+        let index = self.stats_hour.rem_euclid(Self::STATS_BUCKETS as i64) as usize;
+        self.hourly_volume[index] = self.hourly_volume[index].saturating_add(quote_volume);
+        self.hourly_fill_count[index] = self.hourly_fill_count[index]
+            .saturating_add(u32::try_from(fills).unwrap_or(u32::MAX));
+        if self.hourly_open_price[index] == 0 {
+            self.hourly_open_price[index] = first_price;
+        }
+        self.hourly_close_price[index] = last_price;
+        Ok(())
+    }
+    
+    /// Volume, fill count and price move over the 24 hours up to `now`
+    pub fn rolling_stats(&self, now: i64) -> RollingStats {
+        let hour = now.div_euclid(Self::STATS_BUCKET_SECONDS);
+        let mut stats = RollingStats::default();
+        
+        // Oldest hour first, skipping buckets that fell out of the window
+        for age in (0..Self::STATS_BUCKETS as i64).rev() {
+            let bucket_hour = self.stats_hour - age;
+            if bucket_hour <= hour - Self::STATS_BUCKETS as i64 {
+                continue;
+            }
+            let index = bucket_hour.rem_euclid(Self::STATS_BUCKETS as i64) as usize;
+            if self.hourly_fill_count[index] == 0 {
+                continue;
+            }
+            stats.volume += self.hourly_volume[index] as u128;
+            stats.fill_count += self.hourly_fill_count[index] as u64;
+            if stats.open_price == 0 {
+                stats.open_price = self.hourly_open_price[index];
+            }
+            stats.close_price = self.hourly_close_price[index];
+        }
+        stats
+    }
+    
+    /// Average of the hourly closing prices over the rolling 24 hours up to `now`
+    /// (0 if there were no fills), the market's own reference price while its
+    /// oracle is down
+    pub fn recent_twap(&self, now: i64) -> u64 {
+        let hour = now.div_euclid(Self::STATS_BUCKET_SECONDS);
+        let mut sum = 0u128;
+        let mut hours = 0u128;
+        
+        for age in 0..Self::STATS_BUCKETS as i64 {
+            let bucket_hour = self.stats_hour - age;
+            if bucket_hour <= hour - Self::STATS_BUCKETS as i64 {
+                continue;
+            }
+            let index = bucket_hour.rem_euclid(Self::STATS_BUCKETS as i64) as usize;
+            if self.hourly_fill_count[index] == 0 {
+                continue;
+            }
+            sum += self.hourly_close_price[index] as u128;
+            hours += 1;
+        }
+        if hours == 0 {
+            return 0;
+        }
+        (sum / hours) as u64
+    }
+    
+    /// Whether the current epoch has run its full length at `now`
+    pub fn is_epoch_over(&self, now: i64) -> bool {
+        now >= self.epoch_started_at.saturating_add(Self::EPOCH_SECONDS)
+    }
+    
+    /// Reference price for bands and triggers: the median of best bid, best
+    /// ask and last trade
+    ///
+    /// Falls back to the mid when there has been no trade, and to the last
+    /// trade price when either side of the book is empty (0 if neither).
+    pub fn mark_price(&self) -> u64 {
+        match (self.best_bid, self.best_ask, self.last_trade_price) {
+            (0, _, last) | (_, 0, last) => last,
+            (bid, ask, 0) => ((bid as u128 + ask as u128) / 2) as u64,
+            (bid, ask, last) => last.clamp(bid.min(ask), bid.max(ask)),
+        }
+    }
+    
+    /// Whether the circuit breaker has halted trading
+    pub fn is_halted(&self, now: i64) -> bool {
+        now < self.halted_until
+    }
+    
+    /// Price the circuit breaker measures moves from (0 if no window is open)
+    pub fn breaker_reference(&self, now: i64) -> u64 {
+        let window_open = now < self.breaker_window_start.saturating_add(self.breaker_window_seconds);
+        if self.breaker_move_bps > 0 && window_open {
+            self.breaker_reference_price
+        } else {
+            0
+        }
+    }
+    
+    /// Whether a fill at `price` moves further from `reference` than the breaker allows
+    pub fn breaker_exceeded(&self, reference: u64, price: u64) -> bool {
+        self.breaker_move_bps > 0 &&
+            reference > 0 &&
+            crate::oracle::deviation_bps(reference, price) > self.breaker_move_bps as u64
+    }
+    
+    /// Halt trading for the cooldown after a fill at `price` tripped the breaker
+    /// Call after `record_fills`; the first fill after the halt opens a fresh window.
+    /// Emits `CircuitBreakerTripped`
+    pub fn trip_breaker(&mut self, market: Pubkey, price: u64, now: i64, sink: &EventSink) -> Result<()> {
+        let reference_price = self.breaker_reference(now);
+        self.halted_until = now.saturating_add(self.breaker_cooldown_seconds);
+        self.breaker_reference_price = 0;
+        self.breaker_window_start = 0;
+        
+        sink.emit(crate::events::CircuitBreakerTripped {
+            market,
+            reference_price,
+            price,
+            move_bps: self.breaker_move_bps,
+            halted_until: self.halted_until,
+            timestamp: now,
+        })?;
+        msg!("Circuit breaker tripped: reference={}, price={}, halted_until={}",
+             reference_price, price, self.halted_until);
+        Ok(())
+    }
+    
+    /// Execution price of a fill between a resting and an incoming order under `match_price_model`
+    pub fn match_price(&self, maker_price: u64, taker_price: u64) -> u64 {
+        match MatchPriceModel::from_u8(self.match_price_model) {
+            Some(MatchPriceModel::TakerPrice) => taker_price,
+            Some(MatchPriceModel::Midpoint) => {
+                // Orders left off-tick by a tick size change still never trade past their limit
+                let mid = ((maker_price as u128 + taker_price as u128) / 2) as u64;
+                (mid - mid % self.tick_size).max(maker_price.min(taker_price))
+            }
+            _ => maker_price,
+        }
+    }
+    
+    /// Whether a partially filled order's remainder is too small to keep on the book
+    pub fn is_dust(&self, order: &Order) -> bool {
+        order.remaining_size > 0 &&
+            order.remaining_size < order.size &&
+            order.remaining_size < self.dust_threshold
+    }
+    
+    /// Check an order's quote value against the market's minimum and maximum
+    pub fn check_order_notional(&self, notional: u128) -> Result<()> {
+        require!(
+            self.max_order_notional == 0 || notional <= self.max_order_notional as u128,
+            crate::errors::DexError::OrderNotionalTooLarge
+        );
+        require!(
+            notional >= self.min_notional as u128,
+            crate::errors::DexError::OrderNotionalTooSmall
+        );
+        Ok(())
+    }
+    
+    /// Check the exposure caps after an order was added to `side` of the book
+    pub fn check_exposure(&self, orderbook: &Orderbook, side: Side) -> Result<()> {
+        let side_notional = orderbook.open_notional(side, self.lot_size);
+        if self.max_side_notional > 0 {
+            require!(
+                side_notional <= self.max_side_notional,
+                crate::errors::DexError::ExposureCapExceeded
+            );
+        }
+        if self.max_open_notional > 0 {
+            let total = orderbook.open_notional(Side::Bid, self.lot_size)
+                .saturating_add(orderbook.open_notional(Side::Ask, self.lot_size));
+            require!(
+                total <= self.max_open_notional,
+                crate::errors::DexError::ExposureCapExceeded
+            );
+        }
+        Ok(())
+    }
+    
     /// Validate that a price is on a valid tick
     pub fn is_valid_tick(&self, price: u64) -> bool {
-        price >= self.tick_size && price % self.tick_size == 0
+        price >= self.tick_size && price.checked_rem(self.tick_size) == Some(0)
     }
     
     /// Validate that a size is a valid lot
     pub fn is_valid_lot(&self, size: u64) -> bool {
-        size >= self.lot_size && size % self.lot_size == 0
+        size >= self.lot_size && size.checked_rem(self.lot_size) == Some(0)
     }
     
     /// Calculate the minimum price increment
@@ -134,6 +1249,7 @@ impl Market {
 
 /// Trader position account storing balances and open orders per market
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TraderState {
     /// Trader's wallet address
     pub trader: Pubkey,
@@ -153,13 +1269,56 @@ pub struct TraderState {
     /// Quote balance locked in open orders
     pub quote_locked: u64,
     
-    /// Number of open orders
-    pub open_order_count: u16,
+    /// Number of open orders
+    pub open_order_count: u16,
+    
+    /// Cold key that must approve large withdrawals (Pubkey::default() if none)
+    pub withdrawal_co_signer: Pubkey,
+    
+    /// Withdrawals at or above this amount need the co-signer
+    pub co_sign_threshold: u64,
+    
+    /// Withdrawals may only go to the destinations in the trader's allowlist
+    pub withdrawal_allowlist_enabled: bool,
+    
+    /// Key allowed to lock this account on the trader's behalf (Pubkey::default() if none)
+    pub guardian: Pubkey,
+    
+    /// Orders and withdrawals are blocked until this time (0 if never locked)
+    pub locked_until: i64,
+    
+    /// Time of the trader's last deposit (for the market's withdrawal cooldown)
+    pub last_deposit_at: i64,
+    
+    /// Start of the window cancels and fills are counted over (0 if none is open)
+    pub ratio_window_start: i64,
+    
+    /// Orders the trader pulled in the current window
+    pub window_cancels: u32,
+    
+    /// Fills the trader took part in during the current window
+    pub window_fills: u32,
+    
+    /// Loyalty points earned on this market (see `Market::points_for`)
+    pub points: u64,
+    
+    /// Lamports prepaid for placement deposits, held in this account and free to withdraw
+    pub deposit_balance: u64,
+    
+    /// Lamports held as placement deposits for the trader's resting orders
+    pub deposits_held: u64,
+    
+    /// Trading competition the trader entered on this market (Pubkey::default() if none)
+    pub competition: Pubkey,
+    
+    /// Quote volume filled on this market for `competition` since entering it
+    pub competition_volume: u64,
     
     /// Bump seed for PDA derivation
     pub bump: u8,
     
     /// Reserved space
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub _reserved: [u8; 32],
 }
 
@@ -172,21 +1331,114 @@ impl TraderState {
         8 +  // base_locked
         8 +  // quote_locked
         2 +  // open_order_count
+        32 + // withdrawal_co_signer
+        8 +  // co_sign_threshold
+        1 +  // withdrawal_allowlist_enabled
+        32 + // guardian
+        8 +  // locked_until
+        8 +  // last_deposit_at
+        8 +  // ratio_window_start
+        4 +  // window_cancels
+        4 +  // window_fills
+        8 +  // points
+        8 +  // deposit_balance
+        8 +  // deposits_held
+        32 + // competition
+        8 +  // competition_volume
         1 +  // bump
         32;  // reserved
     
+    /// How long `lock_account` blocks orders and withdrawals (24 hours)
+    pub const ACCOUNT_LOCK_SECONDS: i64 = 86_400;
+    
+    /// Length of the window the cancel-to-fill limit counts over (1 hour)
+    pub const CANCEL_RATIO_WINDOW_SECONDS: i64 = 3_600;
+    
+    /// Move an order's placement deposit from the deposit balance into the held deposits
+    pub fn hold_deposit(&mut self, amount: u64) -> Result<()> {
+        require!(self.deposit_balance >= amount, crate::errors::DexError::PlacementDepositRequired);
+        self.deposit_balance -= amount;
+        self.deposits_held = self.deposits_held
+            .checked_add(amount)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Release the deposit of an order that left the book, back to the deposit
+    /// balance or, if `forfeit`, out of the trader's hands
+    pub fn release_deposit(&mut self, amount: u64, forfeit: bool) -> Result<()> {
+        self.deposits_held = self.deposits_held
+            .checked_sub(amount)
+            .ok_or(crate::errors::DexError::MathUnderflow)?;
+        if !forfeit {
+            self.deposit_balance = self.deposit_balance
+                .checked_add(amount)
+                .ok_or(crate::errors::DexError::MathOverflow)?;
+        }
+        Ok(())
+    }
+    
+    /// Count a fill of `quote_amount` at `now` towards the trader's competition
+    /// score, if it entered the competition `market` is running
+    pub fn record_competition_volume(&mut self, market: &Market, quote_amount: u64, now: i64) {
+        if self.competition == market.competition && market.competition_is_live(now) {
+            self.competition_volume = self.competition_volume.saturating_add(quote_amount);
+        }
+    }
+    
+    /// Whether the account is locked down at `now`
+    pub fn is_locked(&self, now: i64) -> bool {
+        now < self.locked_until
+    }
+    
+    /// Count `cancels` pulled orders and `fills` fills towards the cancel-to-fill
+    /// window, opening a new window once the current one is over
+    pub fn record_order_activity(&mut self, cancels: u32, fills: u32, now: i64) {
+        if now >= self.ratio_window_start.saturating_add(Self::CANCEL_RATIO_WINDOW_SECONDS) {
+            self.ratio_window_start = now;
+            self.window_cancels = 0;
+            self.window_fills = 0;
+        }
+        self.window_cancels = self.window_cancels.saturating_add(cancels);
+        self.window_fills = self.window_fills.saturating_add(fills);
+    }
+    
+    /// Whether the trader pulled more than `max_ratio` orders per fill (counting
+    /// at least one fill) in the window open at `now`; 0 turns the limit off
+    pub fn exceeds_cancel_ratio(&self, max_ratio: u16, now: i64) -> bool {
+        let window_open = now < self.ratio_window_start.saturating_add(Self::CANCEL_RATIO_WINDOW_SECONDS);
+        max_ratio > 0 &&
+            window_open &&
+            self.window_cancels as u64 > max_ratio as u64 * self.window_fills.max(1) as u64
+    }
+    
+    /// Whether moving `amount` out of this account needs the co-signer
+    pub fn requires_co_signature(&self, amount: u64) -> bool {
+        self.withdrawal_co_signer != Pubkey::default() && amount >= self.co_sign_threshold
+    }
+    
     /// Get total base balance (available + locked)
     pub fn total_base(&self) -> u64 {
-        self.base_available
-            .checked_add(self.base_locked)
-            .unwrap_or(u64::MAX)
+        self.base_available.saturating_add(self.base_locked)
     }
     
     /// Get total quote balance (available + locked)
     pub fn total_quote(&self) -> u64 {
-        self.quote_available
-            .checked_add(self.quote_locked)
-            .unwrap_or(u64::MAX)
+        self.quote_available.saturating_add(self.quote_locked)
+    }
+    
+    /// Size a reduce-only order on `side` may take, trimmed from `size`
+    ///
+    /// Spot exposure is the base held on the market and can only be long, so
+    /// only asks reduce it, and by at most the base not already committed to
+    /// other asks (whole lots). Derivative markets would measure a signed
+    /// position here instead.
+    pub fn reduce_only_size(&self, side: Side, size: u64, lot_size: u64) -> Result<u64> {
+        require!(side == Side::Ask, crate::errors::DexError::ReduceOnlyWouldIncrease);
+        let reducible = self.base_available - self.base_available % lot_size;
+        let size = size.min(reducible);
+        require!(size > 0, crate::errors::DexError::ReduceOnlyWouldIncrease);
+        Ok(size)
     }
     
     /// Lock base tokens for an order
@@ -248,10 +1500,41 @@ impl TraderState {
             .ok_or(crate::errors::DexError::MathOverflow)?;
         Ok(())
     }
+    
+    /// Apply a buy fill: release the order's locked quote, pay for the
+    /// fill (refunding any price improvement) and receive base
+    pub fn settle_bid_fill(&mut self, quote_released: u64, quote_paid: u64, base_received: u64) -> Result<()> {
+        require!(
+            self.quote_locked >= quote_released && quote_released >= quote_paid,
+            crate::errors::DexError::InvalidAccountState
+        );
+        self.quote_locked -= quote_released;
+        self.quote_available = self.quote_available
+            .checked_add(quote_released - quote_paid)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        self.base_available = self.base_available
+            .checked_add(base_received)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Apply a sell fill: release the order's locked base and receive quote
+    pub fn settle_ask_fill(&mut self, base_released: u64, quote_received: u64) -> Result<()> {
+        require!(
+            self.base_locked >= base_released,
+            crate::errors::DexError::InvalidAccountState
+        );
+        self.base_locked -= base_released;
+        self.quote_available = self.quote_available
+            .checked_add(quote_received)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Ok(())
+    }
 }
 
 /// Pending fill account storing matched orders awaiting settlement
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PendingFill {
     /// Unique fill identifier
     pub fill_id: u128,
@@ -293,6 +1576,7 @@ pub struct PendingFill {
     pub timestamp: i64,
     
     /// Reserved space
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub _reserved: [u8; 32],
 }
 
@@ -313,3 +1597,543 @@ impl PendingFill {
         8 +  // timestamp
         32;  // reserved
 }
+
+/// Large withdrawal awaiting the co-signer's approval
+/// Funds are moved out of the available balance while pending
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PendingWithdrawal {
+    /// Trader state the funds were reserved from
+    pub trader_state: Pubkey,
+    
+    /// Market the withdrawal is for
+    pub market: Pubkey,
+    
+    /// Mint being withdrawn (market base or quote mint)
+    pub mint: Pubkey,
+    
+    /// Amount reserved for withdrawal
+    pub amount: u64,
+    
+    /// Timestamp of the request
+    pub requested_at: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _reserved: [u8; 32],
+}
+
+impl PendingWithdrawal {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // trader_state
+        32 + // market
+        32 + // mint
+        8 +  // amount
+        8 +  // requested_at
+        1 +  // bump
+        32;  // reserved
+}
+
+/// Destination token accounts a trader may withdraw to
+/// Changes only take effect after `CHANGE_DELAY_SECONDS`
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WithdrawalAllowlist {
+    /// Trader state this allowlist protects
+    pub trader_state: Pubkey,
+    
+    /// Allowed destination token accounts (unused entries are Pubkey::default())
+    pub destinations: [Pubkey; 4],
+    
+    /// Proposed replacement list (unused entries are Pubkey::default())
+    pub pending_destinations: [Pubkey; 4],
+    
+    /// When the proposed list can be applied (0 if nothing is pending)
+    pub pending_effective_at: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _reserved: [u8; 32],
+}
+
+impl WithdrawalAllowlist {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +     // trader_state
+        32 * 4 + // destinations
+        32 * 4 + // pending_destinations
+        8 +      // pending_effective_at
+        1 +      // bump
+        32;      // reserved
+    
+    /// Maximum number of allowed destinations
+    pub const MAX_DESTINATIONS: usize = 4;
+    
+    /// Delay before an allowlist change can be applied (24 hours)
+    pub const CHANGE_DELAY_SECONDS: i64 = 86_400;
+    
+    /// Check whether a token account is an allowed destination
+    pub fn allows(&self, destination: &Pubkey) -> bool {
+        *destination != Pubkey::default() && self.destinations.contains(destination)
+    }
+    
+    /// Pack a destination list into the fixed-size array
+    pub fn pack(destinations: &[Pubkey]) -> Result<[Pubkey; 4]> {
+        require!(
+            destinations.len() <= Self::MAX_DESTINATIONS,
+            crate::errors::DexError::InvalidWithdrawalAllowlist
+        );
+        let mut packed = [Pubkey::default(); 4];
+        for (entry, destination) in packed.iter_mut().zip(destinations) {
+            require!(
+                *destination != Pubkey::default(),
+                crate::errors::DexError::InvalidWithdrawalAllowlist
+            );
+            *entry = *destination;
+        }
+        Ok(packed)
+    }
+}
+
+/// Fees earned by an integrator (wallet, frontend) routing flow to a market
+/// One account per market and platform; the tokens stay in the market vaults
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct IntegratorFees {
+    /// Market the fees were earned on
+    pub market: Pubkey,
+    
+    /// Platform key that receives and claims the fees
+    pub platform: Pubkey,
+    
+    /// Unclaimed fees in base units
+    pub base_accrued: u64,
+    
+    /// Unclaimed fees in quote units
+    pub quote_accrued: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _reserved: [u8; 32],
+}
+
+impl IntegratorFees {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // market
+        32 + // platform
+        8 +  // base_accrued
+        8 +  // quote_accrued
+        1 +  // bump
+        32;  // reserved
+    
+    /// Credit the integrator's share of a taker fee and return it
+    /// Bids pay fees in base, asks in quote
+    pub fn accrue_share(&mut self, fee: u64, share_bps: u16, side: Side) -> Result<u64> {
+        let share = crate::matching::calculate_fee(fee, share_bps);
+        let accrued = if side == Side::Bid {
+            &mut self.base_accrued
+        } else {
+            &mut self.quote_accrued
+        };
+        *accrued = accrued
+            .checked_add(share)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Ok(share)
+    }
+}
+
+/// Slab slot of one of a trader's resting orders
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct OpenOrderRef {
+    pub order_id: u128, // 0 = unused entry
+    pub slot: u64,
+}
+
+/// Where a trader's resting orders sit in the orderbook slab
+/// Lets cancels reach an order directly instead of scanning the book.
+/// Fills and crank removals happen without this account, so an entry is
+/// only trusted while its slot still holds that order ID.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct OpenOrders {
+    /// Trader state whose orders are tracked
+    pub trader_state: Pubkey,
+    
+    /// Order ID and slot of each tracked order
+    pub orders: [OpenOrderRef; 32],
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _reserved: [u8; 32],
+}
+
+impl OpenOrders {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +      // trader_state
+        24 * 32 + // orders
+        1 +       // bump
+        32;       // reserved
+    
+    /// Maximum number of tracked orders
+    pub const MAX_ORDERS: usize = 32;
+    
+    /// Whether an entry still points at its order
+    fn is_live(entry: &OpenOrderRef, orderbook: &Orderbook, data: &[u8]) -> bool {
+        entry.order_id != 0 &&
+            orderbook.get_order(data, entry.slot).is_some_and(|order| order.order_id == entry.order_id)
+    }
+    
+    /// Resolve an order ID to its slot and order without scanning the book
+    pub fn lookup(&self, orderbook: &Orderbook, data: &[u8], order_id: u128) -> Option<(u64, Order)> {
+        let entry = self.orders.iter().find(|entry| order_id != 0 && entry.order_id == order_id)?;
+        let order = orderbook.get_order(data, entry.slot)?;
+        (order.order_id == order_id).then_some((entry.slot, order))
+    }
+    
+    /// Track a newly resting order, reusing an empty or stale entry
+    /// Returns false when every entry still points at a live order
+    pub fn insert(&mut self, orderbook: &Orderbook, data: &[u8], order_id: u128, slot: u64) -> bool {
+        match self.orders.iter_mut().find(|entry| !Self::is_live(entry, orderbook, data)) {
+            Some(entry) => {
+                *entry = OpenOrderRef { order_id, slot };
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Stop tracking an order
+    pub fn remove(&mut self, order_id: u128) {
+        for entry in self.orders.iter_mut().filter(|entry| entry.order_id == order_id) {
+            *entry = OpenOrderRef::default();
+        }
+    }
+}
+
+/// One resting order in a data feed's L3 snapshot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FeedOrder {
+    pub order_id: u128,
+    pub trader: Pubkey,
+    pub price: u64,
+    pub size: u64,
+}
+
+/// Premium market data refreshed by a permissionless crank
+/// Subscribers deposit lamports into the account; each refresh pays the
+/// cranker from that balance, so the feed funds its own compute and rent
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DataFeed {
+    /// Market the feed snapshots
+    pub market: Pubkey,
+    
+    /// Slot of the latest refresh (0 if never refreshed)
+    pub last_refresh_slot: u64,
+    
+    /// Number of refreshes paid for so far
+    pub refresh_count: u64,
+    
+    /// Lamports deposited by subscribers over the feed's lifetime
+    pub total_funded: u64,
+    
+    /// Best bids in priority order (unused entries are zeroed)
+    pub bids: [FeedOrder; 16],
+    
+    /// Best asks in priority order (unused entries are zeroed)
+    pub asks: [FeedOrder; 16],
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _reserved: [u8; 32],
+}
+
+impl DataFeed {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +      // market
+        8 +       // last_refresh_slot
+        8 +       // refresh_count
+        8 +       // total_funded
+        64 * 16 + // bids
+        64 * 16 + // asks
+        1 +       // bump
+        32;       // reserved
+    
+    /// Orders kept per side in the L3 snapshot
+    pub const DEPTH: usize = 16;
+    
+    /// Minimum number of slots between two refreshes (~4 seconds)
+    pub const REFRESH_INTERVAL_SLOTS: u64 = 10;
+    
+    /// Lamports paid to the cranker for each refresh
+    pub const REFRESH_REWARD_LAMPORTS: u64 = 10_000;
+    
+    /// Copy up to `DEPTH` orders from the front of one side of the book
+    pub fn snapshot_side(orderbook: &Orderbook, data: &[u8], side: Side) -> [FeedOrder; 16] {
+        let mut entries = [FeedOrder::default(); 16];
+        for (entry, (_, order)) in entries.iter_mut().zip(orderbook.iter_side(data, side)) {
+            *entry = FeedOrder {
+                order_id: order.order_id,
+                trader: order.trader,
+                price: order.price,
+                size: order.remaining_size,
+            };
+        }
+        entries
+    }
+}
+
+/// Trading seat on a permissioned market (RWAs, restricted assets)
+/// Granted and revoked by the market authority; only checked when the
+/// market has `requires_seat` set
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Seat {
+    /// Market the seat is valid on
+    pub market: Pubkey,
+    
+    /// Trader holding the seat
+    pub trader: Pubkey,
+    
+    /// When the seat was granted
+    pub granted_at: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _reserved: [u8; 32],
+}
+
+impl Seat {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // market
+        32 + // trader
+        8 +  // granted_at
+        1 +  // bump
+        32;  // reserved
+}
+
+/// One party's cut of a revenue-share market's fees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RevenueShareEntry {
+    /// Key that claims this cut
+    pub beneficiary: Pubkey,
+    
+    /// Share of the market's fees, in bps (the table sums to 10_000)
+    pub share_bps: u16,
+    
+    /// Base fees claimed so far
+    pub base_claimed: u64,
+    
+    /// Quote fees claimed so far
+    pub quote_claimed: u64,
+}
+
+/// Immutable split of a market's fee revenue between its partners
+///
+/// Created by the protocol authority before the market's first fill and
+/// never changed afterwards. Fees move out of the market's accrued balance
+/// into `base_collected` / `quote_collected` whenever a beneficiary claims,
+/// and each beneficiary draws its share of the running totals on its own.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RevenueShare {
+    /// Market whose fees are split
+    pub market: Pubkey,
+    
+    /// Entries in use at the front of `entries`
+    pub entry_count: u8,
+    
+    /// The split table (unused entries are zeroed)
+    pub entries: [RevenueShareEntry; 8],
+    
+    /// Base fees collected from the market over its lifetime
+    pub base_collected: u64,
+    
+    /// Quote fees collected from the market over its lifetime
+    pub quote_collected: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _reserved: [u8; 32],
+}
+
+impl RevenueShare {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +     // market
+        1 +      // entry_count
+        50 * 8 + // entries
+        8 +      // base_collected
+        8 +      // quote_collected
+        1 +      // bump
+        32;      // reserved
+    
+    /// Most parties a split table can name
+    pub const MAX_ENTRIES: usize = 8;
+    
+    /// Move the market's claimable fees into the split
+    pub fn collect(&mut self, market: &mut Market) -> Result<()> {
+        let base = market.claimable_fees(true);
+        let quote = market.claimable_fees(false);
+        market.base_fees_accrued -= base;
+        market.quote_fees_accrued -= quote;
+        self.base_collected = self.base_collected
+            .checked_add(base)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        self.quote_collected = self.quote_collected
+            .checked_add(quote)
+            .ok_or(crate::errors::DexError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Base and quote fees owed to entry `index` and not yet claimed
+    pub fn owed(&self, index: usize) -> (u64, u64) {
+        let entry = &self.entries[index];
+        let share = |collected: u64| (collected as u128 * entry.share_bps as u128 / 10_000) as u64;
+        (
+            share(self.base_collected).saturating_sub(entry.base_claimed),
+            share(self.quote_collected).saturating_sub(entry.quote_claimed),
+        )
+    }
+}
+
+/// A time-boxed trading competition over up to four markets
+///
+/// Entrants are ranked by the quote volume they fill on the competition's
+/// markets inside `[start, end)`, accrued on their trader states as fills
+/// are applied. After the window scores are recorded for
+/// `SCORING_PERIOD_SECONDS`, then the prize is shared pro rata by score.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Competition {
+    /// Caller-chosen ID (PDA seed)
+    pub competition_id: u64,
+    
+    /// Markets in use at the front of `markets`
+    pub market_count: u8,
+    
+    /// Markets whose volume is scored (unused entries are Pubkey::default())
+    pub markets: [Pubkey; 4],
+    
+    /// Quote mint shared by the markets, so volumes add up
+    pub quote_mint: Pubkey,
+    
+    /// Start of the scoring window (unix timestamp)
+    pub start: i64,
+    
+    /// End of the scoring window (unix timestamp, exclusive)
+    pub end: i64,
+    
+    /// Token the prize is paid in
+    pub prize_mint: Pubkey,
+    
+    /// Token account holding the prize, owned by this account
+    pub prize_vault: Pubkey,
+    
+    /// Prize shared between entrants
+    pub prize_amount: u64,
+    
+    /// Sum of every recorded entry score
+    pub total_score: u64,
+    
+    /// Traders who entered
+    pub entrant_count: u32,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _reserved: [u8; 32],
+}
+
+impl Competition {
+    pub const SIZE: usize = 8 + // discriminator
+        8 +      // competition_id
+        1 +      // market_count
+        32 * 4 + // markets
+        32 +     // quote_mint
+        8 +      // start
+        8 +      // end
+        32 +     // prize_mint
+        32 +     // prize_vault
+        8 +      // prize_amount
+        8 +      // total_score
+        4 +      // entrant_count
+        1 +      // bump
+        32;      // reserved
+    
+    /// Most markets a competition can span
+    pub const MAX_MARKETS: usize = 4;
+    
+    /// How long after the window entrants have to record their scores (3 days)
+    pub const SCORING_PERIOD_SECONDS: i64 = 259_200;
+    
+    /// How long after scoring closes prizes can be claimed before the rest is swept (30 days)
+    pub const CLAIM_PERIOD_SECONDS: i64 = 2_592_000;
+    
+    /// Whether `market` is one of the competition's markets
+    pub fn includes(&self, market: &Pubkey) -> bool {
+        self.markets[..self.market_count as usize].contains(market)
+    }
+    
+    /// When scoring closes and prizes can be claimed
+    pub fn scoring_ends_at(&self) -> i64 {
+        self.end.saturating_add(Self::SCORING_PERIOD_SECONDS)
+    }
+    
+    /// Prize owed for `score` once scoring has closed
+    pub fn prize_for(&self, score: u64) -> u64 {
+        if self.total_score == 0 {
+            return 0;
+        }
+        (self.prize_amount as u128 * score as u128 / self.total_score as u128) as u64
+    }
+}
+
+/// A trader's entry in a competition, holding its recorded score
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CompetitionEntry {
+    /// Competition entered
+    pub competition: Pubkey,
+    
+    /// Trader who entered
+    pub trader: Pubkey,
+    
+    /// Quote volume recorded from the trader's states after the window
+    pub score: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl CompetitionEntry {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // competition
+        32 + // trader
+        8 +  // score
+        1;   // bump
+}
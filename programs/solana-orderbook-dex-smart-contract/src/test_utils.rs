@@ -0,0 +1,391 @@
+use anchor_lang::prelude::*;
+use std::sync::Once;
+use anchor_lang::solana_program::{
+    entrypoint::{ProgramResult, SUCCESS},
+    instruction::Instruction,
+    program_option::COption,
+    program_pack::Pack,
+    program_stubs,
+    program_utils::limited_deserialize,
+    system_instruction::SystemInstruction,
+};
+use anchor_spl::token::spl_token;
+use crate::event_queue::{EventQueue, QueueEvent};
+use crate::events::EventSink;
+use crate::instructions::place_order::{place, PlaceOrderAccounts, PlaceOrderParams};
+use crate::orderbook::{Orderbook, Side, TimeInForce};
+use crate::state::{GlobalConfig, Market, TraderState};
+
+/// Unix time the test clock reports
+pub const NOW: i64 = 1_700_000_000;
+
+/// Slot the test clock reports
+pub const SLOT: u64 = 100;
+
+struct TestClock;
+
+impl program_stubs::SyscallStubs for TestClock {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock { slot: SLOT, unix_timestamp: NOW, ..Clock::default() };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        SUCCESS
+    }
+    
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+    
+    /// Token program CPIs and system transfers run against the accounts
+    /// passed; any other CPI succeeds without running
+    fn sol_invoke_signed(&self, instruction: &Instruction, account_infos: &[AccountInfo], _: &[&[&[u8]]]) -> ProgramResult {
+        let account = |index: usize| account_infos.iter().find(|info| *info.key == instruction.accounts[index].pubkey).unwrap();
+        if instruction.program_id == anchor_lang::system_program::ID {
+            if let Ok(SystemInstruction::Transfer { lamports }) = limited_deserialize(&instruction.data, 64) {
+                **account(0).try_borrow_mut_lamports()? -= lamports;
+                **account(1).try_borrow_mut_lamports()? += lamports;
+            }
+            return Ok(());
+        }
+        if instruction.program_id != spl_token::ID {
+            return Ok(());
+        }
+        let accounts: Vec<AccountInfo> = instruction.accounts.iter()
+            .map(|meta| {
+                let mut info = account_infos.iter().find(|info| *info.key == meta.pubkey).unwrap().clone();
+                info.is_signer |= meta.is_signer;
+                info
+            })
+            .collect();
+        spl_token::processor::Processor::process(&spl_token::ID, &accounts, &instruction.data)
+    }
+}
+
+/// Make `Clock::get` return `NOW` and `SLOT`, `Rent::get` the default rent,
+/// and transfers move balances
+///
+/// The stubs are installed once: replacing them while another test's CPI
+/// holds them could deadlock.
+pub fn install_clock() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(TestClock));
+    });
+}
+
+/// An account of type `T` with every field zeroed
+pub fn zeroed<T: AccountDeserialize>(size: usize) -> T {
+    T::try_deserialize_unchecked(&mut &vec![0u8; size][..]).unwrap()
+}
+
+/// Serialized account data, discriminator included
+pub fn account_data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+/// Key, owner, lamports and data behind one `AccountInfo` in a handler test
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    /// A writable account owned by `owner`
+    pub fn new(owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner,
+            lamports: 1_000_000,
+            data,
+            is_signer: false,
+            executable: false,
+        }
+    }
+    
+    /// A program account holding `account`
+    pub fn program_owned<T: AccountSerialize>(account: &T) -> Self {
+        Self::new(crate::ID, account_data(account))
+    }
+    
+    /// A signing wallet
+    pub fn signer() -> Self {
+        Self { is_signer: true, ..Self::new(anchor_lang::system_program::ID, Vec::new()) }
+    }
+    
+    /// The executable account of program `id`
+    pub fn program(id: Pubkey) -> Self {
+        Self { key: id, executable: true, ..Self::new(Pubkey::default(), Vec::new()) }
+    }
+    
+    /// An SPL token account holding `amount` of `mint`
+    pub fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Self {
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }.pack_into_slice(&mut data);
+        Self::new(spl_token::ID, data)
+    }
+    
+    /// A wrapped SOL token account holding `amount`, backed by as many
+    /// lamports over its rent-exempt reserve
+    pub fn wrapped_sol(owner: Pubkey, amount: u64) -> Self {
+        let mut account = Self::token_account(spl_token::native_mint::ID, owner, amount);
+        let mut state = spl_token::state::Account::unpack(&account.data).unwrap();
+        state.is_native = COption::Some(account.lamports);
+        state.pack_into_slice(&mut account.data);
+        account.lamports += amount;
+        account
+    }
+    
+    /// An SPL mint with `decimals`
+    pub fn mint(decimals: u8) -> Self {
+        let mut data = vec![0u8; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            decimals,
+            is_initialized: true,
+            ..Default::default()
+        }.pack_into_slice(&mut data);
+        Self::new(spl_token::ID, data)
+    }
+    
+    /// Move the account to `key`
+    pub fn at(mut self, key: Pubkey) -> Self {
+        self.key = key;
+        self
+    }
+    
+    pub fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(&self.key, self.is_signer, true, &mut self.lamports, &mut self.data, &self.owner, self.executable, 0)
+    }
+}
+
+/// One `AccountInfo` per test account, in order
+pub fn account_infos(accounts: &mut [TestAccount]) -> Vec<AccountInfo<'_>> {
+    accounts.iter_mut().map(TestAccount::info).collect()
+}
+
+/// Deserialize the account behind `info`
+pub fn load<T: AccountDeserialize>(info: &AccountInfo) -> T {
+    load_data(&info.data.borrow())
+}
+
+/// Deserialize an account from its data
+pub fn load_data<T: AccountDeserialize>(data: &[u8]) -> T {
+    T::try_deserialize(&mut &data[..]).unwrap()
+}
+
+/// Amount held by the token account behind `info`
+pub fn token_balance(info: &AccountInfo) -> u64 {
+    spl_token::state::Account::unpack(&info.data.borrow()).unwrap().amount
+}
+
+/// The sink a handler would emit through (`authority` is only used by `event-cpi` builds)
+pub fn event_sink<'info>(authority: &AccountInfo<'info>) -> EventSink<'info> {
+    #[cfg(feature = "event-cpi")]
+    let sink = EventSink::new(authority.clone(), 255);
+    #[cfg(not(feature = "event-cpi"))]
+    let sink = {
+        let _ = authority;
+        EventSink::new()
+    };
+    sink
+}
+
+/// A live market with an empty book and event queue, plus the traders added to it
+///
+/// Accounts sit at the `Venue` index consts; `add_trader` appends a signer
+/// followed by its trader state. Turn `accounts` into infos with
+/// `account_infos` and drive instructions against them.
+pub struct Venue {
+    pub accounts: Vec<TestAccount>,
+}
+
+/// Indexes of the accounts `Venue::add_vaults` appended
+pub struct VenueVaults {
+    pub base_mint: usize,
+    pub quote_mint: usize,
+    pub base_vault: usize,
+    pub quote_vault: usize,
+    pub token_program: usize,
+}
+
+impl Venue {
+    pub const MARKET: usize = 0;
+    pub const ORDERBOOK: usize = 1;
+    pub const EVENT_QUEUE: usize = 2;
+    pub const GLOBAL_CONFIG: usize = 3;
+    pub const EVENT_AUTHORITY: usize = 4;
+    #[cfg(feature = "event-cpi")]
+    pub const PROGRAM: usize = 5;
+    
+    /// Lot size of venue markets (prices are quote units per lot)
+    pub const LOT_SIZE: u64 = 1_000;
+    
+    /// Room in the venue's book and event queue
+    pub const CAPACITY: usize = 16;
+    
+    /// A market with unit ticks and `LOT_SIZE` lots, configured by `configure`,
+    /// under a 30 bps taker fee and a 10 bps maker rebate
+    pub fn new(configure: impl FnOnce(&mut Market)) -> Self {
+        let market_key = Pubkey::new_unique();
+        let event_queue_key = Pubkey::new_unique();
+        
+        let mut market: Market = zeroed(Market::SIZE);
+        market.tick_size = 1;
+        market.lot_size = Self::LOT_SIZE;
+        market.event_queue = event_queue_key;
+        configure(&mut market);
+        
+        let mut orderbook: Orderbook = zeroed(Orderbook::HEADER_SIZE);
+        orderbook.market = market_key;
+        orderbook.capacity = Self::CAPACITY as u64;
+        let mut orderbook_data = vec![0u8; Orderbook::size_for(Self::CAPACITY)];
+        orderbook.try_serialize(&mut &mut orderbook_data[..Orderbook::HEADER_SIZE]).unwrap();
+        
+        let mut event_queue: EventQueue = zeroed(EventQueue::HEADER_SIZE);
+        event_queue.market = market_key;
+        let mut event_queue_data = vec![0u8; EventQueue::HEADER_SIZE + Self::CAPACITY * QueueEvent::SIZE];
+        event_queue.try_serialize(&mut &mut event_queue_data[..EventQueue::HEADER_SIZE]).unwrap();
+        
+        let mut global_config: GlobalConfig = zeroed(GlobalConfig::SIZE);
+        global_config.taker_fee_bps = 30;
+        global_config.maker_fee_bps = -10;
+        
+        Self {
+            accounts: vec![
+                TestAccount::program_owned(&market).at(market_key),
+                TestAccount::new(crate::ID, orderbook_data),
+                TestAccount::new(crate::ID, event_queue_data).at(event_queue_key),
+                TestAccount::program_owned(&global_config),
+                TestAccount::new(crate::ID, Vec::new()),
+                TestAccount::program(crate::ID),
+            ],
+        }
+    }
+    
+    /// Append a trader holding `base` and `quote` available
+    /// Returns the index of its signer; its trader state follows it
+    pub fn add_trader(&mut self, base: u64, quote: u64) -> usize {
+        let signer = TestAccount::signer();
+        let mut trader_state: TraderState = zeroed(TraderState::SIZE);
+        trader_state.trader = signer.key;
+        trader_state.market = self.accounts[Self::MARKET].key;
+        trader_state.base_available = base;
+        trader_state.quote_available = quote;
+        
+        self.accounts.push(signer);
+        self.accounts.push(TestAccount::program_owned(&trader_state));
+        self.accounts.len() - 2
+    }
+    
+    /// Append the market's mints, vaults (holding `base` and `quote`, matched
+    /// by the vault ledgers) and the token program
+    pub fn add_vaults(&mut self, base: u64, quote: u64) -> VenueVaults {
+        let market_key = self.accounts[Self::MARKET].key;
+        let base_mint = TestAccount::mint(9);
+        let quote_mint = TestAccount::mint(6);
+        let base_vault = TestAccount::token_account(base_mint.key, market_key, base);
+        let quote_vault = TestAccount::token_account(quote_mint.key, market_key, quote);
+        
+        let mut market: Market = load_data(&self.accounts[Self::MARKET].data);
+        market.base_mint = base_mint.key;
+        market.quote_mint = quote_mint.key;
+        market.base_vault = base_vault.key;
+        market.quote_vault = quote_vault.key;
+        market.base_vault_ledger = base;
+        market.quote_vault_ledger = quote;
+        self.accounts[Self::MARKET].data = account_data(&market);
+        
+        let first = self.accounts.len();
+        self.accounts.extend([base_mint, quote_mint, base_vault, quote_vault, TestAccount::program(spl_token::ID)]);
+        VenueVaults {
+            base_mint: first,
+            quote_mint: first + 1,
+            base_vault: first + 2,
+            quote_vault: first + 3,
+            token_program: first + 4,
+        }
+    }
+    
+    /// Append a token account of the market's base or quote mint owned by
+    /// the signer at `owner`, returning its index
+    pub fn add_wallet(&mut self, owner: usize, is_base: bool, amount: u64) -> usize {
+        let market: Market = load_data(&self.accounts[Self::MARKET].data);
+        let mint = if is_base { market.base_mint } else { market.quote_mint };
+        self.accounts.push(TestAccount::token_account(mint, self.accounts[owner].key, amount));
+        self.accounts.len() - 1
+    }
+    
+    /// Limit order params with every option off
+    pub fn order(side: Side, price: u64, size: u64, time_in_force: TimeInForce) -> PlaceOrderParams {
+        PlaceOrderParams {
+            side: side as u8,
+            price,
+            size,
+            time_in_force: time_in_force as u8,
+            client_order_id: 0,
+            dry_run: false,
+            price_ui: None,
+            size_ui: None,
+            oracle_band_bps: 0,
+            expiry_timestamp: 0,
+            quote_budget: None,
+            max_book_impact_bps: 0,
+            quote_proceeds: None,
+            integrator_fee_bps: 0,
+            origin: 0,
+            reduce_only: false,
+        }
+    }
+    
+    /// Place an order for the trader whose signer is at `trader`, saving the
+    /// market and its trader state
+    pub fn place<'info>(infos: &'info [AccountInfo<'info>], trader: usize, params: PlaceOrderParams) -> Result<()> {
+        Self::place_with(infos, trader, params, None, None)
+    }
+    
+    /// `place`, passing the accounts at `oracle` and `fee_pass` as the
+    /// market's oracle and the trader's fee-discount pass
+    pub fn place_with<'info>(
+        infos: &'info [AccountInfo<'info>],
+        trader: usize,
+        params: PlaceOrderParams,
+        oracle: Option<usize>,
+        fee_pass: Option<usize>,
+    ) -> Result<()> {
+        install_clock();
+        let mut market = Account::<Market>::try_from(&infos[Self::MARKET])?;
+        let mut trader_state = Account::<TraderState>::try_from(&infos[trader + 1])?;
+        place(
+            PlaceOrderAccounts {
+                market: &mut market,
+                orderbook: &UncheckedAccount::try_from(&infos[Self::ORDERBOOK]),
+                event_queue: &UncheckedAccount::try_from(&infos[Self::EVENT_QUEUE]),
+                trade_tape: None,
+                global_config: &Account::try_from(&infos[Self::GLOBAL_CONFIG])?,
+                trader_state: &mut trader_state,
+                trader: &Signer::try_from(&infos[trader])?,
+                oracle: oracle.map(|index| &infos[index]),
+                open_orders: None,
+                integrator_fees: None,
+                seat: None,
+                fee_pass: fee_pass.map(|index| InterfaceAccount::try_from(&infos[index])).transpose()?.as_ref(),
+                fee_pass_metadata: None,
+            },
+            params,
+            &event_sink(&infos[Self::EVENT_AUTHORITY]),
+        )?;
+        market.exit(&crate::ID)?;
+        trader_state.exit(&crate::ID)
+    }
+}
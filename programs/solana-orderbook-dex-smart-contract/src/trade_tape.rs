@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use bytemuck::{Pod, Zeroable};
+use crate::errors::DexError;
+use crate::event_queue::QueueEvent;
+use crate::orderbook::Side;
+use crate::state::Market;
+
+/// Trade tape entry
+/// Fixed-size `repr(C)` record without implicit padding, stored raw after the header
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[repr(C)]
+pub struct TradeRecord {
+    /// Sequence number of the trade on this market
+    pub seq_num: u64,
+    
+    /// Fill price
+    pub price: u64,
+    
+    /// Base size filled
+    pub size: u64,
+    
+    /// Timestamp of the fill
+    pub timestamp: i64,
+    
+    /// Resting side's trader (batch fills: the ask's trader)
+    pub maker: Pubkey,
+    
+    /// Aggressing side's trader (batch fills: the bid's trader)
+    pub taker: Pubkey,
+    
+    /// Aggressor side: 0 = bid, 1 = ask, `NO_AGGRESSOR` for batch auction fills
+    pub taker_side: u8,
+    
+    /// Explicit padding to keep the layout free of implicit padding
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _padding: [u8; 7],
+}
+
+unsafe impl Pod for TradeRecord {}
+unsafe impl Zeroable for TradeRecord {}
+
+impl TradeRecord {
+    pub const SIZE: usize = 8 + // seq_num
+        8 +  // price
+        8 +  // size
+        8 +  // timestamp
+        32 + // maker
+        32 + // taker
+        1 +  // taker_side
+        7;   // padding
+    
+    /// `taker_side` of fills from batch auctions, where neither side aggressed
+    pub const NO_AGGRESSOR: u8 = 2;
+    
+    /// Tape record of a fill; `taker_side` is None for batch auction fills
+    pub fn from_fill(event: &QueueEvent, taker_side: Option<Side>) -> Self {
+        let (maker, taker) = match taker_side {
+            Some(Side::Ask) => (event.bid_trader, event.ask_trader),
+            _ => (event.ask_trader, event.bid_trader),
+        };
+        Self {
+            seq_num: 0,
+            price: event.price,
+            size: event.size,
+            timestamp: event.timestamp,
+            maker,
+            taker,
+            taker_side: taker_side.map_or(Self::NO_AGGRESSOR, |side| side as u8),
+            _padding: [0; 7],
+        }
+    }
+}
+
+const _: () = assert!(std::mem::size_of::<TradeRecord>() == TradeRecord::SIZE);
+
+/// Per-market ring buffer of recent trades
+///
+/// Every fill is appended, overwriting the oldest record once the buffer is
+/// full. The trade with sequence number `s` always lives at index
+/// `s % capacity`, so the last `capacity` trades can be read straight from
+/// the account. Capacity is set by the account size.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TradeTape {
+    /// Market this tape belongs to
+    pub market: Pubkey,
+    
+    /// Sequence number of the next trade appended
+    pub seq_num: u64,
+    
+    /// Reserved space for future extensions
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _reserved: [u8; 32],
+    
+    // Trade ring buffer follows (stored as raw bytes)
+}
+
+impl TradeTape {
+    pub const HEADER_SIZE: usize = 8 + // discriminator
+        32 + // market
+        8 +  // seq_num
+        32;  // reserved
+    
+    /// Number of trades the account can hold
+    pub fn capacity(data: &[u8]) -> u64 {
+        (data.len().saturating_sub(Self::HEADER_SIZE) / TradeRecord::SIZE) as u64
+    }
+    
+    /// Append a trade, assigning its sequence number and overwriting the oldest if full
+    pub fn push(&mut self, data: &mut [u8], mut record: TradeRecord) -> Result<()> {
+        let capacity = Self::capacity(data);
+        require!(capacity > 0, DexError::InvalidAccountState);
+        
+        record.seq_num = self.seq_num;
+        let offset = Self::HEADER_SIZE + (self.seq_num % capacity) as usize * TradeRecord::SIZE;
+        data[offset..offset + TradeRecord::SIZE].copy_from_slice(bytemuck::bytes_of(&record));
+        
+        self.seq_num = self.seq_num
+            .checked_add(1)
+            .ok_or(DexError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Append fills to the market's trade tape
+///
+/// Markets without a tape ignore this. A market with one must be passed its
+/// tape by every instruction that fills orders, so the tape never has gaps.
+pub fn record_trades(market: &Market, trade_tape: Option<&AccountInfo>, trades: &[TradeRecord]) -> Result<()> {
+    if market.trade_tape == Pubkey::default() || trades.is_empty() {
+        return Ok(());
+    }
+    
+    let trade_tape = trade_tape.ok_or(DexError::InvalidAccountState)?;
+    require!(trade_tape.key() == market.trade_tape, DexError::InvalidAccountState);
+    
+    let mut data = trade_tape.try_borrow_mut_data()?;
+    let mut tape = TradeTape::try_deserialize(&mut &data[..TradeTape::HEADER_SIZE])?;
+    for trade in trades {
+        tape.push(&mut data, *trade)?;
+    }
+    tape.try_serialize(&mut &mut data[..TradeTape::HEADER_SIZE])?;
+    Ok(())
+}
@@ -108,6 +108,7 @@ describe("solana-orderbook-dex", () => {
         marketId,
         tickSize: new anchor.BN(100), // $0.0001 for 6-decimal quote
         lotSize: new anchor.BN(1000000), // 0.001 base units for 9-decimal base
+        template: 0, // Custom
       })
       .accounts({
         globalConfig,